@@ -0,0 +1,113 @@
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use cynic_codegen::check::{Diagnostic, SchemaChecker, SchemaLoadError, Severity};
+
+use super::CheckArgs;
+
+pub(crate) fn check(args: CheckArgs) -> Result<(), CheckError> {
+    let checker = SchemaChecker::new(&args.schema)?;
+
+    let mut files = Vec::new();
+    find_rust_files(&args.path, &mut files)?;
+
+    let mut errors = 0;
+    let mut warnings = 0;
+    for file in files {
+        let source = std::fs::read_to_string(&file)?;
+        let parsed = syn::parse_file(&source).map_err(|error| CheckError::ParseError {
+            path: file.clone(),
+            message: error.to_string(),
+        })?;
+
+        let mut diagnostics = Vec::new();
+        check_items(&checker, &parsed.items, &mut diagnostics);
+
+        for diagnostic in diagnostics {
+            match diagnostic.severity {
+                Severity::Error => errors += 1,
+                Severity::Warning => warnings += 1,
+            }
+            print_diagnostic(&file, &diagnostic);
+        }
+    }
+
+    if errors == 0 && warnings == 0 {
+        eprintln!("{}", "No problems found".green());
+        return Ok(());
+    }
+
+    let summary = format!("Found {errors} error(s) and {warnings} warning(s)");
+    if errors > 0 || args.deny_warnings {
+        return Err(CheckError::ChecksFailed(summary));
+    }
+    eprintln!("{}", summary.yellow());
+
+    Ok(())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum CheckError {
+    #[error("Couldn't load the schema: {0}")]
+    SchemaError(#[from] SchemaLoadError),
+    #[error("Couldn't parse {}: {message}", path.display())]
+    ParseError { path: PathBuf, message: String },
+    #[error("Couldn't read source files: {0}")]
+    IOError(#[from] std::io::Error),
+    #[error("{0}")]
+    ChecksFailed(String),
+}
+
+fn find_rust_files(path: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !path.is_dir() {
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path.to_owned());
+        }
+        return Ok(());
+    }
+
+    let mut entries = std::fs::read_dir(path)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+    entries.sort();
+
+    for entry in entries {
+        let hidden = entry
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.') || name == "target");
+        if !hidden {
+            find_rust_files(&entry, files)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn check_items(checker: &SchemaChecker, items: &[syn::Item], diagnostics: &mut Vec<Diagnostic>) {
+    for item in items {
+        if let syn::Item::Mod(module) = item {
+            if let Some((_, items)) = &module.content {
+                check_items(checker, items, diagnostics);
+            }
+            continue;
+        }
+        diagnostics.extend(checker.check_item(item));
+    }
+}
+
+fn print_diagnostic(file: &Path, diagnostic: &Diagnostic) {
+    let severity = match diagnostic.severity {
+        Severity::Error => "error".red().bold(),
+        Severity::Warning => "warning".yellow().bold(),
+    };
+    let start = diagnostic.span.start();
+
+    println!("{severity}: {}", diagnostic.message);
+    println!(
+        "  {} {}:{}:{}",
+        "-->".blue(),
+        file.display(),
+        start.line,
+        start.column + 1
+    );
+}
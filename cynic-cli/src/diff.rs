@@ -0,0 +1,76 @@
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use cynic_parser::{TypeSystemDocument, diff::Severity};
+
+use super::DiffArgs;
+
+pub(crate) fn diff(args: DiffArgs) -> Result<(), DiffError> {
+    let old = load_schema(&args.old)?;
+    let new = load_schema(&args.new)?;
+
+    let changes = old.diff(&new);
+
+    let mut breaking = 0;
+    let mut dangerous = 0;
+    for change in &changes {
+        let severity = change.severity();
+        let label = match severity {
+            Severity::Breaking => {
+                breaking += 1;
+                severity.to_string().red().bold()
+            }
+            Severity::Dangerous => {
+                dangerous += 1;
+                severity.to_string().yellow().bold()
+            }
+            Severity::Safe => severity.to_string().green().bold(),
+        };
+
+        println!("{label}: {change}");
+    }
+
+    if changes.is_empty() {
+        eprintln!("{}", "No changes found".green());
+        return Ok(());
+    }
+
+    let summary = format!(
+        "Found {} change(s): {breaking} breaking, {dangerous} dangerous",
+        changes.len()
+    );
+    if breaking > 0 || (dangerous > 0 && args.deny_dangerous) {
+        return Err(DiffError::ChangesFound(summary));
+    }
+    eprintln!("{}", summary.yellow());
+
+    Ok(())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub(crate) enum DiffError {
+    #[error("Couldn't read {}: {error}", path.display())]
+    IOError {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("Couldn't parse {}: {error}", path.display())]
+    ParseError {
+        path: PathBuf,
+        error: cynic_parser::Error,
+    },
+    #[error("{0}")]
+    ChangesFound(String),
+}
+
+fn load_schema(path: &Path) -> Result<TypeSystemDocument, DiffError> {
+    let schema = std::fs::read_to_string(path).map_err(|error| DiffError::IOError {
+        path: path.to_owned(),
+        error,
+    })?;
+
+    cynic_parser::parse_type_system_document(&schema).map_err(|error| DiffError::ParseError {
+        path: path.to_owned(),
+        error,
+    })
+}
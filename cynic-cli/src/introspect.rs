@@ -1,28 +1,64 @@
+use std::path::{Path, PathBuf};
+
 use colored::{ColoredString, Colorize};
 use cynic::http::ReqwestBlockingExt;
 use cynic_introspection::{
-    CapabilitiesQuery, CapabilitySet, IntrospectionQuery, SpecificationVersion,
+    CapabilitiesQuery, CapabilitySet, FederationSdlQuery, IntrospectionQuery, Schema, SchemaError,
+    SpecificationVersion,
 };
 use reqwest::blocking::Client;
 
-use super::{GraphQlVersion, IntrospectArgs};
+use super::{GraphQlVersion, IntrospectArgs, OutputFormat};
 
 pub(crate) fn introspect(args: IntrospectArgs) -> Result<(), IntrospectError> {
-    let client = Client::new();
-    let capabilities = match args.server_version {
-        GraphQlVersion::TwentyEighteen => SpecificationVersion::June2018.capabilities(),
-        GraphQlVersion::TwentyTwentyOne => SpecificationVersion::October2021.capabilities(),
-        GraphQlVersion::TwentyTwentyFive => SpecificationVersion::September2025.capabilities(),
-        GraphQlVersion::AutoDetect => detect_capabilities(&client, &args)?,
+    if args.federation {
+        let url = args.url.as_deref().expect("clap to require a url");
+        let sdl = fetch_federation_sdl(url, &args)?;
+        return write_output(&args, sdl);
+    }
+
+    let (schema, capabilities) = match (&args.from_sdl, &args.url) {
+        (Some(path), _) => load_schema(path, &args)?,
+        (None, Some(url)) => fetch_schema(url, &args)?,
+        (None, None) => unreachable!("clap requires one of url or --from-sdl"),
     };
 
-    let response = client
-        .build(&args)?
-        .run_graphql(IntrospectionQuery::with_capabilities(capabilities))?;
+    let output = match args.format {
+        OutputFormat::Sdl => schema.to_sdl(),
+        OutputFormat::Json => {
+            let json = schema.to_introspection_json(capabilities)?;
+            let mut output = serde_json::to_string_pretty(&json)
+                .expect("serializing a serde_json::Value shouldn't fail");
+            output.push('\n');
+            output
+        }
+    };
+
+    write_output(&args, output)
+}
+
+fn write_output(args: &IntrospectArgs, output: String) -> Result<(), IntrospectError> {
+    match &args.output {
+        None => print!("{output}"),
+        Some(path) => {
+            std::fs::write(path, output)?;
+            eprintln!("{}", format!("Schema was written to {path}").green())
+        }
+    }
+
+    Ok(())
+}
+
+fn fetch_federation_sdl(url: &str, args: &IntrospectArgs) -> Result<String, IntrospectError> {
+    use cynic::QueryBuilder;
+
+    let response = Client::new()
+        .build(url, args)?
+        .run_graphql(FederationSdlQuery::build(()))?;
 
     let errors = response.errors.unwrap_or_default();
     if !errors.is_empty() {
-        eprintln!("{}", "Errors while introspecting: ".red(),);
+        eprintln!("{}", "Errors while fetching the subgraph SDL: ".red());
 
         for error in errors {
             eprintln!("- {}", error.message.red());
@@ -30,21 +66,92 @@ pub(crate) fn introspect(args: IntrospectArgs) -> Result<(), IntrospectError> {
         eprintln!();
     }
 
-    let Some(data) = response.data else {
-        return Err(IntrospectError::IntrospectionQueryFailed);
+    let data = response.data.ok_or(IntrospectError::GraphQlError)?;
+
+    // Make sure we've got valid SDL before writing it anywhere
+    data.type_system_document()?;
+
+    Ok(data.sdl().unwrap_or_default().to_owned())
+}
+
+fn fetch_schema(
+    url: &str,
+    args: &IntrospectArgs,
+) -> Result<(Schema, CapabilitySet), IntrospectError> {
+    let client = Client::new();
+    let capabilities = match args.server_version {
+        GraphQlVersion::TwentyEighteen => SpecificationVersion::June2018.capabilities(),
+        GraphQlVersion::TwentyTwentyOne => SpecificationVersion::October2021.capabilities(),
+        GraphQlVersion::TwentyTwentyFive => SpecificationVersion::September2025.capabilities(),
+        GraphQlVersion::AutoDetect => detect_capabilities(&client, url, args)?,
     };
 
-    let schema = data.into_schema()?;
+    let mut type_depth = IntrospectionQuery::DEFAULT_TYPE_DEPTH;
+    loop {
+        let response =
+            client
+                .build(url, args)?
+                .run_graphql(IntrospectionQuery::with_type_depth(
+                    capabilities.clone(),
+                    type_depth,
+                ))?;
 
-    match args.output {
-        None => print!("{}", schema.to_sdl()),
-        Some(path) => {
-            std::fs::write(&path, schema.to_sdl())?;
-            eprintln!("{}", format!("Schema was written to {path}").green())
+        let errors = response.errors.unwrap_or_default();
+        if !errors.is_empty() {
+            eprintln!("{}", "Errors while introspecting: ".red(),);
+
+            for error in errors {
+                eprintln!("- {}", error.message.red());
+            }
+            eprintln!();
+        }
+
+        let Some(data) = response.data else {
+            return Err(IntrospectError::IntrospectionQueryFailed);
+        };
+
+        match data.into_schema() {
+            Err(SchemaError::TruncatedTypeReference(_))
+                if type_depth < IntrospectionQuery::MAX_TYPE_DEPTH =>
+            {
+                // Some types were too deeply wrapped for our query, so try again
+                // with a deeper one
+                type_depth = type_depth
+                    .saturating_mul(2)
+                    .min(IntrospectionQuery::MAX_TYPE_DEPTH);
+
+                let output = format!("Retrying introspection with a type depth of {type_depth}");
+                eprintln!("{}", output.bright_black());
+            }
+            result => return Ok((result?, capabilities)),
         }
     }
+}
 
-    Ok(())
+fn load_schema(
+    path: &Path,
+    args: &IntrospectArgs,
+) -> Result<(Schema, CapabilitySet), IntrospectError> {
+    let capabilities = match args.server_version {
+        GraphQlVersion::TwentyEighteen => SpecificationVersion::June2018.capabilities(),
+        GraphQlVersion::TwentyTwentyOne => SpecificationVersion::October2021.capabilities(),
+        GraphQlVersion::TwentyTwentyFive | GraphQlVersion::AutoDetect => {
+            SpecificationVersion::September2025.capabilities()
+        }
+    };
+
+    let sdl = std::fs::read_to_string(path).map_err(|error| IntrospectError::ReadError {
+        path: path.to_owned(),
+        error,
+    })?;
+    let document = cynic_parser::parse_type_system_document(&sdl).map_err(|error| {
+        IntrospectError::ParseError {
+            path: path.to_owned(),
+            error,
+        }
+    })?;
+
+    Ok((Schema::try_from(&document)?, capabilities))
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -65,6 +172,16 @@ pub(crate) enum IntrospectError {
     IntrospectionQueryFailed,
     #[error("Couldn't write the schema to file: {0}")]
     IOError(#[from] std::io::Error),
+    #[error("Couldn't read {}: {error}", path.display())]
+    ReadError {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("Couldn't parse {}: {error}", path.display())]
+    ParseError {
+        path: PathBuf,
+        error: cynic_parser::Error,
+    },
 }
 
 impl From<cynic_introspection::SchemaError> for IntrospectError {
@@ -80,15 +197,16 @@ impl From<cynic_introspection::SchemaError> for IntrospectError {
 
 fn detect_capabilities(
     client: &Client,
+    url: &str,
     args: &IntrospectArgs,
 ) -> Result<CapabilitySet, IntrospectError> {
     use cynic::QueryBuilder;
 
-    let output = format!("Detecting capabilities of {}", args.url).bright_black();
+    let output = format!("Detecting capabilities of {url}").bright_black();
     eprintln!("{output}");
 
     let capabilities = client
-        .build(args)?
+        .build(url, args)?
         .run_graphql(CapabilitiesQuery::build(()))?
         .data
         .ok_or(IntrospectError::GraphQlError)?
@@ -107,6 +225,9 @@ fn capability_string(caps: &CapabilitySet) -> ColoredString {
         SpecificationVersion::October2021 => {
             "Server supports the October 2021 specification".bright_black()
         }
+        SpecificationVersion::September2025 => {
+            "Server supports the September 2025 specification".bright_black()
+        }
         _ => "Server supports an unknown version of GraphQL".bright_black(),
     }
 }
@@ -114,6 +235,7 @@ fn capability_string(caps: &CapabilitySet) -> ColoredString {
 trait ReqwestExt {
     fn build(
         &self,
+        url: &str,
         args: &IntrospectArgs,
     ) -> Result<reqwest::blocking::RequestBuilder, IntrospectError>;
 }
@@ -121,9 +243,10 @@ trait ReqwestExt {
 impl ReqwestExt for Client {
     fn build(
         &self,
+        url: &str,
         args: &IntrospectArgs,
     ) -> Result<reqwest::blocking::RequestBuilder, IntrospectError> {
-        let mut builder = self.post(&args.url);
+        let mut builder = self.post(url);
         for header in &args.headers {
             let mut split = header.splitn(2, ':');
             let name = split
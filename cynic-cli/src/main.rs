@@ -4,6 +4,8 @@ use clap::{Args, Parser, Subcommand, ValueEnum};
 use colored::Colorize;
 use cynic_querygen::Generator;
 
+mod check;
+mod diff;
 mod introspect;
 
 fn main() {
@@ -17,6 +19,20 @@ fn main() {
                 eprintln!("{output}");
             }
         }
+        Some(Commands::Check(args)) => {
+            if let Err(error) = check::check(args) {
+                let output = format!("{error}").red();
+                eprintln!("{output}");
+                std::process::exit(1);
+            }
+        }
+        Some(Commands::Diff(args)) => {
+            if let Err(error) = diff::diff(args) {
+                let output = format!("{error}").red();
+                eprintln!("{output}");
+                std::process::exit(1);
+            }
+        }
         Some(Commands::Querygen(args)) => {
             let schema = std::fs::read_to_string(&args.schema).unwrap();
             let query = std::fs::read_to_string(&args.query).unwrap();
@@ -45,12 +61,25 @@ enum Commands {
 
     /// Runs querygen that allows you to generate Rust code from a schema and for a query
     Querygen(QuerygenArgs),
+
+    /// Checks the cynic derives in a crate against a schema, without compiling the crate
+    Check(CheckArgs),
+
+    /// Compares two versions of a schema, failing if there are any breaking changes
+    Diff(DiffArgs),
 }
 
 #[derive(Args)]
 struct IntrospectArgs {
     /// The URL of the GraphQL schema that we should introspect
-    url: String,
+    #[arg(required_unless_present = "from_sdl")]
+    url: Option<String>,
+    /// Build the introspection output from an SDL file instead of querying a server
+    ///
+    /// This can be combined with `--format json` to generate introspection fixtures
+    /// without a running server.
+    #[arg(long, value_name = "PATH", conflicts_with = "url")]
+    from_sdl: Option<PathBuf>,
     /// Any headers to send with the introspection request
     ///
     /// These should be in HTTP format e.g. `-H "Authorization: Bearer a_token_123"`
@@ -68,8 +97,20 @@ struct IntrospectArgs {
     ///
     /// By default we run an additional query to figure out what the server we're talking to
     /// supports.
+    ///
+    /// When used with `--from-sdl` this determines which fields are present in the JSON output,
+    /// with `auto` producing everything the latest specification supports.
     #[arg(long, default_value_t = GraphQlVersion::AutoDetect)]
     server_version: GraphQlVersion,
+    /// The format to output the schema in
+    #[arg(long, default_value_t = OutputFormat::Sdl)]
+    format: OutputFormat,
+    /// Fetch the SDL of an Apollo Federation subgraph from its `_service` field
+    ///
+    /// Unlike introspection, this includes any federation directives (e.g. `@key`) that
+    /// the subgraph uses.
+    #[arg(long, conflicts_with_all = ["from_sdl", "format"])]
+    federation: bool,
 }
 
 #[derive(clap::Parser)]
@@ -83,6 +124,39 @@ struct QuerygenArgs {
     query: PathBuf,
 }
 
+#[derive(Args)]
+struct CheckArgs {
+    /// The path to the GraphQL schema file to check against
+    #[arg(long)]
+    schema: PathBuf,
+
+    /// The Rust file or directory of Rust files to check
+    #[arg(default_value = "src")]
+    path: PathBuf,
+
+    /// Fail if any warnings are found, as well as errors
+    ///
+    /// Warnings are currently raised for any usage of deprecated fields.
+    #[arg(long)]
+    deny_warnings: bool,
+}
+
+#[derive(Args)]
+struct DiffArgs {
+    /// The path to the old version of the schema
+    old: PathBuf,
+
+    /// The path to the new version of the schema
+    new: PathBuf,
+
+    /// Fail if any dangerous changes are found, as well as breaking changes
+    ///
+    /// Dangerous changes won't break existing operations, but might change how
+    /// they behave.
+    #[arg(long)]
+    deny_dangerous: bool,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, Default)]
 enum GraphQlVersion {
     /// Run an introspection query compatible with the 2018 GraphQL specification
@@ -100,6 +174,24 @@ enum GraphQlVersion {
     AutoDetect,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum, Default)]
+enum OutputFormat {
+    /// Output the schema as GraphQL SDL
+    #[default]
+    Sdl,
+    /// Output the result of an introspection query as JSON
+    Json,
+}
+
+impl std::fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OutputFormat::Sdl => write!(f, "sdl"),
+            OutputFormat::Json => write!(f, "json"),
+        }
+    }
+}
+
 impl std::fmt::Display for GraphQlVersion {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
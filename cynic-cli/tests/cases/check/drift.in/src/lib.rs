@@ -0,0 +1,20 @@
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema = "films")]
+pub struct Film {
+    pub title: String,
+    pub director: Option<String>,
+}
+
+mod queries {
+    #[derive(cynic::QueryFragment, Debug)]
+    #[cynic(schema = "films", graphql_type = "Query")]
+    pub struct AllFilms {
+        pub all_films: Vec<Film>,
+        pub films_count: i32,
+    }
+
+    #[derive(Debug)]
+    pub struct NotADerive {
+        pub anything: String,
+    }
+}
@@ -2,5 +2,8 @@
 fn cli_tests() {
     trycmd::TestCases::new()
         .case("tests/cases/help/*.toml")
+        .case("tests/cases/check/*.toml")
+        .case("tests/cases/diff/*.toml")
+        .case("tests/cases/introspect/*.toml")
         .case("README.md");
 }
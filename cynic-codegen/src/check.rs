@@ -0,0 +1,341 @@
+//! Checks cynic derives against a schema without compiling them.
+//!
+//! This runs the same validation that the derives themselves do, but against
+//! a schema provided at runtime rather than the one named in the derives
+//! attributes.  This lets tooling check that a crate still matches a schema
+//! when that schema changes, without having to rebuild the crate.
+
+use std::path::{Path, PathBuf};
+
+use cynic_parser::{
+    TypeSystemDocument,
+    type_system::{Definition, TypeDefinition},
+};
+use darling::FromDeriveInput;
+use proc_macro2::Span;
+use syn::spanned::Spanned;
+
+use crate::{
+    enum_derive::EnumDeriveInput,
+    fragment_derive::FragmentDeriveInput,
+    idents::RenameAll,
+    inline_fragments_derive::InlineFragmentsDeriveInput,
+    input_object_derive::InputObjectDeriveInput,
+    schema::load_schema,
+    schema_for_derives::utils::{Derive, find_derives},
+};
+
+pub use crate::schema::parser::SchemaLoadError;
+
+/// Checks the cynic derives on items against a schema.
+pub struct SchemaChecker {
+    schema_path: PathBuf,
+    document: TypeSystemDocument,
+}
+
+/// A problem found when checking an item against a schema
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The derive would fail to compile against the schema
+    Error,
+    /// The derive would compile, but uses something the schema has deprecated
+    Warning,
+}
+
+impl SchemaChecker {
+    /// Creates a `SchemaChecker` for the schema at `schema_path`
+    pub fn new(schema_path: impl AsRef<Path>) -> Result<Self, SchemaLoadError> {
+        let schema_path = std::path::absolute(schema_path.as_ref())?;
+        let document = load_schema(&std::fs::read_to_string(&schema_path)?)?;
+
+        Ok(SchemaChecker {
+            schema_path,
+            document,
+        })
+    }
+
+    /// Checks any cynic derives on `item` against the schema.
+    ///
+    /// Items without cynic derives, or with derives that don't depend on the
+    /// schema, produce no diagnostics.
+    pub fn check_item(&self, item: &syn::Item) -> Vec<Diagnostic> {
+        let input = match item {
+            syn::Item::Struct(item) => syn::DeriveInput::from(item.clone()),
+            syn::Item::Enum(item) => syn::DeriveInput::from(item.clone()),
+            _ => return vec![],
+        };
+
+        let mut diagnostics = Vec::new();
+        for derive in find_derives(item) {
+            diagnostics.extend(self.check_derive(&derive, &input));
+        }
+        diagnostics
+    }
+
+    fn check_derive(&self, derive: &Derive, input: &syn::DeriveInput) -> Vec<Diagnostic> {
+        let input = &self.with_schema_path(input);
+
+        let result = match derive {
+            Derive::QueryFragment => FragmentDeriveInput::from_derive_input(input)
+                .map_err(syn::Error::from)
+                .map(|fragment| {
+                    let warnings = self.deprecated_fields(&fragment);
+                    let errors = crate::fragment_derive::fragment_derive_impl(fragment)
+                        .err()
+                        .into_iter()
+                        .flatten()
+                        .map(Diagnostic::error);
+                    errors.chain(warnings).collect()
+                }),
+            Derive::InlineFragments => InlineFragmentsDeriveInput::from_derive_input(input)
+                .map_err(syn::Error::from)
+                .map(|input| {
+                    crate::inline_fragments_derive::inline_fragments_derive_impl(input)
+                        .err()
+                        .into_iter()
+                        .flatten()
+                        .map(Diagnostic::error)
+                        .collect()
+                }),
+            Derive::Enum => EnumDeriveInput::from_derive_input(input)
+                .map_err(syn::Error::from)
+                .and_then(|enum_input| {
+                    let schema = crate::schema::Schema::new(enum_input.schema_input()?);
+                    Ok(
+                        crate::enum_derive::enum_derive_impl(enum_input, &schema, input.span())
+                            .err()
+                            .into_iter()
+                            .flatten()
+                            .map(Diagnostic::error)
+                            .collect(),
+                    )
+                }),
+            Derive::InputObject => InputObjectDeriveInput::from_derive_input(input)
+                .map_err(syn::Error::from)
+                .map(|object| {
+                    crate::input_object_derive::input_object_derive_impl(object, input.ident.span())
+                        .err()
+                        .into_iter()
+                        .flatten()
+                        .map(Diagnostic::error)
+                        .collect()
+                }),
+            Derive::QueryVariables | Derive::Scalar => Ok(vec![]),
+        };
+
+        result.unwrap_or_else(|error| error.into_iter().map(Diagnostic::error).collect())
+    }
+
+    /// Replaces any schema the input refers to with the one we're checking against
+    fn with_schema_path(&self, input: &syn::DeriveInput) -> syn::DeriveInput {
+        use darling::ast::NestedMeta;
+        use syn::Meta;
+
+        let mut input = input.clone();
+        let mut attrs = Vec::with_capacity(input.attrs.len() + 1);
+        for attr in input.attrs {
+            let Meta::List(meta_list) = &attr.meta else {
+                attrs.push(attr);
+                continue;
+            };
+            if !meta_list.path.is_ident("cynic") {
+                attrs.push(attr);
+                continue;
+            }
+            let Ok(nested) = NestedMeta::parse_meta_list(meta_list.tokens.clone()) else {
+                attrs.push(attr);
+                continue;
+            };
+            let nested = nested.into_iter().filter(|nested| {
+                !matches!(
+                    nested,
+                    NestedMeta::Meta(Meta::NameValue(name_val))
+                        if name_val.path.is_ident("schema") || name_val.path.is_ident("schema_path")
+                )
+            });
+            attrs.push(syn::parse_quote_spanned! { attr.span() =>
+                #[cynic(#(#nested),*)]
+            });
+        }
+
+        let mut schema_path = proc_macro2::Literal::string(&self.schema_path.to_string_lossy());
+        schema_path.set_span(input.ident.span());
+        attrs.push(syn::parse_quote_spanned! { input.ident.span() =>
+            #[cynic(schema_path = #schema_path)]
+        });
+
+        input.attrs = attrs;
+        input
+    }
+
+    fn deprecated_fields(&self, fragment: &FragmentDeriveInput) -> Vec<Diagnostic> {
+        let Ok(fields) = fragment.validate() else {
+            return vec![];
+        };
+        let type_name = fragment.graphql_type_name();
+        let rename_all = fragment.rename_all.unwrap_or(RenameAll::CamelCase);
+
+        fields
+            .iter()
+            .filter(|field| !field.spread())
+            .filter_map(|field| {
+                let ident = field.graphql_ident(rename_all);
+                let field_name = ident.graphql_name();
+                let reason = self.deprecation_reason(&type_name, &field_name)?;
+                Some(Diagnostic {
+                    severity: Severity::Warning,
+                    message: format!("`{type_name}.{field_name}` is deprecated: {reason}"),
+                    span: ident.span(),
+                })
+            })
+            .collect()
+    }
+
+    /// Returns the reason a field was deprecated, if it is deprecated
+    fn deprecation_reason(&self, type_name: &str, field_name: &str) -> Option<String> {
+        let field = self
+            .document
+            .definitions()
+            .filter_map(|definition| match definition {
+                Definition::Type(ty) | Definition::TypeExtension(ty) => Some(ty),
+                _ => None,
+            })
+            .filter(|ty| ty.name() == type_name)
+            .flat_map(|ty| match ty {
+                TypeDefinition::Object(object) => Some(object.fields()),
+                TypeDefinition::Interface(interface) => Some(interface.fields()),
+                _ => None,
+            })
+            .flatten()
+            .find(|field| field.name() == field_name)?;
+
+        let directive = field
+            .directives()
+            .find(|directive| directive.name() == "deprecated")?;
+
+        Some(
+            directive
+                .arguments()
+                .find(|argument| argument.name() == "reason")
+                .and_then(|argument| argument.value().as_str())
+                .unwrap_or("No longer supported")
+                .to_string(),
+        )
+    }
+}
+
+impl Diagnostic {
+    fn error(error: syn::Error) -> Self {
+        Diagnostic {
+            severity: Severity::Error,
+            message: error.to_string(),
+            span: error.span(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(schema: &str, item: syn::Item) -> Vec<(Severity, String)> {
+        let checker = SchemaChecker::new(
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("../schemas")
+                .join(schema),
+        )
+        .unwrap();
+
+        checker
+            .check_item(&item)
+            .into_iter()
+            .map(|diagnostic| (diagnostic.severity, diagnostic.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_fragment() {
+        let diagnostics = check(
+            "starwars.schema.graphql",
+            syn::parse_quote! {
+                #[derive(cynic::QueryFragment)]
+                #[cynic(schema = "some_other_schema")]
+                struct Film {
+                    title: Option<String>,
+                }
+            },
+        );
+
+        assert!(diagnostics.is_empty(), "{diagnostics:?}");
+    }
+
+    #[test]
+    fn test_mismatched_fragment() {
+        let diagnostics = check(
+            "starwars.schema.graphql",
+            syn::parse_quote! {
+                #[derive(cynic::QueryFragment)]
+                #[cynic(schema_path = "nowhere.graphql")]
+                struct Film {
+                    titel: Option<String>,
+                    director: String,
+                }
+            },
+        );
+
+        insta::assert_debug_snapshot!(diagnostics, @r###"
+        [
+            (
+                Error,
+                "no field `titel` on the GraphQL type `Film`.  Did you mean `title`?",
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn test_deprecated_fields() {
+        let diagnostics = check(
+            "spacex.graphql",
+            syn::parse_quote! {
+                #[derive(cynic::QueryFragment)]
+                struct Capsule {
+                    id: Option<cynic::Id>,
+                    dragon: Option<Dragon>,
+                }
+            },
+        );
+
+        insta::assert_debug_snapshot!(diagnostics, @r###"
+        [
+            (
+                Warning,
+                "`Capsule.dragon` is deprecated: This is not available in the REST API after MongoDB has been deprecated",
+            ),
+        ]
+        "###);
+    }
+
+    #[test]
+    fn test_non_cynic_items() {
+        assert!(
+            check(
+                "starwars.schema.graphql",
+                syn::parse_quote! {
+                    #[derive(Debug)]
+                    struct Film {
+                        nonsense: i32,
+                    }
+                }
+            )
+            .is_empty()
+        );
+    }
+}
@@ -33,6 +33,7 @@ struct Field {
     is_recurse: bool,
     is_feature_flagged: bool,
     is_skippable: bool,
+    is_deferred: bool,
     has_default: bool,
 }
 
@@ -124,7 +125,11 @@ impl quote::ToTokens for StandardDeserializeImpl<'_> {
 
         let field_unwraps = self.fields.iter().zip(&serialized_names).map(|(field, serialized_name)| {
             let rust_name = &field.rust_name;
-            if field.is_recurse || field.is_feature_flagged || field.is_skippable {
+            if field.is_recurse
+                || field.is_feature_flagged
+                || field.is_skippable
+                || field.is_deferred
+            {
                 let span = rust_name.span();
                 quote_spanned!{ span =>
                     let #rust_name = #rust_name.unwrap_or_default();
@@ -222,7 +227,11 @@ impl quote::ToTokens for SpreadingDeserializeImpl<'_> {
         let field_inserts = self.fields.iter().map(|f| {
             let field_name = &f.rust_name;
             let field_ty = &f.ty;
-            if f.is_spread {
+            if f.is_spread && f.is_deferred {
+                quote! {
+                    #field_name: spreadable.deserialize_deferred_spread()?
+                }
+            } else if f.is_spread {
                 quote! {
                     #field_name: <#field_ty as cynic::serde::Deserialize<'de>>::deserialize(
                         spreadable.spread_deserializer()
@@ -239,6 +248,15 @@ impl quote::ToTokens for SpreadingDeserializeImpl<'_> {
                         cynic::__private::Flattened<#field_ty>
                     >(#serialized_name)?.into_inner()
                 }
+            } else if f.is_deferred {
+                let serialized_name = proc_macro2::Literal::string(
+                    f.serialized_name
+                        .as_deref()
+                        .expect("non spread fields must have a serialized_name"),
+                );
+                quote! {
+                    #field_name: spreadable.deserialize_field_or_default(#serialized_name)?
+                }
             } else {
                 let serialized_name = proc_macro2::Literal::string(
                     f.serialized_name
@@ -290,6 +308,7 @@ fn process_field(field: &FragmentDeriveField, schema_field: Option<&schema::Fiel
         is_recurse: field.raw_field.recurse.is_some(),
         is_feature_flagged: field.raw_field.feature.is_some(),
         is_skippable: field.is_skippable(),
+        is_deferred: field.deferred(),
         has_default: field.has_default(),
     }
 }
@@ -10,7 +10,9 @@ use crate::{
         Schema, Unvalidated,
         types::{Field, OutputType},
     },
-    types::{self, CheckMode, check_spread_type, check_types_are_compatible},
+    types::{
+        self, CheckMode, check_deferred_spread_type, check_spread_type, check_types_are_compatible,
+    },
     variables_fields_path,
 };
 
@@ -50,11 +52,13 @@ struct FieldSelection<'a> {
     recurse_limit: Option<u8>,
     span: proc_macro2::Span,
     requires_feature: Option<String>,
+    deferred: bool,
     directives: Vec<AnalysedFieldDirective<'a>>,
 }
 
 struct SpreadSelection {
     rust_field_type: syn::Type,
+    deferred: bool,
     span: proc_macro2::Span,
 }
 
@@ -129,10 +133,16 @@ fn process_field<'a>(
 ) -> Result<Selection<'a>, Errors> {
     let ty = &field.raw_field.ty;
     if field.type_check_mode() == CheckMode::Spreading {
-        check_spread_type(ty)?;
+        let rust_field_type = if field.deferred() {
+            check_deferred_spread_type(ty)?
+        } else {
+            check_spread_type(ty)?;
+            ty.clone()
+        };
 
         return Ok(Selection::Spread(SpreadSelection {
-            rust_field_type: ty.clone(),
+            rust_field_type,
+            deferred: field.deferred(),
             span: ty.span(),
         }));
     }
@@ -186,6 +196,7 @@ fn process_field<'a>(
             .feature
             .as_ref()
             .map(|feature| feature.as_ref().clone()),
+        deferred: field.deferred(),
         directives,
     }))
 }
@@ -393,6 +404,24 @@ impl quote::ToTokens for FieldSelection<'_> {
             }
         };
 
+        let select_tokens = if self.deferred {
+            // Deferred fields are selected inside an inline fragment, as
+            // @defer can't be applied to fields directly
+            quote_spanned! { self.span =>
+                {
+                    let mut fragment_builder = builder.inline_fragment();
+                    fragment_builder.defer();
+                    let mut builder = fragment_builder.select_children::<
+                        <Self as cynic::QueryFragment>::VariablesFields
+                    >();
+
+                    #select_tokens
+                }
+            }
+        } else {
+            select_tokens
+        };
+
         match &self.requires_feature {
             Some(required_feature) => {
                 let string_lit = proc_macro2::Literal::string(required_feature);
@@ -412,6 +441,20 @@ impl quote::ToTokens for SpreadSelection {
         use quote::TokenStreamExt;
         let field_type = &self.rust_field_type;
 
+        if self.deferred {
+            tokens.append_all(quote_spanned! { self.span =>
+                {
+                    let mut fragment_builder = builder.inline_fragment();
+                    fragment_builder.defer();
+                    <#field_type as cynic::QueryFragment>::query(
+                        fragment_builder
+                            .select_children::<<#field_type as cynic::QueryFragment>::VariablesFields>()
+                    );
+                }
+            });
+            return;
+        }
+
         tokens.append_all(quote_spanned! { self.span =>
             <#field_type as cynic::QueryFragment>::query(
                 builder
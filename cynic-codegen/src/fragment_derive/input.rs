@@ -34,7 +34,7 @@ pub struct FragmentDeriveInput {
     pub(super) no_deserialize: bool,
 
     #[darling(default)]
-    pub(super) rename_all: Option<RenameAll>,
+    pub(crate) rename_all: Option<RenameAll>,
 
     #[darling(default)]
     variables: Option<syn::Path>,
@@ -152,6 +152,9 @@ pub struct RawFragmentDeriveField {
     #[darling(default)]
     pub(super) spread: SpannedValue<bool>,
 
+    #[darling(default)]
+    pub(super) defer: SpannedValue<bool>,
+
     #[darling(default)]
     rename: Option<SpannedValue<String>>,
 
@@ -229,6 +232,30 @@ impl RawFragmentDeriveField {
             .into());
         }
 
+        if *self.defer && *self.flatten {
+            return Err(syn::Error::new(
+                self.defer.span(),
+                "A field can't be deferred if it's being flattened",
+            )
+            .into());
+        }
+
+        if *self.defer && self.recurse.is_some() {
+            return Err(syn::Error::new(
+                self.defer.span(),
+                "A field can't be deferred if it's being recursed",
+            )
+            .into());
+        }
+
+        if *self.defer && *self.default {
+            return Err(syn::Error::new(
+                self.defer.span(),
+                "A field can't be deferred if it's also being defaulted",
+            )
+            .into());
+        }
+
         let directives = super::directives::directives_from_field_attrs(&self.attrs)?;
         let skippable = directives.iter().any(|directive| {
             matches!(
@@ -276,6 +303,8 @@ impl FragmentDeriveField {
             CheckMode::Spreading
         } else if self.has_default() {
             CheckMode::Defaulted
+        } else if self.deferred() {
+            CheckMode::Deferred
         } else if self.is_skippable() {
             CheckMode::Skippable
         } else {
@@ -292,15 +321,19 @@ impl FragmentDeriveField {
         })
     }
 
-    pub(super) fn spread(&self) -> bool {
+    pub(crate) fn spread(&self) -> bool {
         *self.raw_field.spread
     }
 
+    pub(super) fn deferred(&self) -> bool {
+        *self.raw_field.defer
+    }
+
     pub(super) fn ident(&self) -> Option<&proc_macro2::Ident> {
         self.raw_field.ident.as_ref()
     }
 
-    pub(super) fn graphql_ident(&self, rename_rule: RenameAll) -> RenamableFieldIdent {
+    pub(crate) fn graphql_ident(&self, rename_rule: RenameAll) -> RenamableFieldIdent {
         let mut ident = RenamableFieldIdent::from(
             self.raw_field
                 .ident
@@ -352,6 +385,7 @@ mod tests {
                         flatten: false.into(),
                         recurse: None,
                         spread: false.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -364,6 +398,7 @@ mod tests {
                         flatten: true.into(),
                         recurse: None,
                         spread: false.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -376,6 +411,7 @@ mod tests {
                         flatten: false.into(),
                         recurse: Some(8.into()),
                         spread: false.into(),
+                        defer: false.into(),
                         rename: Some("fieldThree".to_string().into()),
                         alias: false.into(),
                         feature: None,
@@ -388,6 +424,7 @@ mod tests {
                         flatten: false.into(),
                         recurse: None,
                         spread: true.into(),
+                        defer: false.into(),
                         rename: Some("fieldThree".to_string().into()),
                         alias: true.into(),
                         feature: None,
@@ -422,6 +459,7 @@ mod tests {
                         flatten: false.into(),
                         recurse: None,
                         spread: false.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -434,6 +472,7 @@ mod tests {
                         flatten: true.into(),
                         recurse: Some(8.into()),
                         spread: false.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -446,6 +485,7 @@ mod tests {
                         flatten: true.into(),
                         recurse: Some(8.into()),
                         spread: false.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -458,6 +498,7 @@ mod tests {
                         flatten: true.into(),
                         recurse: None,
                         spread: true.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -470,6 +511,7 @@ mod tests {
                         flatten: false.into(),
                         recurse: Some(8.into()),
                         spread: true.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -482,6 +524,7 @@ mod tests {
                         flatten: false.into(),
                         recurse: Some(8.into()),
                         spread: true.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: true.into(),
                         feature: None,
@@ -538,6 +581,7 @@ mod tests {
                         flatten: false.into(),
                         recurse: None,
                         spread: false.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -550,6 +594,7 @@ mod tests {
                         flatten: true.into(),
                         recurse: None,
                         spread: false.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
@@ -562,6 +607,7 @@ mod tests {
                         flatten: false.into(),
                         recurse: Some(8.into()),
                         spread: false.into(),
+                        defer: false.into(),
                         rename: None,
                         alias: false.into(),
                         feature: None,
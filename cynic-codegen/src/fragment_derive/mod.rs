@@ -74,10 +74,49 @@ pub fn fragment_derive_impl(input: FragmentDeriveInput) -> Result<TokenStream, E
     if !input.no_deserialize {
         deserialize_impl.to_tokens(&mut output);
     }
+    if let Some(operation_type) = root_operation_type(&schema, graphql_name, &input.generics) {
+        let ident = &input.ident;
+        output.extend(quote::quote! {
+            cynic::__register_operation!(#operation_type, #ident);
+        });
+    }
 
     Ok(output)
 }
 
+/// Works out which kind of operation a fragment can be used for, if any.
+///
+/// Generic fragments can't be registered as operations, as we wouldn't know
+/// what to fill their parameters with.
+fn root_operation_type(
+    schema: &Schema<'_, crate::schema::Unvalidated>,
+    graphql_name: &str,
+    generics: &syn::Generics,
+) -> Option<proc_macro2::Ident> {
+    if !generics.params.is_empty() {
+        return None;
+    }
+
+    let roots = schema.root_types().ok()?;
+    let operation_type = if roots.query.name == graphql_name {
+        "Query"
+    } else if roots.mutation.is_some_and(|root| root.name == graphql_name) {
+        "Mutation"
+    } else if roots
+        .subscription
+        .is_some_and(|root| root.name == graphql_name)
+    {
+        "Subscription"
+    } else {
+        return None;
+    };
+
+    Some(proc_macro2::Ident::new(
+        operation_type,
+        proc_macro2::Span::call_site(),
+    ))
+}
+
 fn pair_fields<'a>(
     rust_fields: impl IntoIterator<Item = FragmentDeriveField>,
     rename_all: RenameAll,
@@ -110,6 +110,21 @@ use super::fragment_derive;
         }
     )
 )]
+#[case::deferred_spread(
+    "deferred_spread",
+    parse_quote!(
+        #[derive(cynic::QueryFragment, Debug)]
+        #[cynic(
+            schema_path = "../schemas/starwars.schema.graphql",
+            schema_module = "schema"
+        )]
+        struct Film {
+            title: Option<String>,
+            #[cynic(spread, defer)]
+            details: Option<FilmDetails>,
+        }
+    )
+)]
 #[case::flatten_attr(
     "flatten_attr",
     parse_quote!(
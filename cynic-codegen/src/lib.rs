@@ -1,4 +1,5 @@
 #![deny(rust_2018_idioms)]
+pub mod check;
 pub mod enum_derive;
 pub mod fragment_derive;
 pub mod generics_for_serde;
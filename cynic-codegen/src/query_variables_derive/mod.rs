@@ -125,6 +125,25 @@ pub fn query_variables_derive_impl(
 
     let map_len = field_inserts.len();
 
+    // If the variables are only generic over lifetimes we can name them from
+    // the fields struct, which lets fragments that use them be registered as
+    // persisted operations.
+    let only_generic_over_lifetimes = input.generics.type_params().next().is_none()
+        && input.generics.const_params().next().is_none();
+    let known_variables = only_generic_over_lifetimes.then(|| {
+        let lifetimes = input.generics.lifetimes().map(|_| quote! { 'static });
+        let variables = match input.generics.lifetimes().next() {
+            Some(_) => quote! { #ident<#(#lifetimes),*> },
+            None => quote! { #ident },
+        };
+        quote! {
+            #[automatically_derived]
+            impl cynic::__private::KnownVariables for #fields_struct_ident {
+                type Variables = #variables;
+            }
+        }
+    });
+
     let ident_span = ident.span();
     let fields_struct = quote_spanned! { ident_span =>
         #vis struct #fields_struct_ident;
@@ -148,6 +167,8 @@ pub fn query_variables_derive_impl(
 
     Ok(quote! {
 
+        #known_variables
+
         #[automatically_derived]
         impl #impl_generics cynic::QueryVariables for #ident #ty_generics #where_clause {
             type Fields = #fields_struct_ident;
@@ -65,6 +65,10 @@ impl<'a> Schema<'a, Unvalidated> {
         self.type_index.lookup_directive(name)
     }
 
+    pub fn root_types(&self) -> Result<SchemaRoots<'_>, SchemaError> {
+        self.type_index.root_types()
+    }
+
     pub fn lookup<'b, Kind>(&'b self, name: &str) -> Result<Kind, SchemaError>
     where
         Kind: TryFrom<types::Type<'b>> + 'b,
@@ -1,14 +1,39 @@
+use cynic_parser::type_system::Definition;
+
 /// Loads a schema from a string
+///
+/// Any type extensions in the schema are merged into the types they extend.
+/// Schemas without extensions are used as written.
 pub fn load_schema(sdl: &str) -> Result<cynic_parser::TypeSystemDocument, SchemaLoadError> {
     let ast = cynic_parser::parse_type_system_document(sdl)
         .map_err(|error| SchemaLoadError::ParseError(error.to_string()))?;
-    Ok(ast)
+
+    let has_extensions = ast.definitions().any(|definition| {
+        matches!(
+            definition,
+            Definition::SchemaExtension(_) | Definition::TypeExtension(_)
+        )
+    });
+    if !has_extensions {
+        return Ok(ast);
+    }
+
+    ast.normalize().map_err(|errors| {
+        SchemaLoadError::ConflictingDefinitions(
+            errors
+                .iter()
+                .map(|error| error.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    })
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum SchemaLoadError {
     IoError(String),
     ParseError(String),
+    ConflictingDefinitions(String),
     FileNotFound(String),
     NamedSchemaNotFound(String),
     DefaultSchemaNotFound,
@@ -27,6 +52,9 @@ impl std::fmt::Display for SchemaLoadError {
         match self {
             SchemaLoadError::IoError(e) => write!(f, "Could not load schema file: {}", e),
             SchemaLoadError::ParseError(e) => write!(f, "Could not parse schema file: {}", e),
+            SchemaLoadError::ConflictingDefinitions(e) => {
+                write!(f, "Schema file contains conflicting definitions: {}", e)
+            }
             SchemaLoadError::FileNotFound(e) => write!(f, "Could not find file: {}", e),
             SchemaLoadError::NamedSchemaNotFound(_) => write!(
                 f,
@@ -54,6 +82,8 @@ impl std::fmt::Display for SchemaLoadError {
     }
 }
 
+impl std::error::Error for SchemaLoadError {}
+
 // TODO: I could put a link in this
 const SCHEMA_DOCUMENTATION_TEXT: &str =
     "See the cynic documentation on regsistering schemas if you need help.";
@@ -2,7 +2,7 @@
 use darling::{FromMeta, util::SpannedValue};
 use proc_macro2::TokenStream;
 
-mod utils;
+pub(crate) mod utils;
 
 use utils::Derive;
 
@@ -8,7 +8,7 @@ mod validation;
 pub use self::{
     alignment::{align_defaulted_output_type, align_input_type, align_output_type},
     validation::{
-        CheckMode, check_input_types_are_compatible, check_spread_type, check_types_are_compatible,
-        outer_type_is_option,
+        CheckMode, check_deferred_spread_type, check_input_types_are_compatible, check_spread_type,
+        check_types_are_compatible, outer_type_is_option,
     },
 };
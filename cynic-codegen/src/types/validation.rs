@@ -13,6 +13,7 @@ pub enum CheckMode {
     Recursing,
     Spreading,
     Skippable,
+    Deferred,
     Defaulted,
 }
 
@@ -36,6 +37,16 @@ pub fn check_types_are_compatible(
             }
             output_type_check(gql_type, &parsed_rust_type, false)?;
         }
+        CheckMode::Deferred => {
+            if !outer_type_is_option(rust_type) {
+                return Err(TypeValidationError::DeferredFieldWithoutOption {
+                    provided_type: rust_type.to_string(),
+                    span: rust_type.span(),
+                }
+                .into());
+            }
+            output_type_check(gql_type, &parsed_rust_type, false)?;
+        }
         CheckMode::Defaulted => {
             let gql_type = match gql_type.clone() {
                 TypeRef::Nullable(inner_ty) => *inner_ty,
@@ -89,6 +100,25 @@ pub fn check_spread_type(rust_type: &syn::Type) -> Result<(), syn::Error> {
     inner_fn(&parse_rust_type(rust_type))
 }
 
+/// Checks the type of a deferred spread field, which must be wrapped in
+/// `Option`.
+///
+/// Returns the type inside the `Option` on success.
+pub fn check_deferred_spread_type(rust_type: &syn::Type) -> Result<syn::Type, syn::Error> {
+    let RustType::Optional { inner, .. } = parse_rust_type(rust_type) else {
+        return Err(TypeValidationError::DeferredFieldWithoutOption {
+            provided_type: rust_type.to_string(),
+            span: rust_type.span(),
+        }
+        .into());
+    };
+
+    let inner = inner.to_syn();
+    check_spread_type(&inner)?;
+
+    Ok(inner)
+}
+
 /// Checks if the type is `Option`, `&Option`, `Box<Option>` etc.
 pub fn outer_type_is_option(rust_type: &syn::Type) -> bool {
     fn inner_fn(rust_type: &RustType<'_>) -> bool {
@@ -223,6 +253,7 @@ enum TypeValidationError {
     SpreadOnOption { span: Span },
     SpreadOnVec { span: Span },
     SkippableFieldWithoutError { provided_type: String, span: Span },
+    DeferredFieldWithoutOption { provided_type: String, span: Span },
 }
 
 impl From<TypeValidationError> for syn::Error {
@@ -264,6 +295,9 @@ impl From<TypeValidationError> for syn::Error {
             TypeValidationError::SkippableFieldWithoutError { provided_type, .. } => format!(
                 "This field has @skip or @include on it so it must be optional.  Did you mean Option<{provided_type}>"
             ),
+            TypeValidationError::DeferredFieldWithoutOption { provided_type, .. } => format!(
+                "Deferred fields won't be present in the initial response so must be optional.  Did you mean Option<{provided_type}>"
+            ),
         };
 
         syn::Error::new(span, message)
@@ -287,6 +321,7 @@ impl TypeValidationError {
             TypeValidationError::SpreadOnOption { span } => *span,
             TypeValidationError::SpreadOnVec { span } => *span,
             TypeValidationError::SkippableFieldWithoutError { span, .. } => *span,
+            TypeValidationError::DeferredFieldWithoutOption { span, .. } => *span,
         }
     }
 }
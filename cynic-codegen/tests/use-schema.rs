@@ -25,6 +25,7 @@ fn format_code(input: String) -> String {
     "books.graphql",
     "starwars.schema.graphql",
     "test_cases.graphql",
+    "extensions.graphql",
     "../schemas/simple.graphql",
 ])]
 fn snapshot_use_schema_two(schema_file: &str) {
@@ -4,4 +4,8 @@ fn main() {
         .unwrap()
         .as_default()
         .unwrap();
+
+    cynic_codegen::register_schema("federation")
+        .from_sdl_file("src/federation.graphql")
+        .unwrap();
 }
@@ -1,10 +1,16 @@
 use crate::SpecificationVersion;
 
 /// The set of capaiblities a GraphQL server supports.
-#[derive(Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct CapabilitySet {
     pub(super) specification_version: SpecificationVersion,
     pub(super) supports_oneof: bool,
+    pub(super) supports_specified_by_url: bool,
+    pub(super) supports_repeatable_directives: bool,
+    pub(super) supports_argument_deprecation: bool,
+    pub(super) supports_input_field_deprecation: bool,
+    pub(super) supports_input_value_deprecation: bool,
+    pub(super) supports_federation: bool,
 }
 
 impl CapabilitySet {
@@ -12,14 +18,61 @@ impl CapabilitySet {
     pub fn version_supported(&self) -> SpecificationVersion {
         self.specification_version
     }
+
+    /// Whether the server exposes `isOneOf` on `__Type`
+    pub fn supports_oneof(&self) -> bool {
+        self.supports_oneof
+    }
+
+    /// Whether the server exposes `specifiedByURL` on `__Type`
+    pub fn supports_specified_by_url(&self) -> bool {
+        self.supports_specified_by_url
+    }
+
+    /// Whether the server exposes `isRepeatable` on `__Directive`
+    pub fn supports_repeatable_directives(&self) -> bool {
+        self.supports_repeatable_directives
+    }
+
+    /// Whether the `args` fields of `__Field` & `__Directive` accept an
+    /// `includeDeprecated` argument
+    pub fn supports_argument_deprecation(&self) -> bool {
+        self.supports_argument_deprecation
+    }
+
+    /// Whether `inputFields` on `__Type` accepts an `includeDeprecated` argument
+    pub fn supports_input_field_deprecation(&self) -> bool {
+        self.supports_input_field_deprecation
+    }
+
+    /// Whether the server exposes `isDeprecated` & `deprecationReason` on
+    /// `__InputValue`
+    pub fn supports_input_value_deprecation(&self) -> bool {
+        self.supports_input_value_deprecation
+    }
+
+    /// Whether the server looks like an Apollo Federation subgraph, with a
+    /// `_service { sdl }` field
+    pub fn supports_federation(&self) -> bool {
+        self.supports_federation
+    }
 }
 
 impl SpecificationVersion {
     /// The capabilities of a server that implements this version of the specification
     pub fn capabilities(self) -> CapabilitySet {
+        let october_2021 = self >= SpecificationVersion::October2021;
+        let september_2025 = self >= SpecificationVersion::September2025;
+
         CapabilitySet {
             specification_version: self,
-            supports_oneof: matches!(self, SpecificationVersion::September2025),
+            supports_oneof: september_2025,
+            supports_specified_by_url: october_2021,
+            supports_repeatable_directives: october_2021,
+            supports_argument_deprecation: september_2025,
+            supports_input_field_deprecation: september_2025,
+            supports_input_value_deprecation: september_2025,
+            supports_federation: false,
         }
     }
 }
@@ -10,9 +10,9 @@ use super::query::schema;
 /// A query that detects what capabilities a remote GraphQL server has, which can be used
 /// to determine what introspection query should be made against that server.
 ///
-/// Currently this just determines which version of the specification the server supports,
-/// but it may be extended in the future to detect other capabilities e.g. which RFCs a
-/// server has implemented support for.
+/// This determines which version of the specification the server supports, along with
+/// which of the individual introspection features it implements - so servers that only
+/// partially implement a newer specification can still be introspected fully.
 ///
 /// ```rust
 /// use cynic::{QueryBuilder, http::ReqwestExt};
@@ -43,14 +43,49 @@ pub struct CapabilitiesQuery {
     #[cynic(rename = "__type")]
     #[arguments(name: "__InputValue")]
     input_value_type: Option<Type>,
+
+    #[cynic(rename = "__type")]
+    #[arguments(name: "__Field")]
+    field_type: Option<Type>,
+
+    #[cynic(rename = "__type")]
+    #[arguments(name: "__Directive")]
+    directive_type: Option<Type>,
+
+    #[cynic(rename = "__type")]
+    #[arguments(name: "_Service")]
+    service_type: Option<NamedType>,
 }
 
 impl CapabilitiesQuery {
     /// The capabilities that were detected by this query
     pub fn capabilities(&self) -> CapabilitySet {
+        let type_type = self.type_type.as_ref();
+        let input_value_type = self.input_value_type.as_ref();
+        let field_type = self.field_type.as_ref();
+        let directive_type = self.directive_type.as_ref();
+
+        let has_field =
+            |ty: Option<&Type>, field: &str| ty.and_then(|ty| ty.field(field)).is_some();
+        let has_argument = |ty: Option<&Type>, field: &str, argument: &str| {
+            ty.and_then(|ty| ty.field(field))
+                .is_some_and(|field| field.has_argument(argument))
+        };
+
         CapabilitySet {
             specification_version: self.version_supported(),
-            supports_oneof: self.supports_oneof(),
+            supports_oneof: has_field(type_type, "isOneOf"),
+            supports_specified_by_url: has_field(type_type, "specifiedByURL"),
+            supports_repeatable_directives: has_field(directive_type, "isRepeatable"),
+            supports_argument_deprecation: has_argument(field_type, "args", "includeDeprecated")
+                && has_argument(directive_type, "args", "includeDeprecated"),
+            supports_input_field_deprecation: has_argument(
+                type_type,
+                "inputFields",
+                "includeDeprecated",
+            ),
+            supports_input_value_deprecation: has_field(input_value_type, "isDeprecated"),
+            supports_federation: self.service_type.is_some(),
         }
     }
 
@@ -77,13 +112,6 @@ impl CapabilitiesQuery {
             _ => SpecificationVersion::June2018,
         }
     }
-
-    fn supports_oneof(&self) -> bool {
-        self.type_type
-            .as_ref()
-            .map(|type_type| type_type.fields.iter().any(|field| field.name == "isOneOf"))
-            .unwrap_or_default()
-    }
 }
 
 /// Versions of the GraphQL specification that the CapabilitiesQuery can detect.
@@ -117,6 +145,36 @@ struct Type {
 struct Field {
     /// The name of the field
     name: String,
+    /// The arguments the field accepts
+    args: Vec<InputValue>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "__InputValue")]
+/// Represents an argument of a field
+struct InputValue {
+    /// The name of the argument
+    name: String,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "__Type")]
+/// A type that we only need to know exists
+struct NamedType {
+    #[allow(dead_code)]
+    name: Option<String>,
+}
+
+impl Type {
+    fn field(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+impl Field {
+    fn has_argument(&self, name: &str) -> bool {
+        self.args.iter().any(|argument| argument.name == name)
+    }
 }
 
 #[cfg(test)]
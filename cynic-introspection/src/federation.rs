@@ -0,0 +1,69 @@
+//! Defines a query for fetching the SDL of an Apollo Federation subgraph.
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    graphql_type = "Query",
+    schema = "federation",
+    schema_module = "schema"
+)]
+/// A query that fetches the SDL of an [Apollo Federation][1] subgraph.
+///
+/// Standard introspection doesn't expose the directives a schema uses, so
+/// the result of an [IntrospectionQuery](crate::IntrospectionQuery) will be
+/// missing federation directives like `@key`, `@external` or `@requires`.
+/// Subgraphs expose their full SDL on the `_service` field, which this
+/// query fetches.
+///
+/// ```rust,no_run
+/// use cynic::{QueryBuilder, http::ReqwestBlockingExt};
+/// use cynic_introspection::FederationSdlQuery;
+///
+/// let data = reqwest::blocking::Client::new()
+///     .post("https://example.com/graphql")
+///     .run_graphql(FederationSdlQuery::build(()))
+///     .unwrap()
+///     .data
+///     .unwrap();
+///
+/// println!("{}", data.sdl().unwrap());
+/// ```
+///
+/// [1]: https://www.apollographql.com/docs/federation/
+pub struct FederationSdlQuery {
+    /// The subgraph's `_Service`
+    #[cynic(rename = "_service")]
+    pub service: Service,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    graphql_type = "_Service",
+    schema = "federation",
+    schema_module = "schema"
+)]
+/// Details about a subgraph
+pub struct Service {
+    /// The SDL of the subgraph, including any federation directives
+    pub sdl: Option<String>,
+}
+
+impl FederationSdlQuery {
+    /// The SDL of the subgraph, if the server returned any
+    pub fn sdl(&self) -> Option<&str> {
+        self.service.sdl.as_deref()
+    }
+
+    /// Parses the SDL of the subgraph into a `cynic_parser::TypeSystemDocument`
+    #[cfg(feature = "parser")]
+    pub fn type_system_document(
+        &self,
+    ) -> Result<cynic_parser::TypeSystemDocument, crate::SchemaError> {
+        let sdl = self.sdl().ok_or(crate::SchemaError::MissingFederationSdl)?;
+
+        cynic_parser::parse_type_system_document(sdl)
+            .map_err(|error| crate::SchemaError::InvalidSdl(error.to_string()))
+    }
+}
+
+#[cynic::schema("federation")]
+mod schema {}
@@ -109,6 +109,39 @@
 //! # }
 //! ```
 //!
+//! ### Converting to & from cynic-parser
+//!
+//! With the `parser` feature enabled a [Schema] can be converted into a
+//! `cynic_parser::TypeSystemDocument` with [Schema::to_type_system_document],
+//! and a `TypeSystemDocument` can be converted into a [Schema] with
+//! `Schema::try_from`:
+//!
+//! ```rust
+//! use cynic_introspection::Schema;
+//!
+//! let document =
+//!     cynic_parser::parse_type_system_document("type Query { hello: String }").unwrap();
+//! let schema = Schema::try_from(&document).unwrap();
+//!
+//! assert_eq!(schema.query_type, "Query");
+//! assert_eq!(schema.to_sdl(), "type Query {\n  hello: String\n}\n\n");
+//! ```
+//!
+//! ### Generating introspection responses
+//!
+//! [Schema::to_introspection_json] does the reverse of running an
+//! [IntrospectionQuery]: it returns the JSON a server with a given schema &
+//! [CapabilitySet] would respond with.  Combined with the conversion above this
+//! can be used to create introspection fixtures from an SDL file.
+//!
+//! ### Federation
+//!
+//! Introspection doesn't expose which directives a schema uses, so the
+//! federation directives of an Apollo Federation subgraph are lost when it's
+//! introspected.  [FederationSdlQuery] fetches a subgraph's SDL from its
+//! `_service` field instead.  [CapabilitySet::supports_federation] can be used
+//! to check whether a server supports this query.
+//!
 //! [1]: http://spec.graphql.org/October2021/#sec-Introspection
 //! [2]: https://cynic-rs.dev
 //! [3]: http://spec.graphql.org/June2018
@@ -116,6 +149,7 @@
 
 mod capabilities;
 mod detection;
+pub mod federation;
 pub mod query;
 mod query_builder;
 mod schema;
@@ -124,6 +158,8 @@ pub use capabilities::CapabilitySet;
 #[doc(inline)]
 pub use detection::{CapabilitiesQuery, SpecificationVersion};
 #[doc(inline)]
+pub use federation::FederationSdlQuery;
+#[doc(inline)]
 pub use query::{DirectiveLocation, IntrospectionQuery};
 
 pub use schema::*;
@@ -41,12 +41,12 @@ pub struct Directive {
     /// A description of the directive
     pub description: Option<String>,
     /// Any arguments that can be provided to the directive
-    #[arguments(#[feature = "2025"] includeDeprecated: true)]
+    #[arguments(#[feature = "argumentDeprecation"] includeDeprecated: true)]
     pub args: Vec<InputValue>,
     /// The locations where the directive may be used
     pub locations: Vec<DirectiveLocation>,
     /// Whether the directive is repeatable or not
-    #[cynic(feature = "2021")]
+    #[cynic(feature = "isRepeatable")]
     pub is_repeatable: bool,
 }
 
@@ -67,6 +67,7 @@ pub struct Type {
     #[arguments(includeDeprecated: true)]
     pub fields: Option<Vec<Field>>,
     /// The input fields of the type, if it is an input object
+    #[arguments(#[feature = "inputFieldDeprecation"] includeDeprecated: true)]
     pub input_fields: Option<Vec<InputValue>>,
     /// Any interfaces this type implements, if it is an object or interface
     pub interfaces: Option<Vec<NamedType>>,
@@ -77,7 +78,7 @@ pub struct Type {
     /// or the set of types that implement this interface if it is an interface
     pub possible_types: Option<Vec<NamedType>>,
     /// A URL pointing to a specification for this scalar, if there is one
-    #[cynic(rename = "specifiedByURL", feature = "2021")]
+    #[cynic(rename = "specifiedByURL", feature = "specifiedByURL")]
     pub specified_by_url: Option<String>,
     /// Whether this type is a oneOf input object.
     ///
@@ -110,7 +111,7 @@ pub struct Field {
     /// A description of the field
     pub description: Option<String>,
     /// A list of arguments this field accepts.
-    #[arguments(#[feature = "2025"] includeDeprecated: true)]
+    #[arguments(#[feature = "argumentDeprecation"] includeDeprecated: true)]
     pub args: Vec<InputValue>,
     /// The type of value returned by this field
     #[cynic(rename = "type")]
@@ -134,19 +135,23 @@ pub struct InputValue {
     pub ty: FieldType,
     /// An optional default value for this field, represented as a GraphQL literal
     pub default_value: Option<String>,
-    #[cynic(feature = "2025")]
+    #[cynic(feature = "inputValueDeprecation")]
     /// Whether this field is deprecated and should no longer be used.
     pub is_deprecated: bool,
-    #[cynic(feature = "2025")]
+    #[cynic(feature = "inputValueDeprecation")]
     /// Optionally provides a reason why this field is deprecated
     pub deprecation_reason: Option<String>,
 }
 
-#[derive(cynic::QueryFragment, Debug)]
-#[cynic(graphql_type = "__Type")]
+#[derive(serde::Deserialize)]
+#[serde(from = "RawFieldType")]
 /// The type of a [`Field`].
 ///
-/// This may be either a wrapper or a named type, depending on the field in question
+/// This may be either a wrapper or a named type, depending on the field in question.
+///
+/// This selects `ofType` recursively, to the depth provided to
+/// [`IntrospectionQuery::with_type_depth`] (or
+/// [`IntrospectionQuery::DEFAULT_TYPE_DEPTH`] if that wasn't used).
 pub struct FieldType {
     /// The kind of type this `Type` is describing
     pub kind: TypeKind,
@@ -157,8 +162,77 @@ pub struct FieldType {
     pub name: Option<String>,
     /// If `kind` is [TypeKind::List] or [TypeKind::NonNull] this contains the type
     /// that is wrapped.
-    #[cynic(recurse = 6)]
     pub of_type: Option<Box<FieldType>>,
+    /// Whether the query selected `ofType` for this type.  This is false for the
+    /// innermost type the query selects, so we can tell when a type reference is
+    /// deeper than the query
+    pub(crate) of_type_selected: bool,
+}
+
+impl cynic::QueryFragment for FieldType {
+    type SchemaType = schema::__Type;
+    type VariablesFields = ();
+
+    const TYPE: Option<&'static str> = Some("__Type");
+
+    fn query(mut builder: cynic::queries::SelectionBuilder<'_, Self::SchemaType, ()>) {
+        use schema::__fields::__Type;
+
+        builder.select_field::<__Type::kind, schema::__TypeKind>();
+        builder.select_field::<__Type::name, Option<schema::String>>();
+
+        // IntrospectionQuery::with_type_depth overrides this with a recurse limit
+        if let Some(mut field_builder) = builder.recurse::<__Type::ofType, Option<schema::__Type>>(
+            IntrospectionQuery::DEFAULT_TYPE_DEPTH,
+        ) {
+            <Option<Box<FieldType>> as cynic::QueryFragment>::query(
+                field_builder.select_children(),
+            );
+        }
+    }
+
+    fn name() -> Option<std::borrow::Cow<'static, str>> {
+        Some(std::borrow::Cow::Borrowed("FieldType"))
+    }
+}
+
+impl std::fmt::Debug for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FieldType")
+            .field("kind", &self.kind)
+            .field("name", &self.name)
+            .field("of_type", &self.of_type)
+            .finish()
+    }
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFieldType {
+    kind: TypeKind,
+    name: Option<String>,
+    // The outer option is `None` if `ofType` wasn't in the response at all
+    #[serde(default, deserialize_with = "deserialize_present")]
+    of_type: Option<Option<Box<FieldType>>>,
+}
+
+impl From<RawFieldType> for FieldType {
+    fn from(raw: RawFieldType) -> Self {
+        FieldType {
+            kind: raw.kind,
+            name: raw.name,
+            of_type_selected: raw.of_type.is_some(),
+            of_type: raw.of_type.flatten(),
+        }
+    }
+}
+
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
 }
 
 #[derive(cynic::QueryFragment, Debug)]
@@ -195,6 +269,32 @@ pub enum DirectiveLocation {
     InputFieldDefinition,
 }
 
+impl DirectiveLocation {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            DirectiveLocation::Query => "QUERY",
+            DirectiveLocation::Mutation => "MUTATION",
+            DirectiveLocation::Subscription => "SUBSCRIPTION",
+            DirectiveLocation::Field => "FIELD",
+            DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+            DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+            DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+            DirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
+            DirectiveLocation::Schema => "SCHEMA",
+            DirectiveLocation::Scalar => "SCALAR",
+            DirectiveLocation::Object => "OBJECT",
+            DirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
+            DirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
+            DirectiveLocation::Interface => "INTERFACE",
+            DirectiveLocation::Union => "UNION",
+            DirectiveLocation::Enum => "ENUM",
+            DirectiveLocation::EnumValue => "ENUM_VALUE",
+            DirectiveLocation::InputObject => "INPUT_OBJECT",
+            DirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
+        }
+    }
+}
+
 #[derive(cynic::Enum, Clone, Copy, Debug)]
 #[cynic(graphql_type = "__TypeKind")]
 /// The "kind" of a type
@@ -222,6 +322,21 @@ pub enum TypeKind {
     NonNull,
 }
 
+impl TypeKind {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            TypeKind::Scalar => "SCALAR",
+            TypeKind::Object => "OBJECT",
+            TypeKind::Interface => "INTERFACE",
+            TypeKind::Union => "UNION",
+            TypeKind::Enum => "ENUM",
+            TypeKind::InputObject => "INPUT_OBJECT",
+            TypeKind::List => "LIST",
+            TypeKind::NonNull => "NON_NULL",
+        }
+    }
+}
+
 #[cynic::schema("introspection")]
 pub(crate) mod schema {}
 
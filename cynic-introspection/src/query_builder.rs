@@ -1,23 +1,60 @@
 use cynic::{Operation, OperationBuilder};
 
-use crate::{CapabilitySet, IntrospectionQuery, SpecificationVersion};
+use crate::{CapabilitySet, IntrospectionQuery};
 
 impl IntrospectionQuery {
+    /// The number of levels of `ofType` an IntrospectionQuery selects by default.
+    ///
+    /// This is enough for types like `[[Int!]!]!`, but types with more wrappers
+    /// will need to use [`IntrospectionQuery::with_type_depth`]
+    pub const DEFAULT_TYPE_DEPTH: u8 = 6;
+
+    /// The maximum number of levels of `ofType` an IntrospectionQuery can select
+    pub const MAX_TYPE_DEPTH: u8 = 16;
+
     /// Builds an IntrospectionQuery for a server with the given capabilities
     pub fn with_capabilities(capabilities: CapabilitySet) -> Operation<Self, ()> {
+        Self::with_type_depth(capabilities, Self::DEFAULT_TYPE_DEPTH)
+    }
+
+    /// Builds an IntrospectionQuery for a server with the given capabilities,
+    /// selecting `type_depth` levels of `ofType` for every type reference.
+    ///
+    /// Type references with more wrapping types than this will fail to convert
+    /// with [`SchemaError::TruncatedTypeReference`](crate::SchemaError::TruncatedTypeReference).
+    /// `type_depth` is clamped between 1 and [`IntrospectionQuery::MAX_TYPE_DEPTH`].
+    pub fn with_type_depth(capabilities: CapabilitySet, type_depth: u8) -> Operation<Self, ()> {
         let mut builder = OperationBuilder::query().with_variables(());
-        match capabilities.specification_version {
-            SpecificationVersion::Unknown | SpecificationVersion::June2018 => {}
-            SpecificationVersion::October2021 => builder.enable_feature("2021"),
-            SpecificationVersion::September2025 => {
-                builder.enable_feature("2021");
-                builder.enable_feature("2025");
-                builder.enable_feature("oneOf");
-            }
-        }
-        if capabilities.supports_oneof {
-            builder.enable_feature("oneOf");
+        for feature in capabilities.query_features() {
+            builder.enable_feature(feature);
         }
+        builder.set_recurse_limit::<crate::query::schema::__fields::__Type::ofType>(
+            type_depth.clamp(1, Self::MAX_TYPE_DEPTH),
+        );
         builder.build().expect("to succeed")
     }
 }
+
+impl CapabilitySet {
+    /// The features of `IntrospectionQuery` that should be enabled for a
+    /// server with these capabilities
+    pub(crate) fn query_features(&self) -> Vec<&'static str> {
+        [
+            (self.supports_specified_by_url, "specifiedByURL"),
+            (self.supports_repeatable_directives, "isRepeatable"),
+            (self.supports_argument_deprecation, "argumentDeprecation"),
+            (
+                self.supports_input_field_deprecation,
+                "inputFieldDeprecation",
+            ),
+            (
+                self.supports_input_value_deprecation,
+                "inputValueDeprecation",
+            ),
+            (self.supports_oneof, "oneOf"),
+        ]
+        .into_iter()
+        .filter_map(|(supported, feature)| supported.then_some(feature))
+        .collect()
+    }
+}
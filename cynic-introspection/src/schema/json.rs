@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+use crate::{CapabilitySet, SpecificationVersion, query::TypeKind};
+
+use super::{
+    Deprecated, Directive, EnumValue, Field, FieldType, InputValue, Schema, SchemaError, Type,
+    WrappingType,
+};
+
+impl Schema {
+    /// Returns the JSON a server with this schema would respond to an
+    /// [`IntrospectionQuery`](crate::IntrospectionQuery) with, for a server with
+    /// the given capabilities.
+    ///
+    /// This is the `data` of the response: an object with a single `__schema`
+    /// field.  Only the fields that `IntrospectionQuery::with_capabilities`
+    /// would select are present, so the output can be used as a fixture for
+    /// tools that consume introspection results.
+    pub fn to_introspection_json(&self, capabilities: CapabilitySet) -> Result<Value, SchemaError> {
+        let writer = JsonWriter {
+            kinds: self.types.iter().map(|ty| (ty.name(), ty.kind())).collect(),
+            capabilities,
+        };
+
+        let named_type = |name: &Option<String>| name.as_ref().map(|name| json!({ "name": name }));
+
+        Ok(json!({
+            "__schema": {
+                "queryType": { "name": self.query_type },
+                "mutationType": named_type(&self.mutation_type),
+                "subscriptionType": named_type(&self.subscription_type),
+                "types": self
+                    .types
+                    .iter()
+                    .map(|ty| writer.ty(ty))
+                    .collect::<Result<Vec<_>, _>>()?,
+                "directives": self
+                    .directives
+                    .iter()
+                    .map(|directive| writer.directive(directive))
+                    .collect::<Result<Vec<_>, _>>()?,
+            }
+        }))
+    }
+}
+
+impl Type {
+    fn kind(&self) -> TypeKind {
+        match self {
+            Type::Object(_) => TypeKind::Object,
+            Type::InputObject(_) => TypeKind::InputObject,
+            Type::Enum(_) => TypeKind::Enum,
+            Type::Interface(_) => TypeKind::Interface,
+            Type::Union(_) => TypeKind::Union,
+            Type::Scalar(_) => TypeKind::Scalar,
+        }
+    }
+}
+
+struct JsonWriter<'a> {
+    kinds: HashMap<&'a str, TypeKind>,
+    capabilities: CapabilitySet,
+}
+
+impl JsonWriter<'_> {
+    fn ty(&self, ty: &Type) -> Result<Value, SchemaError> {
+        let named_types =
+            |names: &[String]| Value::from_iter(names.iter().map(|name| json!({ "name": name })));
+
+        let mut fields = Value::Null;
+        let mut input_fields = Value::Null;
+        let mut interfaces = Value::Null;
+        let mut enum_values = Value::Null;
+        let mut possible_types = Value::Null;
+        let mut specified_by_url = Value::Null;
+        let mut is_one_of = Value::Null;
+        let description;
+
+        match ty {
+            Type::Object(object) => {
+                description = &object.description;
+                fields = self.fields(&object.fields)?;
+                interfaces = named_types(&object.interfaces);
+            }
+            Type::Interface(interface) => {
+                description = &interface.description;
+                fields = self.fields(&interface.fields)?;
+                if self.capabilities.specification_version >= SpecificationVersion::October2021 {
+                    // Interfaces implementing interfaces was added in the 2021 spec
+                    interfaces = named_types(&interface.interfaces);
+                }
+                possible_types = named_types(&interface.possible_types);
+            }
+            Type::Union(union) => {
+                description = &union.description;
+                possible_types = named_types(&union.possible_types);
+            }
+            Type::Enum(enum_type) => {
+                description = &enum_type.description;
+                enum_values = Value::from_iter(enum_type.values.iter().map(enum_value));
+            }
+            Type::InputObject(input_object) => {
+                description = &input_object.description;
+                input_fields = self.input_values(
+                    &input_object.fields,
+                    self.capabilities.supports_input_field_deprecation,
+                )?;
+                is_one_of = input_object.is_one_of.into();
+            }
+            Type::Scalar(scalar) => {
+                description = &scalar.description;
+                specified_by_url = scalar.specified_by_url.clone().into();
+            }
+        }
+
+        let mut output = json!({
+            "kind": ty.kind().as_str(),
+            "name": ty.name(),
+            "description": description,
+            "fields": fields,
+            "inputFields": input_fields,
+            "interfaces": interfaces,
+            "enumValues": enum_values,
+            "possibleTypes": possible_types,
+        });
+        if self.capabilities.supports_specified_by_url {
+            output["specifiedByURL"] = specified_by_url;
+        }
+        if self.capabilities.supports_oneof {
+            output["isOneOf"] = is_one_of;
+        }
+
+        Ok(output)
+    }
+
+    fn fields(&self, fields: &[Field]) -> Result<Value, SchemaError> {
+        fields
+            .iter()
+            .map(|field| {
+                let mut output = json!({
+                    "name": field.name,
+                    "description": field.description,
+                    "args": self.input_values(
+                        &field.args,
+                        self.capabilities.supports_argument_deprecation,
+                    )?,
+                    "type": self.type_ref(&field.ty)?,
+                });
+                add_deprecation(&mut output, &field.deprecated);
+                Ok(output)
+            })
+            .collect()
+    }
+
+    fn input_values(
+        &self,
+        values: &[InputValue],
+        include_deprecated: bool,
+    ) -> Result<Value, SchemaError> {
+        values
+            .iter()
+            .filter(|value| include_deprecated || value.deprecated == Deprecated::No)
+            .map(|value| {
+                let mut output = json!({
+                    "name": value.name,
+                    "description": value.description,
+                    "type": self.type_ref(&value.ty)?,
+                    "defaultValue": value.default_value,
+                });
+                if self.capabilities.supports_input_value_deprecation {
+                    add_deprecation(&mut output, &value.deprecated);
+                }
+                Ok(output)
+            })
+            .collect()
+    }
+
+    fn directive(&self, directive: &Directive) -> Result<Value, SchemaError> {
+        let mut output = json!({
+            "name": directive.name,
+            "description": directive.description,
+            "args": self.input_values(
+                &directive.args,
+                self.capabilities.supports_argument_deprecation,
+            )?,
+            "locations": directive
+                .locations
+                .iter()
+                .map(|location| location.as_str())
+                .collect::<Vec<_>>(),
+        });
+        if self.capabilities.supports_repeatable_directives {
+            output["isRepeatable"] = directive.is_repeatable.into();
+        }
+        Ok(output)
+    }
+
+    fn type_ref(&self, ty: &FieldType) -> Result<Value, SchemaError> {
+        let kind = self
+            .kinds
+            .get(ty.name.as_str())
+            .ok_or_else(|| SchemaError::UndefinedType(ty.name.clone()))?;
+
+        let mut output = json!({ "kind": kind.as_str(), "name": ty.name, "ofType": null });
+        for wrapper in ty
+            .wrapping
+            .into_iter()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+        {
+            let kind = match wrapper {
+                WrappingType::List => TypeKind::List,
+                WrappingType::NonNull => TypeKind::NonNull,
+            };
+            output = json!({ "kind": kind.as_str(), "name": null, "ofType": output });
+        }
+
+        Ok(output)
+    }
+}
+
+fn enum_value(value: &EnumValue) -> Value {
+    let mut output = json!({
+        "name": value.name,
+        "description": value.description,
+    });
+    add_deprecation(&mut output, &value.deprecated);
+    output
+}
+
+fn add_deprecation(output: &mut Value, deprecated: &Deprecated) {
+    let (is_deprecated, reason) = match deprecated {
+        Deprecated::No => (false, None),
+        Deprecated::Yes(reason) => (true, reason.as_deref()),
+    };
+    output["isDeprecated"] = is_deprecated.into();
+    output["deprecationReason"] = reason.into();
+}
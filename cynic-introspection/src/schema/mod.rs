@@ -1,5 +1,8 @@
 use crate::query::DirectiveLocation;
 
+mod json;
+#[cfg(feature = "parser")]
+mod parser;
 #[cfg(feature = "sdl")]
 mod sdl;
 
@@ -273,7 +276,7 @@ impl std::fmt::Display for FieldType {
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 /// A list of [WrappingType]s that wrap a [FieldType]
-pub struct FieldWrapping([u8; 8]);
+pub struct FieldWrapping([u8; 16]);
 
 impl std::fmt::Debug for FieldWrapping {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -330,9 +333,36 @@ pub enum SchemaError {
     /// Found a wrapping type that was too nested
     #[error("Found a wrapping type that was too nested")]
     TooMuchWrapping,
+    /// Found a type reference that was cut off before reaching a named type.
+    ///
+    /// This usually means the type had more wrapping types than the
+    /// `IntrospectionQuery` asked for - `IntrospectionQuery::with_type_depth`
+    /// can be used to ask for more.
+    #[error(
+        "Found a type reference that was truncated after {0} wrapping types.  Try running the introspection query with a larger type depth"
+    )]
+    TruncatedTypeReference(usize),
     /// The introspection query didn't return results
     #[error("The introspection query returned no data.  Try looking in the response for errors")]
     IntrospectionQueryFailed,
+    /// The schema had no query type
+    #[error("Could not find the query type for the schema")]
+    MissingQueryType,
+    /// A default value could not be parsed as a GraphQL literal
+    #[error("Could not parse the default value {0}")]
+    InvalidDefaultValue(String),
+    /// A type reference pointed at a type that isn't defined in the schema
+    #[error("Found a reference to a type that isn't defined in the schema: {0}")]
+    UndefinedType(String),
+    /// A federation subgraph didn't return any SDL
+    #[error("The server didn't return any SDL from _service")]
+    MissingFederationSdl,
+    /// The SDL returned by a federation subgraph could not be parsed
+    #[error("Could not parse the SDL returned by the server: {0}")]
+    InvalidSdl(String),
+    /// A `TypeSystemDocument` contained conflicting definitions
+    #[error("The document contains conflicting definitions: {0}")]
+    InvalidDocument(String),
 }
 
 impl TryFrom<crate::query::IntrospectedSchema> for Schema {
@@ -467,7 +497,7 @@ impl TryFrom<crate::query::FieldType> for FieldType {
     type Error = SchemaError;
 
     fn try_from(field_type: crate::query::FieldType) -> Result<Self, Self::Error> {
-        let mut wrapping = [0; 8];
+        let mut wrapping = [0; 16];
         let mut wrapping_pos = 0;
         let mut current_ty = field_type;
         loop {
@@ -478,16 +508,12 @@ impl TryFrom<crate::query::FieldType> for FieldType {
                 crate::query::TypeKind::List => {
                     wrapping[wrapping_pos] = 1;
                     wrapping_pos += 1;
-                    current_ty = *current_ty
-                        .of_type
-                        .ok_or(SchemaError::WrappingTypeWithNoInner)?;
+                    current_ty = current_ty.into_inner_type(wrapping_pos)?;
                 }
                 crate::query::TypeKind::NonNull => {
                     wrapping[wrapping_pos] = 2;
                     wrapping_pos += 1;
-                    current_ty = *current_ty
-                        .of_type
-                        .ok_or(SchemaError::WrappingTypeWithNoInner)?;
+                    current_ty = current_ty.into_inner_type(wrapping_pos)?;
                 }
                 _ => {
                     return Ok(FieldType {
@@ -500,6 +526,20 @@ impl TryFrom<crate::query::FieldType> for FieldType {
     }
 }
 
+impl crate::query::FieldType {
+    fn into_inner_type(self, wrapping_pos: usize) -> Result<Self, SchemaError> {
+        match self.of_type {
+            Some(of_type) => Ok(*of_type),
+            // If the query didn't select ofType it ran out of depth before
+            // reaching the named type, otherwise the server returned null
+            None if !self.of_type_selected => {
+                Err(SchemaError::TruncatedTypeReference(wrapping_pos))
+            }
+            None => Err(SchemaError::WrappingTypeWithNoInner),
+        }
+    }
+}
+
 impl TryFrom<crate::query::InputValue> for InputValue {
     type Error = SchemaError;
 
@@ -565,8 +605,11 @@ mod tests {
                     kind: query::TypeKind::Scalar,
                     name: Some("Int".into()),
                     of_type: None,
+                    of_type_selected: true,
                 })),
+                of_type_selected: true,
             })),
+            of_type_selected: true,
         })
         .unwrap();
 
@@ -576,9 +619,42 @@ mod tests {
             kind: query::TypeKind::Object,
             name: Some("MyObject".into()),
             of_type: None,
+            of_type_selected: true,
         })
         .unwrap();
 
         assert_eq!(ty.to_string(), "MyObject");
     }
+
+    #[test]
+    fn test_truncated_field_type() {
+        // A response where the query stopped selecting ofType
+        let field_type = serde_json::from_value::<query::FieldType>(serde_json::json!({
+            "kind": "NON_NULL",
+            "name": null,
+            "ofType": {"kind": "LIST", "name": null}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            FieldType::try_from(field_type).unwrap_err(),
+            SchemaError::TruncatedTypeReference(2)
+        );
+    }
+
+    #[test]
+    fn test_wrapping_type_with_no_inner() {
+        // A response where the server returned a null ofType
+        let field_type = serde_json::from_value::<query::FieldType>(serde_json::json!({
+            "kind": "NON_NULL",
+            "name": null,
+            "ofType": {"kind": "LIST", "name": null, "ofType": null}
+        }))
+        .unwrap();
+
+        assert_eq!(
+            FieldType::try_from(field_type).unwrap_err(),
+            SchemaError::WrappingTypeWithNoInner
+        );
+    }
 }
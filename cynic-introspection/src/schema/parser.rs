@@ -0,0 +1,610 @@
+use std::collections::HashMap;
+
+use cynic_parser::{
+    ConstValue, TypeSystemDocument,
+    common::{OperationType, WrappingType as ParserWrappingType},
+    type_system::{self as parser, Definition, TypeDefinition, builder::*},
+};
+
+use crate::query::DirectiveLocation;
+
+use super::{
+    Deprecated, Directive, EnumType, EnumValue, Field, FieldType, FieldWrapping, InputObjectType,
+    InputValue, InterfaceType, ObjectType, ScalarType, Schema, SchemaError, Type, UnionType,
+    WrappingType,
+};
+
+/// The scalars & directives that every GraphQL schema has, whether or not
+/// they're written down in its SDL
+const BUILTINS: &str = r#"
+scalar String
+scalar Int
+scalar Float
+scalar Boolean
+scalar ID
+
+directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @include(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+directive @deprecated(
+  reason: String = "No longer supported"
+) on FIELD_DEFINITION | ARGUMENT_DEFINITION | INPUT_FIELD_DEFINITION | ENUM_VALUE
+directive @specifiedBy(url: String!) on SCALAR
+directive @oneOf on INPUT_OBJECT
+"#;
+
+/// The reason a server reports for a bare `@deprecated`, as it's the default
+/// value of the `reason` argument
+const DEFAULT_DEPRECATION_REASON: &str = "No longer supported";
+
+/// The schema of the introspection types, which every server also exposes
+const INTROSPECTION_SCHEMA: &str = include_str!("../schema.graphql");
+
+impl Schema {
+    /// Converts this schema into a [`cynic_parser::TypeSystemDocument`]
+    ///
+    /// Like [`Schema::to_sdl`] this leaves out the introspection types and
+    /// any built in scalars & directives, as these are implicitly part of
+    /// every schema.
+    pub fn to_type_system_document(&self) -> Result<TypeSystemDocument, SchemaError> {
+        let mut builder = TypeSystemDocumentBuilder::new();
+
+        if !self.has_conventional_root_types() {
+            let mut schema = SchemaDefinitionBuilder::new()
+                .root_operation(OperationType::Query, self.query_type.as_str());
+            if let Some(mutation_type) = &self.mutation_type {
+                schema = schema.root_operation(OperationType::Mutation, mutation_type.as_str());
+            }
+            if let Some(subscription_type) = &self.subscription_type {
+                schema =
+                    schema.root_operation(OperationType::Subscription, subscription_type.as_str());
+            }
+            builder = builder.definition(schema);
+        }
+
+        for ty in &self.types {
+            if ty.name().starts_with("__")
+                || matches!(ty, Type::Scalar(scalar) if scalar.is_builtin())
+            {
+                continue;
+            }
+            builder = builder.definition(type_definition(ty)?);
+        }
+
+        for directive in &self.directives {
+            if ["skip", "include", "deprecated", "specifiedBy", "oneOf"]
+                .contains(&directive.name.as_str())
+            {
+                continue;
+            }
+            builder = builder.definition(directive_definition(directive)?);
+        }
+
+        Ok(builder.build())
+    }
+
+    /// Whether the root types of this schema can be inferred from their names,
+    /// without a schema definition
+    fn has_conventional_root_types(&self) -> bool {
+        let conventional = |name: &str, root: Option<&String>| match root {
+            Some(root) => root == name,
+            None => !self.types.iter().any(|ty| ty.name() == name),
+        };
+
+        self.query_type == "Query"
+            && conventional("Mutation", self.mutation_type.as_ref())
+            && conventional("Subscription", self.subscription_type.as_ref())
+    }
+}
+
+/// Converts a [`cynic_parser::TypeSystemDocument`] into a `Schema`
+///
+/// Any extensions in the document are merged into their definitions first.
+/// The resulting schema contains everything a server would return from an
+/// introspection query: the introspection types and any built in scalars &
+/// directives the document doesn't define itself are added.
+impl TryFrom<&TypeSystemDocument> for Schema {
+    type Error = SchemaError;
+
+    fn try_from(document: &TypeSystemDocument) -> Result<Self, Self::Error> {
+        let document = document.normalize().map_err(|errors| {
+            SchemaError::InvalidDocument(
+                errors
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            )
+        })?;
+
+        let mut query_type = None;
+        let mut mutation_type = None;
+        let mut subscription_type = None;
+        let mut has_schema_definition = false;
+        for definition in document.definitions() {
+            let Definition::Schema(schema) = definition else {
+                continue;
+            };
+            has_schema_definition = true;
+            for operation in schema.root_operations() {
+                let name = Some(operation.named_type().to_string());
+                match operation.operation_type() {
+                    OperationType::Query => query_type = name,
+                    OperationType::Mutation => mutation_type = name,
+                    OperationType::Subscription => subscription_type = name,
+                }
+            }
+        }
+
+        let type_definitions = document
+            .definitions()
+            .filter_map(|definition| match definition {
+                Definition::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        if !has_schema_definition {
+            let find = |name: &str| {
+                type_definitions
+                    .iter()
+                    .find(|ty| ty.name() == name)
+                    .map(|ty| ty.name().to_string())
+            };
+            query_type = find("Query");
+            mutation_type = find("Mutation");
+            subscription_type = find("Subscription");
+        }
+
+        let mut possible_types = HashMap::<&str, Vec<String>>::new();
+        for ty in &type_definitions {
+            if let TypeDefinition::Object(object) = ty {
+                for interface in object.implements_interfaces() {
+                    possible_types
+                        .entry(interface)
+                        .or_default()
+                        .push(object.name().to_string());
+                }
+            }
+        }
+
+        let mut types = type_definitions
+            .iter()
+            .map(|ty| schema_type(*ty, &possible_types))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut directives = document
+            .directive_definitions()
+            .map(schema_directive)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let builtins = cynic_parser::parse_type_system_document(BUILTINS)
+            .expect("the builtin definitions to be valid");
+        let introspection = cynic_parser::parse_type_system_document(INTROSPECTION_SCHEMA)
+            .expect("the introspection schema to be valid");
+
+        let implicit_types = builtins
+            .definitions()
+            .chain(introspection.definitions())
+            .filter_map(|definition| match definition {
+                Definition::Type(ty @ TypeDefinition::Scalar(_)) => Some(ty),
+                Definition::Type(ty) if ty.name().starts_with("__") => Some(ty),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for ty in implicit_types {
+            if !types.iter().any(|existing| existing.name() == ty.name()) {
+                types.push(schema_type(ty, &HashMap::new())?);
+            }
+        }
+
+        for directive in builtins.directive_definitions() {
+            if !directives
+                .iter()
+                .any(|existing| existing.name == directive.name())
+            {
+                directives.push(schema_directive(directive)?);
+            }
+        }
+
+        Ok(Schema {
+            query_type: query_type.ok_or(SchemaError::MissingQueryType)?,
+            mutation_type,
+            subscription_type,
+            types,
+            directives,
+        })
+    }
+}
+
+impl From<DirectiveLocation> for parser::DirectiveLocation {
+    fn from(location: DirectiveLocation) -> Self {
+        match location {
+            DirectiveLocation::Query => parser::DirectiveLocation::Query,
+            DirectiveLocation::Mutation => parser::DirectiveLocation::Mutation,
+            DirectiveLocation::Subscription => parser::DirectiveLocation::Subscription,
+            DirectiveLocation::Field => parser::DirectiveLocation::Field,
+            DirectiveLocation::FragmentDefinition => parser::DirectiveLocation::FragmentDefinition,
+            DirectiveLocation::FragmentSpread => parser::DirectiveLocation::FragmentSpread,
+            DirectiveLocation::InlineFragment => parser::DirectiveLocation::InlineFragment,
+            DirectiveLocation::VariableDefinition => parser::DirectiveLocation::VariableDefinition,
+            DirectiveLocation::Schema => parser::DirectiveLocation::Schema,
+            DirectiveLocation::Scalar => parser::DirectiveLocation::Scalar,
+            DirectiveLocation::Object => parser::DirectiveLocation::Object,
+            DirectiveLocation::FieldDefinition => parser::DirectiveLocation::FieldDefinition,
+            DirectiveLocation::ArgumentDefinition => parser::DirectiveLocation::ArgumentDefinition,
+            DirectiveLocation::Interface => parser::DirectiveLocation::Interface,
+            DirectiveLocation::Union => parser::DirectiveLocation::Union,
+            DirectiveLocation::Enum => parser::DirectiveLocation::Enum,
+            DirectiveLocation::EnumValue => parser::DirectiveLocation::EnumValue,
+            DirectiveLocation::InputObject => parser::DirectiveLocation::InputObject,
+            DirectiveLocation::InputFieldDefinition => {
+                parser::DirectiveLocation::InputFieldDefinition
+            }
+        }
+    }
+}
+
+impl From<parser::DirectiveLocation> for DirectiveLocation {
+    fn from(location: parser::DirectiveLocation) -> Self {
+        match location {
+            parser::DirectiveLocation::Query => DirectiveLocation::Query,
+            parser::DirectiveLocation::Mutation => DirectiveLocation::Mutation,
+            parser::DirectiveLocation::Subscription => DirectiveLocation::Subscription,
+            parser::DirectiveLocation::Field => DirectiveLocation::Field,
+            parser::DirectiveLocation::FragmentDefinition => DirectiveLocation::FragmentDefinition,
+            parser::DirectiveLocation::FragmentSpread => DirectiveLocation::FragmentSpread,
+            parser::DirectiveLocation::InlineFragment => DirectiveLocation::InlineFragment,
+            parser::DirectiveLocation::VariableDefinition => DirectiveLocation::VariableDefinition,
+            parser::DirectiveLocation::Schema => DirectiveLocation::Schema,
+            parser::DirectiveLocation::Scalar => DirectiveLocation::Scalar,
+            parser::DirectiveLocation::Object => DirectiveLocation::Object,
+            parser::DirectiveLocation::FieldDefinition => DirectiveLocation::FieldDefinition,
+            parser::DirectiveLocation::ArgumentDefinition => DirectiveLocation::ArgumentDefinition,
+            parser::DirectiveLocation::Interface => DirectiveLocation::Interface,
+            parser::DirectiveLocation::Union => DirectiveLocation::Union,
+            parser::DirectiveLocation::Enum => DirectiveLocation::Enum,
+            parser::DirectiveLocation::EnumValue => DirectiveLocation::EnumValue,
+            parser::DirectiveLocation::InputObject => DirectiveLocation::InputObject,
+            parser::DirectiveLocation::InputFieldDefinition => {
+                DirectiveLocation::InputFieldDefinition
+            }
+        }
+    }
+}
+
+// Schema -> TypeSystemDocument
+
+fn type_definition(ty: &Type) -> Result<TypeDefinitionBuilder, SchemaError> {
+    Ok(match ty {
+        Type::Scalar(scalar) => {
+            let mut builder = ScalarDefinitionBuilder::new(scalar.name.as_str());
+            builder.description = scalar.description.clone().map(Into::into);
+            if let Some(url) = &scalar.specified_by_url {
+                builder = builder
+                    .directive(DirectiveBuilder::new("specifiedBy").argument("url", url.as_str()));
+            }
+            builder.into()
+        }
+        Type::Object(object) => ObjectDefinitionBuilder {
+            name: object.name.clone(),
+            description: object.description.clone().map(Into::into),
+            implements_interfaces: object.interfaces.clone(),
+            directives: vec![],
+            fields: object
+                .fields
+                .iter()
+                .map(field_definition)
+                .collect::<Result<_, _>>()?,
+        }
+        .into(),
+        Type::Interface(interface) => InterfaceDefinitionBuilder {
+            name: interface.name.clone(),
+            description: interface.description.clone().map(Into::into),
+            implements_interfaces: interface.interfaces.clone(),
+            directives: vec![],
+            fields: interface
+                .fields
+                .iter()
+                .map(field_definition)
+                .collect::<Result<_, _>>()?,
+        }
+        .into(),
+        Type::Union(union) => UnionDefinitionBuilder {
+            name: union.name.clone(),
+            description: union.description.clone().map(Into::into),
+            directives: vec![],
+            members: union.possible_types.clone(),
+        }
+        .into(),
+        Type::Enum(enum_type) => EnumDefinitionBuilder {
+            name: enum_type.name.clone(),
+            description: enum_type.description.clone().map(Into::into),
+            directives: vec![],
+            values: enum_type
+                .values
+                .iter()
+                .map(|value| EnumValueDefinitionBuilder {
+                    value: value.name.clone(),
+                    description: value.description.clone().map(Into::into),
+                    directives: deprecated_directive(&value.deprecated)
+                        .into_iter()
+                        .collect(),
+                })
+                .collect(),
+        }
+        .into(),
+        Type::InputObject(input_object) => InputObjectDefinitionBuilder {
+            name: input_object.name.clone(),
+            description: input_object.description.clone().map(Into::into),
+            directives: match input_object.is_one_of {
+                true => vec![DirectiveBuilder::new("oneOf")],
+                false => vec![],
+            },
+            fields: input_object
+                .fields
+                .iter()
+                .map(input_value_definition)
+                .collect::<Result<_, _>>()?,
+        }
+        .into(),
+    })
+}
+
+fn directive_definition(directive: &Directive) -> Result<DirectiveDefinitionBuilder, SchemaError> {
+    Ok(DirectiveDefinitionBuilder {
+        name: directive.name.clone(),
+        description: directive.description.clone().map(Into::into),
+        arguments: directive
+            .args
+            .iter()
+            .map(input_value_definition)
+            .collect::<Result<_, _>>()?,
+        is_repeatable: directive.is_repeatable,
+        locations: directive
+            .locations
+            .iter()
+            .map(|location| (*location).into())
+            .collect(),
+    })
+}
+
+fn field_definition(field: &Field) -> Result<FieldDefinitionBuilder, SchemaError> {
+    Ok(FieldDefinitionBuilder {
+        name: field.name.clone(),
+        description: field.description.clone().map(Into::into),
+        ty: type_builder(&field.ty),
+        arguments: field
+            .args
+            .iter()
+            .map(input_value_definition)
+            .collect::<Result<_, _>>()?,
+        directives: deprecated_directive(&field.deprecated)
+            .into_iter()
+            .collect(),
+    })
+}
+
+fn input_value_definition(value: &InputValue) -> Result<InputValueDefinitionBuilder, SchemaError> {
+    Ok(InputValueDefinitionBuilder {
+        name: value.name.clone(),
+        description: value.description.clone().map(Into::into),
+        ty: type_builder(&value.ty),
+        default_value: value
+            .default_value
+            .as_deref()
+            .map(parse_default_value)
+            .transpose()?,
+        directives: deprecated_directive(&value.deprecated)
+            .into_iter()
+            .collect(),
+    })
+}
+
+fn type_builder(ty: &FieldType) -> TypeBuilder {
+    let mut wrappers = ty
+        .wrapping
+        .into_iter()
+        .map(|wrapper| match wrapper {
+            WrappingType::List => ParserWrappingType::List,
+            WrappingType::NonNull => ParserWrappingType::NonNull,
+        })
+        .collect::<Vec<_>>();
+
+    // TypeBuilder wants its wrappers innermost first
+    wrappers.reverse();
+
+    TypeBuilder {
+        name: ty.name.clone(),
+        wrappers,
+    }
+}
+
+fn deprecated_directive(deprecated: &Deprecated) -> Option<DirectiveBuilder> {
+    match deprecated {
+        Deprecated::No => None,
+        Deprecated::Yes(None) => Some(DirectiveBuilder::new("deprecated")),
+        Deprecated::Yes(Some(reason)) => {
+            Some(DirectiveBuilder::new("deprecated").argument("reason", reason.as_str()))
+        }
+    }
+}
+
+/// Introspection provides default values as GraphQL literals in a string.
+///
+/// cynic-parser can only parse whole documents, so we wrap the value up in
+/// one to parse it.
+fn parse_default_value(value: &str) -> Result<ConstValueBuilder, SchemaError> {
+    let invalid = || SchemaError::InvalidDefaultValue(value.to_string());
+
+    let document = cynic_parser::parse_type_system_document(&format!(
+        "input Input {{ field: Input = {value} }}"
+    ))
+    .map_err(|_| invalid())?;
+
+    let mut definitions = document.definitions();
+    let (Some(Definition::Type(TypeDefinition::InputObject(input))), None) =
+        (definitions.next(), definitions.next())
+    else {
+        return Err(invalid());
+    };
+
+    input
+        .fields()
+        .next()
+        .and_then(|field| field.default_value())
+        .map(Into::into)
+        .ok_or_else(invalid)
+}
+
+// TypeSystemDocument -> Schema
+
+fn schema_type(
+    ty: TypeDefinition<'_>,
+    possible_types: &HashMap<&str, Vec<String>>,
+) -> Result<Type, SchemaError> {
+    Ok(match ty {
+        TypeDefinition::Scalar(scalar) => Type::Scalar(ScalarType {
+            name: scalar.name().to_string(),
+            description: description(scalar.description()),
+            specified_by_url: scalar
+                .directives()
+                .find(|directive| directive.name() == "specifiedBy")
+                .and_then(|directive| string_argument(directive, "url")),
+        }),
+        TypeDefinition::Object(object) => Type::Object(ObjectType {
+            name: object.name().to_string(),
+            description: description(object.description()),
+            fields: object
+                .fields()
+                .map(schema_field)
+                .collect::<Result<_, _>>()?,
+            interfaces: object
+                .implements_interfaces()
+                .map(ToString::to_string)
+                .collect(),
+        }),
+        TypeDefinition::Interface(interface) => Type::Interface(InterfaceType {
+            name: interface.name().to_string(),
+            description: description(interface.description()),
+            fields: interface
+                .fields()
+                .map(schema_field)
+                .collect::<Result<_, _>>()?,
+            interfaces: interface
+                .implements_interfaces()
+                .map(ToString::to_string)
+                .collect(),
+            possible_types: possible_types
+                .get(interface.name())
+                .cloned()
+                .unwrap_or_default(),
+        }),
+        TypeDefinition::Union(union) => Type::Union(UnionType {
+            name: union.name().to_string(),
+            description: description(union.description()),
+            possible_types: union
+                .members()
+                .map(|member| member.name().to_string())
+                .collect(),
+        }),
+        TypeDefinition::Enum(enum_definition) => Type::Enum(EnumType {
+            name: enum_definition.name().to_string(),
+            description: description(enum_definition.description()),
+            values: enum_definition
+                .values()
+                .map(|value| EnumValue {
+                    name: value.value().to_string(),
+                    description: description(value.description()),
+                    deprecated: deprecated(value.directives()),
+                })
+                .collect(),
+        }),
+        TypeDefinition::InputObject(input_object) => Type::InputObject(InputObjectType {
+            name: input_object.name().to_string(),
+            description: description(input_object.description()),
+            fields: input_object
+                .fields()
+                .map(schema_input_value)
+                .collect::<Result<_, _>>()?,
+            is_one_of: input_object
+                .directives()
+                .any(|directive| directive.name() == "oneOf"),
+        }),
+    })
+}
+
+fn schema_directive(directive: parser::DirectiveDefinition<'_>) -> Result<Directive, SchemaError> {
+    Ok(Directive {
+        name: directive.name().to_string(),
+        description: description(directive.description()),
+        args: directive
+            .arguments()
+            .map(schema_input_value)
+            .collect::<Result<_, _>>()?,
+        is_repeatable: directive.is_repeatable(),
+        locations: directive.locations().map(Into::into).collect(),
+    })
+}
+
+fn schema_field(field: parser::FieldDefinition<'_>) -> Result<Field, SchemaError> {
+    Ok(Field {
+        name: field.name().to_string(),
+        description: description(field.description()),
+        args: field
+            .arguments()
+            .map(schema_input_value)
+            .collect::<Result<_, _>>()?,
+        ty: field_type(field.ty())?,
+        deprecated: deprecated(field.directives()),
+    })
+}
+
+fn schema_input_value(value: parser::InputValueDefinition<'_>) -> Result<InputValue, SchemaError> {
+    Ok(InputValue {
+        name: value.name().to_string(),
+        description: description(value.description()),
+        ty: field_type(value.ty())?,
+        default_value: value.default_value().map(|value| value.to_string()),
+        deprecated: deprecated(value.directives()),
+    })
+}
+
+fn field_type(ty: parser::Type<'_>) -> Result<FieldType, SchemaError> {
+    let mut wrapping = [0; 16];
+    for (i, wrapper) in ty.wrappers().enumerate() {
+        let slot = wrapping.get_mut(i).ok_or(SchemaError::TooMuchWrapping)?;
+        *slot = match wrapper {
+            ParserWrappingType::List => 1,
+            ParserWrappingType::NonNull => 2,
+        };
+    }
+
+    Ok(FieldType {
+        wrapping: FieldWrapping(wrapping),
+        name: ty.name().to_string(),
+    })
+}
+
+fn description(description: Option<parser::Description<'_>>) -> Option<String> {
+    description.map(|description| description.literal().to_cow().into_owned())
+}
+
+fn deprecated<'a>(mut directives: impl Iterator<Item = parser::Directive<'a>>) -> Deprecated {
+    match directives.find(|directive| directive.name() == "deprecated") {
+        None => Deprecated::No,
+        Some(directive) => Deprecated::Yes(Some(
+            string_argument(directive, "reason")
+                .unwrap_or_else(|| DEFAULT_DEPRECATION_REASON.to_string()),
+        )),
+    }
+}
+
+fn string_argument(directive: parser::Directive<'_>, name: &str) -> Option<String> {
+    let argument = directive
+        .arguments()
+        .find(|argument| argument.name() == name)?;
+
+    match argument.value() {
+        ConstValue::String(value) => Some(value.value().to_string()),
+        _ => None,
+    }
+}
@@ -1,8 +1,8 @@
 use std::fmt::{self, Display, Write};
 
 use crate::{
-    Deprecated, DirectiveLocation, EnumType, EnumValue, Field, FieldType, InputObjectType,
-    InputValue, InterfaceType, ObjectType, ScalarType, Type, UnionType,
+    Deprecated, EnumType, EnumValue, Field, FieldType, InputObjectType, InputValue, InterfaceType,
+    ObjectType, ScalarType, Type, UnionType,
 };
 
 use super::{Directive, Schema};
@@ -46,7 +46,9 @@ impl Display for SchemaDisplay<'_> {
         }
 
         for directive in &schema.directives {
-            if ["skip", "include", "deprecated", "specifiedBy"].contains(&directive.name.as_str()) {
+            if ["skip", "include", "deprecated", "specifiedBy", "oneOf"]
+                .contains(&directive.name.as_str())
+            {
                 // Skip built in directives, we don't need those...
                 continue;
             }
@@ -438,32 +440,6 @@ impl DirectiveDisplay<'_> {
     }
 }
 
-impl DirectiveLocation {
-    fn as_str(self) -> &'static str {
-        match self {
-            DirectiveLocation::Query => "QUERY",
-            DirectiveLocation::Mutation => "MUTATION",
-            DirectiveLocation::Subscription => "SUBSCRIPTION",
-            DirectiveLocation::Field => "FIELD",
-            DirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
-            DirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
-            DirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
-            DirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
-            DirectiveLocation::Schema => "SCHEMA",
-            DirectiveLocation::Scalar => "SCALAR",
-            DirectiveLocation::Object => "OBJECT",
-            DirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
-            DirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
-            DirectiveLocation::Interface => "INTERFACE",
-            DirectiveLocation::Union => "UNION",
-            DirectiveLocation::Enum => "ENUM",
-            DirectiveLocation::EnumValue => "ENUM_VALUE",
-            DirectiveLocation::InputObject => "INPUT_OBJECT",
-            DirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
-        }
-    }
-}
-
 pub fn indented<D>(f: &mut D) -> indenter::Indented<'_, D> {
     indenter::indented(f).with_str("  ")
 }
@@ -1,5 +1,5 @@
 use cynic::GraphQlResponse;
-use cynic_introspection::{CapabilitiesQuery, SpecificationVersion};
+use cynic_introspection::{CapabilitiesQuery, IntrospectionQuery, SpecificationVersion};
 
 #[test]
 fn shapshot_test_detection_query() {
@@ -10,13 +10,38 @@ fn shapshot_test_detection_query() {
       __type(name: "__Type") {
         fields(includeDeprecated: true) {
           name
+          args {
+            name
+          }
         }
       }
       input_value_type: __type(name: "__InputValue") {
         fields(includeDeprecated: true) {
           name
+          args {
+            name
+          }
         }
       }
+      field_type: __type(name: "__Field") {
+        fields(includeDeprecated: true) {
+          name
+          args {
+            name
+          }
+        }
+      }
+      directive_type: __type(name: "__Directive") {
+        fields(includeDeprecated: true) {
+          name
+          args {
+            name
+          }
+        }
+      }
+      service_type: __type(name: "_Service") {
+        name
+      }
     }
     "#);
 }
@@ -63,3 +88,87 @@ fn detection_query_with_2025_response() {
         SpecificationVersion::September2025
     );
 }
+
+#[test]
+fn detection_query_capabilities_match_specification_versions() {
+    for (response, version) in [
+        (
+            include_str!("detection-responses/2018.json"),
+            SpecificationVersion::June2018,
+        ),
+        (
+            include_str!("detection-responses/2021.json"),
+            SpecificationVersion::October2021,
+        ),
+        (
+            include_str!("detection-responses/2025.json"),
+            SpecificationVersion::September2025,
+        ),
+    ] {
+        let response =
+            serde_json::from_str::<GraphQlResponse<CapabilitiesQuery>>(response).unwrap();
+
+        assert_eq!(
+            response.data.unwrap().capabilities(),
+            version.capabilities()
+        );
+    }
+}
+
+#[test]
+fn detection_query_with_federation_response() {
+    let response = serde_json::from_str::<GraphQlResponse<CapabilitiesQuery>>(include_str!(
+        "detection-responses/federation.json"
+    ))
+    .unwrap();
+
+    let capabilities = response.data.unwrap().capabilities();
+    assert_eq!(
+        capabilities.version_supported(),
+        SpecificationVersion::October2021
+    );
+    assert!(capabilities.supports_federation());
+    assert!(
+        !SpecificationVersion::October2021
+            .capabilities()
+            .supports_federation()
+    );
+}
+
+#[test]
+fn introspection_query_for_partially_supported_features() {
+    let mut response =
+        serde_json::from_str::<serde_json::Value>(include_str!("detection-responses/2018.json"))
+            .unwrap();
+
+    // A 2018 server that has added isOneOf & isRepeatable, but none of the other features
+    response["data"]["__type"]["fields"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({"name": "isOneOf", "args": []}));
+    response["data"]["directive_type"]["fields"]
+        .as_array_mut()
+        .unwrap()
+        .push(serde_json::json!({"name": "isRepeatable", "args": []}));
+
+    let capabilities = serde_json::from_value::<GraphQlResponse<CapabilitiesQuery>>(response)
+        .unwrap()
+        .data
+        .unwrap()
+        .capabilities();
+
+    assert_eq!(
+        capabilities.version_supported(),
+        SpecificationVersion::June2018
+    );
+    assert!(capabilities.supports_oneof());
+    assert!(capabilities.supports_repeatable_directives());
+    assert!(!capabilities.supports_specified_by_url());
+
+    let query = IntrospectionQuery::with_capabilities(capabilities).query;
+    assert!(query.contains("isOneOf"));
+    assert!(query.contains("isRepeatable"));
+    assert!(!query.contains("specifiedByURL"));
+    assert!(!query.contains("args(includeDeprecated: true)"));
+    assert!(!query.contains("inputFields(includeDeprecated: true)"));
+}
@@ -0,0 +1,67 @@
+use cynic::{GraphQlResponse, QueryBuilder};
+use cynic_introspection::{FederationSdlQuery, SchemaError};
+
+#[test]
+fn snapshot_test_federation_query() {
+    insta::assert_snapshot!(FederationSdlQuery::build(()).query, @r#"
+    query FederationSdlQuery {
+      _service {
+        sdl
+      }
+    }
+    "#);
+}
+
+#[test]
+fn test_federation_sdl_parsing() {
+    let response = serde_json::from_value::<GraphQlResponse<FederationSdlQuery>>(
+        serde_json::json!({
+            "data": {
+                "_service": {
+                    "sdl": "type Query { me: User }\n\ntype User @key(fields: \"id\") {\n  id: ID!\n  name: String @external\n}\n"
+                }
+            }
+        }),
+    )
+    .unwrap();
+
+    let data = response.data.unwrap();
+    let document = data.type_system_document().unwrap();
+
+    let user = document
+        .definitions()
+        .filter_map(|definition| match definition {
+            cynic_parser::type_system::Definition::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .find(|ty| ty.name() == "User")
+        .unwrap();
+
+    assert_eq!(
+        user.directives()
+            .map(|directive| directive.name())
+            .collect::<Vec<_>>(),
+        ["key"]
+    );
+}
+
+#[test]
+fn test_federation_sdl_errors() {
+    let missing = serde_json::from_value::<FederationSdlQuery>(serde_json::json!({
+        "_service": { "sdl": null }
+    }))
+    .unwrap();
+    assert!(matches!(
+        missing.type_system_document(),
+        Err(SchemaError::MissingFederationSdl)
+    ));
+
+    let invalid = serde_json::from_value::<FederationSdlQuery>(serde_json::json!({
+        "_service": { "sdl": "type Query {" }
+    }))
+    .unwrap();
+    assert!(matches!(
+        invalid.type_system_document(),
+        Err(SchemaError::InvalidSdl(_))
+    ));
+}
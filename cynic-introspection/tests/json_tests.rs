@@ -0,0 +1,93 @@
+use cynic_introspection::{IntrospectionQuery, Schema, SpecificationVersion};
+
+#[test]
+fn test_introspection_json_roundtrip() {
+    let sdl = std::fs::read_to_string("../schemas/starwars.schema.graphql").unwrap();
+    let document = cynic_parser::parse_type_system_document(&sdl).unwrap();
+    let schema = Schema::try_from(&document).unwrap();
+
+    let json = schema
+        .to_introspection_json(SpecificationVersion::September2025.capabilities())
+        .unwrap();
+    let query = serde_json::from_value::<IntrospectionQuery>(json).unwrap();
+
+    assert_eq!(query.into_schema().unwrap(), schema);
+}
+
+#[test]
+fn test_introspection_json_for_each_version() {
+    let document = cynic_parser::parse_type_system_document(
+        r#"
+        type Query implements Node {
+          id: ID!
+          users(first: Int = 10, after: String @deprecated): [User!]! @deprecated(reason: "No")
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        interface User implements Node {
+          id: ID!
+        }
+
+        input Filter @oneOf {
+          name: String
+          legacy: String @deprecated
+        }
+
+        scalar Date @specifiedBy(url: "https://example.com")
+
+        directive @cached(ttl: Int) repeatable on FIELD_DEFINITION
+        "#,
+    )
+    .unwrap();
+    let schema = Schema::try_from(&document).unwrap();
+
+    for version in [
+        SpecificationVersion::June2018,
+        SpecificationVersion::October2021,
+        SpecificationVersion::September2025,
+    ] {
+        let json = schema
+            .to_introspection_json(version.capabilities())
+            .unwrap();
+        let schema = &json["__schema"];
+
+        let user_types = schema["types"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|ty| {
+                let name = ty["name"].as_str().unwrap();
+                ["Query", "User", "Filter", "Date"].contains(&name)
+            })
+            .collect::<Vec<_>>();
+        let directive = schema["directives"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|directive| directive["name"] == "cached")
+            .unwrap();
+
+        insta::assert_snapshot!(
+            format!("{version:?}"),
+            serde_json::to_string_pretty(&(&schema["queryType"], user_types, directive)).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_undefined_types() {
+    let document = cynic_parser::parse_type_system_document("type Query { user: User }").unwrap();
+    let schema = Schema::try_from(&document).unwrap();
+
+    let error = schema
+        .to_introspection_json(SpecificationVersion::June2018.capabilities())
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Found a reference to a type that isn't defined in the schema: User"
+    );
+}
@@ -0,0 +1,151 @@
+use cynic::http::ReqwestExt;
+use cynic_introspection::{Deprecated, IntrospectionQuery, Schema, SpecificationVersion, Type};
+use graphql_mocks::mocks;
+
+#[tokio::test]
+async fn test_introspection_to_document_roundtrip() {
+    let mock_server = mocks::swapi::serve().await;
+
+    let query =
+        IntrospectionQuery::with_capabilities(SpecificationVersion::October2021.capabilities());
+
+    let result = reqwest::Client::new()
+        .post(mock_server.url())
+        .run_graphql(query)
+        .await
+        .unwrap();
+
+    if result.errors.is_some() {
+        let errors = result.errors.unwrap();
+        panic!("Errors executing query: {errors:?}");
+    }
+    let schema = result.data.unwrap().into_schema().unwrap();
+
+    let document = schema.to_type_system_document().unwrap();
+    let roundtripped = Schema::try_from(&document).unwrap();
+
+    assert_eq!(schema.to_sdl(), roundtripped.to_sdl());
+}
+
+#[test]
+fn test_document_to_schema_roundtrip() {
+    let sdl = std::fs::read_to_string("../schemas/starwars.schema.graphql").unwrap();
+    let document = cynic_parser::parse_type_system_document(&sdl).unwrap();
+
+    let schema = Schema::try_from(&document).unwrap();
+    let roundtripped = Schema::try_from(&schema.to_type_system_document().unwrap()).unwrap();
+
+    assert_eq!(schema, roundtripped);
+}
+
+#[test]
+fn test_document_conversion() {
+    let document = cynic_parser::parse_type_system_document(
+        r#"
+        schema { query: Root mutation: Mutation }
+
+        "The root query"
+        type Root implements Node {
+          id: ID!
+          search(
+            filter: Filter = { roles: [ADMIN], name: "bob" }
+            first: Int = 10 @deprecated(reason: "Use last")
+          ): [Result!]! @deprecated
+          matrix: [[[Int!]!]!]!
+        }
+
+        type Mutation {
+          noop: Boolean
+        }
+
+        extend type Root {
+          now: Date
+        }
+
+        interface Node {
+          id: ID!
+        }
+
+        union Result = Root | Mutation
+
+        enum Role {
+          ADMIN
+          USER @deprecated(reason: "Everyone is an admin")
+        }
+
+        input Filter @oneOf {
+          roles: [Role!]
+          name: String
+        }
+
+        scalar Date @specifiedBy(url: "https://example.com/date")
+
+        directive @cached(ttl: Int = 60) repeatable on FIELD_DEFINITION | OBJECT
+        "#,
+    )
+    .unwrap();
+
+    let schema = Schema::try_from(&document).unwrap();
+
+    assert_eq!(schema.query_type, "Root");
+    assert_eq!(schema.mutation_type.as_deref(), Some("Mutation"));
+    assert!(schema.types.iter().any(|ty| ty.name() == "__Schema"));
+    assert!(schema.types.iter().any(|ty| ty.name() == "String"));
+    assert!(schema.directives.iter().any(|ty| ty.name == "skip"));
+    assert!(schema.directives.iter().any(|ty| ty.name == "oneOf"));
+
+    let Some(Type::Object(root)) = schema.types.iter().find(|ty| ty.name() == "Root") else {
+        panic!("expected Root to be an object")
+    };
+    let search = root
+        .fields
+        .iter()
+        .find(|field| field.name == "search")
+        .unwrap();
+    assert_eq!(
+        search.deprecated,
+        Deprecated::Yes(Some("No longer supported".into()))
+    );
+
+    insta::assert_snapshot!(schema.to_type_system_document().unwrap().to_sdl_pretty(), @r#"
+    schema {
+      query: Root
+      mutation: Mutation
+    }
+
+    "The root query"
+    type Root implements Node {
+      id: ID!
+      search(
+        filter: Filter = { roles: [ADMIN], name: "bob" }
+        first: Int = 10 @deprecated(reason: "Use last")
+      ): [Result!]! @deprecated(reason: "No longer supported")
+      matrix: [[[Int!]!]!]!
+      now: Date
+    }
+
+    type Mutation {
+      noop: Boolean
+    }
+
+    interface Node {
+      id: ID!
+    }
+
+    union Result = Root | Mutation
+
+    enum Role {
+      ADMIN
+      USER @deprecated(reason: "Everyone is an admin")
+    }
+
+    input Filter @oneOf {
+      roles: [Role!]
+      name: String
+    }
+
+    scalar Date @specifiedBy(url: "https://example.com/date")
+
+    directive @cached(ttl: Int = 60) repeatable on FIELD_DEFINITION | OBJECT
+    "#);
+}
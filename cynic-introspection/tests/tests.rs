@@ -124,3 +124,83 @@ fn test_2025_introspection_query() {
 fn build_2025_query() -> cynic::Operation<IntrospectionQuery, ()> {
     IntrospectionQuery::with_capabilities(SpecificationVersion::September2025.capabilities())
 }
+
+#[test]
+fn test_introspection_query_type_depth() {
+    let capabilities = || SpecificationVersion::October2021.capabilities();
+
+    assert_eq!(
+        of_type_depth(&IntrospectionQuery::with_capabilities(capabilities()).query),
+        usize::from(IntrospectionQuery::DEFAULT_TYPE_DEPTH)
+    );
+    assert_eq!(
+        of_type_depth(&IntrospectionQuery::with_type_depth(capabilities(), 9).query),
+        9
+    );
+    assert_eq!(
+        of_type_depth(&IntrospectionQuery::with_type_depth(capabilities(), 100).query),
+        usize::from(IntrospectionQuery::MAX_TYPE_DEPTH)
+    );
+}
+
+/// The deepest nesting of `ofType` selections in a query
+fn of_type_depth(query: &str) -> usize {
+    use cynic_parser::executable::{Iter, Selection};
+
+    fn depth(selections: Iter<'_, Selection<'_>>) -> usize {
+        selections
+            .filter_map(|selection| match selection {
+                Selection::Field(field) => Some(field),
+                _ => None,
+            })
+            .map(|field| {
+                let inner = depth(field.selection_set());
+                if field.name() == "ofType" {
+                    inner + 1
+                } else {
+                    inner
+                }
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    let document = cynic_parser::parse_executable_document(query).unwrap();
+    document
+        .operations()
+        .map(|operation| depth(operation.selection_set()))
+        .max()
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_deeply_wrapped_types() {
+    let document =
+        cynic_parser::parse_type_system_document("type Query { matrix: [[[[Int!]!]!]!]! }")
+            .unwrap();
+    let schema = cynic_introspection::Schema::try_from(&document).unwrap();
+    let json = schema
+        .to_introspection_json(SpecificationVersion::October2021.capabilities())
+        .unwrap();
+
+    let query = serde_json::from_value::<IntrospectionQuery>(json.clone()).unwrap();
+    assert_eq!(query.into_schema().unwrap(), schema);
+
+    // Simulate a server responding to a query that only asked for the default depth
+    let mut truncated = json;
+    let field_type = truncated["__schema"]["types"]
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .find(|ty| ty["name"] == "Query")
+        .unwrap()["fields"][0]["type"]
+        .pointer_mut("/ofType/ofType/ofType/ofType/ofType")
+        .unwrap();
+    field_type.as_object_mut().unwrap().remove("ofType");
+
+    let query = serde_json::from_value::<IntrospectionQuery>(truncated).unwrap();
+    assert_eq!(
+        query.into_schema(),
+        Err(SchemaError::TruncatedTypeReference(6))
+    );
+}
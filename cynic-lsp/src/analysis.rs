@@ -0,0 +1,515 @@
+//! Working out what's under the cursor, and answering questions about it.
+
+use std::collections::HashMap;
+
+use cynic_parser::{
+    ExecutableDocument, Span, TypeSystemDocument,
+    common::OperationType,
+    executable::{ExecutableDefinition, Selection},
+    type_system::{
+        Definition, Description, FieldDefinition, InputValueDefinition, Type, TypeDefinition,
+    },
+};
+
+use crate::protocol::{CompletionItem, MarkupContent};
+
+/// The name we insert at the cursor when working out completions
+const COMPLETION_PLACEHOLDER: &str = "__cynic_lsp_completion";
+
+/// Something in a document that refers to part of the schema
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Target<'a> {
+    Type(&'a str),
+    Field {
+        parent: &'a str,
+        name: &'a str,
+    },
+    Argument {
+        parent: &'a str,
+        field: &'a str,
+        name: &'a str,
+    },
+    Fragment(&'a str),
+}
+
+/// An index of the types in a schema
+pub struct TypeIndex<'a> {
+    types: HashMap<&'a str, Vec<TypeDefinition<'a>>>,
+    roots: HashMap<OperationType, &'a str>,
+}
+
+impl<'a> TypeIndex<'a> {
+    pub fn new(document: &'a TypeSystemDocument) -> Self {
+        let mut types = HashMap::<_, Vec<_>>::new();
+        let mut roots = HashMap::new();
+
+        for definition in document.definitions() {
+            match definition {
+                Definition::Type(ty) | Definition::TypeExtension(ty) => {
+                    types.entry(ty.name()).or_default().push(ty);
+                }
+                Definition::Schema(schema) | Definition::SchemaExtension(schema) => {
+                    for root in schema.root_operations() {
+                        roots.insert(root.operation_type(), root.named_type());
+                    }
+                }
+                Definition::Directive(_) => {}
+            }
+        }
+
+        TypeIndex { types, roots }
+    }
+
+    /// The root type for an operation, falling back to the default names if
+    /// there's no schema definition
+    pub fn root(&self, operation_type: OperationType) -> Option<&'a str> {
+        if let Some(root) = self.roots.get(&operation_type) {
+            return Some(root);
+        }
+        let name = match operation_type {
+            OperationType::Query => "Query",
+            OperationType::Mutation => "Mutation",
+            OperationType::Subscription => "Subscription",
+        };
+        self.types.get_key_value(name).map(|(name, _)| *name)
+    }
+
+    pub fn definitions(&self, name: &str) -> &[TypeDefinition<'a>] {
+        self.types.get(name).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// The fields of an object or interface
+    pub fn fields(&self, name: &str) -> impl Iterator<Item = FieldDefinition<'a>> + '_ {
+        self.definitions(name).iter().flat_map(
+            |definition| -> Box<dyn Iterator<Item = FieldDefinition<'a>>> {
+                match definition {
+                    TypeDefinition::Object(object) => Box::new(object.fields()),
+                    TypeDefinition::Interface(interface) => Box::new(interface.fields()),
+                    _ => Box::new(std::iter::empty()),
+                }
+            },
+        )
+    }
+
+    pub fn field(&self, parent: &str, name: &str) -> Option<FieldDefinition<'a>> {
+        self.fields(parent).find(|field| field.name() == name)
+    }
+
+    /// The fields of an input object
+    pub fn input_fields(&self, name: &str) -> impl Iterator<Item = InputValueDefinition<'a>> + '_ {
+        self.definitions(name)
+            .iter()
+            .filter_map(|definition| match definition {
+                TypeDefinition::InputObject(input) => Some(input.fields()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    pub fn argument(
+        &self,
+        parent: &str,
+        field: &str,
+        name: &str,
+    ) -> Option<InputValueDefinition<'a>> {
+        self.field(parent, field)?
+            .arguments()
+            .find(|argument| argument.name() == name)
+    }
+}
+
+/// Finds the target at `offset` in an executable document
+pub fn executable_target<'a>(
+    document: &'a ExecutableDocument,
+    index: &TypeIndex<'a>,
+    offset: usize,
+) -> Option<(Target<'a>, Span)> {
+    for definition in document.definitions() {
+        let (parent, selections) = match definition {
+            ExecutableDefinition::Operation(operation) => {
+                for variable in operation.variable_definitions() {
+                    let ty = variable.ty();
+                    if contains(ty.name_span(), offset) {
+                        return Some((Target::Type(ty.name()), ty.name_span()));
+                    }
+                }
+                (
+                    index.root(operation.operation_type()),
+                    operation.selection_set(),
+                )
+            }
+            ExecutableDefinition::Fragment(fragment) => {
+                if contains(fragment.name_span(), offset) {
+                    return Some((Target::Fragment(fragment.name()), fragment.name_span()));
+                }
+                if contains(fragment.type_condition_span(), offset) {
+                    return Some((
+                        Target::Type(fragment.type_condition()),
+                        fragment.type_condition_span(),
+                    ));
+                }
+                (Some(fragment.type_condition()), fragment.selection_set())
+            }
+        };
+
+        if let Some(target) = selection_target(selections, parent, index, offset) {
+            return Some(target);
+        }
+    }
+
+    None
+}
+
+fn selection_target<'a>(
+    selections: impl Iterator<Item = Selection<'a>>,
+    parent: Option<&'a str>,
+    index: &TypeIndex<'a>,
+    offset: usize,
+) -> Option<(Target<'a>, Span)> {
+    for selection in selections {
+        match selection {
+            Selection::Field(field) => {
+                let field_parent = parent.filter(|_| field.name() != "__typename");
+                if contains(field.name_span(), offset) {
+                    let parent = field_parent?;
+                    let target = Target::Field {
+                        parent,
+                        name: field.name(),
+                    };
+                    return Some((target, field.name_span()));
+                }
+
+                for argument in field.arguments() {
+                    if contains(argument.name_span(), offset) {
+                        let target = Target::Argument {
+                            parent: field_parent?,
+                            field: field.name(),
+                            name: argument.name(),
+                        };
+                        return Some((target, argument.name_span()));
+                    }
+                }
+
+                let child_parent = field_parent
+                    .and_then(|parent| index.field(parent, field.name()))
+                    .map(|definition| definition.ty().name());
+
+                if let Some(target) =
+                    selection_target(field.selection_set(), child_parent, index, offset)
+                {
+                    return Some(target);
+                }
+            }
+            Selection::InlineFragment(fragment) => {
+                if let (Some(name), Some(span)) =
+                    (fragment.type_condition(), fragment.type_condition_span())
+                {
+                    if contains(span, offset) {
+                        return Some((Target::Type(name), span));
+                    }
+                }
+
+                let parent = fragment.type_condition().or(parent);
+                if let Some(target) =
+                    selection_target(fragment.selection_set(), parent, index, offset)
+                {
+                    return Some(target);
+                }
+            }
+            Selection::FragmentSpread(spread) => {
+                if contains(spread.fragment_name_span(), offset) {
+                    return Some((
+                        Target::Fragment(spread.fragment_name()),
+                        spread.fragment_name_span(),
+                    ));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the target at `offset` in a type system document
+pub fn schema_target<'a>(
+    document: &'a TypeSystemDocument,
+    offset: usize,
+) -> Option<(Target<'a>, Span)> {
+    let type_target = |ty: Type<'a>| {
+        contains(ty.name_span(), offset).then(|| (Target::Type(ty.name()), ty.name_span()))
+    };
+
+    for definition in document.definitions() {
+        match definition {
+            Definition::Type(ty) | Definition::TypeExtension(ty) => {
+                if contains(ty.name_span(), offset) {
+                    return Some((Target::Type(ty.name()), ty.name_span()));
+                }
+
+                let fields = match ty {
+                    TypeDefinition::Object(object) => Some(object.fields()),
+                    TypeDefinition::Interface(interface) => Some(interface.fields()),
+                    _ => None,
+                };
+                for field in fields.into_iter().flatten() {
+                    if contains(field.name_span(), offset) {
+                        let target = Target::Field {
+                            parent: ty.name(),
+                            name: field.name(),
+                        };
+                        return Some((target, field.name_span()));
+                    }
+                    if let Some(target) = type_target(field.ty()) {
+                        return Some(target);
+                    }
+                    for argument in field.arguments() {
+                        if contains(argument.name_span(), offset) {
+                            let target = Target::Argument {
+                                parent: ty.name(),
+                                field: field.name(),
+                                name: argument.name(),
+                            };
+                            return Some((target, argument.name_span()));
+                        }
+                        if let Some(target) = type_target(argument.ty()) {
+                            return Some(target);
+                        }
+                    }
+                }
+
+                match ty {
+                    TypeDefinition::InputObject(input) => {
+                        for field in input.fields() {
+                            if contains(field.name_span(), offset) {
+                                let target = Target::Field {
+                                    parent: ty.name(),
+                                    name: field.name(),
+                                };
+                                return Some((target, field.name_span()));
+                            }
+                            if let Some(target) = type_target(field.ty()) {
+                                return Some(target);
+                            }
+                        }
+                    }
+                    TypeDefinition::Union(union) => {
+                        for member in union.members() {
+                            if contains(member.span(), offset) {
+                                return Some((Target::Type(member.name()), member.span()));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            Definition::Schema(schema) | Definition::SchemaExtension(schema) => {
+                for root in schema.root_operations() {
+                    if contains(root.named_type_span(), offset) {
+                        return Some((Target::Type(root.named_type()), root.named_type_span()));
+                    }
+                }
+            }
+            Definition::Directive(directive) => {
+                for argument in directive.arguments() {
+                    if let Some(target) = type_target(argument.ty()) {
+                        return Some(target);
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// The spans in the schema that define a target
+pub fn definition_spans(target: &Target<'_>, index: &TypeIndex<'_>) -> Vec<Span> {
+    match target {
+        Target::Type(name) => index
+            .definitions(name)
+            .iter()
+            .map(|definition| definition.name_span())
+            .collect(),
+        Target::Field { parent, name } => index
+            .fields(parent)
+            .filter(|field| field.name() == *name)
+            .map(|field| field.name_span())
+            .chain(
+                index
+                    .input_fields(parent)
+                    .filter(|field| field.name() == *name)
+                    .map(|field| field.name_span()),
+            )
+            .collect(),
+        Target::Argument {
+            parent,
+            field,
+            name,
+        } => index
+            .argument(parent, field, name)
+            .map(|argument| argument.name_span())
+            .into_iter()
+            .collect(),
+        Target::Fragment(_) => vec![],
+    }
+}
+
+/// The spans of any fragments named `name` in a document
+pub fn fragment_spans(document: &ExecutableDocument, name: &str) -> Vec<Span> {
+    document
+        .fragments()
+        .filter(|fragment| fragment.name() == name)
+        .map(|fragment| fragment.name_span())
+        .collect()
+}
+
+/// Markdown describing a target
+pub fn hover(target: &Target<'_>, index: &TypeIndex<'_>) -> Option<String> {
+    let (signature, description) = match target {
+        Target::Type(name) => {
+            let definitions = index.definitions(name);
+            let keyword = match definitions.first()? {
+                TypeDefinition::Scalar(_) => "scalar",
+                TypeDefinition::Object(_) => "type",
+                TypeDefinition::Interface(_) => "interface",
+                TypeDefinition::Union(_) => "union",
+                TypeDefinition::Enum(_) => "enum",
+                TypeDefinition::InputObject(_) => "input",
+            };
+            let description = definitions
+                .iter()
+                .find_map(|definition| definition.description());
+            (format!("{keyword} {name}"), description)
+        }
+        Target::Field { parent, name } => {
+            if let Some(field) = index.field(parent, name) {
+                (field_signature(parent, &field), field.description())
+            } else {
+                let field = index
+                    .input_fields(parent)
+                    .find(|field| field.name() == *name)?;
+                (
+                    format!("{parent}.{name}: {}", field.ty()),
+                    field.description(),
+                )
+            }
+        }
+        Target::Argument {
+            parent,
+            field,
+            name,
+        } => {
+            let argument = index.argument(parent, field, name)?;
+            (format!("{name}: {}", argument.ty()), argument.description())
+        }
+        Target::Fragment(_) => return None,
+    };
+
+    Some(markdown(&signature, description))
+}
+
+/// The completions available at `offset` in an executable document
+pub fn completions(text: &str, schema: &TypeSystemDocument, offset: usize) -> Vec<CompletionItem> {
+    let index = TypeIndex::new(schema);
+    let start = text[..offset]
+        .rfind(|c: char| !is_name_char(c))
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let end = text[offset..]
+        .find(|c: char| !is_name_char(c))
+        .map(|index| offset + index)
+        .unwrap_or(text.len());
+
+    // The document is probably invalid while someone is typing, so we insert a
+    // placeholder where the cursor is & see what that parses as.
+    for placeholder in [
+        COMPLETION_PLACEHOLDER.to_string(),
+        format!("{COMPLETION_PLACEHOLDER}: null"),
+    ] {
+        let text = format!("{}{placeholder}{}", &text[..start], &text[end..]);
+        let (document, _) = cynic_parser::parse_executable_document_recovering(&text);
+
+        match executable_target(&document, &index, start) {
+            Some((Target::Field { parent, .. }, _)) => return field_completions(&index, parent),
+            Some((Target::Argument { parent, field, .. }, _)) => {
+                return argument_completions(&index, parent, field);
+            }
+            _ => {}
+        }
+    }
+
+    vec![]
+}
+
+fn field_completions(index: &TypeIndex<'_>, parent: &str) -> Vec<CompletionItem> {
+    index
+        .fields(parent)
+        .map(|field| CompletionItem {
+            label: field.name().to_string(),
+            kind: CompletionItem::FIELD,
+            detail: Some(field.ty().to_string()),
+            documentation: field
+                .description()
+                .map(|description| MarkupContent::markdown(description.to_cow().into_owned())),
+        })
+        .chain(std::iter::once(CompletionItem {
+            label: "__typename".into(),
+            kind: CompletionItem::FIELD,
+            detail: Some("String!".into()),
+            documentation: None,
+        }))
+        .collect()
+}
+
+fn argument_completions(index: &TypeIndex<'_>, parent: &str, field: &str) -> Vec<CompletionItem> {
+    let Some(field) = index.field(parent, field) else {
+        return vec![];
+    };
+
+    field
+        .arguments()
+        .map(|argument| CompletionItem {
+            label: argument.name().to_string(),
+            kind: CompletionItem::PROPERTY,
+            detail: Some(argument.ty().to_string()),
+            documentation: argument
+                .description()
+                .map(|description| MarkupContent::markdown(description.to_cow().into_owned())),
+        })
+        .collect()
+}
+
+fn field_signature(parent: &str, field: &FieldDefinition<'_>) -> String {
+    let arguments = field
+        .arguments()
+        .map(|argument| format!("{}: {}", argument.name(), argument.ty()))
+        .collect::<Vec<_>>();
+
+    match arguments.is_empty() {
+        true => format!("{parent}.{}: {}", field.name(), field.ty()),
+        false => format!(
+            "{parent}.{}({}): {}",
+            field.name(),
+            arguments.join(", "),
+            field.ty()
+        ),
+    }
+}
+
+fn markdown(signature: &str, description: Option<Description<'_>>) -> String {
+    let mut output = format!("```graphql\n{signature}\n```");
+    if let Some(description) = description {
+        output.push_str("\n\n");
+        output.push_str(description.to_cow().trim());
+    }
+    output
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// Whether `offset` is inside `span`, counting a cursor just after the end
+fn contains(span: Span, offset: usize) -> bool {
+    span.start <= offset && offset <= span.end
+}
@@ -0,0 +1,30 @@
+//! A GraphQL language server built on top of `cynic-parser`.
+//!
+//! This is mostly intended to be used via the `cynic-lsp` binary, but the
+//! server can be run over any reader & writer with [`run`].
+
+mod analysis;
+mod protocol;
+mod server;
+mod transport;
+mod workspace;
+
+use std::io::{self, BufRead, Write};
+
+/// Runs the language server, reading messages from `input` and writing
+/// responses & notifications to `output` until the client asks us to exit
+pub fn run(mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut server = server::Server::default();
+
+    while let Some(message) = transport::read_message(&mut input)? {
+        for outgoing in server.handle(message) {
+            transport::write_message(&mut output, &outgoing)?;
+        }
+
+        if server.has_exited() {
+            break;
+        }
+    }
+
+    Ok(())
+}
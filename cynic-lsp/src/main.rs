@@ -0,0 +1,9 @@
+fn main() {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+
+    if let Err(error) = cynic_lsp::run(stdin.lock(), stdout.lock()) {
+        eprintln!("cynic-lsp: {error}");
+        std::process::exit(1);
+    }
+}
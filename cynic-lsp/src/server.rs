@@ -0,0 +1,237 @@
+//! Dispatching of incoming requests & notifications
+
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+
+use crate::{
+    analysis::{self, Target, TypeIndex},
+    protocol::{
+        DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams, Hover,
+        InitializeParams, Location, MarkupContent, PublishDiagnosticsParams,
+        TextDocumentPositionParams,
+    },
+    workspace::{Kind, Workspace},
+};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+#[derive(Default)]
+pub struct Server {
+    workspace: Workspace,
+    exited: bool,
+}
+
+impl Server {
+    pub fn has_exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Handles a message from the client, returning any messages to send back
+    pub fn handle(&mut self, message: Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default().to_string();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let Some(id) = message.get("id").cloned() else {
+            return self.handle_notification(&method, params);
+        };
+
+        if method.is_empty() {
+            // A response to a request we sent, which we never do
+            return vec![];
+        }
+
+        let response = match self.handle_request(&method, params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        };
+
+        vec![response]
+    }
+
+    fn handle_request(&mut self, method: &str, params: Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => {
+                let params = parse_params::<InitializeParams>(params)?;
+                let schema = params
+                    .initialization_options
+                    .unwrap_or_default()
+                    .schema
+                    .into_vec();
+                self.workspace.configure(params.root_uri.as_deref(), schema);
+
+                Ok(json!({
+                    "capabilities": {
+                        "textDocumentSync": { "openClose": true, "change": 2 },
+                        "hoverProvider": true,
+                        "definitionProvider": true,
+                        "completionProvider": {},
+                    },
+                    "serverInfo": {
+                        "name": "cynic-lsp",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }))
+            }
+            "shutdown" => Ok(Value::Null),
+            "textDocument/definition" => {
+                let params = parse_params::<TextDocumentPositionParams>(params)?;
+                Ok(json!(self.definition(params)))
+            }
+            "textDocument/hover" => {
+                let params = parse_params::<TextDocumentPositionParams>(params)?;
+                Ok(json!(self.hover(params)))
+            }
+            "textDocument/completion" => {
+                let params = parse_params::<TextDocumentPositionParams>(params)?;
+                Ok(json!(self.completion(params)))
+            }
+            _ => Err((METHOD_NOT_FOUND, format!("unknown method: {method}"))),
+        }
+    }
+
+    fn handle_notification(&mut self, method: &str, params: Value) -> Vec<Value> {
+        let uris = match method {
+            "exit" => {
+                self.exited = true;
+                return vec![];
+            }
+            "textDocument/didOpen" => {
+                let Ok(params) = parse_params::<DidOpenTextDocumentParams>(params) else {
+                    return vec![];
+                };
+                self.workspace
+                    .open(params.text_document.uri, params.text_document.text)
+            }
+            "textDocument/didChange" => {
+                let Ok(params) = parse_params::<DidChangeTextDocumentParams>(params) else {
+                    return vec![];
+                };
+                self.workspace
+                    .change(&params.text_document.uri, params.content_changes)
+            }
+            "textDocument/didClose" => {
+                let Ok(params) = parse_params::<DidCloseTextDocumentParams>(params) else {
+                    return vec![];
+                };
+                self.workspace.close(&params.text_document.uri)
+            }
+            _ => return vec![],
+        };
+
+        uris.into_iter()
+            .map(|uri| {
+                let diagnostics = self.workspace.diagnostics(&uri);
+                json!({
+                    "jsonrpc": "2.0",
+                    "method": "textDocument/publishDiagnostics",
+                    "params": PublishDiagnosticsParams { uri, diagnostics },
+                })
+            })
+            .collect()
+    }
+
+    fn definition(&self, params: TextDocumentPositionParams) -> Vec<Location> {
+        let uri = &params.text_document.uri;
+        let Some(document) = self.workspace.document(uri) else {
+            return vec![];
+        };
+        let schema = self.workspace.schema();
+        let index = TypeIndex::new(&schema.parsed.document);
+        let offset = document.line_index.offset(&document.text, params.position);
+
+        let target = match document.kind {
+            Kind::Schema => self
+                .workspace
+                .schema_offset(uri)
+                .and_then(|start| analysis::schema_target(&schema.parsed.document, start + offset))
+                .map(|(target, _)| target),
+            Kind::Executable => document
+                .executable()
+                .and_then(|executable| analysis::executable_target(executable, &index, offset))
+                .map(|(target, _)| target),
+        };
+
+        match target {
+            Some(Target::Fragment(name)) => self
+                .workspace
+                .executables()
+                .flat_map(|(uri, executable)| {
+                    let document = self.workspace.document(uri).unwrap();
+                    analysis::fragment_spans(&executable.document, name)
+                        .into_iter()
+                        .map(|span| Location {
+                            uri: uri.to_string(),
+                            range: document.line_index.range(&document.text, span),
+                        })
+                })
+                .collect(),
+            Some(target) => analysis::definition_spans(&target, &index)
+                .into_iter()
+                .filter_map(|span| self.workspace.schema_location(span))
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    fn hover(&self, params: TextDocumentPositionParams) -> Option<Hover> {
+        let uri = &params.text_document.uri;
+        let document = self.workspace.document(uri)?;
+        let schema = self.workspace.schema();
+        let index = TypeIndex::new(&schema.parsed.document);
+        let offset = document.line_index.offset(&document.text, params.position);
+
+        let (contents, range) = match document.kind {
+            Kind::Schema => {
+                let (target, span) = analysis::schema_target(
+                    &schema.parsed.document,
+                    self.workspace.schema_offset(uri)? + offset,
+                )?;
+                (
+                    analysis::hover(&target, &index)?,
+                    self.workspace.schema_location(span)?.range,
+                )
+            }
+            Kind::Executable => {
+                let (target, span) =
+                    analysis::executable_target(document.executable()?, &index, offset)?;
+                (
+                    analysis::hover(&target, &index)?,
+                    document.line_index.range(&document.text, span),
+                )
+            }
+        };
+
+        Some(Hover {
+            contents: MarkupContent::markdown(contents),
+            range,
+        })
+    }
+
+    fn completion(
+        &self,
+        params: TextDocumentPositionParams,
+    ) -> Vec<crate::protocol::CompletionItem> {
+        let Some(document) = self.workspace.document(&params.text_document.uri) else {
+            return vec![];
+        };
+        if document.kind != Kind::Executable {
+            return vec![];
+        }
+
+        let offset = document.line_index.offset(&document.text, params.position);
+        analysis::completions(
+            &document.text,
+            &self.workspace.schema().parsed.document,
+            offset,
+        )
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, (i64, String)> {
+    serde_json::from_value(params).map_err(|error| (INVALID_PARAMS, error.to_string()))
+}
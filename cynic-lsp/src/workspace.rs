@@ -0,0 +1,509 @@
+//! The documents the server knows about, and the results of parsing them.
+//!
+//! Every schema document is merged into a single `TypeSystemDocument` so that
+//! types can be split across files.  We keep track of where each file starts
+//! in the merged text so that spans can be mapped back to the file they came
+//! from.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use cynic_parser::{
+    Error, ExecutableDocument, Span, TextEdit, TypeSystemDocument, validation::ValidationError,
+};
+
+use crate::protocol::{Diagnostic, LineIndex, Location, TextDocumentContentChangeEvent};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    Schema,
+    Executable,
+}
+
+pub struct Document {
+    pub text: String,
+    pub line_index: LineIndex,
+    pub kind: Kind,
+    open: bool,
+    /// Whether this document was listed in the schema config
+    configured: bool,
+    executable: Option<Parsed<ExecutableDocument>>,
+}
+
+pub struct Parsed<T> {
+    pub document: T,
+    pub errors: Vec<Error>,
+}
+
+pub struct Schema {
+    /// The offset at which each schema document starts in the merged text
+    files: Vec<(usize, String)>,
+    pub parsed: Parsed<TypeSystemDocument>,
+    validation_errors: Vec<ValidationError>,
+}
+
+#[derive(Default)]
+pub struct Workspace {
+    documents: BTreeMap<String, Document>,
+    schema: Schema,
+}
+
+impl Workspace {
+    /// Loads the schema files listed in the servers configuration
+    pub fn configure(&mut self, root_uri: Option<&str>, schema_paths: Vec<String>) {
+        let root = root_uri.and_then(uri_to_path);
+
+        for path in schema_paths {
+            let path = match &root {
+                Some(root) => root.join(path),
+                None => PathBuf::from(path),
+            };
+            let Ok(path) = std::path::absolute(&path) else {
+                continue;
+            };
+            let text = std::fs::read_to_string(&path).unwrap_or_default();
+
+            self.documents.insert(
+                path_to_uri(&path),
+                Document::new(text, Kind::Schema, false, true),
+            );
+        }
+
+        self.rebuild_schema(None);
+    }
+
+    pub fn document(&self, uri: &str) -> Option<&Document> {
+        self.documents.get(uri)
+    }
+
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    pub fn executables(&self) -> impl Iterator<Item = (&str, &Parsed<ExecutableDocument>)> {
+        self.documents
+            .iter()
+            .filter_map(|(uri, document)| Some((uri.as_str(), document.executable.as_ref()?)))
+    }
+
+    /// Opens a document, returning the URIs that need new diagnostics
+    pub fn open(&mut self, uri: String, text: String) -> Vec<String> {
+        let configured = self
+            .documents
+            .get(&uri)
+            .is_some_and(|document| document.configured);
+
+        let kind = match configured {
+            true => Kind::Schema,
+            false => detect_kind(&text),
+        };
+
+        self.documents
+            .insert(uri.clone(), Document::new(text, kind, true, configured));
+
+        match kind {
+            Kind::Schema => {
+                self.rebuild_schema(None);
+                self.published_uris()
+            }
+            Kind::Executable => vec![uri],
+        }
+    }
+
+    /// Applies some changes to a document, returning the URIs that need new
+    /// diagnostics
+    pub fn change(
+        &mut self,
+        uri: &str,
+        changes: Vec<TextDocumentContentChangeEvent>,
+    ) -> Vec<String> {
+        let Some(document) = self.documents.get_mut(uri) else {
+            return vec![];
+        };
+
+        let previous_kind = document.kind;
+        let mut edits = Vec::with_capacity(changes.len());
+        for change in changes {
+            let span = match change.range {
+                Some(range) => Span::new(
+                    document.line_index.offset(&document.text, range.start),
+                    document.line_index.offset(&document.text, range.end),
+                ),
+                None => Span::new(0, document.text.len()),
+            };
+            let edit = TextEdit::new(span, &change.text);
+            document.text = edit.apply(&document.text);
+            document.line_index = LineIndex::new(&document.text);
+            edits.push((span, change.text));
+        }
+
+        if !document.configured {
+            document.kind = detect_kind(&document.text);
+        }
+
+        match (previous_kind, document.kind) {
+            (Kind::Executable, Kind::Executable) => {
+                document.reparse_executable(&edits);
+                vec![uri.to_string()]
+            }
+            (Kind::Schema, Kind::Schema) => {
+                let edit = match edits.as_slice() {
+                    [(span, replacement)] => Some((uri, *span, replacement.as_str())),
+                    _ => None,
+                };
+                self.rebuild_schema(edit);
+                self.published_uris()
+            }
+            _ => {
+                document.executable = None;
+                document.parse_executable();
+                self.rebuild_schema(None);
+                self.published_uris()
+            }
+        }
+    }
+
+    /// Closes a document, returning the URIs that need new diagnostics
+    pub fn close(&mut self, uri: &str) -> Vec<String> {
+        let Some(document) = self.documents.get_mut(uri) else {
+            return vec![];
+        };
+
+        if document.configured {
+            // We still need configured schema files, so go back to what's on disk
+            let text = uri_to_path(uri)
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .unwrap_or_default();
+            *document = Document::new(text, Kind::Schema, false, true);
+        } else {
+            let document = self.documents.remove(uri).unwrap();
+            if document.kind == Kind::Executable {
+                return vec![uri.to_string()];
+            }
+        }
+
+        self.rebuild_schema(None);
+
+        let mut uris = self.published_uris();
+        if !uris.iter().any(|published| published == uri) {
+            uris.push(uri.to_string());
+        }
+        uris
+    }
+
+    /// The diagnostics for a document
+    pub fn diagnostics(&self, uri: &str) -> Vec<Diagnostic> {
+        let Some(document) = self.documents.get(uri) else {
+            return vec![];
+        };
+
+        match &document.executable {
+            Some(executable) => self.executable_diagnostics(document, executable),
+            None => self.schema_diagnostics(uri),
+        }
+    }
+
+    /// Converts a span in the merged schema text into a location in the file
+    /// it came from
+    pub fn schema_location(&self, span: Span) -> Option<Location> {
+        let (start, uri) = self.schema.file_at(span.start)?;
+        let document = self.documents.get(uri)?;
+        let span = Span::new(span.start - start, span.end - start);
+
+        Some(Location {
+            uri: uri.clone(),
+            range: document.line_index.range(&document.text, span),
+        })
+    }
+
+    /// The offset of a schema document in the merged schema text
+    pub fn schema_offset(&self, uri: &str) -> Option<usize> {
+        self.schema
+            .files
+            .iter()
+            .find(|(_, file)| file == uri)
+            .map(|(start, _)| *start)
+    }
+
+    fn executable_diagnostics(
+        &self,
+        document: &Document,
+        executable: &Parsed<ExecutableDocument>,
+    ) -> Vec<Diagnostic> {
+        let to_diagnostic = |span: Span, message: String| Diagnostic {
+            range: document.line_index.range(&document.text, span),
+            severity: Diagnostic::ERROR,
+            source: "cynic-lsp",
+            message,
+        };
+
+        if !executable.errors.is_empty() {
+            return executable
+                .errors
+                .iter()
+                .filter(|error| !matches!(error, Error::EmptyExecutableDocument))
+                .map(|error| {
+                    let span = error.span().unwrap_or_default();
+                    to_diagnostic(span, error.to_string())
+                })
+                .collect();
+        }
+
+        if !self.schema.is_usable() {
+            return vec![];
+        }
+
+        cynic_parser::validation::validate_executable_document(
+            &executable.document,
+            &self.schema.parsed.document,
+        )
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|error| to_diagnostic(error.span(), error.to_string()))
+        .collect()
+    }
+
+    fn schema_diagnostics(&self, uri: &str) -> Vec<Diagnostic> {
+        let parse_errors = self
+            .schema
+            .parsed
+            .errors
+            .iter()
+            .filter_map(|error| Some((error.span()?, error.to_string())));
+
+        let validation_errors = self
+            .schema
+            .validation_errors
+            .iter()
+            .map(|error| (error.span(), error.to_string()));
+
+        parse_errors
+            .chain(validation_errors)
+            .filter_map(|(span, message)| {
+                let location = self.schema_location(span)?;
+                (location.uri == uri).then_some(Diagnostic {
+                    range: location.range,
+                    severity: Diagnostic::ERROR,
+                    source: "cynic-lsp",
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    /// Merges all the schema documents & parses the result
+    ///
+    /// If `edit` is provided & it's the only thing that's changed since the
+    /// last time this was called we try to reparse incrementally.
+    fn rebuild_schema(&mut self, edit: Option<(&str, Span, &str)>) {
+        let mut text = String::new();
+        let mut files = Vec::new();
+        for (uri, document) in &self.documents {
+            if document.kind == Kind::Schema && (document.open || document.configured) {
+                files.push((text.len(), uri.clone()));
+                text.push_str(&document.text);
+                text.push('\n');
+            }
+        }
+
+        let previous = std::mem::take(&mut self.schema);
+        let incremental = edit.and_then(|(uri, span, replacement)| {
+            if !previous.parsed.errors.is_empty()
+                || previous
+                    .files
+                    .iter()
+                    .map(|(_, uri)| uri)
+                    .ne(files.iter().map(|(_, uri)| uri))
+            {
+                return None;
+            }
+            let (start, _) = previous.files.iter().find(|(_, file)| file == uri)?;
+            let edit = TextEdit::new(Span::new(span.start + start, span.end + start), replacement);
+
+            cynic_parser::reparse_type_system_document(previous.parsed.document, &text, &edit).ok()
+        });
+
+        let parsed = match incremental {
+            Some(document) => Parsed {
+                document,
+                errors: vec![],
+            },
+            None => {
+                let (document, errors) = cynic_parser::parse_type_system_document_recovering(&text);
+                Parsed { document, errors }
+            }
+        };
+
+        let validation_errors = match parsed.errors.is_empty() {
+            true => parsed.document.validate().err().unwrap_or_default(),
+            false => vec![],
+        };
+
+        self.schema = Schema {
+            files,
+            parsed,
+            validation_errors,
+        };
+    }
+
+    /// The URIs of every document we publish diagnostics for
+    fn published_uris(&self) -> Vec<String> {
+        self.documents
+            .iter()
+            .filter(|(_, document)| document.open || document.configured)
+            .map(|(uri, _)| uri.clone())
+            .collect()
+    }
+}
+
+impl Document {
+    fn new(text: String, kind: Kind, open: bool, configured: bool) -> Self {
+        let mut document = Document {
+            line_index: LineIndex::new(&text),
+            text,
+            kind,
+            open,
+            configured,
+            executable: None,
+        };
+        document.parse_executable();
+        document
+    }
+
+    pub fn executable(&self) -> Option<&ExecutableDocument> {
+        Some(&self.executable.as_ref()?.document)
+    }
+
+    fn parse_executable(&mut self) {
+        if self.kind != Kind::Executable {
+            return;
+        }
+
+        let (document, errors) = cynic_parser::parse_executable_document_recovering(&self.text);
+        self.executable = Some(Parsed { document, errors });
+    }
+
+    /// Reparses an executable document after some edits, incrementally if
+    /// possible
+    fn reparse_executable(&mut self, edits: &[(Span, String)]) {
+        let Some(previous) = self.executable.take() else {
+            return self.parse_executable();
+        };
+
+        if let ([(span, replacement)], true) = (edits, previous.errors.is_empty()) {
+            let edit = TextEdit::new(*span, replacement);
+            if let Ok(document) =
+                cynic_parser::reparse_executable_document(previous.document, &self.text, &edit)
+            {
+                self.executable = Some(Parsed {
+                    document,
+                    errors: vec![],
+                });
+                return;
+            }
+        }
+
+        self.parse_executable();
+    }
+}
+
+impl Schema {
+    /// Whether the schema parsed cleanly, and so is worth validating against
+    pub fn is_usable(&self) -> bool {
+        !self.files.is_empty() && self.parsed.errors.is_empty()
+    }
+
+    fn file_at(&self, offset: usize) -> Option<&(usize, String)> {
+        let index = self.files.partition_point(|(start, _)| *start <= offset);
+        self.files.get(index.checked_sub(1)?)
+    }
+}
+
+impl Default for Schema {
+    fn default() -> Self {
+        Schema {
+            files: vec![],
+            parsed: Parsed {
+                document: TypeSystemDocument::default(),
+                errors: vec![],
+            },
+            validation_errors: vec![],
+        }
+    }
+}
+
+/// Works out whether a document is a schema or contains queries from the
+/// first keyword in it
+fn detect_kind(text: &str) -> Kind {
+    let mut remaining = text;
+    loop {
+        remaining = remaining
+            .trim_start_matches(|c: char| c.is_whitespace() || c == ',' || c == '\u{feff}');
+
+        if let Some(comment) = remaining.strip_prefix('#') {
+            remaining = comment.split_once('\n').map(|(_, rest)| rest).unwrap_or("");
+        } else if let Some(description) = remaining.strip_prefix("\"\"\"") {
+            remaining = description
+                .split_once("\"\"\"")
+                .map(|(_, rest)| rest)
+                .unwrap_or("");
+        } else if let Some(description) = remaining.strip_prefix('"') {
+            remaining = skip_string(description);
+        } else {
+            break;
+        }
+    }
+
+    let keyword = remaining
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .next()
+        .unwrap_or_default();
+
+    match keyword {
+        "schema" | "scalar" | "type" | "interface" | "union" | "enum" | "input" | "extend"
+        | "directive" => Kind::Schema,
+        _ => Kind::Executable,
+    }
+}
+
+fn skip_string(text: &str) -> &str {
+    let mut escaped = false;
+    for (index, char) in text.char_indices() {
+        match char {
+            '\\' if !escaped => escaped = true,
+            '"' if !escaped => return &text[index + 1..],
+            _ => escaped = false,
+        }
+    }
+    ""
+}
+
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut iter = path.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+
+    Some(PathBuf::from(String::from_utf8(bytes).ok()?))
+}
+
+pub fn path_to_uri(path: &std::path::Path) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.to_string_lossy().bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
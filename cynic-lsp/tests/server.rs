@@ -0,0 +1,441 @@
+use std::io::Cursor;
+
+use serde_json::{Value, json};
+
+const SCHEMA: &str = include_str!("schema.graphql");
+
+const QUERY: &str = r#"query GetUser {
+  user(id: "1") {
+    name
+    ...UserFriends
+  }
+}
+
+fragment UserFriends on User {
+  friends {
+    id
+  }
+}
+"#;
+
+#[test]
+fn test_initialize() {
+    let messages = run(vec![initialize(json!(null))]);
+
+    assert_eq!(messages[0]["id"], 1);
+    assert_eq!(
+        messages[0]["result"]["capabilities"],
+        json!({
+            "textDocumentSync": { "openClose": true, "change": 2 },
+            "hoverProvider": true,
+            "definitionProvider": true,
+            "completionProvider": {},
+        })
+    );
+}
+
+#[test]
+fn test_unknown_request() {
+    let messages = run(vec![request(2, "textDocument/rename", json!({}))]);
+
+    assert_eq!(messages[0]["error"]["code"], -32601);
+}
+
+#[test]
+fn test_parse_error_diagnostics() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open("file:///query.graphql", "query { user(id: ) { name } }"),
+    ]);
+
+    let diagnostics = diagnostics(&messages, "file:///query.graphql");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]["range"],
+        json!({
+            "start": { "line": 0, "character": 17 },
+            "end": { "line": 0, "character": 18 },
+        })
+    );
+}
+
+#[test]
+fn test_validation_diagnostics() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open(
+            "file:///query.graphql",
+            "query {\n  user(id: \"1\") { email }\n}",
+        ),
+    ]);
+
+    assert_eq!(
+        diagnostics(&messages, "file:///query.graphql"),
+        vec![json!({
+            "range": {
+                "start": { "line": 1, "character": 18 },
+                "end": { "line": 1, "character": 23 },
+            },
+            "severity": 1,
+            "source": "cynic-lsp",
+            "message": "no field named email on the type User",
+        })]
+    );
+}
+
+#[test]
+fn test_schema_diagnostics() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open(
+            "file:///other.graphql",
+            "type Other {\n  user: Missing\n}\n",
+        ),
+    ]);
+
+    let diagnostics = diagnostics(&messages, "file:///other.graphql");
+    assert_eq!(diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics[0]["range"]["start"],
+        json!({ "line": 1, "character": 8 })
+    );
+    assert!(
+        self::diagnostics(&messages, "file:///schema.graphql").is_empty(),
+        "errors should only be reported against the file they're in"
+    );
+}
+
+#[test]
+fn test_diagnostics_after_change() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open(
+            "file:///query.graphql",
+            "query { user(id: \"1\") { name } }",
+        ),
+        change(
+            "file:///query.graphql",
+            json!({ "start": { "line": 0, "character": 24 }, "end": { "line": 0, "character": 28 } }),
+            "nmae",
+        ),
+        change(
+            "file:///query.graphql",
+            json!({ "start": { "line": 0, "character": 24 }, "end": { "line": 0, "character": 28 } }),
+            "name",
+        ),
+    ]);
+
+    let published = messages
+        .iter()
+        .filter(|message| message["params"]["uri"] == "file:///query.graphql")
+        .map(|message| message["params"]["diagnostics"].as_array().unwrap().len())
+        .collect::<Vec<_>>();
+
+    assert_eq!(published, vec![0, 1, 0]);
+}
+
+#[test]
+fn test_goto_field_definition() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open("file:///query.graphql", QUERY),
+        request(
+            2,
+            "textDocument/definition",
+            position("file:///query.graphql", 2, 6),
+        ),
+    ]);
+
+    assert_eq!(
+        response(&messages, 2),
+        &json!([{
+            "uri": "file:///schema.graphql",
+            "range": {
+                "start": { "line": 14, "character": 2 },
+                "end": { "line": 14, "character": 6 },
+            },
+        }])
+    );
+}
+
+#[test]
+fn test_goto_type_definition() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open("file:///query.graphql", QUERY),
+        // The `User` in the fragments type condition
+        request(
+            2,
+            "textDocument/definition",
+            position("file:///query.graphql", 7, 25),
+        ),
+        // The `User` in the type of `Query.user`
+        request(
+            3,
+            "textDocument/definition",
+            position("file:///schema.graphql", 5, 6),
+        ),
+    ]);
+
+    let expected = json!([{
+        "uri": "file:///schema.graphql",
+        "range": {
+            "start": { "line": 12, "character": 5 },
+            "end": { "line": 12, "character": 9 },
+        },
+    }]);
+    assert_eq!(response(&messages, 2), &expected);
+    assert_eq!(response(&messages, 3), &expected);
+}
+
+#[test]
+fn test_goto_fragment_definition() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open("file:///query.graphql", QUERY),
+        request(
+            2,
+            "textDocument/definition",
+            position("file:///query.graphql", 3, 10),
+        ),
+    ]);
+
+    assert_eq!(
+        response(&messages, 2),
+        &json!([{
+            "uri": "file:///query.graphql",
+            "range": {
+                "start": { "line": 7, "character": 9 },
+                "end": { "line": 7, "character": 20 },
+            },
+        }])
+    );
+}
+
+#[test]
+fn test_hover() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open("file:///query.graphql", QUERY),
+        request(
+            2,
+            "textDocument/hover",
+            position("file:///query.graphql", 1, 3),
+        ),
+        request(
+            3,
+            "textDocument/hover",
+            position("file:///query.graphql", 1, 8),
+        ),
+        request(
+            4,
+            "textDocument/hover",
+            position("file:///query.graphql", 7, 25),
+        ),
+    ]);
+
+    assert_eq!(
+        response(&messages, 2)["contents"]["value"],
+        "```graphql\nQuery.user(id: ID!): User\n```\n\nLooks up a user by their ID"
+    );
+    assert_eq!(
+        response(&messages, 3)["contents"]["value"],
+        "```graphql\nid: ID!\n```\n\nThe ID of the user"
+    );
+    assert_eq!(
+        response(&messages, 4)["contents"]["value"],
+        "```graphql\ntype User\n```\n\nSomeone who uses the app"
+    );
+    assert_eq!(
+        response(&messages, 2)["range"],
+        json!({
+            "start": { "line": 1, "character": 2 },
+            "end": { "line": 1, "character": 6 },
+        })
+    );
+}
+
+#[test]
+fn test_field_completion() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open(
+            "file:///query.graphql",
+            "query {\n  user(id: \"1\") {\n    na\n  }\n}\n",
+        ),
+        request(
+            2,
+            "textDocument/completion",
+            position("file:///query.graphql", 2, 6),
+        ),
+    ]);
+
+    assert_eq!(
+        labels(response(&messages, 2)),
+        vec!["id", "name", "friends", "__typename"]
+    );
+}
+
+#[test]
+fn test_field_completion_on_empty_line() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open("file:///query.graphql", "query {\n  \n}\n"),
+        request(
+            2,
+            "textDocument/completion",
+            position("file:///query.graphql", 1, 2),
+        ),
+    ]);
+
+    assert_eq!(
+        labels(response(&messages, 2)),
+        vec!["user", "users", "__typename"]
+    );
+    assert_eq!(response(&messages, 2)[0]["detail"], "User");
+}
+
+#[test]
+fn test_argument_completion() {
+    let messages = run(vec![
+        initialize(json!(null)),
+        open("file:///schema.graphql", SCHEMA),
+        open("file:///query.graphql", "query {\n  users() { name }\n}\n"),
+        request(
+            2,
+            "textDocument/completion",
+            position("file:///query.graphql", 1, 8),
+        ),
+    ]);
+
+    assert_eq!(labels(response(&messages, 2)), vec!["first", "after"]);
+}
+
+#[test]
+fn test_schema_from_initialization_options() {
+    let root = format!("file://{}/tests", env!("CARGO_MANIFEST_DIR"));
+    let messages = run(vec![
+        initialize(
+            json!({ "rootUri": root, "initializationOptions": { "schema": ["schema.graphql"] } }),
+        ),
+        open("file:///query.graphql", QUERY),
+        request(
+            2,
+            "textDocument/definition",
+            position("file:///query.graphql", 1, 3),
+        ),
+    ]);
+
+    assert!(diagnostics(&messages, "file:///query.graphql").is_empty());
+    assert_eq!(
+        response(&messages, 2)[0]["uri"],
+        format!("{root}/schema.graphql")
+    );
+}
+
+fn run(messages: Vec<Value>) -> Vec<Value> {
+    let mut input = Vec::new();
+    for message in messages {
+        let content = message.to_string();
+        input.extend(format!("Content-Length: {}\r\n\r\n{content}", content.len()).bytes());
+    }
+
+    let mut output = Vec::new();
+    cynic_lsp::run(Cursor::new(input), &mut output).unwrap();
+
+    let mut output = output.as_slice();
+    let mut messages = Vec::new();
+    while !output.is_empty() {
+        let header_end = output.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+        let header = std::str::from_utf8(&output[..header_end]).unwrap();
+        let length = header
+            .strip_prefix("Content-Length: ")
+            .unwrap()
+            .parse::<usize>()
+            .unwrap();
+        let content = &output[header_end + 4..header_end + 4 + length];
+        messages.push(serde_json::from_slice(content).unwrap());
+        output = &output[header_end + 4 + length..];
+    }
+    messages
+}
+
+fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params })
+}
+
+fn notification(method: &str, params: Value) -> Value {
+    json!({ "jsonrpc": "2.0", "method": method, "params": params })
+}
+
+fn initialize(params: Value) -> Value {
+    let params = match params {
+        Value::Null => json!({ "rootUri": null }),
+        params => params,
+    };
+    request(1, "initialize", params)
+}
+
+fn open(uri: &str, text: &str) -> Value {
+    notification(
+        "textDocument/didOpen",
+        json!({
+            "textDocument": { "uri": uri, "languageId": "graphql", "version": 1, "text": text }
+        }),
+    )
+}
+
+fn change(uri: &str, range: Value, text: &str) -> Value {
+    notification(
+        "textDocument/didChange",
+        json!({
+            "textDocument": { "uri": uri, "version": 2 },
+            "contentChanges": [{ "range": range, "text": text }],
+        }),
+    )
+}
+
+fn position(uri: &str, line: u32, character: u32) -> Value {
+    json!({
+        "textDocument": { "uri": uri },
+        "position": { "line": line, "character": character },
+    })
+}
+
+fn response(messages: &[Value], id: u64) -> &Value {
+    &messages.iter().find(|message| message["id"] == id).unwrap()["result"]
+}
+
+/// The last diagnostics published for a document
+fn diagnostics(messages: &[Value], uri: &str) -> Vec<Value> {
+    messages
+        .iter()
+        .rev()
+        .find(|message| {
+            message["method"] == "textDocument/publishDiagnostics"
+                && message["params"]["uri"] == uri
+        })
+        .unwrap()["params"]["diagnostics"]
+        .as_array()
+        .unwrap()
+        .clone()
+}
+
+fn labels(completions: &Value) -> Vec<&str> {
+    completions
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|item| item["label"].as_str().unwrap())
+        .collect()
+}
@@ -12,7 +12,7 @@ pub use types::*;
 
 pub(crate) use strings::{trim_block_string_whitespace, unquote_block_string, unquote_string};
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum OperationType {
     Query,
     Mutation,
@@ -0,0 +1,904 @@
+//! Comparing two versions of a schema.
+//!
+//! [`diff_type_system_documents`] compares an old & new `TypeSystemDocument`
+//! and returns every [`Change`] between them.  Each change is classified as
+//! breaking, dangerous or safe, roughly following the classification that
+//! `graphql-js` uses.
+
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::{
+    TypeSystemDocument,
+    common::{OperationType, WrappingType},
+    type_system::{
+        Definition, Description, Directive, DirectiveDefinition, DirectiveLocation,
+        EnumValueDefinition, FieldDefinition, InputValueDefinition, TypeDefinition,
+    },
+};
+
+/// Compares two versions of a schema, returning all the changes between them
+///
+/// Type extensions are merged into the types they extend before comparing.
+///
+/// ```rust
+/// use cynic_parser::{diff::Severity, parse_type_system_document};
+///
+/// let old = parse_type_system_document("type Query { hello: String, goodbye: String }").unwrap();
+/// let new = parse_type_system_document("type Query { hello: String! }").unwrap();
+///
+/// let changes = cynic_parser::diff::diff_type_system_documents(&old, &new);
+///
+/// assert_eq!(changes[0].to_string(), "the type of the field Query.hello changed from String to String!");
+/// assert_eq!(changes[0].severity(), Severity::Safe);
+/// assert_eq!(changes[1].to_string(), "the field Query.goodbye was removed");
+/// assert_eq!(changes[1].severity(), Severity::Breaking);
+/// ```
+pub fn diff_type_system_documents(
+    old: &TypeSystemDocument,
+    new: &TypeSystemDocument,
+) -> Vec<Change> {
+    let mut differ = Differ { changes: vec![] };
+    differ.diff_schemas(&Schema::new(old), &Schema::new(new));
+    differ.changes
+}
+
+/// A change between two versions of a schema
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Change {
+    /// The schema coordinate of the thing that changed, e.g. `User.name` or
+    /// `Query.user(id:)`.  Changes to the schema definition use `schema`.
+    pub coordinate: String,
+    pub kind: ChangeKind,
+}
+
+/// How likely a change is to break existing clients
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// The change will break some existing operations
+    Breaking,
+    /// The change won't break existing operations, but could change how they
+    /// behave - e.g. a new enum value that clients don't know how to handle
+    Dangerous,
+    /// The change can't affect existing operations
+    Safe,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    TypeAdded {
+        kind: &'static str,
+    },
+    TypeRemoved {
+        kind: &'static str,
+    },
+    TypeKindChanged {
+        old_kind: &'static str,
+        new_kind: &'static str,
+    },
+    FieldAdded,
+    FieldRemoved,
+    FieldTypeChanged {
+        old_type: String,
+        new_type: String,
+    },
+    ArgumentAdded {
+        ty: String,
+        required: bool,
+    },
+    ArgumentRemoved,
+    ArgumentTypeChanged {
+        old_type: String,
+        new_type: String,
+    },
+    ArgumentDefaultChanged {
+        old_default: Option<String>,
+        new_default: Option<String>,
+    },
+    InputFieldAdded {
+        ty: String,
+        required: bool,
+    },
+    InputFieldRemoved,
+    InputFieldTypeChanged {
+        old_type: String,
+        new_type: String,
+    },
+    InputFieldDefaultChanged {
+        old_default: Option<String>,
+        new_default: Option<String>,
+    },
+    EnumValueAdded,
+    EnumValueRemoved,
+    UnionMemberAdded {
+        member: String,
+    },
+    UnionMemberRemoved {
+        member: String,
+    },
+    InterfaceAdded {
+        interface: String,
+    },
+    InterfaceRemoved {
+        interface: String,
+    },
+    DescriptionChanged {
+        old_description: Option<String>,
+        new_description: Option<String>,
+    },
+    DeprecationAdded {
+        reason: Option<String>,
+    },
+    DeprecationRemoved,
+    DirectiveAdded,
+    DirectiveRemoved,
+    DirectiveLocationAdded {
+        location: DirectiveLocation,
+    },
+    DirectiveLocationRemoved {
+        location: DirectiveLocation,
+    },
+    DirectiveRepeatableChanged {
+        repeatable: bool,
+    },
+    RootOperationTypeChanged {
+        operation_type: OperationType,
+        old_type: Option<String>,
+        new_type: Option<String>,
+    },
+}
+
+impl Change {
+    pub fn severity(&self) -> Severity {
+        match &self.kind {
+            ChangeKind::TypeRemoved { .. }
+            | ChangeKind::TypeKindChanged { .. }
+            | ChangeKind::FieldRemoved
+            | ChangeKind::ArgumentRemoved
+            | ChangeKind::InputFieldRemoved
+            | ChangeKind::EnumValueRemoved
+            | ChangeKind::UnionMemberRemoved { .. }
+            | ChangeKind::InterfaceRemoved { .. }
+            | ChangeKind::DirectiveRemoved
+            | ChangeKind::DirectiveLocationRemoved { .. } => Severity::Breaking,
+
+            ChangeKind::ArgumentAdded { required, .. }
+            | ChangeKind::InputFieldAdded { required, .. } => match required {
+                true => Severity::Breaking,
+                false => Severity::Dangerous,
+            },
+
+            ChangeKind::FieldTypeChanged { .. } => {
+                // Making an output type stricter is fine, as clients will
+                // still get something they can handle
+                match self.is_safe_type_change(true) {
+                    true => Severity::Safe,
+                    false => Severity::Breaking,
+                }
+            }
+            ChangeKind::ArgumentTypeChanged { .. } | ChangeKind::InputFieldTypeChanged { .. } => {
+                // Making an input type less strict is fine, as everything
+                // clients currently send will still be accepted
+                match self.is_safe_type_change(false) {
+                    true => Severity::Safe,
+                    false => Severity::Breaking,
+                }
+            }
+
+            ChangeKind::DirectiveRepeatableChanged { repeatable } => match repeatable {
+                true => Severity::Safe,
+                false => Severity::Breaking,
+            },
+
+            ChangeKind::RootOperationTypeChanged {
+                old_type, new_type, ..
+            } => match (old_type, new_type) {
+                (Some(_), None) => Severity::Breaking,
+                (Some(_), Some(_)) => Severity::Dangerous,
+                _ => Severity::Safe,
+            },
+
+            ChangeKind::ArgumentDefaultChanged { .. }
+            | ChangeKind::InputFieldDefaultChanged { .. }
+            | ChangeKind::EnumValueAdded
+            | ChangeKind::UnionMemberAdded { .. }
+            | ChangeKind::InterfaceAdded { .. } => Severity::Dangerous,
+
+            ChangeKind::TypeAdded { .. }
+            | ChangeKind::FieldAdded
+            | ChangeKind::DescriptionChanged { .. }
+            | ChangeKind::DeprecationAdded { .. }
+            | ChangeKind::DeprecationRemoved
+            | ChangeKind::DirectiveAdded
+            | ChangeKind::DirectiveLocationAdded { .. } => Severity::Safe,
+        }
+    }
+
+    pub fn is_breaking(&self) -> bool {
+        self.severity() == Severity::Breaking
+    }
+
+    fn is_safe_type_change(&self, output: bool) -> bool {
+        let (ChangeKind::FieldTypeChanged { old_type, new_type }
+        | ChangeKind::ArgumentTypeChanged { old_type, new_type }
+        | ChangeKind::InputFieldTypeChanged { old_type, new_type }) = &self.kind
+        else {
+            return false;
+        };
+
+        let (old_name, old_wrappers) = parse_type(old_type);
+        let (new_name, new_wrappers) = parse_type(new_type);
+        if old_name != new_name {
+            return false;
+        }
+
+        match output {
+            true => is_stricter(&old_wrappers, &new_wrappers),
+            false => is_stricter(&new_wrappers, &old_wrappers),
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coordinate = &self.coordinate;
+        match &self.kind {
+            ChangeKind::TypeAdded { kind } => write!(f, "the {kind} {coordinate} was added"),
+            ChangeKind::TypeRemoved { kind } => write!(f, "the {kind} {coordinate} was removed"),
+            ChangeKind::TypeKindChanged { old_kind, new_kind } => {
+                write!(
+                    f,
+                    "the type {coordinate} changed kind from {old_kind} to {new_kind}"
+                )
+            }
+            ChangeKind::FieldAdded => write!(f, "the field {coordinate} was added"),
+            ChangeKind::FieldRemoved => write!(f, "the field {coordinate} was removed"),
+            ChangeKind::FieldTypeChanged { old_type, new_type } => write!(
+                f,
+                "the type of the field {coordinate} changed from {old_type} to {new_type}"
+            ),
+            ChangeKind::ArgumentAdded { ty, required } => write!(
+                f,
+                "the {} argument {coordinate} of type {ty} was added",
+                requirement(*required)
+            ),
+            ChangeKind::ArgumentRemoved => write!(f, "the argument {coordinate} was removed"),
+            ChangeKind::ArgumentTypeChanged { old_type, new_type } => write!(
+                f,
+                "the type of the argument {coordinate} changed from {old_type} to {new_type}"
+            ),
+            ChangeKind::ArgumentDefaultChanged {
+                old_default,
+                new_default,
+            } => write!(
+                f,
+                "the default value of the argument {coordinate} changed from {} to {}",
+                default_value(old_default),
+                default_value(new_default)
+            ),
+            ChangeKind::InputFieldAdded { ty, required } => write!(
+                f,
+                "the {} input field {coordinate} of type {ty} was added",
+                requirement(*required)
+            ),
+            ChangeKind::InputFieldRemoved => {
+                write!(f, "the input field {coordinate} was removed")
+            }
+            ChangeKind::InputFieldTypeChanged { old_type, new_type } => write!(
+                f,
+                "the type of the input field {coordinate} changed from {old_type} to {new_type}"
+            ),
+            ChangeKind::InputFieldDefaultChanged {
+                old_default,
+                new_default,
+            } => write!(
+                f,
+                "the default value of the input field {coordinate} changed from {} to {}",
+                default_value(old_default),
+                default_value(new_default)
+            ),
+            ChangeKind::EnumValueAdded => write!(f, "the enum value {coordinate} was added"),
+            ChangeKind::EnumValueRemoved => write!(f, "the enum value {coordinate} was removed"),
+            ChangeKind::UnionMemberAdded { member } => {
+                write!(f, "{member} was added to the union {coordinate}")
+            }
+            ChangeKind::UnionMemberRemoved { member } => {
+                write!(f, "{member} was removed from the union {coordinate}")
+            }
+            ChangeKind::InterfaceAdded { interface } => {
+                write!(f, "{coordinate} now implements the interface {interface}")
+            }
+            ChangeKind::InterfaceRemoved { interface } => {
+                write!(
+                    f,
+                    "{coordinate} no longer implements the interface {interface}"
+                )
+            }
+            ChangeKind::DescriptionChanged { .. } => {
+                write!(f, "the description of {coordinate} changed")
+            }
+            ChangeKind::DeprecationAdded { reason: None } => {
+                write!(f, "{coordinate} was deprecated")
+            }
+            ChangeKind::DeprecationAdded {
+                reason: Some(reason),
+            } => write!(f, "{coordinate} was deprecated: {reason}"),
+            ChangeKind::DeprecationRemoved => write!(f, "{coordinate} is no longer deprecated"),
+            ChangeKind::DirectiveAdded => write!(f, "the directive {coordinate} was added"),
+            ChangeKind::DirectiveRemoved => write!(f, "the directive {coordinate} was removed"),
+            ChangeKind::DirectiveLocationAdded { location } => {
+                write!(
+                    f,
+                    "the directive {coordinate} can now be used on {location}"
+                )
+            }
+            ChangeKind::DirectiveLocationRemoved { location } => write!(
+                f,
+                "the directive {coordinate} can no longer be used on {location}"
+            ),
+            ChangeKind::DirectiveRepeatableChanged { repeatable: true } => {
+                write!(f, "the directive {coordinate} is now repeatable")
+            }
+            ChangeKind::DirectiveRepeatableChanged { repeatable: false } => {
+                write!(f, "the directive {coordinate} is no longer repeatable")
+            }
+            ChangeKind::RootOperationTypeChanged {
+                operation_type,
+                old_type,
+                new_type,
+            } => match (old_type, new_type) {
+                (Some(old_type), Some(new_type)) => write!(
+                    f,
+                    "the {operation_type} root type changed from {old_type} to {new_type}"
+                ),
+                (Some(_), None) => write!(f, "the schema no longer supports {operation_type}s"),
+                (None, _) => write!(f, "the schema now supports {operation_type}s"),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Breaking => write!(f, "breaking"),
+            Severity::Dangerous => write!(f, "dangerous"),
+            Severity::Safe => write!(f, "safe"),
+        }
+    }
+}
+
+fn requirement(required: bool) -> &'static str {
+    match required {
+        true => "required",
+        false => "optional",
+    }
+}
+
+fn default_value(value: &Option<String>) -> &str {
+    value.as_deref().unwrap_or("nothing")
+}
+
+/// Whether `new` is at least as strict as `old`, given the wrappers of two
+/// types with the same name.
+fn is_stricter(old: &[WrappingType], new: &[WrappingType]) -> bool {
+    match (old, new) {
+        ([], []) => true,
+        ([WrappingType::NonNull, old @ ..], [WrappingType::NonNull, new @ ..])
+        | ([WrappingType::List, old @ ..], [WrappingType::List, new @ ..]) => is_stricter(old, new),
+        (old, [WrappingType::NonNull, new @ ..]) => is_stricter(old, new),
+        _ => false,
+    }
+}
+
+/// Splits a printed type into its name & wrappers, from outermost to innermost
+fn parse_type(ty: &str) -> (&str, Vec<WrappingType>) {
+    let mut wrappers = Vec::new();
+    let mut remaining = ty;
+    loop {
+        if let Some(inner) = remaining.strip_suffix('!') {
+            wrappers.push(WrappingType::NonNull);
+            remaining = inner;
+        } else if let Some(inner) = remaining
+            .strip_prefix('[')
+            .and_then(|inner| inner.strip_suffix(']'))
+        {
+            wrappers.push(WrappingType::List);
+            remaining = inner;
+        } else {
+            return (remaining, wrappers);
+        }
+    }
+}
+
+/// The types & directives of a schema, with any extensions merged in
+struct Schema<'a> {
+    types: IndexMap<&'a str, Vec<TypeDefinition<'a>>>,
+    directives: IndexMap<&'a str, DirectiveDefinition<'a>>,
+    roots: IndexMap<OperationType, &'a str>,
+}
+
+impl<'a> Schema<'a> {
+    fn new(document: &'a TypeSystemDocument) -> Self {
+        let mut types = IndexMap::<_, Vec<_>>::new();
+        let mut directives = IndexMap::new();
+        let mut roots = IndexMap::new();
+        let mut has_schema_definition = false;
+
+        for definition in document.definitions() {
+            match definition {
+                Definition::Type(ty) | Definition::TypeExtension(ty) => {
+                    types.entry(ty.name()).or_default().push(ty);
+                }
+                Definition::Directive(directive) => {
+                    directives.insert(directive.name(), directive);
+                }
+                Definition::Schema(schema) | Definition::SchemaExtension(schema) => {
+                    has_schema_definition = true;
+                    for root in schema.root_operations() {
+                        roots.insert(root.operation_type(), root.named_type());
+                    }
+                }
+            }
+        }
+
+        if !has_schema_definition {
+            for (operation_type, name) in [
+                (OperationType::Query, "Query"),
+                (OperationType::Mutation, "Mutation"),
+                (OperationType::Subscription, "Subscription"),
+            ] {
+                if let Some((name, _)) = types.get_key_value(name) {
+                    roots.insert(operation_type, *name);
+                }
+            }
+        }
+
+        Schema {
+            types,
+            directives,
+            roots,
+        }
+    }
+}
+
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn push(&mut self, coordinate: impl Into<String>, kind: ChangeKind) {
+        self.changes.push(Change {
+            coordinate: coordinate.into(),
+            kind,
+        })
+    }
+
+    fn diff_schemas(&mut self, old: &Schema<'_>, new: &Schema<'_>) {
+        for operation_type in [
+            OperationType::Query,
+            OperationType::Mutation,
+            OperationType::Subscription,
+        ] {
+            let old_type = old.roots.get(&operation_type).copied();
+            let new_type = new.roots.get(&operation_type).copied();
+            if old_type != new_type {
+                self.push(
+                    "schema",
+                    ChangeKind::RootOperationTypeChanged {
+                        operation_type,
+                        old_type: old_type.map(Into::into),
+                        new_type: new_type.map(Into::into),
+                    },
+                );
+            }
+        }
+
+        for (name, old_definitions) in &old.types {
+            match new.types.get(name) {
+                Some(new_definitions) => self.diff_types(name, old_definitions, new_definitions),
+                None => self.push(
+                    *name,
+                    ChangeKind::TypeRemoved {
+                        kind: type_kind(&old_definitions[0]),
+                    },
+                ),
+            }
+        }
+        for (name, new_definitions) in &new.types {
+            if !old.types.contains_key(name) {
+                self.push(
+                    *name,
+                    ChangeKind::TypeAdded {
+                        kind: type_kind(&new_definitions[0]),
+                    },
+                );
+            }
+        }
+
+        for (name, old_directive) in &old.directives {
+            match new.directives.get(name) {
+                Some(new_directive) => self.diff_directives(old_directive, new_directive),
+                None => self.push(format!("@{name}"), ChangeKind::DirectiveRemoved),
+            }
+        }
+        for name in new.directives.keys() {
+            if !old.directives.contains_key(name) {
+                self.push(format!("@{name}"), ChangeKind::DirectiveAdded);
+            }
+        }
+    }
+
+    fn diff_types(&mut self, name: &str, old: &[TypeDefinition<'_>], new: &[TypeDefinition<'_>]) {
+        let (old_kind, new_kind) = (type_kind(&old[0]), type_kind(&new[0]));
+        if old_kind != new_kind {
+            self.push(name, ChangeKind::TypeKindChanged { old_kind, new_kind });
+            return;
+        }
+
+        self.diff_descriptions(
+            name,
+            old.iter().find_map(|definition| definition.description()),
+            new.iter().find_map(|definition| definition.description()),
+        );
+
+        self.diff_lists(
+            name,
+            &old.iter()
+                .flat_map(implemented_interfaces)
+                .collect::<Vec<_>>(),
+            &new.iter()
+                .flat_map(implemented_interfaces)
+                .collect::<Vec<_>>(),
+            |interface| ChangeKind::InterfaceRemoved { interface },
+            |interface| ChangeKind::InterfaceAdded { interface },
+        );
+
+        let members = |definitions: &[TypeDefinition<'_>]| {
+            definitions
+                .iter()
+                .filter_map(|definition| match definition {
+                    TypeDefinition::Union(union) => {
+                        Some(union.members().map(|member| member.name().to_string()))
+                    }
+                    _ => None,
+                })
+                .flatten()
+                .collect::<Vec<_>>()
+        };
+        self.diff_lists(
+            name,
+            &members(old),
+            &members(new),
+            |member| ChangeKind::UnionMemberRemoved { member },
+            |member| ChangeKind::UnionMemberAdded { member },
+        );
+
+        let old_fields = fields(old);
+        let new_fields = fields(new);
+        for (field_name, old_field) in &old_fields {
+            let coordinate = format!("{name}.{field_name}");
+            match new_fields.get(field_name) {
+                Some(new_field) => self.diff_fields(&coordinate, old_field, new_field),
+                None => self.push(coordinate, ChangeKind::FieldRemoved),
+            }
+        }
+        for field_name in new_fields.keys() {
+            if !old_fields.contains_key(field_name) {
+                self.push(format!("{name}.{field_name}"), ChangeKind::FieldAdded);
+            }
+        }
+
+        let old_fields = input_fields(old);
+        let new_fields = input_fields(new);
+        self.diff_input_values(name, &old_fields, &new_fields, InputValueKind::InputField);
+
+        let old_values = enum_values(old);
+        let new_values = enum_values(new);
+        for (value, old_value) in &old_values {
+            let coordinate = format!("{name}.{value}");
+            match new_values.get(value) {
+                Some(new_value) => {
+                    self.diff_descriptions(
+                        &coordinate,
+                        old_value.description(),
+                        new_value.description(),
+                    );
+                    self.diff_deprecations(
+                        &coordinate,
+                        old_value.directives(),
+                        new_value.directives(),
+                    );
+                }
+                None => self.push(coordinate, ChangeKind::EnumValueRemoved),
+            }
+        }
+        for value in new_values.keys() {
+            if !old_values.contains_key(value) {
+                self.push(format!("{name}.{value}"), ChangeKind::EnumValueAdded);
+            }
+        }
+    }
+
+    fn diff_fields(
+        &mut self,
+        coordinate: &str,
+        old: &FieldDefinition<'_>,
+        new: &FieldDefinition<'_>,
+    ) {
+        let (old_type, new_type) = (old.ty().to_string(), new.ty().to_string());
+        if old_type != new_type {
+            self.push(
+                coordinate,
+                ChangeKind::FieldTypeChanged { old_type, new_type },
+            );
+        }
+
+        self.diff_descriptions(coordinate, old.description(), new.description());
+        self.diff_deprecations(coordinate, old.directives(), new.directives());
+
+        let old_arguments = old.arguments().map(|arg| (arg.name(), arg)).collect();
+        let new_arguments = new.arguments().map(|arg| (arg.name(), arg)).collect();
+        self.diff_input_values(
+            coordinate,
+            &old_arguments,
+            &new_arguments,
+            InputValueKind::Argument,
+        );
+    }
+
+    fn diff_directives(&mut self, old: &DirectiveDefinition<'_>, new: &DirectiveDefinition<'_>) {
+        let coordinate = format!("@{}", old.name());
+
+        self.diff_descriptions(&coordinate, old.description(), new.description());
+
+        if old.is_repeatable() != new.is_repeatable() {
+            self.push(
+                &coordinate,
+                ChangeKind::DirectiveRepeatableChanged {
+                    repeatable: new.is_repeatable(),
+                },
+            );
+        }
+
+        self.diff_lists(
+            &coordinate,
+            &old.locations().collect::<Vec<_>>(),
+            &new.locations().collect::<Vec<_>>(),
+            |location| ChangeKind::DirectiveLocationRemoved { location },
+            |location| ChangeKind::DirectiveLocationAdded { location },
+        );
+
+        let old_arguments = old.arguments().map(|arg| (arg.name(), arg)).collect();
+        let new_arguments = new.arguments().map(|arg| (arg.name(), arg)).collect();
+        self.diff_input_values(
+            &coordinate,
+            &old_arguments,
+            &new_arguments,
+            InputValueKind::Argument,
+        );
+    }
+
+    /// Diffs the arguments of a field or directive, or the fields of an input
+    /// object
+    fn diff_input_values(
+        &mut self,
+        parent: &str,
+        old: &IndexMap<&str, InputValueDefinition<'_>>,
+        new: &IndexMap<&str, InputValueDefinition<'_>>,
+        kind: InputValueKind,
+    ) {
+        let coordinate = |name: &str| match kind {
+            InputValueKind::Argument => format!("{parent}({name}:)"),
+            InputValueKind::InputField => format!("{parent}.{name}"),
+        };
+
+        for (name, old_value) in old {
+            let coordinate = coordinate(name);
+            let Some(new_value) = new.get(name) else {
+                self.push(
+                    coordinate,
+                    match kind {
+                        InputValueKind::Argument => ChangeKind::ArgumentRemoved,
+                        InputValueKind::InputField => ChangeKind::InputFieldRemoved,
+                    },
+                );
+                continue;
+            };
+
+            let (old_type, new_type) = (old_value.ty().to_string(), new_value.ty().to_string());
+            if old_type != new_type {
+                self.push(
+                    &coordinate,
+                    match kind {
+                        InputValueKind::Argument => {
+                            ChangeKind::ArgumentTypeChanged { old_type, new_type }
+                        }
+                        InputValueKind::InputField => {
+                            ChangeKind::InputFieldTypeChanged { old_type, new_type }
+                        }
+                    },
+                );
+            }
+
+            let old_default = old_value.default_value();
+            let new_default = new_value.default_value();
+            if old_default != new_default {
+                let old_default = old_default.map(|value| value.to_string());
+                let new_default = new_default.map(|value| value.to_string());
+                self.push(
+                    &coordinate,
+                    match kind {
+                        InputValueKind::Argument => ChangeKind::ArgumentDefaultChanged {
+                            old_default,
+                            new_default,
+                        },
+                        InputValueKind::InputField => ChangeKind::InputFieldDefaultChanged {
+                            old_default,
+                            new_default,
+                        },
+                    },
+                );
+            }
+
+            self.diff_descriptions(
+                &coordinate,
+                old_value.description(),
+                new_value.description(),
+            );
+            self.diff_deprecations(&coordinate, old_value.directives(), new_value.directives());
+        }
+
+        for (name, new_value) in new {
+            if old.contains_key(name) {
+                continue;
+            }
+            let ty = new_value.ty();
+            let required = ty.is_non_null() && new_value.default_value().is_none();
+            let ty = ty.to_string();
+            self.push(
+                coordinate(name),
+                match kind {
+                    InputValueKind::Argument => ChangeKind::ArgumentAdded { ty, required },
+                    InputValueKind::InputField => ChangeKind::InputFieldAdded { ty, required },
+                },
+            );
+        }
+    }
+
+    fn diff_descriptions(
+        &mut self,
+        coordinate: &str,
+        old: Option<Description<'_>>,
+        new: Option<Description<'_>>,
+    ) {
+        let old_description = old.map(|description| description.to_cow().into_owned());
+        let new_description = new.map(|description| description.to_cow().into_owned());
+        if old_description != new_description {
+            self.push(
+                coordinate,
+                ChangeKind::DescriptionChanged {
+                    old_description,
+                    new_description,
+                },
+            );
+        }
+    }
+
+    fn diff_deprecations<'a>(
+        &mut self,
+        coordinate: &str,
+        old: impl IntoIterator<Item = Directive<'a>>,
+        new: impl IntoIterator<Item = Directive<'a>>,
+    ) {
+        match (deprecation(old), deprecation(new)) {
+            (None, Some(reason)) => self.push(coordinate, ChangeKind::DeprecationAdded { reason }),
+            (Some(_), None) => self.push(coordinate, ChangeKind::DeprecationRemoved),
+            _ => {}
+        }
+    }
+
+    fn diff_lists<T: PartialEq + Clone>(
+        &mut self,
+        coordinate: &str,
+        old: &[T],
+        new: &[T],
+        removed: impl Fn(T) -> ChangeKind,
+        added: impl Fn(T) -> ChangeKind,
+    ) {
+        for item in old {
+            if !new.contains(item) {
+                self.push(coordinate, removed(item.clone()));
+            }
+        }
+        for item in new {
+            if !old.contains(item) {
+                self.push(coordinate, added(item.clone()));
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum InputValueKind {
+    Argument,
+    InputField,
+}
+
+fn type_kind(definition: &TypeDefinition<'_>) -> &'static str {
+    match definition {
+        TypeDefinition::Scalar(_) => "scalar",
+        TypeDefinition::Object(_) => "object",
+        TypeDefinition::Interface(_) => "interface",
+        TypeDefinition::Union(_) => "union",
+        TypeDefinition::Enum(_) => "enum",
+        TypeDefinition::InputObject(_) => "input object",
+    }
+}
+
+fn implemented_interfaces(definition: &TypeDefinition<'_>) -> Vec<String> {
+    let interfaces = match definition {
+        TypeDefinition::Object(object) => object.implements_interfaces().collect(),
+        TypeDefinition::Interface(interface) => interface.implements_interfaces().collect(),
+        _ => vec![],
+    };
+    interfaces.into_iter().map(Into::into).collect()
+}
+
+fn fields<'a>(definitions: &[TypeDefinition<'a>]) -> IndexMap<&'a str, FieldDefinition<'a>> {
+    definitions
+        .iter()
+        .flat_map(
+            |definition| -> Box<dyn Iterator<Item = FieldDefinition<'a>>> {
+                match definition {
+                    TypeDefinition::Object(object) => Box::new(object.fields()),
+                    TypeDefinition::Interface(interface) => Box::new(interface.fields()),
+                    _ => Box::new(std::iter::empty()),
+                }
+            },
+        )
+        .map(|field| (field.name(), field))
+        .collect()
+}
+
+fn input_fields<'a>(
+    definitions: &[TypeDefinition<'a>],
+) -> IndexMap<&'a str, InputValueDefinition<'a>> {
+    definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            TypeDefinition::InputObject(input) => Some(input.fields()),
+            _ => None,
+        })
+        .flatten()
+        .map(|field| (field.name(), field))
+        .collect()
+}
+
+fn enum_values<'a>(
+    definitions: &[TypeDefinition<'a>],
+) -> IndexMap<&'a str, EnumValueDefinition<'a>> {
+    definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            TypeDefinition::Enum(definition) => Some(definition.values()),
+            _ => None,
+        })
+        .flatten()
+        .map(|value| (value.value(), value))
+        .collect()
+}
+
+/// Finds any `@deprecated` directive, returning its reason
+fn deprecation<'a>(directives: impl IntoIterator<Item = Directive<'a>>) -> Option<Option<String>> {
+    let directive = directives
+        .into_iter()
+        .find(|directive| directive.name() == "deprecated")?;
+
+    Some(
+        directive
+            .argument("reason")
+            .and_then(|reason| reason.value().as_str().map(Into::into)),
+    )
+}
@@ -0,0 +1,825 @@
+//! Builders for constructing & editing `ExecutableDocument`s in code.
+//!
+//! Like the [type system builders](crate::type_system::builder) these are
+//! plain owned structs with public fields.  They can be created from scratch
+//! or converted from an existing document, edited & then built into a new
+//! `ExecutableDocument`.  [`ExecutableDocumentBuilder`] also has a number of
+//! transforms that can be applied to a whole document, e.g. to inline
+//! fragments or sort selections.
+//!
+//! ```rust
+//! use cynic_parser::{common::OperationType, executable::builder::*};
+//!
+//! let document = ExecutableDocumentBuilder::new()
+//!     .definition(
+//!         OperationDefinitionBuilder::new(OperationType::Query)
+//!             .name("UserQuery")
+//!             .variable(VariableDefinitionBuilder::new(
+//!                 "id",
+//!                 TypeBuilder::named("ID").non_null(),
+//!             ))
+//!             .selection(
+//!                 FieldSelectionBuilder::new("user")
+//!                     .argument("id", ValueBuilder::Variable("id".into()))
+//!                     .selection(FieldSelectionBuilder::new("name")),
+//!             ),
+//!     )
+//!     .build();
+//!
+//! assert_eq!(
+//!     document.to_string(),
+//!     "query UserQuery($id: ID!) {\n  user(id: $id) {\n    name\n  }\n}\n"
+//! );
+//! ```
+
+use crate::{
+    Span,
+    common::{IdRange, OperationType},
+    values::{
+        self, ConstValue, Value,
+        storage::{ValueKind, ValueRecord},
+    },
+};
+
+use super::{
+    Directive, ExecutableDefinition, ExecutableDocument, FieldSelection, FragmentDefinition,
+    FragmentSpread, InlineFragment, OperationDefinition, Selection, Type, VariableDefinition,
+    ids::*, storage::*, writer::ExecutableAstWriter,
+};
+
+pub use crate::type_system::builder::TypeBuilder;
+
+/// Builds an [`ExecutableDocument`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ExecutableDocumentBuilder {
+    pub definitions: Vec<ExecutableDefinitionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExecutableDefinitionBuilder {
+    Operation(OperationDefinitionBuilder),
+    Fragment(FragmentDefinitionBuilder),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OperationDefinitionBuilder {
+    pub description: Option<String>,
+    pub operation_type: OperationType,
+    pub name: Option<String>,
+    pub variable_definitions: Vec<VariableDefinitionBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub selection_set: Vec<SelectionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FragmentDefinitionBuilder {
+    pub description: Option<String>,
+    pub name: String,
+    pub type_condition: String,
+    pub directives: Vec<DirectiveBuilder>,
+    pub selection_set: Vec<SelectionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableDefinitionBuilder {
+    pub description: Option<String>,
+    pub name: String,
+    pub ty: TypeBuilder,
+    /// The default value of the variable.  This should not contain any variables
+    pub default_value: Option<ValueBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SelectionBuilder {
+    Field(FieldSelectionBuilder),
+    InlineFragment(InlineFragmentBuilder),
+    FragmentSpread(FragmentSpreadBuilder),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldSelectionBuilder {
+    pub alias: Option<String>,
+    pub name: String,
+    pub arguments: Vec<(String, ValueBuilder)>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub selection_set: Vec<SelectionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InlineFragmentBuilder {
+    pub type_condition: Option<String>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub selection_set: Vec<SelectionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FragmentSpreadBuilder {
+    pub fragment_name: String,
+    pub directives: Vec<DirectiveBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirectiveBuilder {
+    pub name: String,
+    pub arguments: Vec<(String, ValueBuilder)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValueBuilder {
+    Variable(String),
+    Int(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Null,
+    Enum(String),
+    List(Vec<ValueBuilder>),
+    Object(Vec<(String, ValueBuilder)>),
+}
+
+impl ExecutableDocumentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn definition(mut self, definition: impl Into<ExecutableDefinitionBuilder>) -> Self {
+        self.definitions.push(definition.into());
+        self
+    }
+
+    pub fn operations(&self) -> impl Iterator<Item = &OperationDefinitionBuilder> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                ExecutableDefinitionBuilder::Operation(operation) => Some(operation),
+                ExecutableDefinitionBuilder::Fragment(_) => None,
+            })
+    }
+
+    pub fn fragments(&self) -> impl Iterator<Item = &FragmentDefinitionBuilder> {
+        self.definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                ExecutableDefinitionBuilder::Operation(_) => None,
+                ExecutableDefinitionBuilder::Fragment(fragment) => Some(fragment),
+            })
+    }
+
+    /// Builds the document
+    ///
+    /// The spans in the document will all be empty, as it has no source text.
+    pub fn build(&self) -> ExecutableDocument {
+        let mut writer = ExecutableAstWriter::new();
+        for definition in &self.definitions {
+            match definition {
+                ExecutableDefinitionBuilder::Operation(operation) => {
+                    let record = operation.record(&mut writer);
+                    writer.operation_definition(record);
+                }
+                ExecutableDefinitionBuilder::Fragment(fragment) => {
+                    let record = fragment.record(&mut writer);
+                    writer.fragment_definition(record);
+                }
+            }
+        }
+        writer.finish()
+    }
+}
+
+impl OperationDefinitionBuilder {
+    pub fn new(operation_type: OperationType) -> Self {
+        OperationDefinitionBuilder {
+            description: None,
+            operation_type,
+            name: None,
+            variable_definitions: Vec::new(),
+            directives: Vec::new(),
+            selection_set: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn variable(mut self, variable: VariableDefinitionBuilder) -> Self {
+        self.variable_definitions.push(variable);
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn selection(mut self, selection: impl Into<SelectionBuilder>) -> Self {
+        self.selection_set.push(selection.into());
+        self
+    }
+}
+
+impl FragmentDefinitionBuilder {
+    pub fn new(name: impl Into<String>, type_condition: impl Into<String>) -> Self {
+        FragmentDefinitionBuilder {
+            description: None,
+            name: name.into(),
+            type_condition: type_condition.into(),
+            directives: Vec::new(),
+            selection_set: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn selection(mut self, selection: impl Into<SelectionBuilder>) -> Self {
+        self.selection_set.push(selection.into());
+        self
+    }
+}
+
+impl VariableDefinitionBuilder {
+    pub fn new(name: impl Into<String>, ty: TypeBuilder) -> Self {
+        VariableDefinitionBuilder {
+            description: None,
+            name: name.into(),
+            ty,
+            default_value: None,
+            directives: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn default_value(mut self, value: impl Into<ValueBuilder>) -> Self {
+        self.default_value = Some(value.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+}
+
+impl FieldSelectionBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        FieldSelectionBuilder {
+            alias: None,
+            name: name.into(),
+            arguments: Vec::new(),
+            directives: Vec::new(),
+            selection_set: Vec::new(),
+        }
+    }
+
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+
+    pub fn argument(mut self, name: impl Into<String>, value: impl Into<ValueBuilder>) -> Self {
+        self.arguments.push((name.into(), value.into()));
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn selection(mut self, selection: impl Into<SelectionBuilder>) -> Self {
+        self.selection_set.push(selection.into());
+        self
+    }
+
+    /// The key this field will have in the response: its alias if it has
+    /// one, otherwise its name
+    pub fn response_key(&self) -> &str {
+        self.alias.as_deref().unwrap_or(&self.name)
+    }
+}
+
+impl InlineFragmentBuilder {
+    pub fn new() -> Self {
+        InlineFragmentBuilder {
+            type_condition: None,
+            directives: Vec::new(),
+            selection_set: Vec::new(),
+        }
+    }
+
+    pub fn type_condition(mut self, type_condition: impl Into<String>) -> Self {
+        self.type_condition = Some(type_condition.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn selection(mut self, selection: impl Into<SelectionBuilder>) -> Self {
+        self.selection_set.push(selection.into());
+        self
+    }
+}
+
+impl Default for InlineFragmentBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FragmentSpreadBuilder {
+    pub fn new(fragment_name: impl Into<String>) -> Self {
+        FragmentSpreadBuilder {
+            fragment_name: fragment_name.into(),
+            directives: Vec::new(),
+        }
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+}
+
+impl DirectiveBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        DirectiveBuilder {
+            name: name.into(),
+            arguments: Vec::new(),
+        }
+    }
+
+    pub fn argument(mut self, name: impl Into<String>, value: impl Into<ValueBuilder>) -> Self {
+        self.arguments.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl From<OperationDefinitionBuilder> for ExecutableDefinitionBuilder {
+    fn from(value: OperationDefinitionBuilder) -> Self {
+        ExecutableDefinitionBuilder::Operation(value)
+    }
+}
+
+impl From<FragmentDefinitionBuilder> for ExecutableDefinitionBuilder {
+    fn from(value: FragmentDefinitionBuilder) -> Self {
+        ExecutableDefinitionBuilder::Fragment(value)
+    }
+}
+
+impl From<FieldSelectionBuilder> for SelectionBuilder {
+    fn from(value: FieldSelectionBuilder) -> Self {
+        SelectionBuilder::Field(value)
+    }
+}
+
+impl From<InlineFragmentBuilder> for SelectionBuilder {
+    fn from(value: InlineFragmentBuilder) -> Self {
+        SelectionBuilder::InlineFragment(value)
+    }
+}
+
+impl From<FragmentSpreadBuilder> for SelectionBuilder {
+    fn from(value: FragmentSpreadBuilder) -> Self {
+        SelectionBuilder::FragmentSpread(value)
+    }
+}
+
+impl From<i32> for ValueBuilder {
+    fn from(value: i32) -> Self {
+        ValueBuilder::Int(value.into())
+    }
+}
+
+impl From<i64> for ValueBuilder {
+    fn from(value: i64) -> Self {
+        ValueBuilder::Int(value)
+    }
+}
+
+impl From<f64> for ValueBuilder {
+    fn from(value: f64) -> Self {
+        ValueBuilder::Float(value)
+    }
+}
+
+impl From<bool> for ValueBuilder {
+    fn from(value: bool) -> Self {
+        ValueBuilder::Boolean(value)
+    }
+}
+
+impl From<&str> for ValueBuilder {
+    fn from(value: &str) -> Self {
+        ValueBuilder::String(value.into())
+    }
+}
+
+impl From<String> for ValueBuilder {
+    fn from(value: String) -> Self {
+        ValueBuilder::String(value)
+    }
+}
+
+impl<T> From<Vec<T>> for ValueBuilder
+where
+    T: Into<ValueBuilder>,
+{
+    fn from(value: Vec<T>) -> Self {
+        ValueBuilder::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T> From<Option<T>> for ValueBuilder
+where
+    T: Into<ValueBuilder>,
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => ValueBuilder::Null,
+        }
+    }
+}
+
+// Conversions from readers, for editing existing documents
+
+fn description(description: Option<super::Description<'_>>) -> Option<String> {
+    description.map(|description| description.literal().to_cow().into_owned())
+}
+
+fn directives<'a>(directives: impl Iterator<Item = Directive<'a>>) -> Vec<DirectiveBuilder> {
+    directives.map(Into::into).collect()
+}
+
+fn selection_set<'a>(selections: impl Iterator<Item = Selection<'a>>) -> Vec<SelectionBuilder> {
+    selections.map(Into::into).collect()
+}
+
+impl From<&ExecutableDocument> for ExecutableDocumentBuilder {
+    fn from(document: &ExecutableDocument) -> Self {
+        ExecutableDocumentBuilder {
+            definitions: document.definitions().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ExecutableDefinition<'_>> for ExecutableDefinitionBuilder {
+    fn from(definition: ExecutableDefinition<'_>) -> Self {
+        match definition {
+            ExecutableDefinition::Operation(operation) => {
+                ExecutableDefinitionBuilder::Operation(operation.into())
+            }
+            ExecutableDefinition::Fragment(fragment) => {
+                ExecutableDefinitionBuilder::Fragment(fragment.into())
+            }
+        }
+    }
+}
+
+impl From<OperationDefinition<'_>> for OperationDefinitionBuilder {
+    fn from(operation: OperationDefinition<'_>) -> Self {
+        OperationDefinitionBuilder {
+            description: description(operation.description()),
+            operation_type: operation.operation_type(),
+            name: operation.name().map(Into::into),
+            variable_definitions: operation.variable_definitions().map(Into::into).collect(),
+            directives: directives(operation.directives()),
+            selection_set: selection_set(operation.selection_set()),
+        }
+    }
+}
+
+impl From<FragmentDefinition<'_>> for FragmentDefinitionBuilder {
+    fn from(fragment: FragmentDefinition<'_>) -> Self {
+        FragmentDefinitionBuilder {
+            description: description(fragment.description()),
+            name: fragment.name().into(),
+            type_condition: fragment.type_condition().into(),
+            directives: directives(fragment.directives()),
+            selection_set: selection_set(fragment.selection_set()),
+        }
+    }
+}
+
+impl From<VariableDefinition<'_>> for VariableDefinitionBuilder {
+    fn from(variable: VariableDefinition<'_>) -> Self {
+        VariableDefinitionBuilder {
+            description: description(variable.description()),
+            name: variable.name().into(),
+            ty: variable.ty().into(),
+            default_value: variable.default_value().map(Into::into),
+            directives: directives(variable.directives()),
+        }
+    }
+}
+
+impl From<Selection<'_>> for SelectionBuilder {
+    fn from(selection: Selection<'_>) -> Self {
+        match selection {
+            Selection::Field(field) => SelectionBuilder::Field(field.into()),
+            Selection::InlineFragment(fragment) => {
+                SelectionBuilder::InlineFragment(fragment.into())
+            }
+            Selection::FragmentSpread(spread) => SelectionBuilder::FragmentSpread(spread.into()),
+        }
+    }
+}
+
+impl From<FieldSelection<'_>> for FieldSelectionBuilder {
+    fn from(field: FieldSelection<'_>) -> Self {
+        FieldSelectionBuilder {
+            alias: field.alias().map(Into::into),
+            name: field.name().into(),
+            arguments: field
+                .arguments()
+                .map(|argument| (argument.name().into(), argument.value().into()))
+                .collect(),
+            directives: directives(field.directives()),
+            selection_set: selection_set(field.selection_set()),
+        }
+    }
+}
+
+impl From<InlineFragment<'_>> for InlineFragmentBuilder {
+    fn from(fragment: InlineFragment<'_>) -> Self {
+        InlineFragmentBuilder {
+            type_condition: fragment.type_condition().map(Into::into),
+            directives: directives(fragment.directives()),
+            selection_set: selection_set(fragment.selection_set()),
+        }
+    }
+}
+
+impl From<FragmentSpread<'_>> for FragmentSpreadBuilder {
+    fn from(spread: FragmentSpread<'_>) -> Self {
+        FragmentSpreadBuilder {
+            fragment_name: spread.fragment_name().into(),
+            directives: directives(spread.directives()),
+        }
+    }
+}
+
+impl From<Directive<'_>> for DirectiveBuilder {
+    fn from(directive: Directive<'_>) -> Self {
+        DirectiveBuilder {
+            name: directive.name().into(),
+            arguments: directive
+                .arguments()
+                .map(|argument| (argument.name().into(), argument.value().into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<Type<'_>> for TypeBuilder {
+    fn from(ty: Type<'_>) -> Self {
+        TypeBuilder {
+            name: ty.name().into(),
+            wrappers: ty.wrappers().rev().collect(),
+        }
+    }
+}
+
+impl From<Value<'_>> for ValueBuilder {
+    fn from(value: Value<'_>) -> Self {
+        match value {
+            Value::Variable(inner) => ValueBuilder::Variable(inner.name().into()),
+            Value::Int(inner) => ValueBuilder::Int(inner.as_i64()),
+            Value::Float(inner) => ValueBuilder::Float(inner.value()),
+            Value::String(inner) => ValueBuilder::String(inner.value().into()),
+            Value::Boolean(inner) => ValueBuilder::Boolean(inner.value()),
+            Value::Null(_) => ValueBuilder::Null,
+            Value::Enum(inner) => ValueBuilder::Enum(inner.name().into()),
+            Value::List(list) => ValueBuilder::List(list.items().map(Into::into).collect()),
+            Value::Object(object) => ValueBuilder::Object(
+                object
+                    .fields()
+                    .map(|field| (field.name().into(), field.value().into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<ConstValue<'_>> for ValueBuilder {
+    fn from(value: ConstValue<'_>) -> Self {
+        ValueBuilder::from(Value::from(value))
+    }
+}
+
+// Writing builders into an ExecutableAstWriter.
+//
+// As with the parser, children need to be written before their parents so
+// that the writers cursors produce the right ranges.
+
+impl OperationDefinitionBuilder {
+    fn record(&self, writer: &mut ExecutableAstWriter) -> OperationDefinitionRecord {
+        for variable in &self.variable_definitions {
+            let record = VariableDefinitionRecord {
+                description: write_description(writer, &variable.description),
+                name: writer.ident(&variable.name),
+                name_span: Span::default(),
+                ty: write_type(writer, &variable.ty),
+                default_value: variable.default_value.as_ref().map(|value| {
+                    let record = value_record(writer, value);
+                    writer.values.const_value(record)
+                }),
+                directives: write_directives(writer, &variable.directives),
+            };
+            writer.variable_definition(record);
+        }
+        let variable_definitions =
+            writer.variable_definition_range(Some(self.variable_definitions.len()));
+
+        OperationDefinitionRecord {
+            description: write_description(writer, &self.description),
+            operation_type: self.operation_type,
+            operation_type_span: None,
+            name: self.name.as_ref().map(|name| writer.ident(name)),
+            name_span: None,
+            variable_definitions,
+            directives: write_directives(writer, &self.directives),
+            selection_set: write_selection_set(writer, &self.selection_set),
+            selection_set_span: Span::default(),
+        }
+    }
+}
+
+impl FragmentDefinitionBuilder {
+    fn record(&self, writer: &mut ExecutableAstWriter) -> FragmentDefinitionRecord {
+        FragmentDefinitionRecord {
+            description: write_description(writer, &self.description),
+            name: writer.ident(&self.name),
+            name_span: Span::default(),
+            type_condition: writer.ident(&self.type_condition),
+            type_condition_span: Span::default(),
+            directives: write_directives(writer, &self.directives),
+            selection_set: write_selection_set(writer, &self.selection_set),
+            selection_set_span: Span::default(),
+        }
+    }
+}
+
+fn write_description(
+    writer: &mut ExecutableAstWriter,
+    description: &Option<String>,
+) -> Option<DescriptionId> {
+    let description = description.as_deref()?;
+    let literal = StringLiteralId::String(writer.intern_string(description));
+    Some(writer.description(DescriptionRecord {
+        literal,
+        span: Span::default(),
+    }))
+}
+
+fn write_selection_set(
+    writer: &mut ExecutableAstWriter,
+    selections: &[SelectionBuilder],
+) -> IdRange<SelectionId> {
+    let records = selections
+        .iter()
+        .map(|selection| match selection {
+            SelectionBuilder::Field(field) => {
+                let record = FieldSelectionRecord {
+                    alias: field.alias.as_ref().map(|alias| writer.ident(alias)),
+                    alias_span: None,
+                    name: writer.ident(&field.name),
+                    name_span: Span::default(),
+                    arguments: write_arguments(writer, &field.arguments),
+                    directives: write_directives(writer, &field.directives),
+                    selection_set: write_selection_set(writer, &field.selection_set),
+                    selection_set_span: None,
+                };
+                SelectionRecord::Field(writer.field_selection(record))
+            }
+            SelectionBuilder::InlineFragment(fragment) => {
+                let record = InlineFragmentRecord {
+                    type_condition: fragment
+                        .type_condition
+                        .as_ref()
+                        .map(|type_condition| writer.ident(type_condition)),
+                    type_condition_span: None,
+                    directives: write_directives(writer, &fragment.directives),
+                    selection_set: write_selection_set(writer, &fragment.selection_set),
+                    selection_set_span: Span::default(),
+                };
+                SelectionRecord::InlineFragment(writer.inline_fragment(record))
+            }
+            SelectionBuilder::FragmentSpread(spread) => {
+                let record = FragmentSpreadRecord {
+                    fragment_name: writer.ident(&spread.fragment_name),
+                    fragment_name_span: Span::default(),
+                    directives: write_directives(writer, &spread.directives),
+                };
+                SelectionRecord::FragmentSpread(writer.fragment_spread(record))
+            }
+        })
+        .collect();
+
+    writer.selection_set(records)
+}
+
+fn write_type(writer: &mut ExecutableAstWriter, ty: &TypeBuilder) -> TypeId {
+    let name = writer.ident(&ty.name);
+    writer.type_reference(TypeRecord {
+        name,
+        name_start: 0,
+        wrappers: ty.wrappers.iter().copied().collect(),
+        span: Span::default(),
+    })
+}
+
+fn write_arguments(
+    writer: &mut ExecutableAstWriter,
+    arguments: &[(String, ValueBuilder)],
+) -> IdRange<ArgumentId> {
+    let records = arguments
+        .iter()
+        .map(|(name, value)| {
+            let record = value_record(writer, value);
+            ArgumentRecord {
+                name: writer.ident(name),
+                name_span: Span::default(),
+                value: writer.values.value(record),
+            }
+        })
+        .collect();
+
+    writer.arguments(records)
+}
+
+fn write_directives(
+    writer: &mut ExecutableAstWriter,
+    directives: &[DirectiveBuilder],
+) -> IdRange<DirectiveId> {
+    for directive in directives {
+        let record = DirectiveRecord {
+            name: writer.ident(&directive.name),
+            name_span: Span::default(),
+            arguments: write_arguments(writer, &directive.arguments),
+        };
+        writer.directive(record);
+    }
+    writer.directive_range(Some(directives.len()))
+}
+
+fn value_record(writer: &mut ExecutableAstWriter, value: &ValueBuilder) -> ValueRecord {
+    let kind = match value {
+        ValueBuilder::Variable(name) => ValueKind::Variable(value_string(writer, name)),
+        ValueBuilder::Int(value) => ValueKind::Int(*value),
+        ValueBuilder::Float(value) => ValueKind::Float(*value),
+        ValueBuilder::String(value) => ValueKind::String(value_string(writer, value)),
+        ValueBuilder::Boolean(value) => ValueKind::Boolean(*value),
+        ValueBuilder::Null => ValueKind::Null,
+        ValueBuilder::Enum(value) => ValueKind::Enum(value_string(writer, value)),
+        ValueBuilder::List(items) => {
+            let items = items
+                .iter()
+                .map(|item| value_record(writer, item))
+                .collect();
+            ValueKind::List(writer.values.list(items))
+        }
+        ValueBuilder::Object(fields) => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| {
+                    let name = value_string(writer, name);
+                    let record = value_record(writer, value);
+                    (name, Span::default(), writer.values.value(record))
+                })
+                .collect();
+            ValueKind::Object(writer.values.fields(fields))
+        }
+    };
+
+    ValueRecord {
+        span: Span::default(),
+        kind,
+    }
+}
+
+fn value_string(writer: &mut ExecutableAstWriter, string: &str) -> values::ids::StringId {
+    values::ids::StringId::from_executable_id(writer.intern_string(string))
+}
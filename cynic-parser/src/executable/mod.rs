@@ -2,13 +2,16 @@ use std::sync::Arc;
 
 use indexmap::IndexSet;
 
+pub mod builder;
 pub mod ids;
 pub mod iter;
 pub mod writer;
 
 mod extensions;
 mod generated;
+mod reparse;
 mod string_literal;
+mod transforms;
 mod types;
 mod values;
 
@@ -101,6 +104,18 @@ impl ExecutableDocument {
             ExecutableDefinition::Fragment(reader) => Some(reader),
         })
     }
+
+    /// Returns a normalized copy of this document, suitable for hashing
+    ///
+    /// Fragments are inlined, unused variables removed, redundant aliases
+    /// stripped, identical selections collapsed and the remaining contents
+    /// sorted.  See [`builder::ExecutableDocumentBuilder::normalize`] for
+    /// details
+    pub fn normalize(&self) -> ExecutableDocument {
+        builder::ExecutableDocumentBuilder::from(self)
+            .normalize()
+            .build()
+    }
 }
 
 pub mod storage {
@@ -0,0 +1,128 @@
+use crate::{
+    Error,
+    incremental::{self, TextEdit},
+    parser,
+};
+
+use super::{ExecutableDefinition, ExecutableDocument, writer::ExecutableAstWriter};
+
+impl ExecutableDocument {
+    /// Reparses the definitions of this document that were affected by `edit`
+    ///
+    /// `input` should be the text of the document after `edit` was applied.
+    /// Falls back to parsing the whole of `input` if the affected definitions
+    /// can't be parsed on their own.
+    pub(crate) fn reparse(
+        mut self,
+        input: &str,
+        edit: &TextEdit<'_>,
+    ) -> Result<ExecutableDocument, Error> {
+        if input.trim().is_empty() {
+            return crate::parse_executable_document(input);
+        }
+
+        let definition_ends = self
+            .definitions()
+            .map(|definition| match definition {
+                ExecutableDefinition::Operation(operation) => operation.selection_set_span().end,
+                ExecutableDefinition::Fragment(fragment) => fragment.selection_set_span().end,
+            })
+            .collect::<Vec<_>>();
+
+        let Some(region) = incremental::affected_region(&definition_ends, input, edit) else {
+            return crate::parse_executable_document(input);
+        };
+        let Some(tokens) = incremental::lex_region(input, &region) else {
+            return crate::parse_executable_document(input);
+        };
+
+        self.shift_spans(region.old_end, region.delta);
+
+        // Anything we parse now is appended to the end of the storage, leaving
+        // the ids of all the other definitions intact.
+        let previous_definitions = self.definitions.len();
+        let mut writer = ExecutableAstWriter::update(self);
+        if !tokens.is_empty()
+            && parser::ExecutableDocumentParser::new()
+                .parse(input, &mut writer, tokens)
+                .is_err()
+        {
+            return crate::parse_executable_document(input);
+        }
+
+        let mut document = writer.finish();
+        let reparsed = document.definitions.split_off(previous_definitions);
+        document.definitions.splice(region.definitions, reparsed);
+
+        Ok(document)
+    }
+
+    fn shift_spans(&mut self, position: usize, delta: isize) {
+        let ExecutableDocument {
+            strings: _,
+            block_strings: _,
+            definitions: _,
+            operations,
+            fragments,
+            selections: _,
+            field_selections,
+            inline_fragments,
+            fragment_spreads,
+            directives,
+            arguments,
+            variables,
+            descriptions,
+            types,
+            values,
+        } = self;
+
+        for record in operations {
+            if let Some(span) = &mut record.operation_type_span {
+                span.shift_from(position, delta);
+            }
+            if let Some(span) = &mut record.name_span {
+                span.shift_from(position, delta);
+            }
+            record.selection_set_span.shift_from(position, delta);
+        }
+        for record in fragments {
+            record.name_span.shift_from(position, delta);
+            record.type_condition_span.shift_from(position, delta);
+            record.selection_set_span.shift_from(position, delta);
+        }
+        for record in field_selections {
+            if let Some(span) = &mut record.alias_span {
+                span.shift_from(position, delta);
+            }
+            record.name_span.shift_from(position, delta);
+            if let Some(span) = &mut record.selection_set_span {
+                span.shift_from(position, delta);
+            }
+        }
+        for record in inline_fragments {
+            if let Some(span) = &mut record.type_condition_span {
+                span.shift_from(position, delta);
+            }
+            record.selection_set_span.shift_from(position, delta);
+        }
+        for record in fragment_spreads {
+            record.fragment_name_span.shift_from(position, delta);
+        }
+        for record in directives {
+            record.name_span.shift_from(position, delta);
+        }
+        for record in arguments {
+            record.name_span.shift_from(position, delta);
+        }
+        for record in variables {
+            record.name_span.shift_from(position, delta);
+        }
+        for record in descriptions {
+            record.span.shift_from(position, delta);
+        }
+        for record in types {
+            record.span.shift_from(position, delta);
+        }
+        values.shift_spans(position, delta);
+    }
+}
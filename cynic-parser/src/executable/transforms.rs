@@ -0,0 +1,463 @@
+//! Transforms that can be applied to an `ExecutableDocumentBuilder`.
+//!
+//! These are mostly useful for normalizing a document before hashing it,
+//! e.g. for caching or persisted queries.  See
+//! [`ExecutableDocument::normalize`](super::ExecutableDocument::normalize)
+//! for a transform that applies all of them.
+
+use std::collections::{HashMap, HashSet};
+
+use super::builder::{
+    DirectiveBuilder, ExecutableDefinitionBuilder, ExecutableDocumentBuilder,
+    FragmentDefinitionBuilder, InlineFragmentBuilder, SelectionBuilder, ValueBuilder,
+};
+
+impl ExecutableDocumentBuilder {
+    /// Applies all of the transforms in this module, in an order that
+    /// produces a normalized document.
+    pub fn normalize(&mut self) -> &mut Self {
+        self.inline_fragments()
+            .remove_unused_fragments()
+            .strip_redundant_aliases()
+            .collapse_identical_selections()
+            .remove_unused_variables()
+            .sort()
+    }
+
+    /// Replaces every spread of a named fragment with an equivalent inline
+    /// fragment.
+    ///
+    /// Any directives on the spread are moved onto the inline fragment.
+    /// Spreads of fragments that are not defined in this document, or that
+    /// would spread themselves, are left as they are.
+    ///
+    /// This does not remove the fragment definitions themselves; see
+    /// [`remove_unused_fragments`](Self::remove_unused_fragments)
+    pub fn inline_fragments(&mut self) -> &mut Self {
+        let fragments = self
+            .fragments()
+            .map(|fragment| (fragment.name.clone(), fragment.clone()))
+            .collect::<HashMap<_, _>>();
+
+        for definition in &mut self.definitions {
+            match definition {
+                ExecutableDefinitionBuilder::Operation(operation) => {
+                    inline_spreads(&mut operation.selection_set, &fragments, &mut vec![]);
+                }
+                ExecutableDefinitionBuilder::Fragment(fragment) => {
+                    let mut stack = vec![fragment.name.as_str()];
+                    inline_spreads(&mut fragment.selection_set, &fragments, &mut stack);
+                }
+            }
+        }
+        self
+    }
+
+    /// Removes any fragment definitions that can not be reached from an
+    /// operation in this document.
+    pub fn remove_unused_fragments(&mut self) -> &mut Self {
+        let fragments = self.fragment_map();
+        let mut used = HashSet::new();
+        for operation in self.operations() {
+            used_fragments(&operation.selection_set, &fragments, &mut used);
+        }
+        let used = used.into_iter().map(str::to_owned).collect::<HashSet<_>>();
+
+        self.definitions.retain(|definition| match definition {
+            ExecutableDefinitionBuilder::Operation(_) => true,
+            ExecutableDefinitionBuilder::Fragment(fragment) => used.contains(&fragment.name),
+        });
+        self
+    }
+
+    /// Removes any variable definitions that are not used by their operation,
+    /// or by any of the fragments that operation spreads.
+    pub fn remove_unused_variables(&mut self) -> &mut Self {
+        let used_per_operation = {
+            let fragments = self.fragment_map();
+            self.definitions
+                .iter()
+                .map(|definition| {
+                    let ExecutableDefinitionBuilder::Operation(operation) = definition else {
+                        return HashSet::new();
+                    };
+                    let mut reachable = HashSet::new();
+                    used_fragments(&operation.selection_set, &fragments, &mut reachable);
+
+                    let mut variables = HashSet::new();
+                    directive_variables(&operation.directives, &mut variables);
+                    selection_variables(&operation.selection_set, &mut variables);
+                    for name in reachable {
+                        let Some(fragment) = fragments.get(name) else {
+                            continue;
+                        };
+                        directive_variables(&fragment.directives, &mut variables);
+                        selection_variables(&fragment.selection_set, &mut variables);
+                    }
+                    variables
+                        .into_iter()
+                        .map(str::to_owned)
+                        .collect::<HashSet<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for (definition, used) in self.definitions.iter_mut().zip(used_per_operation) {
+            if let ExecutableDefinitionBuilder::Operation(operation) = definition {
+                operation
+                    .variable_definitions
+                    .retain(|variable| used.contains(&variable.name));
+            }
+        }
+        self
+    }
+
+    /// Removes any aliases that are the same as the name of their field.
+    pub fn strip_redundant_aliases(&mut self) -> &mut Self {
+        for selections in self.selection_sets_mut() {
+            visit_selections(selections, &mut |selection| {
+                if let SelectionBuilder::Field(field) = selection {
+                    if field.alias.as_ref() == Some(&field.name) {
+                        field.alias = None;
+                    }
+                }
+            });
+        }
+        self
+    }
+
+    /// Merges selections that are identical apart from their sub-selections:
+    ///
+    /// - Fields with the same response key, name, arguments & directives.
+    /// - Inline fragments with the same type condition & directives.
+    /// - Fragment spreads with the same name & directives.
+    ///
+    /// Arguments are compared without regard to their order.
+    pub fn collapse_identical_selections(&mut self) -> &mut Self {
+        for selections in self.selection_sets_mut() {
+            collapse_selections(selections);
+        }
+        self
+    }
+
+    /// Sorts the contents of the document into a deterministic order.
+    ///
+    /// - Variable definitions are sorted by name.
+    /// - Fields are sorted by response key, followed by inline fragments
+    ///   sorted by type condition, followed by fragment spreads sorted by name.
+    /// - Arguments & the fields of input objects are sorted by name.
+    ///
+    /// The order of definitions & directives is left as it is, as is the
+    /// order of otherwise equal selections.
+    pub fn sort(&mut self) -> &mut Self {
+        for definition in &mut self.definitions {
+            let (directives, selections) = match definition {
+                ExecutableDefinitionBuilder::Operation(operation) => {
+                    operation
+                        .variable_definitions
+                        .sort_by(|lhs, rhs| lhs.name.cmp(&rhs.name));
+                    for variable in &mut operation.variable_definitions {
+                        sort_directives(&mut variable.directives);
+                        if let Some(value) = &mut variable.default_value {
+                            sort_value(value);
+                        }
+                    }
+                    (&mut operation.directives, &mut operation.selection_set)
+                }
+                ExecutableDefinitionBuilder::Fragment(fragment) => {
+                    (&mut fragment.directives, &mut fragment.selection_set)
+                }
+            };
+            sort_directives(directives);
+            sort_selections(selections);
+        }
+        self
+    }
+
+    fn fragment_map(&self) -> HashMap<&str, &FragmentDefinitionBuilder> {
+        self.fragments()
+            .map(|fragment| (fragment.name.as_str(), fragment))
+            .collect()
+    }
+
+    fn selection_sets_mut(&mut self) -> impl Iterator<Item = &mut Vec<SelectionBuilder>> {
+        self.definitions
+            .iter_mut()
+            .map(|definition| match definition {
+                ExecutableDefinitionBuilder::Operation(operation) => &mut operation.selection_set,
+                ExecutableDefinitionBuilder::Fragment(fragment) => &mut fragment.selection_set,
+            })
+    }
+}
+
+fn inline_spreads<'a>(
+    selections: &mut [SelectionBuilder],
+    fragments: &'a HashMap<String, FragmentDefinitionBuilder>,
+    stack: &mut Vec<&'a str>,
+) {
+    for selection in selections {
+        match selection {
+            SelectionBuilder::Field(field) => {
+                inline_spreads(&mut field.selection_set, fragments, stack);
+            }
+            SelectionBuilder::InlineFragment(fragment) => {
+                inline_spreads(&mut fragment.selection_set, fragments, stack);
+            }
+            SelectionBuilder::FragmentSpread(spread) => {
+                let Some((name, fragment)) = fragments.get_key_value(&spread.fragment_name) else {
+                    continue;
+                };
+                if stack.contains(&name.as_str()) {
+                    continue;
+                }
+
+                let mut selection_set = fragment.selection_set.clone();
+                stack.push(name);
+                inline_spreads(&mut selection_set, fragments, stack);
+                stack.pop();
+
+                *selection = SelectionBuilder::InlineFragment(InlineFragmentBuilder {
+                    type_condition: Some(fragment.type_condition.clone()),
+                    directives: std::mem::take(&mut spread.directives),
+                    selection_set,
+                });
+            }
+        }
+    }
+}
+
+/// Collects the names of all the fragments that are transitively spread by
+/// `selections`
+fn used_fragments<'a>(
+    selections: &'a [SelectionBuilder],
+    fragments: &HashMap<&'a str, &'a FragmentDefinitionBuilder>,
+    used: &mut HashSet<&'a str>,
+) {
+    for selection in selections {
+        match selection {
+            SelectionBuilder::Field(field) => {
+                used_fragments(&field.selection_set, fragments, used);
+            }
+            SelectionBuilder::InlineFragment(fragment) => {
+                used_fragments(&fragment.selection_set, fragments, used);
+            }
+            SelectionBuilder::FragmentSpread(spread) => {
+                let name = spread.fragment_name.as_str();
+                if !used.insert(name) {
+                    continue;
+                }
+                if let Some(fragment) = fragments.get(name) {
+                    used_fragments(&fragment.selection_set, fragments, used);
+                }
+            }
+        }
+    }
+}
+
+fn selection_variables<'a>(selections: &'a [SelectionBuilder], variables: &mut HashSet<&'a str>) {
+    for selection in selections {
+        match selection {
+            SelectionBuilder::Field(field) => {
+                for (_, value) in &field.arguments {
+                    value_variables(value, variables);
+                }
+                directive_variables(&field.directives, variables);
+                selection_variables(&field.selection_set, variables);
+            }
+            SelectionBuilder::InlineFragment(fragment) => {
+                directive_variables(&fragment.directives, variables);
+                selection_variables(&fragment.selection_set, variables);
+            }
+            SelectionBuilder::FragmentSpread(spread) => {
+                directive_variables(&spread.directives, variables);
+            }
+        }
+    }
+}
+
+fn directive_variables<'a>(directives: &'a [DirectiveBuilder], variables: &mut HashSet<&'a str>) {
+    for directive in directives {
+        for (_, value) in &directive.arguments {
+            value_variables(value, variables);
+        }
+    }
+}
+
+fn value_variables<'a>(value: &'a ValueBuilder, variables: &mut HashSet<&'a str>) {
+    match value {
+        ValueBuilder::Variable(name) => {
+            variables.insert(name);
+        }
+        ValueBuilder::List(items) => {
+            for item in items {
+                value_variables(item, variables);
+            }
+        }
+        ValueBuilder::Object(fields) => {
+            for (_, value) in fields {
+                value_variables(value, variables);
+            }
+        }
+        ValueBuilder::Int(_)
+        | ValueBuilder::Float(_)
+        | ValueBuilder::String(_)
+        | ValueBuilder::Boolean(_)
+        | ValueBuilder::Null
+        | ValueBuilder::Enum(_) => {}
+    }
+}
+
+fn visit_selections(
+    selections: &mut [SelectionBuilder],
+    visitor: &mut impl FnMut(&mut SelectionBuilder),
+) {
+    for selection in selections {
+        visitor(selection);
+        match selection {
+            SelectionBuilder::Field(field) => visit_selections(&mut field.selection_set, visitor),
+            SelectionBuilder::InlineFragment(fragment) => {
+                visit_selections(&mut fragment.selection_set, visitor)
+            }
+            SelectionBuilder::FragmentSpread(_) => {}
+        }
+    }
+}
+
+fn collapse_selections(selections: &mut Vec<SelectionBuilder>) {
+    let mut collapsed = Vec::<SelectionBuilder>::with_capacity(selections.len());
+    for selection in selections.drain(..) {
+        let existing = collapsed
+            .iter_mut()
+            .find(|existing| can_collapse(existing, &selection));
+
+        match (existing, selection) {
+            (Some(SelectionBuilder::Field(existing)), SelectionBuilder::Field(field)) => {
+                existing.selection_set.extend(field.selection_set);
+            }
+            (
+                Some(SelectionBuilder::InlineFragment(existing)),
+                SelectionBuilder::InlineFragment(fragment),
+            ) => {
+                existing.selection_set.extend(fragment.selection_set);
+            }
+            (Some(_), _) => {}
+            (None, selection) => collapsed.push(selection),
+        }
+    }
+
+    for selection in &mut collapsed {
+        match selection {
+            SelectionBuilder::Field(field) => collapse_selections(&mut field.selection_set),
+            SelectionBuilder::InlineFragment(fragment) => {
+                collapse_selections(&mut fragment.selection_set)
+            }
+            SelectionBuilder::FragmentSpread(_) => {}
+        }
+    }
+
+    *selections = collapsed;
+}
+
+fn can_collapse(lhs: &SelectionBuilder, rhs: &SelectionBuilder) -> bool {
+    match (lhs, rhs) {
+        (SelectionBuilder::Field(lhs), SelectionBuilder::Field(rhs)) => {
+            lhs.response_key() == rhs.response_key()
+                && lhs.name == rhs.name
+                && same_arguments(&lhs.arguments, &rhs.arguments)
+                && same_directives(&lhs.directives, &rhs.directives)
+        }
+        (SelectionBuilder::InlineFragment(lhs), SelectionBuilder::InlineFragment(rhs)) => {
+            lhs.type_condition == rhs.type_condition
+                && same_directives(&lhs.directives, &rhs.directives)
+        }
+        (SelectionBuilder::FragmentSpread(lhs), SelectionBuilder::FragmentSpread(rhs)) => {
+            lhs.fragment_name == rhs.fragment_name
+                && same_directives(&lhs.directives, &rhs.directives)
+        }
+        _ => false,
+    }
+}
+
+fn same_directives(lhs: &[DirectiveBuilder], rhs: &[DirectiveBuilder]) -> bool {
+    lhs.len() == rhs.len()
+        && lhs.iter().zip(rhs).all(|(lhs, rhs)| {
+            lhs.name == rhs.name && same_arguments(&lhs.arguments, &rhs.arguments)
+        })
+}
+
+fn same_arguments(lhs: &[(String, ValueBuilder)], rhs: &[(String, ValueBuilder)]) -> bool {
+    let sorted = |arguments: &[(String, ValueBuilder)]| {
+        let mut arguments = arguments.to_vec();
+        arguments.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0));
+        for (_, value) in &mut arguments {
+            sort_value(value);
+        }
+        arguments
+    };
+
+    lhs.len() == rhs.len() && sorted(lhs) == sorted(rhs)
+}
+
+fn sort_selections(selections: &mut [SelectionBuilder]) {
+    selections.sort_by(|lhs, rhs| selection_key(lhs).cmp(&selection_key(rhs)));
+
+    for selection in selections {
+        match selection {
+            SelectionBuilder::Field(field) => {
+                sort_arguments(&mut field.arguments);
+                sort_directives(&mut field.directives);
+                sort_selections(&mut field.selection_set);
+            }
+            SelectionBuilder::InlineFragment(fragment) => {
+                sort_directives(&mut fragment.directives);
+                sort_selections(&mut fragment.selection_set);
+            }
+            SelectionBuilder::FragmentSpread(spread) => {
+                sort_directives(&mut spread.directives);
+            }
+        }
+    }
+}
+
+fn selection_key(selection: &SelectionBuilder) -> (u8, &str, &str) {
+    match selection {
+        SelectionBuilder::Field(field) => (0, field.response_key(), &field.name),
+        SelectionBuilder::InlineFragment(fragment) => (
+            1,
+            fragment.type_condition.as_deref().unwrap_or_default(),
+            "",
+        ),
+        SelectionBuilder::FragmentSpread(spread) => (2, &spread.fragment_name, ""),
+    }
+}
+
+fn sort_directives(directives: &mut [DirectiveBuilder]) {
+    for directive in directives {
+        sort_arguments(&mut directive.arguments);
+    }
+}
+
+fn sort_arguments(arguments: &mut [(String, ValueBuilder)]) {
+    arguments.sort_by(|lhs, rhs| lhs.0.cmp(&rhs.0));
+    for (_, value) in arguments {
+        sort_value(value);
+    }
+}
+
+fn sort_value(value: &mut ValueBuilder) {
+    match value {
+        ValueBuilder::List(items) => {
+            for item in items {
+                sort_value(item);
+            }
+        }
+        ValueBuilder::Object(fields) => sort_arguments(fields),
+        ValueBuilder::Variable(_)
+        | ValueBuilder::Int(_)
+        | ValueBuilder::Float(_)
+        | ValueBuilder::String(_)
+        | ValueBuilder::Boolean(_)
+        | ValueBuilder::Null
+        | ValueBuilder::Enum(_) => {}
+    }
+}
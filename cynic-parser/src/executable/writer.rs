@@ -61,6 +61,50 @@ impl ExecutableAstWriter {
         Self::default()
     }
 
+    pub fn update(document: ExecutableDocument) -> Self {
+        let ExecutableDocument {
+            strings,
+            block_strings,
+            definitions,
+            operations,
+            fragments,
+            selections,
+            field_selections,
+            inline_fragments,
+            fragment_spreads,
+            directives,
+            arguments,
+            variables,
+            descriptions,
+            types,
+            values,
+        } = document;
+
+        let values = crate::values::writer::ValueWriter::update(values);
+        let strings = Arc::unwrap_or_clone(strings);
+
+        ExecutableAstWriter {
+            directive_cursor: DirectiveId::new(directives.len()),
+            variable_definition_cursor: VariableDefinitionId::new(variables.len()),
+
+            strings,
+            block_strings,
+            definitions,
+            operations,
+            fragments,
+            selections,
+            field_selections,
+            inline_fragments,
+            fragment_spreads,
+            directives,
+            arguments,
+            variables,
+            descriptions,
+            types,
+            values,
+        }
+    }
+
     pub fn finish(self) -> ExecutableDocument {
         // TODO: Possibly assert things in here for safety...
         let ExecutableAstWriter {
@@ -245,3 +289,68 @@ impl ExecutableAstWriter {
         StringId::new(id)
     }
 }
+
+impl crate::recovery::Rollback for ExecutableAstWriter {
+    type Checkpoint = WriterCheckpoint;
+
+    fn checkpoint(&self) -> WriterCheckpoint {
+        WriterCheckpoint {
+            values: self.values.checkpoint(),
+            block_strings: self.block_strings.len(),
+            definitions: self.definitions.len(),
+            operations: self.operations.len(),
+            fragments: self.fragments.len(),
+            selections: self.selections.len(),
+            field_selections: self.field_selections.len(),
+            inline_fragments: self.inline_fragments.len(),
+            fragment_spreads: self.fragment_spreads.len(),
+            directives: self.directives.len(),
+            arguments: self.arguments.len(),
+            variables: self.variables.len(),
+            descriptions: self.descriptions.len(),
+            types: self.types.len(),
+            directive_cursor: self.directive_cursor,
+            variable_definition_cursor: self.variable_definition_cursor,
+        }
+    }
+
+    fn rollback(&mut self, checkpoint: WriterCheckpoint) {
+        self.values.rollback(checkpoint.values);
+        self.block_strings.truncate(checkpoint.block_strings);
+        self.definitions.truncate(checkpoint.definitions);
+        self.operations.truncate(checkpoint.operations);
+        self.fragments.truncate(checkpoint.fragments);
+        self.selections.truncate(checkpoint.selections);
+        self.field_selections.truncate(checkpoint.field_selections);
+        self.inline_fragments.truncate(checkpoint.inline_fragments);
+        self.fragment_spreads.truncate(checkpoint.fragment_spreads);
+        self.directives.truncate(checkpoint.directives);
+        self.arguments.truncate(checkpoint.arguments);
+        self.variables.truncate(checkpoint.variables);
+        self.descriptions.truncate(checkpoint.descriptions);
+        self.types.truncate(checkpoint.types);
+        self.directive_cursor = checkpoint.directive_cursor;
+        self.variable_definition_cursor = checkpoint.variable_definition_cursor;
+    }
+}
+
+/// The state of an `ExecutableAstWriter` at some point in time
+#[derive(Clone, Copy)]
+pub(crate) struct WriterCheckpoint {
+    values: crate::values::writer::ValueCheckpoint,
+    block_strings: usize,
+    definitions: usize,
+    operations: usize,
+    fragments: usize,
+    selections: usize,
+    field_selections: usize,
+    inline_fragments: usize,
+    fragment_spreads: usize,
+    directives: usize,
+    arguments: usize,
+    variables: usize,
+    descriptions: usize,
+    types: usize,
+    directive_cursor: DirectiveId,
+    variable_definition_cursor: VariableDefinitionId,
+}
@@ -0,0 +1,130 @@
+//! Incremental reparsing of documents after an edit.
+//!
+//! Rather than parsing a whole document again we work out which top level
+//! definitions an edit could have affected and only re-lex & re-parse those.
+//! Definitions before the edit are left as they are, and definitions after it
+//! keep their storage & ids but have their spans moved to account for the
+//! edit.
+
+use std::ops::Range;
+
+use crate::{
+    Span,
+    lexer::{Lexer, Token},
+    parser::AdditionalErrors,
+};
+
+type SpannedToken<'input> = Result<(usize, Token<'input>, usize), AdditionalErrors>;
+
+/// A change to the text of a document
+///
+/// `span` is the span of the replaced text in the document before the edit,
+/// and `replacement` is the text that replaced it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextEdit<'a> {
+    pub span: Span,
+    pub replacement: &'a str,
+}
+
+impl<'a> TextEdit<'a> {
+    pub fn new(span: Span, replacement: &'a str) -> Self {
+        TextEdit { span, replacement }
+    }
+
+    /// Applies this edit to the text of a document
+    pub fn apply(&self, input: &str) -> String {
+        let mut output = String::with_capacity(input.len() + self.replacement.len());
+        output.push_str(&input[..self.span.start]);
+        output.push_str(self.replacement);
+        output.push_str(&input[self.span.end..]);
+        output
+    }
+
+    /// How much this edit moves any text after it
+    fn delta(&self) -> isize {
+        self.replacement.len() as isize - (self.span.end as isize - self.span.start as isize)
+    }
+}
+
+/// The part of a document that needs to be reparsed after an edit
+pub(crate) struct Region {
+    /// The indices of the definitions in the previous document that need to
+    /// be replaced
+    pub definitions: Range<usize>,
+
+    /// Where the region starts, which is the same before & after the edit
+    pub start: usize,
+
+    /// Where the region ended before the edit.  Anything at or after this
+    /// needs to be moved by `delta`.
+    pub old_end: usize,
+
+    /// Where the region ends after the edit
+    pub new_end: usize,
+
+    pub delta: isize,
+}
+
+/// Works out which definitions an edit affected
+///
+/// `definition_ends` are the ends of each definition before the edit, and
+/// `input` is the text of the document after the edit.  Returns `None` if the
+/// edit doesn't line up with `input`.
+pub(crate) fn affected_region(
+    definition_ends: &[usize],
+    input: &str,
+    edit: &TextEdit<'_>,
+) -> Option<Region> {
+    let delta = edit.delta();
+    let old_len = input.len().checked_add_signed(-delta)?;
+
+    if edit.span.start > edit.span.end
+        || edit.span.end > old_len
+        || input.get(edit.span.start..edit.span.start + edit.replacement.len())
+            != Some(edit.replacement)
+    {
+        return None;
+    }
+
+    // Any definition that ends before the edit is untouched, as is any
+    // definition that comes after one ending after the edit.  Note that
+    // touching counts as overlapping, since typing at the very end of a
+    // definition can change it.
+    let first = definition_ends.partition_point(|end| *end < edit.span.start);
+    let last = definition_ends.partition_point(|end| *end <= edit.span.end);
+
+    let start = match first {
+        0 => 0,
+        _ => definition_ends[first - 1],
+    };
+
+    let (definitions, old_end) = match definition_ends.get(last) {
+        Some(end) => (first..last + 1, *end),
+        None => (first..definition_ends.len(), old_len),
+    };
+
+    Some(Region {
+        definitions,
+        start,
+        old_end,
+        new_end: old_end.checked_add_signed(delta)?,
+        delta,
+    })
+}
+
+/// Lexes the region of `input` that needs to be reparsed
+///
+/// Returns `None` if there were any lexical errors.
+pub(crate) fn lex_region<'input>(
+    input: &'input str,
+    region: &Region,
+) -> Option<Vec<SpannedToken<'input>>> {
+    let offset = region.start;
+
+    Lexer::new(input.get(region.start..region.new_end)?)
+        .map(|token| {
+            let (start, token, end) = token.ok()?;
+            Some(Ok((start + offset, token, end + offset)))
+        })
+        .collect()
+}
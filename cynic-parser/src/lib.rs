@@ -1,16 +1,23 @@
 pub mod common;
 pub mod executable;
+pub mod merge;
 pub mod schema_coordinates;
 pub mod type_system;
+pub mod validation;
 pub mod values;
 
 mod errors;
+mod incremental;
 mod lexer;
+mod recovery;
 mod span;
 
 #[allow(clippy::all)]
 mod parser;
 
+#[cfg(feature = "print")]
+pub mod diff;
+
 #[cfg(feature = "print")]
 pub mod printing;
 
@@ -20,6 +27,7 @@ pub use errors::Report;
 pub use self::{
     errors::Error,
     executable::ExecutableDocument,
+    incremental::TextEdit,
     schema_coordinates::{SchemaCoordinate, parse_schema_coordinate},
     span::Span,
     type_system::TypeSystemDocument,
@@ -39,6 +47,29 @@ pub fn parse_type_system_document(input: &str) -> Result<TypeSystemDocument, Err
     Ok(ast.finish())
 }
 
+/// Parses a `TypeSystemDocument`, keeping any `#` comments in it
+///
+/// The comments are attached to nearby definitions, fields, arguments & enum
+/// values and can be read back with e.g. [`type_system::FieldDefinition::leading_comments`].
+/// The pretty printer will also re-emit them.
+///
+/// ```rust
+/// let document = cynic_parser::parse_type_system_document_with_comments(
+///     "# The root query\ntype Query {\n  hello: String # Says hello\n}",
+/// )
+/// .unwrap();
+///
+/// let definition = document.definitions().next().unwrap();
+/// let comments = definition.leading_comments().collect::<Vec<_>>();
+/// assert_eq!(comments[0].text(), " The root query");
+/// ```
+pub fn parse_type_system_document_with_comments(input: &str) -> Result<TypeSystemDocument, Error> {
+    let mut document = parse_type_system_document(input)?;
+    document.attach_comments(input);
+
+    Ok(document)
+}
+
 pub fn parse_executable_document(input: &str) -> Result<ExecutableDocument, Error> {
     if input.trim().is_empty() {
         return Err(Error::EmptyExecutableDocument);
@@ -52,6 +83,113 @@ pub fn parse_executable_document(input: &str) -> Result<ExecutableDocument, Erro
     Ok(ast.finish())
 }
 
+/// Parses a `TypeSystemDocument`, recovering from any errors it finds
+///
+/// Unlike [`parse_type_system_document`] this doesn't stop at the first
+/// error.  Any definition that fails to parse is skipped, and the document
+/// containing every definition that did parse is returned alongside all of
+/// the errors.
+///
+/// ```rust
+/// let (document, errors) = cynic_parser::parse_type_system_document_recovering(
+///     "type Query { hello: String } type Broken { oops } scalar Date",
+/// );
+///
+/// assert_eq!(document.definitions().count(), 2);
+/// assert_eq!(errors.len(), 1);
+/// ```
+pub fn parse_type_system_document_recovering(input: &str) -> (TypeSystemDocument, Vec<Error>) {
+    let mut ast = type_system::writer::TypeSystemAstWriter::new();
+    if input.trim().is_empty() {
+        return (ast.finish(), vec![Error::EmptyTypeSystemDocument]);
+    }
+
+    let errors = recovery::parse_with_recovery(
+        input,
+        &mut ast,
+        recovery::starts_type_system_definition,
+        |ast, tokens| {
+            parser::TypeSystemDocumentParser::new()
+                .parse(input, ast, tokens)
+                .map_err(Error::from)
+        },
+    );
+
+    (ast.finish(), errors)
+}
+
+/// Parses an `ExecutableDocument`, recovering from any errors it finds
+///
+/// Unlike [`parse_executable_document`] this doesn't stop at the first
+/// error.  Any definition that fails to parse is skipped, and the document
+/// containing every definition that did parse is returned alongside all of
+/// the errors.
+pub fn parse_executable_document_recovering(input: &str) -> (ExecutableDocument, Vec<Error>) {
+    let mut ast = executable::writer::ExecutableAstWriter::new();
+    if input.trim().is_empty() {
+        return (ast.finish(), vec![Error::EmptyExecutableDocument]);
+    }
+
+    let errors = recovery::parse_with_recovery(
+        input,
+        &mut ast,
+        recovery::starts_executable_definition,
+        |ast, tokens| {
+            parser::ExecutableDocumentParser::new()
+                .parse(input, ast, tokens)
+                .map_err(Error::from)
+        },
+    );
+
+    (ast.finish(), errors)
+}
+
+/// Updates a `TypeSystemDocument` after an edit to its text
+///
+/// `input` should be the full text of the document after `edit` was applied.
+/// Only the definitions that the edit touched are re-lexed & re-parsed, the
+/// rest keep their storage & ids with their spans moved to account for the
+/// edit.  The exception is directive definitions, which are renumbered to
+/// drop any that were replaced.  This makes it suitable for e.g. reparsing on every keystroke in an
+/// editor.
+///
+/// The storage for any replaced definitions isn't reclaimed, so it's worth
+/// doing a full parse every so often when making lots of edits.
+///
+/// ```rust
+/// use cynic_parser::{Span, TextEdit, type_system::{Definition, TypeDefinition}};
+///
+/// let input = "type Query { hello: String }\n\nscalar Date\n";
+/// let document = cynic_parser::parse_type_system_document(input).unwrap();
+///
+/// let edit = TextEdit::new(Span::new(13, 18), "goodbye");
+/// let input = edit.apply(input);
+/// let document = cynic_parser::reparse_type_system_document(document, &input, &edit).unwrap();
+///
+/// let Some(Definition::Type(TypeDefinition::Object(query))) = document.definitions().next() else {
+///     unreachable!()
+/// };
+/// assert_eq!(query.fields().next().unwrap().name(), "goodbye");
+/// ```
+pub fn reparse_type_system_document(
+    previous: TypeSystemDocument,
+    input: &str,
+    edit: &TextEdit<'_>,
+) -> Result<TypeSystemDocument, Error> {
+    previous.reparse(input, edit)
+}
+
+/// Updates an `ExecutableDocument` after an edit to its text
+///
+/// See [`reparse_type_system_document`] for details.
+pub fn reparse_executable_document(
+    previous: ExecutableDocument,
+    input: &str,
+    edit: &TextEdit<'_>,
+) -> Result<ExecutableDocument, Error> {
+    previous.reparse(input, edit)
+}
+
 trait AstLookup<Id> {
     type Output: ?Sized;
 
@@ -0,0 +1,472 @@
+//! Combining type system documents.
+//!
+//! [`merge_type_system_documents`] takes any number of `TypeSystemDocument`s -
+//! e.g. a schema that has been split across several files - and produces a
+//! single document with every type extension folded into the definition it
+//! extends.  [`TypeSystemDocument::normalize`] does the same for a single
+//! document.
+//!
+//! Any conflicting definitions are reported as errors rather than silently
+//! picking one of them.
+
+use std::{collections::HashSet, fmt};
+
+use indexmap::IndexMap;
+
+use crate::{
+    Span, TypeSystemDocument,
+    type_system::{
+        Definition, Description, Directive, DirectiveDefinition, SchemaDefinition, TypeDefinition,
+        builder::*,
+    },
+    validation::ValidationError,
+};
+
+/// Merges several type system documents into one, folding every type
+/// extension into the definition it extends
+///
+/// Definitions are output in the order they were first seen in.  Fields,
+/// values, members & directives from extensions are appended to those from
+/// the definition they extend, in the order the extensions appear.  An
+/// extension of a type that has no definition is treated as the definition of
+/// that type.
+///
+/// The merged document is built with a [`TypeSystemDocumentBuilder`], so
+/// like any built document it has no source text: its spans are all empty
+/// and comments are not carried over.  The spans in any [`MergeError`]s refer
+/// to the text of the document the conflict was found in.
+///
+/// ```rust
+/// use cynic_parser::{merge::merge_type_system_documents, parse_type_system_document};
+///
+/// let query = parse_type_system_document("type Query { hello: String }").unwrap();
+/// let users = parse_type_system_document(
+///     "extend type Query { users: [User!]! } type User { name: String! }"
+/// ).unwrap();
+///
+/// let merged = merge_type_system_documents([&query, &users]).unwrap();
+///
+/// assert_eq!(merged.definitions().count(), 2);
+/// ```
+///
+/// # Errors
+///
+/// Returns every conflict found: types or directives that are defined more
+/// than once, extensions of a different kind from the type they extend, and
+/// fields, enum values, union members or root operations that are
+/// defined more than once.
+pub fn merge_type_system_documents<'a>(
+    documents: impl IntoIterator<Item = &'a TypeSystemDocument>,
+) -> Result<TypeSystemDocument, Vec<MergeError>> {
+    let mut merger = Merger {
+        definitions: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    let mut entries = IndexMap::<Key<'a>, Entry<'a>>::new();
+
+    for (document, source) in documents.into_iter().enumerate() {
+        for definition in source.definitions() {
+            match definition {
+                Definition::Schema(schema) => {
+                    let Entry::Schema(parts) = entries
+                        .entry(Key::Schema)
+                        .or_insert_with(|| Entry::Schema(Parts::default()))
+                    else {
+                        unreachable!()
+                    };
+                    if parts.definition.is_some() {
+                        merger.error(
+                            document,
+                            ValidationError::DuplicateSchemaDefinition {
+                                span: schema.span(),
+                            },
+                        );
+                        continue;
+                    }
+                    parts.definition = Some((document, schema));
+                }
+                Definition::SchemaExtension(schema) => {
+                    let Entry::Schema(parts) = entries
+                        .entry(Key::Schema)
+                        .or_insert_with(|| Entry::Schema(Parts::default()))
+                    else {
+                        unreachable!()
+                    };
+                    parts.extensions.push((document, schema));
+                }
+                Definition::Type(ty) => {
+                    let Entry::Type(parts) = entries
+                        .entry(Key::Type(ty.name()))
+                        .or_insert_with(|| Entry::Type(Parts::default()))
+                    else {
+                        unreachable!()
+                    };
+                    if parts.definition.is_some() {
+                        merger.error(
+                            document,
+                            ValidationError::DuplicateTypeDefinition {
+                                name: ty.name().to_string(),
+                                span: ty.name_span(),
+                            },
+                        );
+                        continue;
+                    }
+                    parts.definition = Some((document, ty));
+                }
+                Definition::TypeExtension(ty) => {
+                    let Entry::Type(parts) = entries
+                        .entry(Key::Type(ty.name()))
+                        .or_insert_with(|| Entry::Type(Parts::default()))
+                    else {
+                        unreachable!()
+                    };
+                    parts.extensions.push((document, ty));
+                }
+                Definition::Directive(directive) => {
+                    if entries.contains_key(&Key::Directive(directive.name())) {
+                        merger.error(
+                            document,
+                            ValidationError::DuplicateDirectiveDefinition {
+                                name: directive.name().to_string(),
+                                span: directive.name_span(),
+                            },
+                        );
+                        continue;
+                    }
+                    entries.insert(
+                        Key::Directive(directive.name()),
+                        Entry::Directive(directive),
+                    );
+                }
+            }
+        }
+    }
+
+    for entry in entries.into_values() {
+        match entry {
+            Entry::Schema(parts) => merger.merge_schema(parts),
+            Entry::Type(parts) => merger.merge_type(parts),
+            Entry::Directive(directive) => merger.merge_directive(directive),
+        }
+    }
+
+    if !merger.errors.is_empty() {
+        return Err(merger.errors);
+    }
+
+    Ok(TypeSystemDocumentBuilder {
+        definitions: merger.definitions,
+    }
+    .build())
+}
+
+/// A conflict found while merging type system documents
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MergeError {
+    /// The index of the document the conflicting definition was found in
+    pub document: usize,
+
+    /// The conflict.  Its span refers to the text of `document`
+    pub error: ValidationError,
+}
+
+impl MergeError {
+    pub fn span(&self) -> Span {
+        self.error.span()
+    }
+}
+
+impl std::error::Error for MergeError {}
+
+impl fmt::Display for MergeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+#[derive(Hash, PartialEq, Eq)]
+enum Key<'a> {
+    Schema,
+    Type(&'a str),
+    Directive(&'a str),
+}
+
+enum Entry<'a> {
+    Schema(Parts<SchemaDefinition<'a>>),
+    Type(Parts<TypeDefinition<'a>>),
+    Directive(DirectiveDefinition<'a>),
+}
+
+/// A definition & its extensions, each alongside the index of the document
+/// they came from
+struct Parts<T> {
+    definition: Option<(usize, T)>,
+    extensions: Vec<(usize, T)>,
+}
+
+impl<T> Default for Parts<T> {
+    fn default() -> Self {
+        Parts {
+            definition: None,
+            extensions: Vec::new(),
+        }
+    }
+}
+
+impl<T> Parts<T> {
+    fn into_vec(self) -> Vec<(usize, T)> {
+        self.definition.into_iter().chain(self.extensions).collect()
+    }
+}
+
+struct Merger {
+    definitions: Vec<DefinitionBuilder>,
+    errors: Vec<MergeError>,
+}
+
+impl Merger {
+    fn error(&mut self, document: usize, error: ValidationError) {
+        self.errors.push(MergeError { document, error });
+    }
+
+    /// Filters out any items whose name has already been seen, reporting an
+    /// error for each one
+    fn unique<'a, T>(
+        &mut self,
+        items: impl IntoIterator<Item = (usize, T)>,
+        name: impl Fn(&T) -> &'a str,
+        error: impl Fn(&T) -> ValidationError,
+    ) -> Vec<T> {
+        let mut seen = HashSet::new();
+        let mut output = Vec::new();
+        for (document, item) in items {
+            if !seen.insert(name(&item)) {
+                let error = error(&item);
+                self.error(document, error);
+                continue;
+            }
+            output.push(item);
+        }
+        output
+    }
+
+    fn merge_schema(&mut self, parts: Parts<SchemaDefinition<'_>>) {
+        let parts = parts.into_vec();
+
+        let mut seen = HashSet::new();
+        let mut root_operations = Vec::new();
+        for (document, schema) in &parts {
+            for operation in schema.root_operations() {
+                if !seen.insert(operation.operation_type()) {
+                    self.error(
+                        *document,
+                        ValidationError::DuplicateRootOperation {
+                            operation_type: operation.operation_type(),
+                            span: operation.operation_type_span(),
+                        },
+                    );
+                    continue;
+                }
+                root_operations.push((operation.operation_type(), operation.named_type().into()));
+            }
+        }
+
+        self.definitions
+            .push(DefinitionBuilder::Schema(SchemaDefinitionBuilder {
+                description: description(parts.iter().map(|(_, schema)| schema.description())),
+                directives: directives(parts.iter().flat_map(|(_, schema)| schema.directives())),
+                root_operations,
+            }));
+    }
+
+    fn merge_directive(&mut self, directive: DirectiveDefinition<'_>) {
+        self.definitions
+            .push(DefinitionBuilder::Directive(directive.into()));
+    }
+
+    fn merge_type(&mut self, parts: Parts<TypeDefinition<'_>>) {
+        let mut parts = parts.into_vec();
+        let (_, primary) = parts[0];
+
+        parts.retain(|(document, ty)| {
+            if std::mem::discriminant(ty) == std::mem::discriminant(&primary) {
+                return true;
+            }
+            self.errors.push(MergeError {
+                document: *document,
+                error: ValidationError::ExtensionKindMismatch {
+                    name: ty.name().to_string(),
+                    span: ty.name_span(),
+                },
+            });
+            false
+        });
+
+        let type_name = primary.name();
+        let name = type_name.to_string();
+        let description = description(parts.iter().map(|(_, ty)| ty.description()));
+        let directives = directives(parts.iter().flat_map(|(_, ty)| ty.directives()));
+
+        let definition = match primary {
+            TypeDefinition::Scalar(_) => TypeDefinitionBuilder::Scalar(ScalarDefinitionBuilder {
+                name,
+                description,
+                directives,
+            }),
+            TypeDefinition::Object(_) | TypeDefinition::Interface(_) => {
+                let parts = parts
+                    .iter()
+                    .map(|(document, ty)| match ty {
+                        TypeDefinition::Object(object) => (
+                            *document,
+                            object.fields(),
+                            object.implements_interfaces().collect::<Vec<_>>(),
+                        ),
+                        TypeDefinition::Interface(interface) => (
+                            *document,
+                            interface.fields(),
+                            interface.implements_interfaces().collect::<Vec<_>>(),
+                        ),
+                        _ => unreachable!(),
+                    })
+                    .collect::<Vec<_>>();
+
+                let fields = self
+                    .unique(
+                        parts.iter().flat_map(|(document, fields, _)| {
+                            fields.clone().map(|field| (*document, field))
+                        }),
+                        |field| field.name(),
+                        |field| ValidationError::DuplicateField {
+                            type_name: type_name.to_string(),
+                            name: field.name().to_string(),
+                            span: field.name_span(),
+                        },
+                    )
+                    .into_iter()
+                    .map(Into::into)
+                    .collect();
+
+                let mut implements_interfaces = Vec::<String>::new();
+                for interface in parts.iter().flat_map(|(_, _, interfaces)| interfaces) {
+                    if !implements_interfaces.iter().any(|name| name == interface) {
+                        implements_interfaces.push(interface.to_string());
+                    }
+                }
+
+                if let TypeDefinition::Object(_) = primary {
+                    TypeDefinitionBuilder::Object(ObjectDefinitionBuilder {
+                        name,
+                        description,
+                        implements_interfaces,
+                        directives,
+                        fields,
+                    })
+                } else {
+                    TypeDefinitionBuilder::Interface(InterfaceDefinitionBuilder {
+                        name,
+                        description,
+                        implements_interfaces,
+                        directives,
+                        fields,
+                    })
+                }
+            }
+            TypeDefinition::Union(_) => {
+                let members = self
+                    .unique(
+                        parts.iter().flat_map(|(document, ty)| {
+                            let TypeDefinition::Union(union) = ty else {
+                                unreachable!()
+                            };
+                            union.members().map(|member| (*document, member))
+                        }),
+                        |member| member.name(),
+                        |member| ValidationError::DuplicateUnionMember {
+                            union_name: type_name.to_string(),
+                            name: member.name().to_string(),
+                            span: member.span(),
+                        },
+                    )
+                    .into_iter()
+                    .map(|member| member.name().to_string())
+                    .collect();
+
+                TypeDefinitionBuilder::Union(UnionDefinitionBuilder {
+                    name,
+                    description,
+                    directives,
+                    members,
+                })
+            }
+            TypeDefinition::Enum(_) => {
+                let values = self
+                    .unique(
+                        parts.iter().flat_map(|(document, ty)| {
+                            let TypeDefinition::Enum(definition) = ty else {
+                                unreachable!()
+                            };
+                            definition.values().map(|value| (*document, value))
+                        }),
+                        |value| value.value(),
+                        |value| ValidationError::DuplicateEnumValue {
+                            enum_name: type_name.to_string(),
+                            name: value.value().to_string(),
+                            span: value.value_span(),
+                        },
+                    )
+                    .into_iter()
+                    .map(Into::into)
+                    .collect();
+
+                TypeDefinitionBuilder::Enum(EnumDefinitionBuilder {
+                    name,
+                    description,
+                    directives,
+                    values,
+                })
+            }
+            TypeDefinition::InputObject(_) => {
+                let fields = self
+                    .unique(
+                        parts.iter().flat_map(|(document, ty)| {
+                            let TypeDefinition::InputObject(input) = ty else {
+                                unreachable!()
+                            };
+                            input.fields().map(|field| (*document, field))
+                        }),
+                        |field| field.name(),
+                        |field| ValidationError::DuplicateField {
+                            type_name: type_name.to_string(),
+                            name: field.name().to_string(),
+                            span: field.name_span(),
+                        },
+                    )
+                    .into_iter()
+                    .map(Into::into)
+                    .collect();
+
+                TypeDefinitionBuilder::InputObject(InputObjectDefinitionBuilder {
+                    name,
+                    description,
+                    directives,
+                    fields,
+                })
+            }
+        };
+
+        self.definitions.push(DefinitionBuilder::Type(definition));
+    }
+}
+
+/// The first description found amongst a definition & its extensions
+fn description<'a>(
+    descriptions: impl IntoIterator<Item = Option<Description<'a>>>,
+) -> Option<DescriptionBuilder> {
+    descriptions.into_iter().flatten().next().map(Into::into)
+}
+
+fn directives<'a>(directives: impl IntoIterator<Item = Directive<'a>>) -> Vec<DirectiveBuilder> {
+    directives.into_iter().map(Into::into).collect()
+}
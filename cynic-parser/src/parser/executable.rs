@@ -1,5 +1,5 @@
 // auto-generated: "lalrpop 0.22.0"
-// sha3: e154bba307888fbe0674a4cf64a96477b8ba3a5b559fe5079cf1516e06af1a14
+// sha3: 3b76a47baff9c1ab0e0558871ff190d2cb6ef7edc701c1c8b11d7d4c9fadf9a3
 use crate::lexer;
 use crate::{
     Span,
@@ -7696,7 +7696,7 @@ fn __action15<'input>(
             alias_span: alias.as_ref().map(|_| Span::new(alias_start, alias_end)),
             alias,
             name,
-            name_span: Span::new(alias_start, alias_end),
+            name_span: Span::new(name_start, name_end),
             arguments,
             directives,
             selection_set_span: if selection_set.is_empty() {
@@ -9,6 +9,7 @@ pub use schema::*;
 
 use crate::{common::MalformedStringError, lexer::LexicalError};
 
+#[derive(Clone)]
 pub enum AdditionalErrors {
     Lexical(LexicalError),
     MalformedString(MalformedStringError),
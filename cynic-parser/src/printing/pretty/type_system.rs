@@ -32,60 +32,14 @@ impl crate::TypeSystemDocument {
 
         let builder = allocator
             .intersperse(
-                definitions.map(|definition| match definition {
-                    Definition::Schema(reader) => NodeDisplay(reader, *options).pretty(&allocator),
-                    Definition::SchemaExtension(reader) => allocator
-                        .text("extend")
-                        .append(allocator.space())
-                        .append(NodeDisplay(reader, *options).pretty(&allocator)),
-                    Definition::Type(TypeDefinition::Scalar(reader)) => {
-                        NodeDisplay(reader, *options).pretty(&allocator)
-                    }
-                    Definition::Type(TypeDefinition::Object(reader)) => {
-                        NodeDisplay(reader, *options).pretty(&allocator)
-                    }
-                    Definition::Type(TypeDefinition::Interface(reader)) => {
-                        NodeDisplay(reader, *options).pretty(&allocator)
-                    }
-                    Definition::Type(TypeDefinition::Union(reader)) => {
-                        NodeDisplay(reader, *options).pretty(&allocator)
-                    }
-                    Definition::Type(TypeDefinition::Enum(reader)) => {
-                        NodeDisplay(reader, *options).pretty(&allocator)
-                    }
-                    Definition::Type(TypeDefinition::InputObject(reader)) => {
-                        NodeDisplay(reader, *options).pretty(&allocator)
-                    }
-                    Definition::TypeExtension(TypeDefinition::Scalar(reader)) => allocator
-                        .text("extend")
-                        .append(allocator.space())
-                        .append(NodeDisplay(reader, *options).pretty(&allocator)),
-                    Definition::TypeExtension(TypeDefinition::Object(reader)) => allocator
-                        .text("extend")
-                        .append(allocator.space())
-                        .append(NodeDisplay(reader, *options).pretty(&allocator)),
-                    Definition::TypeExtension(TypeDefinition::Interface(reader)) => allocator
-                        .text("extend")
-                        .append(allocator.space())
-                        .append(NodeDisplay(reader, *options).pretty(&allocator)),
-                    Definition::TypeExtension(TypeDefinition::Union(reader)) => allocator
-                        .text("extend")
-                        .append(allocator.space())
-                        .append(NodeDisplay(reader, *options).pretty(&allocator)),
-                    Definition::TypeExtension(TypeDefinition::Enum(reader)) => allocator
-                        .text("extend")
-                        .append(allocator.space())
-                        .append(NodeDisplay(reader, *options).pretty(&allocator)),
-                    Definition::TypeExtension(TypeDefinition::InputObject(reader)) => allocator
-                        .text("extend")
-                        .append(allocator.space())
-                        .append(NodeDisplay(reader, *options).pretty(&allocator)),
-                    Definition::Directive(reader) => {
-                        NodeDisplay(reader, *options).pretty(&allocator)
-                    }
+                definitions.map(|definition| {
+                    leading_comments(definition.leading_comments(), *options, &allocator)
+                        .append(pretty_definition(definition, options, &allocator))
+                        .append(trailing_comment(definition.trailing_comment(), &allocator))
                 }),
                 allocator.concat([allocator.hardline(), allocator.hardline()]),
             )
+            .append(self.pretty_end_of_document_comments(options, &allocator))
             .pretty(&allocator);
 
         #[allow(clippy::needless_borrow)] // This doesn't work without the borrow :|
@@ -93,6 +47,129 @@ impl crate::TypeSystemDocument {
             format!("{}\n", (&*builder).pretty(80))
         }
     }
+
+    fn pretty_end_of_document_comments<'a>(
+        &'a self,
+        options: &PrettyOptions,
+        allocator: &'a Allocator<'a>,
+    ) -> pretty::DocBuilder<'a, Allocator<'a>, ()> {
+        let mut comments = self.end_of_document_comments().peekable();
+        if comments.peek().is_none() {
+            return allocator.nil();
+        }
+
+        let mut builder = allocator.nil();
+        if self.definitions().len() != 0 {
+            builder = builder
+                .append(allocator.hardline())
+                .append(allocator.hardline());
+        }
+
+        builder.append(allocator.intersperse(
+            comments.map(|comment| NodeDisplay(comment, *options)),
+            allocator.hardline(),
+        ))
+    }
+}
+
+/// Prints a definition, including `extend` for extensions
+fn pretty_definition<'a>(
+    definition: Definition<'a>,
+    options: &PrettyOptions,
+    allocator: &'a Allocator<'a>,
+) -> pretty::DocBuilder<'a, Allocator<'a>, ()> {
+    match definition {
+        Definition::Schema(reader) => NodeDisplay(reader, *options).pretty(allocator),
+        Definition::SchemaExtension(reader) => allocator
+            .text("extend")
+            .append(allocator.space())
+            .append(NodeDisplay(reader, *options).pretty(allocator)),
+        Definition::Type(TypeDefinition::Scalar(reader)) => {
+            NodeDisplay(reader, *options).pretty(allocator)
+        }
+        Definition::Type(TypeDefinition::Object(reader)) => {
+            NodeDisplay(reader, *options).pretty(allocator)
+        }
+        Definition::Type(TypeDefinition::Interface(reader)) => {
+            NodeDisplay(reader, *options).pretty(allocator)
+        }
+        Definition::Type(TypeDefinition::Union(reader)) => {
+            NodeDisplay(reader, *options).pretty(allocator)
+        }
+        Definition::Type(TypeDefinition::Enum(reader)) => {
+            NodeDisplay(reader, *options).pretty(allocator)
+        }
+        Definition::Type(TypeDefinition::InputObject(reader)) => {
+            NodeDisplay(reader, *options).pretty(allocator)
+        }
+        Definition::TypeExtension(TypeDefinition::Scalar(reader)) => allocator
+            .text("extend")
+            .append(allocator.space())
+            .append(NodeDisplay(reader, *options).pretty(allocator)),
+        Definition::TypeExtension(TypeDefinition::Object(reader)) => allocator
+            .text("extend")
+            .append(allocator.space())
+            .append(NodeDisplay(reader, *options).pretty(allocator)),
+        Definition::TypeExtension(TypeDefinition::Interface(reader)) => allocator
+            .text("extend")
+            .append(allocator.space())
+            .append(NodeDisplay(reader, *options).pretty(allocator)),
+        Definition::TypeExtension(TypeDefinition::Union(reader)) => allocator
+            .text("extend")
+            .append(allocator.space())
+            .append(NodeDisplay(reader, *options).pretty(allocator)),
+        Definition::TypeExtension(TypeDefinition::Enum(reader)) => allocator
+            .text("extend")
+            .append(allocator.space())
+            .append(NodeDisplay(reader, *options).pretty(allocator)),
+        Definition::TypeExtension(TypeDefinition::InputObject(reader)) => allocator
+            .text("extend")
+            .append(allocator.space())
+            .append(NodeDisplay(reader, *options).pretty(allocator)),
+        Definition::Directive(reader) => NodeDisplay(reader, *options).pretty(allocator),
+    }
+}
+
+/// Prints comments on the lines before a node
+fn leading_comments<'a>(
+    comments: impl Iterator<Item = Comment<'a>>,
+    options: PrettyOptions,
+    allocator: &'a Allocator<'a>,
+) -> pretty::DocBuilder<'a, Allocator<'a>, ()> {
+    allocator.concat(comments.map(|comment| {
+        NodeDisplay(comment, options)
+            .pretty(allocator)
+            .append(allocator.hardline())
+    }))
+}
+
+/// Prints a comment at the end of a nodes line.
+///
+/// Nothing can follow this on the same line, so it also forces any enclosing
+/// group to break.
+fn trailing_comment<'a>(
+    comment: Option<Comment<'a>>,
+    allocator: &'a Allocator<'a>,
+) -> pretty::DocBuilder<'a, Allocator<'a>, ()> {
+    match comment {
+        Some(comment) => allocator
+            .text(format!(" #{}", comment.text()))
+            .append(allocator.nil().flat_alt(allocator.fail())),
+        None => allocator.nil(),
+    }
+}
+
+/// Prints comments that come after the last entry in the body of a definition
+fn dangling_comments<'a>(
+    comments: impl Iterator<Item = Comment<'a>>,
+    options: PrettyOptions,
+    allocator: &'a Allocator<'a>,
+) -> pretty::DocBuilder<'a, Allocator<'a>, ()> {
+    allocator.concat(comments.map(|comment| {
+        allocator
+            .hardline()
+            .append(NodeDisplay(comment, options).pretty(allocator))
+    }))
 }
 
 impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<SchemaDefinition<'a>> {
@@ -127,13 +204,22 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<SchemaDefinition<'a>> {
                     allocator
                         .intersperse(
                             roots.map(|operation| {
-                                allocator
-                                    .text(operation.operation_type().as_str())
+                                leading_comments(operation.leading_comments(), self.1, allocator)
+                                    .append(operation.operation_type().as_str())
                                     .append(": ")
                                     .append(operation.named_type())
+                                    .append(trailing_comment(
+                                        operation.trailing_comment(),
+                                        allocator,
+                                    ))
                             }),
                             allocator.hardline(),
                         )
+                        .append(dangling_comments(
+                            self.0.dangling_comments(),
+                            self.1,
+                            allocator,
+                        ))
                         .indent(2),
                 )
                 .append(allocator.hardline())
@@ -210,6 +296,11 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<ObjectDefinition<'a>> {
                     allocator
                         .hardline()
                         .append(FieldSequence::new(fields, self.1))
+                        .append(dangling_comments(
+                            self.0.dangling_comments(),
+                            self.1,
+                            allocator,
+                        ))
                         .nest(2),
                 )
                 .append(allocator.hardline())
@@ -257,13 +348,15 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<FieldDefinition<'a>> {
                 .group();
         }
 
-        description
+        leading_comments(self.0.leading_comments(), self.1, allocator)
+            .append(description)
             .append(allocator.text(self.0.name()))
             .append(arguments_pretty)
             .append(allocator.text(":"))
             .append(allocator.space())
             .append(self.with_node(self.0.ty()))
             .append(directives_pretty)
+            .append(trailing_comment(self.0.trailing_comment(), allocator))
     }
 }
 
@@ -308,6 +401,11 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<InterfaceDefinition<'a>> {
                     allocator
                         .hardline()
                         .append(FieldSequence::new(fields, self.1))
+                        .append(dangling_comments(
+                            self.0.dangling_comments(),
+                            self.1,
+                            allocator,
+                        ))
                         .nest(2),
                 )
                 .append(allocator.hardline())
@@ -405,6 +503,11 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<EnumDefinition<'a>> {
                     allocator
                         .hardline()
                         .append(FieldSequence::new(values, self.1))
+                        .append(dangling_comments(
+                            self.0.dangling_comments(),
+                            self.1,
+                            allocator,
+                        ))
                         .nest(2),
                 )
                 .append(allocator.hardline())
@@ -441,7 +544,9 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<EnumValueDefinition<'a>> {
             builder = builder.append(directives_pretty);
         }
 
-        builder
+        leading_comments(self.0.leading_comments(), self.1, allocator)
+            .append(builder)
+            .append(trailing_comment(self.0.trailing_comment(), allocator))
     }
 }
 
@@ -477,6 +582,11 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<InputObjectDefinition<'a>> {
                     allocator
                         .hardline()
                         .append(FieldSequence::new(fields, self.1))
+                        .append(dangling_comments(
+                            self.0.dangling_comments(),
+                            self.1,
+                            allocator,
+                        ))
                         .nest(2),
                 )
                 .append(allocator.hardline())
@@ -524,7 +634,10 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<InputValueDefinition<'a>> {
             value_builder = value_builder.append(directives_pretty);
         }
 
-        description.append(value_builder)
+        leading_comments(self.0.leading_comments(), self.1, allocator)
+            .append(description)
+            .append(value_builder)
+            .append(trailing_comment(self.0.trailing_comment(), allocator))
     }
 }
 
@@ -614,6 +727,12 @@ impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<Argument<'a>> {
     }
 }
 
+impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<Comment<'a>> {
+    fn pretty(self, allocator: &'a Allocator<'a>) -> pretty::DocBuilder<'a, Allocator<'a>, ()> {
+        allocator.text(format!("#{}", self.0.text()))
+    }
+}
+
 impl<'a> Pretty<'a, Allocator<'a>> for NodeDisplay<Description<'a>> {
     fn pretty(self, allocator: &'a Allocator<'a>) -> pretty::DocBuilder<'a, Allocator<'a>, ()> {
         NodeDisplay(self.0.literal(), self.1).pretty(allocator)
@@ -0,0 +1,182 @@
+//! Error recovery for the parsers.
+//!
+//! The LALRPOP parsers stop at the first error, so to recover we split the
+//! token stream into chunks that (probably) contain a single definition and
+//! parse those one at a time.  Chunks that fail to parse are rolled back out
+//! of the writer and we skip ahead to the next definition.
+
+use crate::{
+    Error,
+    lexer::{Lexer, Token},
+    parser::AdditionalErrors,
+};
+
+type SpannedToken<'input> = Result<(usize, Token<'input>, usize), AdditionalErrors>;
+
+/// A writer that can discard anything written after a checkpoint
+pub(crate) trait Rollback {
+    type Checkpoint: Copy;
+
+    fn checkpoint(&self) -> Self::Checkpoint;
+    fn rollback(&mut self, checkpoint: Self::Checkpoint);
+}
+
+/// Whether a token could be the start of a type system definition
+pub(crate) fn starts_type_system_definition(
+    previous: Option<&Token<'_>>,
+    token: &Token<'_>,
+) -> bool {
+    !matches!(previous, Some(Token::Extend))
+        && matches!(
+            token,
+            Token::Schema
+                | Token::Scalar
+                | Token::Type
+                | Token::Interface
+                | Token::Union
+                | Token::Enum
+                | Token::Input
+                | Token::Extend
+                | Token::Directive
+        )
+}
+
+/// Whether a token could be the start of an executable definition
+pub(crate) fn starts_executable_definition(
+    previous: Option<&Token<'_>>,
+    token: &Token<'_>,
+) -> bool {
+    match token {
+        Token::Query | Token::Mutation | Token::Subscription | Token::Fragment => true,
+        // Anonymous queries can only follow the end of another definition
+        Token::OpenBrace => matches!(previous, Some(Token::CloseBrace)),
+        _ => false,
+    }
+}
+
+/// Parses `input` one definition at a time, returning the errors for any
+/// definitions that couldn't be parsed.
+pub(crate) fn parse_with_recovery<'input, W: Rollback>(
+    input: &'input str,
+    writer: &mut W,
+    starts_definition: fn(Option<&Token<'_>>, &Token<'_>) -> bool,
+    mut parse: impl FnMut(&mut W, Vec<SpannedToken<'input>>) -> Result<(), Error>,
+) -> Vec<Error> {
+    let tokens = Lexer::new(input).collect::<Vec<_>>();
+    let boundaries = definition_boundaries(input, &tokens, starts_definition);
+
+    let boundary_after = |index: usize| {
+        boundaries
+            .iter()
+            .copied()
+            .find(|boundary| *boundary > index)
+            .unwrap_or(tokens.len())
+    };
+
+    let mut errors = Vec::new();
+    let mut start = 0;
+    while start < tokens.len() {
+        let mut end = boundary_after(start);
+        loop {
+            let checkpoint = writer.checkpoint();
+            let Err(error) = parse(writer, tokens[start..end].to_vec()) else {
+                start = end;
+                break;
+            };
+            writer.rollback(checkpoint);
+
+            // Running out of tokens usually means a chunk boundary was
+            // actually inside a definition (e.g. a field named `type`), so
+            // try again with a bigger chunk
+            if matches!(error, Error::UnrecognizedEof { .. }) && end < tokens.len() {
+                end = boundary_after(end);
+                continue;
+            }
+
+            // Resume from the last definition that started before the error,
+            // or the next one after it if that's where we started.
+            let error_position = error.span().map(|span| span.start).unwrap_or_default();
+            let error_index = tokens[start..end]
+                .iter()
+                .position(|token| token_start(token) >= error_position)
+                .map(|index| start + index)
+                .unwrap_or(end);
+            start = boundaries
+                .iter()
+                .copied()
+                .filter(|boundary| *boundary > start && *boundary <= error_index)
+                .last()
+                .unwrap_or_else(|| boundary_after(error_index));
+
+            errors.push(error);
+            break;
+        }
+    }
+
+    errors
+}
+
+/// Finds the indices of tokens that look like the start of a definition.
+///
+/// This is a heuristic: a definition keyword counts if it's not nested inside
+/// any brackets or if it's the first thing on its line.  Any descriptions
+/// directly before a definition are included in that definition.
+fn definition_boundaries(
+    input: &str,
+    tokens: &[SpannedToken<'_>],
+    starts_definition: fn(Option<&Token<'_>>, &Token<'_>) -> bool,
+) -> Vec<usize> {
+    let mut boundaries = Vec::new();
+    let mut depth = 0usize;
+    let mut previous = None;
+    let mut previous_is_description = false;
+
+    for (index, spanned) in tokens.iter().enumerate() {
+        let Ok((start, token, _)) = spanned else {
+            continue;
+        };
+
+        if index != 0
+            && starts_definition(previous, token)
+            && (depth == 0 || starts_line(input, *start))
+        {
+            boundaries.push(if previous_is_description {
+                index - 1
+            } else {
+                index
+            });
+        }
+
+        previous_is_description = matches!(
+            token,
+            Token::StringLiteral(_) | Token::BlockStringLiteral(_)
+        ) && (depth == 0 || starts_line(input, *start));
+
+        match token {
+            Token::OpenBrace | Token::OpenBracket | Token::OpenParen => depth += 1,
+            Token::CloseBrace | Token::CloseBracket | Token::CloseParen => {
+                depth = depth.saturating_sub(1)
+            }
+            _ => {}
+        }
+        previous = Some(token);
+    }
+
+    boundaries
+}
+
+fn token_start(token: &SpannedToken<'_>) -> usize {
+    match token {
+        Ok((start, _, _)) => *start,
+        Err(AdditionalErrors::Lexical(error)) => error.span().start,
+        Err(_) => usize::MAX,
+    }
+}
+
+fn starts_line(input: &str, position: usize) -> bool {
+    input[..position]
+        .chars()
+        .rev()
+        .take_while(|char| *char != '\n')
+        .all(char::is_whitespace)
+}
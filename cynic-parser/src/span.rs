@@ -15,6 +15,14 @@ impl Span {
     pub fn overlaps(&self, other: Span) -> bool {
         self.start < other.end && other.start < self.end
     }
+
+    /// Moves this [Span] by `delta` if it starts at or after `position`
+    pub(crate) fn shift_from(&mut self, position: usize, delta: isize) {
+        if self.start >= position {
+            self.start = self.start.saturating_add_signed(delta);
+            self.end = self.end.saturating_add_signed(delta);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,1268 @@
+//! Builders for constructing & editing `TypeSystemDocument`s in code.
+//!
+//! The builders are plain owned structs with public fields, so they can be
+//! created from scratch or converted from an existing document (every reader
+//! has a `From` impl for its builder), edited & then built into a new
+//! `TypeSystemDocument`.
+//!
+//! ```rust
+//! use cynic_parser::type_system::builder::*;
+//!
+//! let document = TypeSystemDocumentBuilder::new()
+//!     .definition(
+//!         ObjectDefinitionBuilder::new("Query")
+//!             .description("The root query")
+//!             .field(
+//!                 FieldDefinitionBuilder::new("user", TypeBuilder::named("User"))
+//!                     .argument(InputValueDefinitionBuilder::new(
+//!                         "id",
+//!                         TypeBuilder::named("ID").non_null(),
+//!                     )),
+//!             ),
+//!     )
+//!     .definition(
+//!         ObjectDefinitionBuilder::new("User")
+//!             .directive(DirectiveBuilder::new("key").argument("fields", "id"))
+//!             .field(FieldDefinitionBuilder::new("id", TypeBuilder::named("ID").non_null())),
+//!     )
+//!     .build();
+//!
+//! assert_eq!(document.definitions().count(), 2);
+//! ```
+//!
+//! Editing an existing document:
+//!
+//! ```rust
+//! use cynic_parser::type_system::builder::*;
+//!
+//! let document = cynic_parser::parse_type_system_document(
+//!     "type User { id: ID! legacyId: Int }",
+//! )
+//! .unwrap();
+//!
+//! let mut builder = TypeSystemDocumentBuilder::from(&document);
+//! for definition in &mut builder.definitions {
+//!     if let DefinitionBuilder::Type(TypeDefinitionBuilder::Object(object)) = definition {
+//!         object.fields.retain(|field| field.name != "legacyId");
+//!     }
+//! }
+//!
+//! assert_eq!(
+//!     builder.build().to_sdl_pretty(),
+//!     "type User {\n  id: ID!\n}\n"
+//! );
+//! ```
+
+use crate::{
+    Span, TypeSystemDocument,
+    common::{IdRange, OperationType, StringLiteralKind, WrappingType},
+    values::{
+        self, ConstValue,
+        storage::{ValueKind, ValueRecord},
+    },
+};
+
+use super::{
+    Definition, Description, Directive, DirectiveDefinition, DirectiveLocation, EnumDefinition,
+    EnumValueDefinition, FieldDefinition, InputObjectDefinition, InputValueDefinition,
+    InterfaceDefinition, ObjectDefinition, ScalarDefinition, SchemaDefinition, Type,
+    TypeDefinition, UnionDefinition, ids::*, storage::*, writer::TypeSystemAstWriter,
+};
+
+/// Builds a [`TypeSystemDocument`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TypeSystemDocumentBuilder {
+    pub definitions: Vec<DefinitionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DefinitionBuilder {
+    Schema(SchemaDefinitionBuilder),
+    SchemaExtension(SchemaDefinitionBuilder),
+    Type(TypeDefinitionBuilder),
+    TypeExtension(TypeDefinitionBuilder),
+    Directive(DirectiveDefinitionBuilder),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum TypeDefinitionBuilder {
+    Scalar(ScalarDefinitionBuilder),
+    Object(ObjectDefinitionBuilder),
+    Interface(InterfaceDefinitionBuilder),
+    Union(UnionDefinitionBuilder),
+    Enum(EnumDefinitionBuilder),
+    InputObject(InputObjectDefinitionBuilder),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SchemaDefinitionBuilder {
+    pub description: Option<DescriptionBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub root_operations: Vec<(OperationType, String)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScalarDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub implements_interfaces: Vec<String>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub fields: Vec<FieldDefinitionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InterfaceDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub implements_interfaces: Vec<String>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub fields: Vec<FieldDefinitionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnionDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub members: Vec<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub values: Vec<EnumValueDefinitionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputObjectDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+    pub fields: Vec<InputValueDefinitionBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirectiveDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub arguments: Vec<InputValueDefinitionBuilder>,
+    pub is_repeatable: bool,
+    pub locations: Vec<DirectiveLocation>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub ty: TypeBuilder,
+    pub arguments: Vec<InputValueDefinitionBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+}
+
+/// Builds an argument of a field or directive, or a field of an input object
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputValueDefinitionBuilder {
+    pub name: String,
+    pub description: Option<DescriptionBuilder>,
+    pub ty: TypeBuilder,
+    pub default_value: Option<ConstValueBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EnumValueDefinitionBuilder {
+    pub value: String,
+    pub description: Option<DescriptionBuilder>,
+    pub directives: Vec<DirectiveBuilder>,
+}
+
+/// Builds a description
+///
+/// Block descriptions hold the raw text between their `"""`s, so that
+/// descriptions taken from an existing document print exactly as they were
+/// written.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DescriptionBuilder {
+    String(String),
+    Block(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DirectiveBuilder {
+    pub name: String,
+    pub arguments: Vec<(String, ConstValueBuilder)>,
+}
+
+/// Builds a reference to a type, e.g. `[String!]!`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TypeBuilder {
+    pub name: String,
+    /// The wrappers around the named type, from the innermost outwards
+    pub wrappers: Vec<WrappingType>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConstValueBuilder {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Null,
+    Enum(String),
+    List(Vec<ConstValueBuilder>),
+    Object(Vec<(String, ConstValueBuilder)>),
+}
+
+impl TypeSystemDocumentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn definition(mut self, definition: impl Into<DefinitionBuilder>) -> Self {
+        self.definitions.push(definition.into());
+        self
+    }
+
+    /// Builds the document
+    ///
+    /// The spans in the document will all be empty, as it has no source text.
+    pub fn build(&self) -> TypeSystemDocument {
+        let mut writer = TypeSystemAstWriter::new();
+        for definition in &self.definitions {
+            definition.write(&mut writer);
+        }
+        writer.finish()
+    }
+}
+
+impl SchemaDefinitionBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn root_operation(
+        mut self,
+        operation_type: OperationType,
+        named_type: impl Into<String>,
+    ) -> Self {
+        self.root_operations
+            .push((operation_type, named_type.into()));
+        self
+    }
+}
+
+impl ScalarDefinitionBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        ScalarDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            directives: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+}
+
+impl ObjectDefinitionBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        ObjectDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            implements_interfaces: Vec::new(),
+            directives: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn implements(mut self, interface: impl Into<String>) -> Self {
+        self.implements_interfaces.push(interface.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn field(mut self, field: FieldDefinitionBuilder) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Returns the field with the given name, if there is one
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut FieldDefinitionBuilder> {
+        self.fields.iter_mut().find(|field| field.name == name)
+    }
+}
+
+impl InterfaceDefinitionBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        InterfaceDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            implements_interfaces: Vec::new(),
+            directives: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn implements(mut self, interface: impl Into<String>) -> Self {
+        self.implements_interfaces.push(interface.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn field(mut self, field: FieldDefinitionBuilder) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Returns the field with the given name, if there is one
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut FieldDefinitionBuilder> {
+        self.fields.iter_mut().find(|field| field.name == name)
+    }
+}
+
+impl UnionDefinitionBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        UnionDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            directives: Vec::new(),
+            members: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn member(mut self, member: impl Into<String>) -> Self {
+        self.members.push(member.into());
+        self
+    }
+}
+
+impl EnumDefinitionBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        EnumDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            directives: Vec::new(),
+            values: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn value(mut self, value: impl Into<EnumValueDefinitionBuilder>) -> Self {
+        self.values.push(value.into());
+        self
+    }
+}
+
+impl InputObjectDefinitionBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        InputObjectDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            directives: Vec::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+
+    pub fn field(mut self, field: InputValueDefinitionBuilder) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Returns the field with the given name, if there is one
+    pub fn field_mut(&mut self, name: &str) -> Option<&mut InputValueDefinitionBuilder> {
+        self.fields.iter_mut().find(|field| field.name == name)
+    }
+}
+
+impl DirectiveDefinitionBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        DirectiveDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            arguments: Vec::new(),
+            is_repeatable: false,
+            locations: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn argument(mut self, argument: InputValueDefinitionBuilder) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+
+    pub fn repeatable(mut self) -> Self {
+        self.is_repeatable = true;
+        self
+    }
+
+    pub fn location(mut self, location: DirectiveLocation) -> Self {
+        self.locations.push(location);
+        self
+    }
+}
+
+impl FieldDefinitionBuilder {
+    pub fn new(name: impl Into<String>, ty: TypeBuilder) -> Self {
+        FieldDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            ty,
+            arguments: Vec::new(),
+            directives: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn argument(mut self, argument: InputValueDefinitionBuilder) -> Self {
+        self.arguments.push(argument);
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+}
+
+impl InputValueDefinitionBuilder {
+    pub fn new(name: impl Into<String>, ty: TypeBuilder) -> Self {
+        InputValueDefinitionBuilder {
+            name: name.into(),
+            description: None,
+            ty,
+            default_value: None,
+            directives: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn default_value(mut self, value: impl Into<ConstValueBuilder>) -> Self {
+        self.default_value = Some(value.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+}
+
+impl EnumValueDefinitionBuilder {
+    pub fn new(value: impl Into<String>) -> Self {
+        EnumValueDefinitionBuilder {
+            value: value.into(),
+            description: None,
+            directives: Vec::new(),
+        }
+    }
+
+    pub fn description(mut self, description: impl Into<DescriptionBuilder>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn directive(mut self, directive: DirectiveBuilder) -> Self {
+        self.directives.push(directive);
+        self
+    }
+}
+
+impl DirectiveBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        DirectiveBuilder {
+            name: name.into(),
+            arguments: Vec::new(),
+        }
+    }
+
+    pub fn argument(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<ConstValueBuilder>,
+    ) -> Self {
+        self.arguments.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl TypeBuilder {
+    /// A nullable reference to the named type
+    pub fn named(name: impl Into<String>) -> Self {
+        TypeBuilder {
+            name: name.into(),
+            wrappers: Vec::new(),
+        }
+    }
+
+    /// Makes this type non-nullable
+    pub fn non_null(mut self) -> Self {
+        self.wrappers.push(WrappingType::NonNull);
+        self
+    }
+
+    /// Wraps this type in a list
+    pub fn list(mut self) -> Self {
+        self.wrappers.push(WrappingType::List);
+        self
+    }
+}
+
+impl From<&str> for EnumValueDefinitionBuilder {
+    fn from(value: &str) -> Self {
+        EnumValueDefinitionBuilder::new(value)
+    }
+}
+
+impl From<String> for EnumValueDefinitionBuilder {
+    fn from(value: String) -> Self {
+        EnumValueDefinitionBuilder::new(value)
+    }
+}
+
+impl From<i32> for ConstValueBuilder {
+    fn from(value: i32) -> Self {
+        ConstValueBuilder::Int(value.into())
+    }
+}
+
+impl From<i64> for ConstValueBuilder {
+    fn from(value: i64) -> Self {
+        ConstValueBuilder::Int(value)
+    }
+}
+
+impl From<f64> for ConstValueBuilder {
+    fn from(value: f64) -> Self {
+        ConstValueBuilder::Float(value)
+    }
+}
+
+impl From<bool> for ConstValueBuilder {
+    fn from(value: bool) -> Self {
+        ConstValueBuilder::Boolean(value)
+    }
+}
+
+impl From<&str> for ConstValueBuilder {
+    fn from(value: &str) -> Self {
+        ConstValueBuilder::String(value.into())
+    }
+}
+
+impl From<String> for ConstValueBuilder {
+    fn from(value: String) -> Self {
+        ConstValueBuilder::String(value)
+    }
+}
+
+impl<T> From<Vec<T>> for ConstValueBuilder
+where
+    T: Into<ConstValueBuilder>,
+{
+    fn from(value: Vec<T>) -> Self {
+        ConstValueBuilder::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T> From<Option<T>> for ConstValueBuilder
+where
+    T: Into<ConstValueBuilder>,
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => ConstValueBuilder::Null,
+        }
+    }
+}
+
+macro_rules! impl_into_definition {
+    ($builder:ident, $variant:ident) => {
+        impl From<$builder> for TypeDefinitionBuilder {
+            fn from(value: $builder) -> Self {
+                TypeDefinitionBuilder::$variant(value)
+            }
+        }
+
+        impl From<$builder> for DefinitionBuilder {
+            fn from(value: $builder) -> Self {
+                DefinitionBuilder::Type(TypeDefinitionBuilder::$variant(value))
+            }
+        }
+    };
+}
+
+impl_into_definition!(ScalarDefinitionBuilder, Scalar);
+impl_into_definition!(ObjectDefinitionBuilder, Object);
+impl_into_definition!(InterfaceDefinitionBuilder, Interface);
+impl_into_definition!(UnionDefinitionBuilder, Union);
+impl_into_definition!(EnumDefinitionBuilder, Enum);
+impl_into_definition!(InputObjectDefinitionBuilder, InputObject);
+
+impl From<TypeDefinitionBuilder> for DefinitionBuilder {
+    fn from(value: TypeDefinitionBuilder) -> Self {
+        DefinitionBuilder::Type(value)
+    }
+}
+
+impl From<SchemaDefinitionBuilder> for DefinitionBuilder {
+    fn from(value: SchemaDefinitionBuilder) -> Self {
+        DefinitionBuilder::Schema(value)
+    }
+}
+
+impl From<DirectiveDefinitionBuilder> for DefinitionBuilder {
+    fn from(value: DirectiveDefinitionBuilder) -> Self {
+        DefinitionBuilder::Directive(value)
+    }
+}
+
+// Conversions from readers, for editing existing documents
+
+fn description(description: Option<Description<'_>>) -> Option<DescriptionBuilder> {
+    description.map(Into::into)
+}
+
+fn directives<'a>(directives: impl Iterator<Item = Directive<'a>>) -> Vec<DirectiveBuilder> {
+    directives.map(Into::into).collect()
+}
+
+impl From<&TypeSystemDocument> for TypeSystemDocumentBuilder {
+    fn from(document: &TypeSystemDocument) -> Self {
+        TypeSystemDocumentBuilder {
+            definitions: document.definitions().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Definition<'_>> for DefinitionBuilder {
+    fn from(definition: Definition<'_>) -> Self {
+        match definition {
+            Definition::Schema(schema) => DefinitionBuilder::Schema(schema.into()),
+            Definition::SchemaExtension(schema) => {
+                DefinitionBuilder::SchemaExtension(schema.into())
+            }
+            Definition::Type(ty) => DefinitionBuilder::Type(ty.into()),
+            Definition::TypeExtension(ty) => DefinitionBuilder::TypeExtension(ty.into()),
+            Definition::Directive(directive) => DefinitionBuilder::Directive(directive.into()),
+        }
+    }
+}
+
+impl From<TypeDefinition<'_>> for TypeDefinitionBuilder {
+    fn from(definition: TypeDefinition<'_>) -> Self {
+        match definition {
+            TypeDefinition::Scalar(inner) => TypeDefinitionBuilder::Scalar(inner.into()),
+            TypeDefinition::Object(inner) => TypeDefinitionBuilder::Object(inner.into()),
+            TypeDefinition::Interface(inner) => TypeDefinitionBuilder::Interface(inner.into()),
+            TypeDefinition::Union(inner) => TypeDefinitionBuilder::Union(inner.into()),
+            TypeDefinition::Enum(inner) => TypeDefinitionBuilder::Enum(inner.into()),
+            TypeDefinition::InputObject(inner) => TypeDefinitionBuilder::InputObject(inner.into()),
+        }
+    }
+}
+
+impl From<SchemaDefinition<'_>> for SchemaDefinitionBuilder {
+    fn from(schema: SchemaDefinition<'_>) -> Self {
+        SchemaDefinitionBuilder {
+            description: description(schema.description()),
+            directives: directives(schema.directives()),
+            root_operations: schema
+                .root_operations()
+                .map(|operation| (operation.operation_type(), operation.named_type().into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<ScalarDefinition<'_>> for ScalarDefinitionBuilder {
+    fn from(scalar: ScalarDefinition<'_>) -> Self {
+        ScalarDefinitionBuilder {
+            name: scalar.name().into(),
+            description: description(scalar.description()),
+            directives: directives(scalar.directives()),
+        }
+    }
+}
+
+impl From<ObjectDefinition<'_>> for ObjectDefinitionBuilder {
+    fn from(object: ObjectDefinition<'_>) -> Self {
+        ObjectDefinitionBuilder {
+            name: object.name().into(),
+            description: description(object.description()),
+            implements_interfaces: object.implements_interfaces().map(Into::into).collect(),
+            directives: directives(object.directives()),
+            fields: object.fields().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<InterfaceDefinition<'_>> for InterfaceDefinitionBuilder {
+    fn from(interface: InterfaceDefinition<'_>) -> Self {
+        InterfaceDefinitionBuilder {
+            name: interface.name().into(),
+            description: description(interface.description()),
+            implements_interfaces: interface.implements_interfaces().map(Into::into).collect(),
+            directives: directives(interface.directives()),
+            fields: interface.fields().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<UnionDefinition<'_>> for UnionDefinitionBuilder {
+    fn from(union: UnionDefinition<'_>) -> Self {
+        UnionDefinitionBuilder {
+            name: union.name().into(),
+            description: description(union.description()),
+            directives: directives(union.directives()),
+            members: union.members().map(|member| member.name().into()).collect(),
+        }
+    }
+}
+
+impl From<EnumDefinition<'_>> for EnumDefinitionBuilder {
+    fn from(definition: EnumDefinition<'_>) -> Self {
+        EnumDefinitionBuilder {
+            name: definition.name().into(),
+            description: description(definition.description()),
+            directives: directives(definition.directives()),
+            values: definition.values().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<InputObjectDefinition<'_>> for InputObjectDefinitionBuilder {
+    fn from(input: InputObjectDefinition<'_>) -> Self {
+        InputObjectDefinitionBuilder {
+            name: input.name().into(),
+            description: description(input.description()),
+            directives: directives(input.directives()),
+            fields: input.fields().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<DirectiveDefinition<'_>> for DirectiveDefinitionBuilder {
+    fn from(directive: DirectiveDefinition<'_>) -> Self {
+        DirectiveDefinitionBuilder {
+            name: directive.name().into(),
+            description: description(directive.description()),
+            arguments: directive.arguments().map(Into::into).collect(),
+            is_repeatable: directive.is_repeatable(),
+            locations: directive.locations().collect(),
+        }
+    }
+}
+
+impl From<FieldDefinition<'_>> for FieldDefinitionBuilder {
+    fn from(field: FieldDefinition<'_>) -> Self {
+        FieldDefinitionBuilder {
+            name: field.name().into(),
+            description: description(field.description()),
+            ty: field.ty().into(),
+            arguments: field.arguments().map(Into::into).collect(),
+            directives: directives(field.directives()),
+        }
+    }
+}
+
+impl From<InputValueDefinition<'_>> for InputValueDefinitionBuilder {
+    fn from(input_value: InputValueDefinition<'_>) -> Self {
+        InputValueDefinitionBuilder {
+            name: input_value.name().into(),
+            description: description(input_value.description()),
+            ty: input_value.ty().into(),
+            default_value: input_value.default_value().map(Into::into),
+            directives: directives(input_value.directives()),
+        }
+    }
+}
+
+impl From<EnumValueDefinition<'_>> for EnumValueDefinitionBuilder {
+    fn from(value: EnumValueDefinition<'_>) -> Self {
+        EnumValueDefinitionBuilder {
+            value: value.value().into(),
+            description: description(value.description()),
+            directives: directives(value.directives()),
+        }
+    }
+}
+
+impl From<Description<'_>> for DescriptionBuilder {
+    fn from(description: Description<'_>) -> Self {
+        let literal = description.literal();
+        match literal.kind() {
+            StringLiteralKind::String => DescriptionBuilder::String(literal.to_cow().into_owned()),
+            StringLiteralKind::Block => {
+                DescriptionBuilder::Block(literal.raw_untrimmed_str().to_string())
+            }
+        }
+    }
+}
+
+impl From<&str> for DescriptionBuilder {
+    fn from(description: &str) -> Self {
+        DescriptionBuilder::String(description.into())
+    }
+}
+
+impl From<String> for DescriptionBuilder {
+    fn from(description: String) -> Self {
+        DescriptionBuilder::String(description)
+    }
+}
+
+impl From<Directive<'_>> for DirectiveBuilder {
+    fn from(directive: Directive<'_>) -> Self {
+        DirectiveBuilder {
+            name: directive.name().into(),
+            arguments: directive
+                .arguments()
+                .map(|argument| (argument.name().into(), argument.value().into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<Type<'_>> for TypeBuilder {
+    fn from(ty: Type<'_>) -> Self {
+        TypeBuilder {
+            name: ty.name().into(),
+            wrappers: ty.wrappers().rev().collect(),
+        }
+    }
+}
+
+impl From<ConstValue<'_>> for ConstValueBuilder {
+    fn from(value: ConstValue<'_>) -> Self {
+        match value {
+            ConstValue::Int(inner) => ConstValueBuilder::Int(inner.as_i64()),
+            ConstValue::Float(inner) => ConstValueBuilder::Float(inner.value()),
+            ConstValue::String(inner) => ConstValueBuilder::String(inner.value().into()),
+            ConstValue::Boolean(inner) => ConstValueBuilder::Boolean(inner.value()),
+            ConstValue::Null(_) => ConstValueBuilder::Null,
+            ConstValue::Enum(inner) => ConstValueBuilder::Enum(inner.name().into()),
+            ConstValue::List(list) => {
+                ConstValueBuilder::List(list.items().map(Into::into).collect())
+            }
+            ConstValue::Object(object) => ConstValueBuilder::Object(
+                object
+                    .fields()
+                    .map(|field| (field.name().into(), field.value().into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+// Writing builders into a TypeSystemAstWriter.
+//
+// As with the parser, children need to be written before their parents so
+// that the writers cursors produce the right ranges.
+
+impl DefinitionBuilder {
+    fn write(&self, writer: &mut TypeSystemAstWriter) -> DefinitionId {
+        match self {
+            DefinitionBuilder::Schema(schema) => {
+                let record = schema.record(writer);
+                writer.schema_definition(record)
+            }
+            DefinitionBuilder::SchemaExtension(schema) => {
+                let record = schema.record(writer);
+                writer.schema_extension(record)
+            }
+            DefinitionBuilder::Type(ty) => ty.write(writer, false),
+            DefinitionBuilder::TypeExtension(ty) => ty.write(writer, true),
+            DefinitionBuilder::Directive(directive) => {
+                let name = writer.ident(&directive.name);
+                let description = write_description(writer, &directive.description);
+                let arguments = write_input_values(writer, &directive.arguments);
+                writer.directive_definition(DirectiveDefinitionRecord {
+                    name,
+                    name_span: Span::default(),
+                    description,
+                    arguments,
+                    is_repeatable: directive.is_repeatable,
+                    locations: directive.locations.clone(),
+                    span: Span::default(),
+                })
+            }
+        }
+    }
+}
+
+impl SchemaDefinitionBuilder {
+    fn record(&self, writer: &mut TypeSystemAstWriter) -> SchemaDefinitionRecord {
+        let description = write_description(writer, &self.description);
+        let directives = write_directives(writer, &self.directives);
+        let root_operations = self
+            .root_operations
+            .iter()
+            .map(
+                |(operation_type, named_type)| RootOperationTypeDefinitionRecord {
+                    operation_type: *operation_type,
+                    operation_type_span: Span::default(),
+                    named_type: writer.ident(named_type),
+                    named_type_span: Span::default(),
+                    span: Span::default(),
+                },
+            )
+            .collect();
+        let root_operations = writer.root_operation_definitions(root_operations);
+
+        SchemaDefinitionRecord {
+            description,
+            directives,
+            root_operations,
+            span: Span::default(),
+        }
+    }
+}
+
+impl TypeDefinitionBuilder {
+    fn write(&self, writer: &mut TypeSystemAstWriter, extension: bool) -> DefinitionId {
+        match self {
+            TypeDefinitionBuilder::Scalar(scalar) => {
+                let record = ScalarDefinitionRecord {
+                    name: writer.ident(&scalar.name),
+                    name_span: Span::default(),
+                    description: write_description(writer, &scalar.description),
+                    directives: write_directives(writer, &scalar.directives),
+                    span: Span::default(),
+                };
+                if extension {
+                    writer.scalar_extension(record)
+                } else {
+                    writer.scalar_definition(record)
+                }
+            }
+            TypeDefinitionBuilder::Object(object) => {
+                let record = ObjectDefinitionRecord {
+                    name: writer.ident(&object.name),
+                    name_span: Span::default(),
+                    description: write_description(writer, &object.description),
+                    fields: write_fields(writer, &object.fields),
+                    directives: write_directives(writer, &object.directives),
+                    implements_interfaces: object
+                        .implements_interfaces
+                        .iter()
+                        .map(|interface| writer.ident(interface))
+                        .collect(),
+                    span: Span::default(),
+                };
+                if extension {
+                    writer.object_extension(record)
+                } else {
+                    writer.object_definition(record)
+                }
+            }
+            TypeDefinitionBuilder::Interface(interface) => {
+                let record = InterfaceDefinitionRecord {
+                    name: writer.ident(&interface.name),
+                    name_span: Span::default(),
+                    description: write_description(writer, &interface.description),
+                    fields: write_fields(writer, &interface.fields),
+                    directives: write_directives(writer, &interface.directives),
+                    implements_interfaces: interface
+                        .implements_interfaces
+                        .iter()
+                        .map(|interface| writer.ident(interface))
+                        .collect(),
+                    span: Span::default(),
+                };
+                if extension {
+                    writer.interface_extension(record)
+                } else {
+                    writer.interface_definition(record)
+                }
+            }
+            TypeDefinitionBuilder::Union(union) => {
+                for member in &union.members {
+                    let name = writer.ident(member);
+                    writer.union_member(UnionMemberRecord {
+                        name,
+                        span: Span::default(),
+                    });
+                }
+                let record = UnionDefinitionRecord {
+                    name: writer.ident(&union.name),
+                    name_span: Span::default(),
+                    description: write_description(writer, &union.description),
+                    members: writer.union_member_range(Some(union.members.len())),
+                    directives: write_directives(writer, &union.directives),
+                    span: Span::default(),
+                };
+                if extension {
+                    writer.union_extension(record)
+                } else {
+                    writer.union_definition(record)
+                }
+            }
+            TypeDefinitionBuilder::Enum(definition) => {
+                for value in &definition.values {
+                    let record = EnumValueDefinitionRecord {
+                        value: writer.ident(&value.value),
+                        value_span: Span::default(),
+                        description: write_description(writer, &value.description),
+                        directives: write_directives(writer, &value.directives),
+                        span: Span::default(),
+                    };
+                    writer.enum_value_definition(record);
+                }
+                let record = EnumDefinitionRecord {
+                    name: writer.ident(&definition.name),
+                    name_span: Span::default(),
+                    description: write_description(writer, &definition.description),
+                    values: writer.enum_value_definition_range(Some(definition.values.len())),
+                    directives: write_directives(writer, &definition.directives),
+                    span: Span::default(),
+                };
+                if extension {
+                    writer.enum_extension(record)
+                } else {
+                    writer.enum_definition(record)
+                }
+            }
+            TypeDefinitionBuilder::InputObject(input) => {
+                let record = InputObjectDefinitionRecord {
+                    name: writer.ident(&input.name),
+                    name_span: Span::default(),
+                    description: write_description(writer, &input.description),
+                    fields: write_input_values(writer, &input.fields),
+                    directives: write_directives(writer, &input.directives),
+                    span: Span::default(),
+                };
+                if extension {
+                    writer.input_object_extension(record)
+                } else {
+                    writer.input_object_definition(record)
+                }
+            }
+        }
+    }
+}
+
+fn write_description(
+    writer: &mut TypeSystemAstWriter,
+    description: &Option<DescriptionBuilder>,
+) -> Option<DescriptionId> {
+    let literal = match description.as_ref()? {
+        DescriptionBuilder::String(description) => {
+            StringLiteralId::String(writer.intern_string(description))
+        }
+        DescriptionBuilder::Block(description) => {
+            StringLiteralId::Block(writer.block_string(description))
+        }
+    };
+    Some(writer.description(DescriptionRecord {
+        literal,
+        span: Span::default(),
+    }))
+}
+
+fn write_fields(
+    writer: &mut TypeSystemAstWriter,
+    fields: &[FieldDefinitionBuilder],
+) -> IdRange<FieldDefinitionId> {
+    for field in fields {
+        let record = FieldDefinitionRecord {
+            name: writer.ident(&field.name),
+            name_span: Span::default(),
+            ty: write_type(writer, &field.ty),
+            arguments: write_input_values(writer, &field.arguments),
+            description: write_description(writer, &field.description),
+            directives: write_directives(writer, &field.directives),
+            span: Span::default(),
+        };
+        writer.field_definition(record);
+    }
+    writer.field_definition_range(Some(fields.len()))
+}
+
+fn write_input_values(
+    writer: &mut TypeSystemAstWriter,
+    input_values: &[InputValueDefinitionBuilder],
+) -> IdRange<InputValueDefinitionId> {
+    for input_value in input_values {
+        let record = InputValueDefinitionRecord {
+            name: writer.ident(&input_value.name),
+            name_span: Span::default(),
+            ty: write_type(writer, &input_value.ty),
+            description: write_description(writer, &input_value.description),
+            default_value: input_value
+                .default_value
+                .as_ref()
+                .map(|value| write_const_value(writer, value)),
+            default_value_span: Span::default(),
+            directives: write_directives(writer, &input_value.directives),
+            span: Span::default(),
+        };
+        writer.input_value_definition(record);
+    }
+    writer.input_value_definition_range(Some(input_values.len()))
+}
+
+fn write_type(writer: &mut TypeSystemAstWriter, ty: &TypeBuilder) -> TypeId {
+    let name = writer.ident(&ty.name);
+    writer.type_reference(TypeRecord {
+        name,
+        name_start: 0,
+        wrappers: ty.wrappers.iter().copied().collect(),
+        span: Span::default(),
+    })
+}
+
+fn write_directives(
+    writer: &mut TypeSystemAstWriter,
+    directives: &[DirectiveBuilder],
+) -> IdRange<DirectiveId> {
+    for directive in directives {
+        for (name, value) in &directive.arguments {
+            let record = ArgumentRecord {
+                name: writer.ident(name),
+                name_span: Span::default(),
+                value: write_const_value(writer, value),
+                span: Span::default(),
+            };
+            writer.argument(record);
+        }
+        let arguments = writer.argument_range(Some(directive.arguments.len()));
+        let name = writer.ident(&directive.name);
+        writer.directive(DirectiveRecord {
+            name,
+            name_span: Span::default(),
+            arguments,
+            arguments_span: Span::default(),
+        });
+    }
+    writer.directive_range(Some(directives.len()))
+}
+
+fn write_const_value(writer: &mut TypeSystemAstWriter, value: &ConstValueBuilder) -> ConstValueId {
+    let record = const_value_record(writer, value);
+    writer.values.const_value(record)
+}
+
+fn const_value_record(writer: &mut TypeSystemAstWriter, value: &ConstValueBuilder) -> ValueRecord {
+    let kind = match value {
+        ConstValueBuilder::Int(value) => ValueKind::Int(*value),
+        ConstValueBuilder::Float(value) => ValueKind::Float(*value),
+        ConstValueBuilder::String(value) => ValueKind::String(value_string(writer, value)),
+        ConstValueBuilder::Boolean(value) => ValueKind::Boolean(*value),
+        ConstValueBuilder::Null => ValueKind::Null,
+        ConstValueBuilder::Enum(value) => ValueKind::Enum(value_string(writer, value)),
+        ConstValueBuilder::List(items) => {
+            let items = items
+                .iter()
+                .map(|item| const_value_record(writer, item))
+                .collect();
+            ValueKind::List(writer.values.list(items))
+        }
+        ConstValueBuilder::Object(fields) => {
+            let fields = fields
+                .iter()
+                .map(|(name, value)| {
+                    let name = value_string(writer, name);
+                    let value = write_const_value(writer, value);
+                    (name, Span::default(), value)
+                })
+                .collect();
+            ValueKind::Object(writer.values.const_fields(fields))
+        }
+    };
+
+    ValueRecord {
+        span: Span::default(),
+        kind,
+    }
+}
+
+fn value_string(writer: &mut TypeSystemAstWriter, string: &str) -> values::ids::StringId {
+    values::ids::StringId::from_type_system_id(writer.intern_string(string))
+}
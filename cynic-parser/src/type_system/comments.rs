@@ -0,0 +1,405 @@
+//! Support for preserving `#` comments in a [`TypeSystemDocument`]
+//!
+//! The lexer throws comments away, so when they're wanted we find them in the
+//! gaps between tokens after parsing and attach each of them to a nearby node:
+//!
+//! - A comment that follows some code on the same line is a trailing comment
+//!   of the node that ended closest before it on that line.
+//! - Any other comment leads the next node in the document.
+//! - A comment with no next node inside the definition it appears in (e.g.
+//!   one just before a closing `}`) dangles inside that definition.
+//! - A comment with no next node at all goes at the end of the document.
+
+use std::collections::HashMap;
+
+use crate::{
+    Span,
+    lexer::Lexer,
+    type_system::{
+        Definition, DirectiveDefinition, EnumDefinition, EnumValueDefinition, FieldDefinition,
+        InputObjectDefinition, InputValueDefinition, InterfaceDefinition, ObjectDefinition,
+        RootOperationTypeDefinition, ScalarDefinition, SchemaDefinition, TypeDefinition,
+        TypeSystemDocument, UnionDefinition,
+    },
+};
+
+/// A `#` comment from the source of a document
+#[derive(Clone, Copy, Debug)]
+pub struct Comment<'a> {
+    text: &'a str,
+    span: Span,
+}
+
+impl<'a> Comment<'a> {
+    /// The text of the comment, without the leading `#`
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The span of the comment, including the leading `#`
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct CommentStore {
+    /// Whether comments were kept when parsing this document
+    enabled: bool,
+
+    comments: Vec<CommentRecord>,
+
+    /// The comments attached to each node, keyed on the start of its span
+    attachments: HashMap<usize, Attachments>,
+
+    end_of_document: Vec<usize>,
+}
+
+struct CommentRecord {
+    text: Box<str>,
+    span: Span,
+}
+
+#[derive(Default)]
+struct Attachments {
+    leading: Vec<usize>,
+    trailing: Option<usize>,
+    dangling: Vec<usize>,
+}
+
+impl CommentStore {
+    pub(crate) fn new(input: &str, document: &TypeSystemDocument) -> Self {
+        let tokens = Lexer::new(input)
+            .filter_map(|token| token.ok())
+            .map(|(start, _, end)| Span::new(start, end))
+            .collect::<Vec<_>>();
+
+        let comments = find_comments(input, &tokens);
+        let mut store = CommentStore {
+            enabled: true,
+            ..CommentStore::default()
+        };
+        if comments.is_empty() {
+            return store;
+        }
+
+        let nodes = collect_nodes(document, &tokens);
+
+        for (index, comment) in comments.iter().enumerate() {
+            if let Some(node) = trailing_node(input, comment.span, &nodes) {
+                store.attachments_mut(node).trailing = Some(index);
+                continue;
+            }
+
+            let enclosing = nodes
+                .iter()
+                .filter(|node| {
+                    node.has_body
+                        && node.span.start < comment.span.start
+                        && comment.span.end <= node.span.end
+                })
+                .max_by_key(|node| node.span.start);
+
+            let next = nodes
+                .iter()
+                .filter(|node| node.span.start >= comment.span.end)
+                .filter(|node| {
+                    enclosing.is_none_or(|enclosing| node.span.end <= enclosing.span.end)
+                })
+                .min_by_key(|node| node.span.start);
+
+            match (next, enclosing) {
+                (Some(next), _) => store.attachments_mut(next).leading.push(index),
+                (None, Some(enclosing)) => store.attachments_mut(enclosing).dangling.push(index),
+                (None, None) => store.end_of_document.push(index),
+            }
+        }
+
+        store.comments = comments;
+        store
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn attachments_mut(&mut self, node: &Node) -> &mut Attachments {
+        self.attachments.entry(node.span.start).or_default()
+    }
+
+    fn read<'a>(&'a self, indices: &'a [usize]) -> impl Iterator<Item = Comment<'a>> + 'a {
+        indices.iter().map(|index| self.comment(*index))
+    }
+
+    fn comment(&self, index: usize) -> Comment<'_> {
+        let record = &self.comments[index];
+        Comment {
+            text: &record.text,
+            span: record.span,
+        }
+    }
+
+    fn leading(&self, span: Span) -> impl Iterator<Item = Comment<'_>> + '_ {
+        let indices = self
+            .attachments
+            .get(&span.start)
+            .map(|attachments| attachments.leading.as_slice())
+            .unwrap_or_default();
+
+        self.read(indices)
+    }
+
+    fn trailing(&self, span: Span) -> Option<Comment<'_>> {
+        let index = self.attachments.get(&span.start)?.trailing?;
+        Some(self.comment(index))
+    }
+
+    fn dangling(&self, span: Span) -> impl Iterator<Item = Comment<'_>> + '_ {
+        let indices = self
+            .attachments
+            .get(&span.start)
+            .map(|attachments| attachments.dangling.as_slice())
+            .unwrap_or_default();
+
+        self.read(indices)
+    }
+}
+
+struct Node {
+    span: Span,
+    has_body: bool,
+}
+
+fn collect_nodes(document: &TypeSystemDocument, tokens: &[Span]) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut push = |mut span: Span, has_body: bool| {
+        // Spans of nodes that end with something optional (e.g. directives)
+        // can extend up to the next token when it's missing, so we shrink
+        // them back to the last token they actually contain.
+        let last_token = tokens.partition_point(|token| token.end <= span.end);
+        if last_token != 0 {
+            span.end = tokens[last_token - 1].end;
+        }
+        nodes.push(Node { span, has_body })
+    };
+
+    for definition in document.definitions() {
+        match definition {
+            Definition::Schema(schema) | Definition::SchemaExtension(schema) => {
+                push(schema.span(), schema.root_operations().len() != 0);
+                for operation in schema.root_operations() {
+                    push(operation.span(), false);
+                }
+            }
+            Definition::Type(ty) | Definition::TypeExtension(ty) => match ty {
+                TypeDefinition::Scalar(scalar) => push(scalar.span(), false),
+                TypeDefinition::Object(object) => {
+                    push(object.span(), object.fields().len() != 0);
+                    for field in object.fields() {
+                        push(field.span(), false);
+                        for argument in field.arguments() {
+                            push(argument.span(), false);
+                        }
+                    }
+                }
+                TypeDefinition::Interface(interface) => {
+                    push(interface.span(), interface.fields().len() != 0);
+                    for field in interface.fields() {
+                        push(field.span(), false);
+                        for argument in field.arguments() {
+                            push(argument.span(), false);
+                        }
+                    }
+                }
+                TypeDefinition::Union(union) => push(union.span(), false),
+                TypeDefinition::Enum(definition) => {
+                    push(definition.span(), definition.values().len() != 0);
+                    for value in definition.values() {
+                        push(value.span(), false);
+                    }
+                }
+                TypeDefinition::InputObject(input) => {
+                    push(input.span(), input.fields().len() != 0);
+                    for field in input.fields() {
+                        push(field.span(), false);
+                    }
+                }
+            },
+            Definition::Directive(directive) => {
+                push(directive.span(), false);
+                for argument in directive.arguments() {
+                    push(argument.span(), false);
+                }
+            }
+        }
+    }
+
+    nodes
+}
+
+/// Finds the node a comment trails, if there's code before it on its line
+fn trailing_node<'a>(input: &str, comment: Span, nodes: &'a [Node]) -> Option<&'a Node> {
+    let line_start = input[..comment.start]
+        .rfind('\n')
+        .map(|index| index + 1)
+        .unwrap_or_default();
+
+    if input[line_start..comment.start].trim().is_empty() {
+        return None;
+    }
+
+    nodes
+        .iter()
+        .filter(|node| line_start <= node.span.end && node.span.end <= comment.start)
+        .max_by_key(|node| (node.span.end, node.span.start))
+}
+
+fn find_comments(input: &str, tokens: &[Span]) -> Vec<CommentRecord> {
+    let mut comments = Vec::new();
+    let mut position = 0;
+
+    let end_of_input = Span::new(input.len(), input.len());
+
+    for token in tokens.iter().chain([&end_of_input]) {
+        let (token_start, token_end) = (token.start, token.end);
+        // Anything between tokens is whitespace, commas or comments
+        let mut gap = &input[position..token_start];
+        let mut gap_start = position;
+        while let Some(hash) = gap.find('#') {
+            let length = gap[hash..].find('\n').unwrap_or(gap.len() - hash);
+            let start = gap_start + hash;
+            let end = start + length;
+
+            comments.push(CommentRecord {
+                text: input[start + 1..end].trim_end().into(),
+                span: Span::new(start, end),
+            });
+
+            gap = &input[end..token_start];
+            gap_start = end;
+        }
+        position = token_end;
+    }
+
+    comments
+}
+
+impl TypeSystemDocument {
+    /// Finds the comments in `input` and attaches them to this document
+    pub(crate) fn attach_comments(&mut self, input: &str) {
+        self.comments = CommentStore::new(input, self);
+    }
+
+    /// Any comments that come after the last definition in the document
+    pub fn end_of_document_comments(&self) -> impl Iterator<Item = Comment<'_>> + '_ {
+        self.comments.read(&self.comments.end_of_document)
+    }
+}
+
+macro_rules! impl_comments {
+    ($($reader:ident),*) => {
+        $(
+            impl<'a> $reader<'a> {
+                /// The comments on the lines before this node
+                ///
+                /// This is only populated when the document was parsed with
+                /// [`crate::parse_type_system_document_with_comments`]
+                pub fn leading_comments(&self) -> impl Iterator<Item = Comment<'a>> + 'a {
+                    self.0.document.comments.leading(self.span())
+                }
+
+                /// The comment at the end of the line this node ends on
+                ///
+                /// This is only populated when the document was parsed with
+                /// [`crate::parse_type_system_document_with_comments`]
+                pub fn trailing_comment(&self) -> Option<Comment<'a>> {
+                    self.0.document.comments.trailing(self.span())
+                }
+            }
+        )*
+    };
+}
+
+impl_comments!(
+    SchemaDefinition,
+    ScalarDefinition,
+    ObjectDefinition,
+    InterfaceDefinition,
+    UnionDefinition,
+    EnumDefinition,
+    InputObjectDefinition,
+    DirectiveDefinition,
+    RootOperationTypeDefinition,
+    FieldDefinition,
+    InputValueDefinition,
+    EnumValueDefinition
+);
+
+macro_rules! impl_dangling_comments {
+    ($($reader:ident),*) => {
+        $(
+            impl<'a> $reader<'a> {
+                /// Any comments after the last entry in the body of this definition
+                pub fn dangling_comments(&self) -> impl Iterator<Item = Comment<'a>> + 'a {
+                    self.0.document.comments.dangling(self.span())
+                }
+            }
+        )*
+    };
+}
+
+impl_dangling_comments!(
+    SchemaDefinition,
+    ObjectDefinition,
+    InterfaceDefinition,
+    EnumDefinition,
+    InputObjectDefinition
+);
+
+impl<'a> Definition<'a> {
+    /// The comments on the lines before this definition
+    pub fn leading_comments(&self) -> Box<dyn Iterator<Item = Comment<'a>> + 'a> {
+        match self {
+            Definition::Schema(def) | Definition::SchemaExtension(def) => {
+                Box::new(def.leading_comments())
+            }
+            Definition::Type(ty) | Definition::TypeExtension(ty) => ty.leading_comments(),
+            Definition::Directive(def) => Box::new(def.leading_comments()),
+        }
+    }
+
+    /// The comment at the end of the line this definition ends on
+    pub fn trailing_comment(&self) -> Option<Comment<'a>> {
+        match self {
+            Definition::Schema(def) | Definition::SchemaExtension(def) => def.trailing_comment(),
+            Definition::Type(ty) | Definition::TypeExtension(ty) => ty.trailing_comment(),
+            Definition::Directive(def) => def.trailing_comment(),
+        }
+    }
+}
+
+impl<'a> TypeDefinition<'a> {
+    /// The comments on the lines before this definition
+    pub fn leading_comments(&self) -> Box<dyn Iterator<Item = Comment<'a>> + 'a> {
+        match self {
+            TypeDefinition::Scalar(inner) => Box::new(inner.leading_comments()),
+            TypeDefinition::Object(inner) => Box::new(inner.leading_comments()),
+            TypeDefinition::Interface(inner) => Box::new(inner.leading_comments()),
+            TypeDefinition::Union(inner) => Box::new(inner.leading_comments()),
+            TypeDefinition::Enum(inner) => Box::new(inner.leading_comments()),
+            TypeDefinition::InputObject(inner) => Box::new(inner.leading_comments()),
+        }
+    }
+
+    /// The comment at the end of the line this definition ends on
+    pub fn trailing_comment(&self) -> Option<Comment<'a>> {
+        match self {
+            TypeDefinition::Scalar(inner) => inner.trailing_comment(),
+            TypeDefinition::Object(inner) => inner.trailing_comment(),
+            TypeDefinition::Interface(inner) => inner.trailing_comment(),
+            TypeDefinition::Union(inner) => inner.trailing_comment(),
+            TypeDefinition::Enum(inner) => inner.trailing_comment(),
+            TypeDefinition::InputObject(inner) => inner.trailing_comment(),
+        }
+    }
+}
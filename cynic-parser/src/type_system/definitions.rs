@@ -68,6 +68,17 @@ impl<'a> TypeDefinition<'a> {
         }
     }
 
+    pub fn name_span(&self) -> crate::Span {
+        match self {
+            TypeDefinition::Scalar(inner) => inner.name_span(),
+            TypeDefinition::Object(inner) => inner.name_span(),
+            TypeDefinition::Interface(inner) => inner.name_span(),
+            TypeDefinition::Union(inner) => inner.name_span(),
+            TypeDefinition::Enum(inner) => inner.name_span(),
+            TypeDefinition::InputObject(inner) => inner.name_span(),
+        }
+    }
+
     pub fn directives(&self) -> Iter<'a, Directive<'a>> {
         match self {
             TypeDefinition::Scalar(inner) => inner.directives(),
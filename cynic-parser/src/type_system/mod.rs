@@ -2,13 +2,16 @@ use std::{str::FromStr, sync::Arc};
 
 use indexmap::IndexSet;
 
+pub mod builder;
 pub mod ids;
 pub mod iter;
 pub mod writer;
 
+mod comments;
 mod definitions;
 mod extensions;
 mod generated;
+mod reparse;
 mod schemas;
 mod string_literal;
 mod types;
@@ -17,6 +20,7 @@ mod values;
 use crate::common::IdRange;
 
 pub use self::{
+    comments::Comment,
     definitions::{Definition, TypeDefinition},
     generated::{
         arguments::Argument,
@@ -69,6 +73,8 @@ pub struct TypeSystemDocument {
     descriptions: Vec<storage::DescriptionRecord>,
 
     values: crate::values::ValueStore,
+
+    comments: comments::CommentStore,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -220,6 +226,30 @@ impl TypeSystemDocument {
             .enumerate()
             .map(|(index, _)| self.read(DirectiveDefinitionId::new(index)))
     }
+
+    /// Checks that the schema in this document is well formed
+    ///
+    /// See [`crate::validation::validate_type_system_document`] for details
+    pub fn validate(&self) -> Result<(), Vec<crate::validation::ValidationError>> {
+        crate::validation::validate_type_system_document(self)
+    }
+
+    /// Returns a copy of this document with every type extension folded into
+    /// the definition it extends
+    ///
+    /// See [`crate::merge::merge_type_system_documents`] for details
+    pub fn normalize(&self) -> Result<TypeSystemDocument, Vec<crate::validation::ValidationError>> {
+        crate::merge::merge_type_system_documents([self])
+            .map_err(|errors| errors.into_iter().map(|error| error.error).collect())
+    }
+
+    /// Compares this schema with a newer version of it
+    ///
+    /// See [`crate::diff::diff_type_system_documents`] for details
+    #[cfg(feature = "print")]
+    pub fn diff(&self, new: &TypeSystemDocument) -> Vec<crate::diff::Change> {
+        crate::diff::diff_type_system_documents(self, new)
+    }
 }
 
 pub mod storage {
@@ -0,0 +1,185 @@
+use crate::{
+    Error,
+    incremental::{self, TextEdit},
+    parser,
+};
+
+use super::{
+    DefinitionRecord, TypeSystemDocument, ids::DirectiveDefinitionId, writer::TypeSystemAstWriter,
+};
+
+impl TypeSystemDocument {
+    /// Reparses the definitions of this document that were affected by `edit`
+    ///
+    /// `input` should be the text of the document after `edit` was applied.
+    /// Falls back to parsing the whole of `input` if the affected definitions
+    /// can't be parsed on their own.
+    pub(crate) fn reparse(
+        mut self,
+        input: &str,
+        edit: &TextEdit<'_>,
+    ) -> Result<TypeSystemDocument, Error> {
+        let keep_comments = self.comments.is_enabled();
+        let parse_everything = || match keep_comments {
+            true => crate::parse_type_system_document_with_comments(input),
+            false => crate::parse_type_system_document(input),
+        };
+
+        if input.trim().is_empty() {
+            return parse_everything();
+        }
+
+        let definition_ends = self
+            .definitions()
+            .map(|definition| definition.span().end)
+            .collect::<Vec<_>>();
+
+        let Some(region) = incremental::affected_region(&definition_ends, input, edit) else {
+            return parse_everything();
+        };
+        let Some(tokens) = incremental::lex_region(input, &region) else {
+            return parse_everything();
+        };
+
+        self.shift_spans(region.old_end, region.delta);
+
+        // Anything we parse now is appended to the end of the storage, leaving
+        // the ids of all the other definitions intact.
+        let previous_definitions = self.definitions.len();
+        let mut writer = TypeSystemAstWriter::update(self);
+        if !tokens.is_empty()
+            && parser::TypeSystemDocumentParser::new()
+                .parse(input, &mut writer, tokens)
+                .is_err()
+        {
+            return parse_everything();
+        }
+
+        let mut document = writer.finish();
+        let reparsed = document.definitions.split_off(previous_definitions);
+        document.definitions.splice(region.definitions, reparsed);
+        document.remove_stale_directive_definitions();
+
+        if keep_comments {
+            document.attach_comments(input);
+        }
+
+        Ok(document)
+    }
+
+    /// Removes the records of any directive definitions that were replaced
+    ///
+    /// Unlike the other definitions, `directive_definitions` reads these
+    /// straight from storage so they can't be left lying around.
+    fn remove_stale_directive_definitions(&mut self) {
+        let mut previous = std::mem::take(&mut self.directive_definitions)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        for definition in &mut self.definitions {
+            if let DefinitionRecord::Directive(id) = definition {
+                let record = previous[id.get()]
+                    .take()
+                    .expect("directive definitions to only be used once");
+                *id = DirectiveDefinitionId::new(self.directive_definitions.len());
+                self.directive_definitions.push(record);
+            }
+        }
+    }
+
+    fn shift_spans(&mut self, position: usize, delta: isize) {
+        let TypeSystemDocument {
+            strings: _,
+            block_strings: _,
+            definitions: _,
+            schema_definitions,
+            scalar_definitions,
+            object_definitions,
+            interface_definitions,
+            union_definitions,
+            enum_definitions,
+            input_object_definitions,
+            directive_definitions,
+            root_operation_definitions,
+            field_definitions,
+            input_value_definitions,
+            enum_value_definitions,
+            union_members,
+            type_references,
+            directives,
+            arguments,
+            descriptions,
+            values,
+            comments: _,
+        } = self;
+
+        for record in schema_definitions {
+            record.span.shift_from(position, delta);
+        }
+        for record in scalar_definitions {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in object_definitions {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in interface_definitions {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in union_definitions {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in enum_definitions {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in input_object_definitions {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in directive_definitions {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in root_operation_definitions {
+            record.operation_type_span.shift_from(position, delta);
+            record.named_type_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in field_definitions {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in input_value_definitions {
+            record.name_span.shift_from(position, delta);
+            record.default_value_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in enum_value_definitions {
+            record.value_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in union_members {
+            record.span.shift_from(position, delta);
+        }
+        for record in type_references {
+            record.span.shift_from(position, delta);
+        }
+        for record in directives {
+            record.name_span.shift_from(position, delta);
+            record.arguments_span.shift_from(position, delta);
+        }
+        for record in arguments {
+            record.name_span.shift_from(position, delta);
+            record.span.shift_from(position, delta);
+        }
+        for record in descriptions {
+            record.span.shift_from(position, delta);
+        }
+        values.shift_spans(position, delta);
+    }
+}
@@ -11,6 +11,8 @@ use super::{ids::*, storage::*};
 pub struct TypeSystemAstWriter {
     pub(crate) values: crate::values::writer::ValueWriter,
 
+    comments: super::comments::CommentStore,
+
     strings: IndexSet<Box<str>>,
     block_strings: Vec<Box<str>>,
 
@@ -72,6 +74,7 @@ impl TypeSystemAstWriter {
             descriptions: Default::default(),
 
             values: Default::default(),
+            comments: Default::default(),
 
             field_id_cursor: FieldDefinitionId::new(0),
             input_value_id_cursor: InputValueDefinitionId::new(0),
@@ -105,6 +108,7 @@ impl TypeSystemAstWriter {
             arguments,
             descriptions,
             values,
+            comments,
         } = ast;
 
         let values = ValueWriter::update(values);
@@ -140,6 +144,7 @@ impl TypeSystemAstWriter {
             descriptions,
 
             values,
+            comments,
         }
     }
 
@@ -168,6 +173,7 @@ impl TypeSystemAstWriter {
             descriptions,
 
             values,
+            comments,
 
             field_id_cursor: _,
             input_value_id_cursor: _,
@@ -202,6 +208,7 @@ impl TypeSystemAstWriter {
             arguments,
             descriptions,
             values,
+            comments,
         }
     }
 
@@ -575,3 +582,109 @@ impl Default for TypeSystemAstWriter {
         Self::new()
     }
 }
+
+impl crate::recovery::Rollback for TypeSystemAstWriter {
+    type Checkpoint = WriterCheckpoint;
+
+    fn checkpoint(&self) -> WriterCheckpoint {
+        WriterCheckpoint {
+            values: self.values.checkpoint(),
+            block_strings: self.block_strings.len(),
+            definitions: self.definitions.len(),
+            schema_definitions: self.schema_definitions.len(),
+            scalar_definitions: self.scalar_definitions.len(),
+            object_definitions: self.object_definitions.len(),
+            interface_definitions: self.interface_definitions.len(),
+            union_definitions: self.union_definitions.len(),
+            enum_definitions: self.enum_definitions.len(),
+            input_object_definitions: self.input_object_definitions.len(),
+            directive_definitions: self.directive_definitions.len(),
+            root_operation_definitions: self.root_operation_definitions.len(),
+            field_definitions: self.field_definitions.len(),
+            input_value_definitions: self.input_value_definitions.len(),
+            enum_value_definitions: self.enum_value_definitions.len(),
+            union_members: self.union_members.len(),
+            type_references: self.type_references.len(),
+            directives: self.directives.len(),
+            arguments: self.arguments.len(),
+            descriptions: self.descriptions.len(),
+            field_id_cursor: self.field_id_cursor,
+            input_value_id_cursor: self.input_value_id_cursor,
+            enum_value_id_cursor: self.enum_value_id_cursor,
+            directive_id_cursor: self.directive_id_cursor,
+            argument_id_cursor: self.argument_id_cursor,
+            union_member_id_cursor: self.union_member_id_cursor,
+        }
+    }
+
+    fn rollback(&mut self, checkpoint: WriterCheckpoint) {
+        self.values.rollback(checkpoint.values);
+        self.block_strings.truncate(checkpoint.block_strings);
+        self.definitions.truncate(checkpoint.definitions);
+        self.schema_definitions
+            .truncate(checkpoint.schema_definitions);
+        self.scalar_definitions
+            .truncate(checkpoint.scalar_definitions);
+        self.object_definitions
+            .truncate(checkpoint.object_definitions);
+        self.interface_definitions
+            .truncate(checkpoint.interface_definitions);
+        self.union_definitions
+            .truncate(checkpoint.union_definitions);
+        self.enum_definitions.truncate(checkpoint.enum_definitions);
+        self.input_object_definitions
+            .truncate(checkpoint.input_object_definitions);
+        self.directive_definitions
+            .truncate(checkpoint.directive_definitions);
+        self.root_operation_definitions
+            .truncate(checkpoint.root_operation_definitions);
+        self.field_definitions
+            .truncate(checkpoint.field_definitions);
+        self.input_value_definitions
+            .truncate(checkpoint.input_value_definitions);
+        self.enum_value_definitions
+            .truncate(checkpoint.enum_value_definitions);
+        self.union_members.truncate(checkpoint.union_members);
+        self.type_references.truncate(checkpoint.type_references);
+        self.directives.truncate(checkpoint.directives);
+        self.arguments.truncate(checkpoint.arguments);
+        self.descriptions.truncate(checkpoint.descriptions);
+        self.field_id_cursor = checkpoint.field_id_cursor;
+        self.input_value_id_cursor = checkpoint.input_value_id_cursor;
+        self.enum_value_id_cursor = checkpoint.enum_value_id_cursor;
+        self.directive_id_cursor = checkpoint.directive_id_cursor;
+        self.argument_id_cursor = checkpoint.argument_id_cursor;
+        self.union_member_id_cursor = checkpoint.union_member_id_cursor;
+    }
+}
+
+/// The state of a `TypeSystemAstWriter` at some point in time
+#[derive(Clone, Copy)]
+pub(crate) struct WriterCheckpoint {
+    values: crate::values::writer::ValueCheckpoint,
+    block_strings: usize,
+    definitions: usize,
+    schema_definitions: usize,
+    scalar_definitions: usize,
+    object_definitions: usize,
+    interface_definitions: usize,
+    union_definitions: usize,
+    enum_definitions: usize,
+    input_object_definitions: usize,
+    directive_definitions: usize,
+    root_operation_definitions: usize,
+    field_definitions: usize,
+    input_value_definitions: usize,
+    enum_value_definitions: usize,
+    union_members: usize,
+    type_references: usize,
+    directives: usize,
+    arguments: usize,
+    descriptions: usize,
+    field_id_cursor: FieldDefinitionId,
+    input_value_id_cursor: InputValueDefinitionId,
+    enum_value_id_cursor: EnumValueDefinitionId,
+    directive_id_cursor: DirectiveId,
+    argument_id_cursor: ArgumentId,
+    union_member_id_cursor: UnionMemberId,
+}
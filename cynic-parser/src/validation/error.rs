@@ -0,0 +1,776 @@
+use std::fmt;
+
+use crate::{Span, common::OperationType, type_system::DirectiveLocation};
+
+/// A problem found while validating a document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A type that isn't defined in the schema was referenced
+    UnknownType { name: String, span: Span },
+
+    /// A field was selected that doesn't exist on its parent type
+    UnknownField {
+        type_name: String,
+        field_name: String,
+        span: Span,
+    },
+
+    /// An argument was provided that the field or directive doesn't accept
+    UnknownArgument {
+        /// The field (as `Type.field`) or directive (as `@directive`) the argument was passed to
+        coordinate: String,
+        name: String,
+        span: Span,
+    },
+
+    /// The same argument was provided more than once
+    DuplicateArgument { name: String, span: Span },
+
+    /// A required argument wasn't provided
+    MissingArgument {
+        /// The field (as `Type.field`) or directive (as `@directive`) that is missing an argument
+        coordinate: String,
+        name: String,
+        span: Span,
+    },
+
+    /// A value doesn't match the type expected at its position
+    InvalidValue { expected_type: String, span: Span },
+
+    /// An input object value contains a field that the input object doesn't have
+    UnknownInputField {
+        type_name: String,
+        name: String,
+        span: Span,
+    },
+
+    /// An input object value is missing a required field
+    MissingInputField {
+        type_name: String,
+        name: String,
+        span: Span,
+    },
+
+    /// An input object value contains the same field more than once
+    DuplicateInputField { name: String, span: Span },
+
+    /// A directive that isn't defined in the schema was used
+    UnknownDirective { name: String, span: Span },
+
+    /// A directive was used somewhere it isn't allowed
+    MisplacedDirective {
+        name: String,
+        location: DirectiveLocation,
+        span: Span,
+    },
+
+    /// A non-repeatable directive was used more than once at the same location
+    RepeatedDirective { name: String, span: Span },
+
+    /// More than one operation has the same name
+    DuplicateOperationName { name: String, span: Span },
+
+    /// An anonymous operation was found in a document with other operations
+    AnonymousOperationNotAlone { span: Span },
+
+    /// An operation uses an operation type that the schema doesn't support
+    UnsupportedOperation {
+        operation_type: OperationType,
+        span: Span,
+    },
+
+    /// A subscription selects more than one root field
+    MultipleSubscriptionRootFields { span: Span },
+
+    /// A subscription selects an introspection field at its root
+    IntrospectionSubscriptionRootField { span: Span },
+
+    /// More than one fragment has the same name
+    DuplicateFragmentName { name: String, span: Span },
+
+    /// A fragment was spread that isn't defined in the document
+    UnknownFragment { name: String, span: Span },
+
+    /// A fragment was defined but never used
+    UnusedFragment { name: String, span: Span },
+
+    /// A fragment spreads itself, either directly or via other fragments
+    FragmentCycle { name: String, span: Span },
+
+    /// A fragment has a type condition on a type that isn't an object,
+    /// interface or union
+    NonCompositeTypeCondition { type_name: String, span: Span },
+
+    /// A fragment can never match at the position it was spread
+    ImpossibleFragmentSpread {
+        type_name: String,
+        parent_type: String,
+        span: Span,
+    },
+
+    /// A field with a scalar or enum type has a selection set
+    SelectionOnLeafField {
+        field_name: String,
+        type_name: String,
+        span: Span,
+    },
+
+    /// A field with an object, interface or union type has no selection set
+    MissingSelectionSet {
+        field_name: String,
+        type_name: String,
+        span: Span,
+    },
+
+    /// More than one variable in an operation has the same name
+    DuplicateVariable { name: String, span: Span },
+
+    /// A variable was declared with a type that isn't an input type
+    NonInputVariableType {
+        name: String,
+        type_name: String,
+        span: Span,
+    },
+
+    /// A variable was used that isn't defined by the operation
+    UndefinedVariable {
+        name: String,
+        operation: Option<String>,
+        span: Span,
+    },
+
+    /// A variable was defined but never used
+    UnusedVariable { name: String, span: Span },
+
+    /// A variable was used in a position that expects a different type
+    VariableTypeMismatch {
+        name: String,
+        variable_type: String,
+        expected_type: String,
+        span: Span,
+    },
+
+    /// More than one type in a schema has the same name
+    DuplicateTypeDefinition { name: String, span: Span },
+
+    /// More than one directive in a schema has the same name
+    DuplicateDirectiveDefinition { name: String, span: Span },
+
+    /// A schema has more than one schema definition
+    DuplicateSchemaDefinition { span: Span },
+
+    /// A schema defines the same root operation type more than once
+    DuplicateRootOperation {
+        operation_type: OperationType,
+        span: Span,
+    },
+
+    /// A schema definition doesn't define a query root type
+    MissingQueryRoot { span: Span },
+
+    /// A root operation type isn't an object type
+    NonObjectRootType {
+        operation_type: OperationType,
+        name: String,
+        span: Span,
+    },
+
+    /// A type extension extends a type that isn't defined
+    ExtensionOfUnknownType { name: String, span: Span },
+
+    /// A type extension is a different kind of type from the type it extends
+    ExtensionKindMismatch { name: String, span: Span },
+
+    /// A name starting with `__` was used, which is reserved for introspection
+    ReservedName { name: String, span: Span },
+
+    /// An object, interface or input object has no fields
+    EmptyFields { type_name: String, span: Span },
+
+    /// An enum has no values
+    EmptyEnum { name: String, span: Span },
+
+    /// A union has no members
+    EmptyUnion { name: String, span: Span },
+
+    /// A type defines the same field more than once
+    DuplicateField {
+        type_name: String,
+        name: String,
+        span: Span,
+    },
+
+    /// A field or directive defines the same argument more than once
+    DuplicateArgumentDefinition {
+        /// The field (as `Type.field`) or directive (as `@directive`) the argument is defined on
+        coordinate: String,
+        name: String,
+        span: Span,
+    },
+
+    /// An enum defines the same value more than once
+    DuplicateEnumValue {
+        enum_name: String,
+        name: String,
+        span: Span,
+    },
+
+    /// A union contains the same member more than once
+    DuplicateUnionMember {
+        union_name: String,
+        name: String,
+        span: Span,
+    },
+
+    /// A field has a type that can't be used as an output
+    NonOutputType {
+        /// The field the type was used on, as `Type.field`
+        coordinate: String,
+        type_name: String,
+        span: Span,
+    },
+
+    /// An argument or input field has a type that can't be used as an input
+    NonInputType {
+        /// The argument (as `Type.field(argument:)`) or input field (as `Type.field`)
+        coordinate: String,
+        type_name: String,
+        span: Span,
+    },
+
+    /// A union contains a type that isn't an object
+    NonObjectUnionMember {
+        union_name: String,
+        name: String,
+        span: Span,
+    },
+
+    /// A type declares that it implements something that isn't an interface
+    ImplementsNonInterface {
+        type_name: String,
+        interface: String,
+        span: Span,
+    },
+
+    /// A type declares that it implements the same interface more than once
+    DuplicateImplementedInterface {
+        type_name: String,
+        interface: String,
+        span: Span,
+    },
+
+    /// An interface declares that it implements itself
+    InterfaceImplementsItself { name: String, span: Span },
+
+    /// A type implements an interface, but not the interfaces that interface implements
+    MissingTransitiveInterface {
+        type_name: String,
+        interface: String,
+        /// The interface that implements `interface`
+        via: String,
+        span: Span,
+    },
+
+    /// A type implements an interface but doesn't have one of its fields
+    MissingInterfaceField {
+        type_name: String,
+        interface: String,
+        field_name: String,
+        span: Span,
+    },
+
+    /// A field has a type that isn't compatible with the interface field it implements
+    InterfaceFieldTypeMismatch {
+        /// The implementing field, as `Type.field`
+        coordinate: String,
+        /// The interface field, as `Interface.field`
+        interface_coordinate: String,
+        field_type: String,
+        interface_field_type: String,
+        span: Span,
+    },
+
+    /// A field doesn't have an argument from the interface field it implements
+    MissingInterfaceArgument {
+        /// The implementing field, as `Type.field`
+        coordinate: String,
+        /// The interface field, as `Interface.field`
+        interface_coordinate: String,
+        name: String,
+        span: Span,
+    },
+
+    /// An argument has a different type from the interface argument it implements
+    InterfaceArgumentTypeMismatch {
+        /// The implementing argument, as `Type.field(argument:)`
+        coordinate: String,
+        argument_type: String,
+        interface_argument_type: String,
+        span: Span,
+    },
+
+    /// A field has a required argument that the interface field it implements doesn't have
+    RequiredExtraArgument {
+        /// The argument, as `Type.field(argument:)`
+        coordinate: String,
+        /// The interface field, as `Interface.field`
+        interface_coordinate: String,
+        span: Span,
+    },
+
+    /// A field of a `@oneOf` input object is non-nullable or has a default
+    InvalidOneOfField {
+        /// The field, as `Type.field`
+        coordinate: String,
+        span: Span,
+    },
+
+    /// An input object references itself through non-nullable fields,
+    /// so no value of it could ever be provided
+    InputObjectCycle { name: String, span: Span },
+}
+
+impl ValidationError {
+    pub fn span(&self) -> Span {
+        match self {
+            ValidationError::UnknownType { span, .. }
+            | ValidationError::UnknownField { span, .. }
+            | ValidationError::UnknownArgument { span, .. }
+            | ValidationError::DuplicateArgument { span, .. }
+            | ValidationError::MissingArgument { span, .. }
+            | ValidationError::InvalidValue { span, .. }
+            | ValidationError::UnknownInputField { span, .. }
+            | ValidationError::MissingInputField { span, .. }
+            | ValidationError::DuplicateInputField { span, .. }
+            | ValidationError::UnknownDirective { span, .. }
+            | ValidationError::MisplacedDirective { span, .. }
+            | ValidationError::RepeatedDirective { span, .. }
+            | ValidationError::DuplicateOperationName { span, .. }
+            | ValidationError::AnonymousOperationNotAlone { span }
+            | ValidationError::UnsupportedOperation { span, .. }
+            | ValidationError::MultipleSubscriptionRootFields { span }
+            | ValidationError::IntrospectionSubscriptionRootField { span }
+            | ValidationError::DuplicateFragmentName { span, .. }
+            | ValidationError::UnknownFragment { span, .. }
+            | ValidationError::UnusedFragment { span, .. }
+            | ValidationError::FragmentCycle { span, .. }
+            | ValidationError::NonCompositeTypeCondition { span, .. }
+            | ValidationError::ImpossibleFragmentSpread { span, .. }
+            | ValidationError::SelectionOnLeafField { span, .. }
+            | ValidationError::MissingSelectionSet { span, .. }
+            | ValidationError::DuplicateVariable { span, .. }
+            | ValidationError::NonInputVariableType { span, .. }
+            | ValidationError::UndefinedVariable { span, .. }
+            | ValidationError::UnusedVariable { span, .. }
+            | ValidationError::VariableTypeMismatch { span, .. }
+            | ValidationError::DuplicateTypeDefinition { span, .. }
+            | ValidationError::DuplicateDirectiveDefinition { span, .. }
+            | ValidationError::DuplicateSchemaDefinition { span, .. }
+            | ValidationError::DuplicateRootOperation { span, .. }
+            | ValidationError::MissingQueryRoot { span, .. }
+            | ValidationError::NonObjectRootType { span, .. }
+            | ValidationError::ExtensionOfUnknownType { span, .. }
+            | ValidationError::ExtensionKindMismatch { span, .. }
+            | ValidationError::ReservedName { span, .. }
+            | ValidationError::EmptyFields { span, .. }
+            | ValidationError::EmptyEnum { span, .. }
+            | ValidationError::EmptyUnion { span, .. }
+            | ValidationError::DuplicateField { span, .. }
+            | ValidationError::DuplicateArgumentDefinition { span, .. }
+            | ValidationError::DuplicateEnumValue { span, .. }
+            | ValidationError::DuplicateUnionMember { span, .. }
+            | ValidationError::NonOutputType { span, .. }
+            | ValidationError::NonInputType { span, .. }
+            | ValidationError::NonObjectUnionMember { span, .. }
+            | ValidationError::ImplementsNonInterface { span, .. }
+            | ValidationError::DuplicateImplementedInterface { span, .. }
+            | ValidationError::InterfaceImplementsItself { span, .. }
+            | ValidationError::MissingTransitiveInterface { span, .. }
+            | ValidationError::MissingInterfaceField { span, .. }
+            | ValidationError::InterfaceFieldTypeMismatch { span, .. }
+            | ValidationError::MissingInterfaceArgument { span, .. }
+            | ValidationError::InterfaceArgumentTypeMismatch { span, .. }
+            | ValidationError::RequiredExtraArgument { span, .. }
+            | ValidationError::InvalidOneOfField { span, .. }
+            | ValidationError::InputObjectCycle { span, .. } => *span,
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::UnknownType { name, .. } => {
+                write!(f, "unknown type: {name}")
+            }
+            ValidationError::UnknownField {
+                type_name,
+                field_name,
+                ..
+            } => {
+                write!(f, "no field named {field_name} on the type {type_name}")
+            }
+            ValidationError::UnknownArgument {
+                coordinate, name, ..
+            } => {
+                write!(f, "{coordinate} has no argument named {name}")
+            }
+            ValidationError::DuplicateArgument { name, .. } => {
+                write!(f, "the argument {name} was provided more than once")
+            }
+            ValidationError::MissingArgument {
+                coordinate, name, ..
+            } => {
+                write!(f, "{coordinate} is missing the required argument {name}")
+            }
+            ValidationError::InvalidValue { expected_type, .. } => {
+                write!(f, "expected a value of type {expected_type}")
+            }
+            ValidationError::UnknownInputField {
+                type_name, name, ..
+            } => {
+                write!(f, "no field named {name} on the input type {type_name}")
+            }
+            ValidationError::MissingInputField {
+                type_name, name, ..
+            } => {
+                write!(f, "the input type {type_name} requires the field {name}")
+            }
+            ValidationError::DuplicateInputField { name, .. } => {
+                write!(f, "the input field {name} was provided more than once")
+            }
+            ValidationError::UnknownDirective { name, .. } => {
+                write!(f, "unknown directive: @{name}")
+            }
+            ValidationError::MisplacedDirective { name, location, .. } => {
+                write!(f, "the directive @{name} can't be used on {location}")
+            }
+            ValidationError::RepeatedDirective { name, .. } => {
+                write!(f, "the directive @{name} can only be used once here")
+            }
+            ValidationError::DuplicateOperationName { name, .. } => {
+                write!(f, "there is more than one operation named {name}")
+            }
+            ValidationError::AnonymousOperationNotAlone { .. } => {
+                write!(
+                    f,
+                    "anonymous operations must be the only operation in a document"
+                )
+            }
+            ValidationError::UnsupportedOperation { operation_type, .. } => {
+                write!(f, "the schema does not support {operation_type} operations")
+            }
+            ValidationError::MultipleSubscriptionRootFields { .. } => {
+                write!(f, "subscriptions must select exactly one root field")
+            }
+            ValidationError::IntrospectionSubscriptionRootField { .. } => {
+                write!(
+                    f,
+                    "subscriptions can't select introspection fields at their root"
+                )
+            }
+            ValidationError::DuplicateFragmentName { name, .. } => {
+                write!(f, "there is more than one fragment named {name}")
+            }
+            ValidationError::UnknownFragment { name, .. } => {
+                write!(f, "unknown fragment: {name}")
+            }
+            ValidationError::UnusedFragment { name, .. } => {
+                write!(f, "the fragment {name} is never used")
+            }
+            ValidationError::FragmentCycle { name, .. } => {
+                write!(f, "the fragment {name} spreads itself")
+            }
+            ValidationError::NonCompositeTypeCondition { type_name, .. } => {
+                write!(
+                    f,
+                    "fragments can't be used on {type_name} as it is not an object, interface or union"
+                )
+            }
+            ValidationError::ImpossibleFragmentSpread {
+                type_name,
+                parent_type,
+                ..
+            } => {
+                write!(
+                    f,
+                    "a fragment on {type_name} can never match inside {parent_type}"
+                )
+            }
+            ValidationError::SelectionOnLeafField {
+                field_name,
+                type_name,
+                ..
+            } => {
+                write!(
+                    f,
+                    "the field {field_name} has type {type_name} which can't have a selection set"
+                )
+            }
+            ValidationError::MissingSelectionSet {
+                field_name,
+                type_name,
+                ..
+            } => {
+                write!(
+                    f,
+                    "the field {field_name} has type {type_name} which requires a selection set"
+                )
+            }
+            ValidationError::DuplicateVariable { name, .. } => {
+                write!(f, "the variable ${name} is defined more than once")
+            }
+            ValidationError::NonInputVariableType {
+                name, type_name, ..
+            } => {
+                write!(
+                    f,
+                    "the variable ${name} has type {type_name} which is not an input type"
+                )
+            }
+            ValidationError::UndefinedVariable {
+                name,
+                operation: Some(operation),
+                ..
+            } => {
+                write!(
+                    f,
+                    "the variable ${name} is not defined by the operation {operation}"
+                )
+            }
+            ValidationError::UndefinedVariable {
+                name,
+                operation: None,
+                ..
+            } => {
+                write!(f, "the variable ${name} is not defined by the operation")
+            }
+            ValidationError::UnusedVariable { name, .. } => {
+                write!(f, "the variable ${name} is never used")
+            }
+            ValidationError::VariableTypeMismatch {
+                name,
+                variable_type,
+                expected_type,
+                ..
+            } => {
+                write!(
+                    f,
+                    "the variable ${name} has type {variable_type} but is used where {expected_type} is expected"
+                )
+            }
+            ValidationError::DuplicateTypeDefinition { name, .. } => {
+                write!(f, "there is more than one type named {name}")
+            }
+            ValidationError::DuplicateDirectiveDefinition { name, .. } => {
+                write!(f, "there is more than one directive named @{name}")
+            }
+            ValidationError::DuplicateSchemaDefinition { .. } => {
+                write!(f, "there is more than one schema definition")
+            }
+            ValidationError::DuplicateRootOperation { operation_type, .. } => {
+                write!(
+                    f,
+                    "the {operation_type} root type is defined more than once"
+                )
+            }
+            ValidationError::MissingQueryRoot { .. } => {
+                write!(f, "the schema must define a query root type")
+            }
+            ValidationError::NonObjectRootType {
+                operation_type,
+                name,
+                ..
+            } => {
+                write!(
+                    f,
+                    "the {operation_type} root type must be an object, but {name} is not"
+                )
+            }
+            ValidationError::ExtensionOfUnknownType { name, .. } => {
+                write!(f, "can't extend {name} as it isn't defined")
+            }
+            ValidationError::ExtensionKindMismatch { name, .. } => {
+                write!(
+                    f,
+                    "this extension of {name} is a different kind of type from its definition"
+                )
+            }
+            ValidationError::ReservedName { name, .. } => {
+                write!(
+                    f,
+                    "the name {name} is reserved, as it starts with two underscores"
+                )
+            }
+            ValidationError::EmptyFields { type_name, .. } => {
+                write!(f, "the type {type_name} must define at least one field")
+            }
+            ValidationError::EmptyEnum { name, .. } => {
+                write!(f, "the enum {name} must define at least one value")
+            }
+            ValidationError::EmptyUnion { name, .. } => {
+                write!(f, "the union {name} must have at least one member")
+            }
+            ValidationError::DuplicateField {
+                type_name, name, ..
+            } => {
+                write!(
+                    f,
+                    "the field {name} is defined more than once on {type_name}"
+                )
+            }
+            ValidationError::DuplicateArgumentDefinition {
+                coordinate, name, ..
+            } => {
+                write!(
+                    f,
+                    "the argument {name} is defined more than once on {coordinate}"
+                )
+            }
+            ValidationError::DuplicateEnumValue {
+                enum_name, name, ..
+            } => {
+                write!(
+                    f,
+                    "the value {name} is defined more than once on {enum_name}"
+                )
+            }
+            ValidationError::DuplicateUnionMember {
+                union_name, name, ..
+            } => {
+                write!(
+                    f,
+                    "{name} is a member of the union {union_name} more than once"
+                )
+            }
+            ValidationError::NonOutputType {
+                coordinate,
+                type_name,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{coordinate} has type {type_name} which is not an output type"
+                )
+            }
+            ValidationError::NonInputType {
+                coordinate,
+                type_name,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{coordinate} has type {type_name} which is not an input type"
+                )
+            }
+            ValidationError::NonObjectUnionMember {
+                union_name, name, ..
+            } => {
+                write!(
+                    f,
+                    "the union {union_name} can only contain object types, but {name} is not an object"
+                )
+            }
+            ValidationError::ImplementsNonInterface {
+                type_name,
+                interface,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{type_name} can't implement {interface} as it is not an interface"
+                )
+            }
+            ValidationError::DuplicateImplementedInterface {
+                type_name,
+                interface,
+                ..
+            } => {
+                write!(f, "{type_name} implements {interface} more than once")
+            }
+            ValidationError::InterfaceImplementsItself { name, .. } => {
+                write!(f, "the interface {name} can't implement itself")
+            }
+            ValidationError::MissingTransitiveInterface {
+                type_name,
+                interface,
+                via,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{type_name} must implement {interface} because it implements {via}"
+                )
+            }
+            ValidationError::MissingInterfaceField {
+                type_name,
+                interface,
+                field_name,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{type_name} is missing the field {field_name} from the interface {interface}"
+                )
+            }
+            ValidationError::InterfaceFieldTypeMismatch {
+                coordinate,
+                interface_coordinate,
+                field_type,
+                interface_field_type,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{coordinate} has type {field_type} which is not compatible with {interface_coordinate} of type {interface_field_type}"
+                )
+            }
+            ValidationError::MissingInterfaceArgument {
+                coordinate,
+                interface_coordinate,
+                name,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{coordinate} is missing the argument {name} from {interface_coordinate}"
+                )
+            }
+            ValidationError::InterfaceArgumentTypeMismatch {
+                coordinate,
+                argument_type,
+                interface_argument_type,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{coordinate} has type {argument_type} but the interface expects {interface_argument_type}"
+                )
+            }
+            ValidationError::RequiredExtraArgument {
+                coordinate,
+                interface_coordinate,
+                ..
+            } => {
+                write!(
+                    f,
+                    "{coordinate} must be optional as it is not an argument of {interface_coordinate}"
+                )
+            }
+            ValidationError::InvalidOneOfField { coordinate, .. } => {
+                write!(
+                    f,
+                    "{coordinate} must be nullable and have no default, as its type is a @oneOf input object"
+                )
+            }
+            ValidationError::InputObjectCycle { name, .. } => {
+                write!(
+                    f,
+                    "the input object {name} references itself through non-nullable fields"
+                )
+            }
+        }
+    }
+}
@@ -0,0 +1,753 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    ExecutableDocument, Span, Value,
+    common::OperationType,
+    executable::{
+        Argument, Directive, FragmentDefinition, Iter, OperationDefinition, Selection,
+        VariableDefinition,
+    },
+    type_system::{DirectiveLocation, InputValueDefinition},
+};
+
+use super::{
+    ValidationError,
+    index::{SchemaIndex, TypeKind, TypeRef},
+};
+
+pub(super) struct ExecutableValidator<'a> {
+    schema: SchemaIndex<'a>,
+    document: &'a ExecutableDocument,
+    fragments: HashMap<&'a str, FragmentDefinition<'a>>,
+    errors: Vec<ValidationError>,
+}
+
+/// Things found while validating the selection set of an operation or fragment
+#[derive(Default)]
+struct Scope<'a> {
+    variable_usages: Vec<VariableUsage<'a>>,
+    fragment_spreads: Vec<&'a str>,
+}
+
+struct VariableUsage<'a> {
+    name: &'a str,
+    expected_type: TypeRef<'a>,
+    /// Whether the argument or input field the variable is used for has a default
+    has_location_default: bool,
+    span: Span,
+}
+
+impl<'a> ExecutableValidator<'a> {
+    pub fn new(schema: SchemaIndex<'a>, document: &'a ExecutableDocument) -> Self {
+        ExecutableValidator {
+            schema,
+            document,
+            fragments: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn validate(mut self) -> Vec<ValidationError> {
+        let mut fragment_scopes = HashMap::new();
+        for fragment in self.document.fragments() {
+            if self.fragments.contains_key(fragment.name()) {
+                self.errors.push(ValidationError::DuplicateFragmentName {
+                    name: fragment.name().to_string(),
+                    span: fragment.name_span(),
+                });
+                continue;
+            }
+            self.fragments.insert(fragment.name(), fragment);
+        }
+
+        let fragments = self.fragments.values().copied().collect::<Vec<_>>();
+        for fragment in fragments {
+            let scope = self.validate_fragment(fragment);
+            fragment_scopes.insert(fragment.name(), scope);
+        }
+
+        self.check_fragment_cycles(&fragment_scopes);
+
+        let operation_count = self.document.operations().count();
+        let mut operation_names = HashSet::new();
+        let mut used_fragments = HashSet::new();
+        for operation in self.document.operations() {
+            match operation.name() {
+                Some(name) if !operation_names.insert(name) => {
+                    self.errors.push(ValidationError::DuplicateOperationName {
+                        name: name.to_string(),
+                        span: operation
+                            .name_span()
+                            .unwrap_or_else(|| operation_span(operation)),
+                    });
+                }
+                None if operation_count > 1 => {
+                    self.errors
+                        .push(ValidationError::AnonymousOperationNotAlone {
+                            span: operation_span(operation),
+                        });
+                }
+                _ => {}
+            }
+
+            self.validate_operation(operation, &fragment_scopes, &mut used_fragments);
+        }
+
+        let mut unused_fragments = self
+            .fragments
+            .values()
+            .filter(|fragment| !used_fragments.contains(fragment.name()))
+            .collect::<Vec<_>>();
+        unused_fragments.sort_by_key(|fragment| fragment.name_span().start);
+        for fragment in unused_fragments {
+            self.errors.push(ValidationError::UnusedFragment {
+                name: fragment.name().to_string(),
+                span: fragment.name_span(),
+            });
+        }
+
+        let mut errors = self.errors;
+        errors.sort_by_key(|error| error.span().start);
+        errors
+    }
+
+    fn validate_fragment(&mut self, fragment: FragmentDefinition<'a>) -> Scope<'a> {
+        let mut scope = Scope::default();
+
+        self.validate_directives(
+            fragment.directives(),
+            DirectiveLocation::FragmentDefinition,
+            &mut scope,
+        );
+
+        if self.check_type_condition(fragment.type_condition(), fragment.type_condition_span()) {
+            self.validate_selection_set(
+                fragment.type_condition(),
+                fragment.selection_set(),
+                &mut scope,
+            );
+        }
+
+        scope
+    }
+
+    fn validate_operation(
+        &mut self,
+        operation: OperationDefinition<'a>,
+        fragment_scopes: &HashMap<&'a str, Scope<'a>>,
+        used_fragments: &mut HashSet<&'a str>,
+    ) {
+        let mut scope = Scope::default();
+
+        let location = match operation.operation_type() {
+            OperationType::Query => DirectiveLocation::Query,
+            OperationType::Mutation => DirectiveLocation::Mutation,
+            OperationType::Subscription => DirectiveLocation::Subscription,
+        };
+        self.validate_directives(operation.directives(), location, &mut scope);
+
+        let variables = self.validate_variable_definitions(operation);
+
+        match self.schema.root_type(operation.operation_type()) {
+            Some(root_type) => {
+                self.validate_selection_set(root_type, operation.selection_set(), &mut scope)
+            }
+            None => self.errors.push(ValidationError::UnsupportedOperation {
+                operation_type: operation.operation_type(),
+                span: operation_span(operation),
+            }),
+        }
+
+        if operation.operation_type() == OperationType::Subscription {
+            self.check_subscription_root(operation);
+        }
+
+        // Gather up everything used by this operation, including
+        // anything used inside the fragments it spreads
+        let mut usages = scope.variable_usages.iter().collect::<Vec<_>>();
+        let mut to_visit = scope.fragment_spreads.clone();
+        let mut visited = HashSet::new();
+        while let Some(name) = to_visit.pop() {
+            if !visited.insert(name) {
+                continue;
+            }
+            if let Some(fragment_scope) = fragment_scopes.get(name) {
+                usages.extend(fragment_scope.variable_usages.iter());
+                to_visit.extend(fragment_scope.fragment_spreads.iter().copied());
+            }
+        }
+        used_fragments.extend(visited);
+
+        let mut used_variables = HashSet::new();
+        for usage in usages {
+            used_variables.insert(usage.name);
+            let Some(variable) = variables.get(usage.name) else {
+                self.errors.push(ValidationError::UndefinedVariable {
+                    name: usage.name.to_string(),
+                    operation: operation.name().map(str::to_string),
+                    span: usage.span,
+                });
+                continue;
+            };
+
+            if !variable_usage_allowed(*variable, usage) {
+                self.errors.push(ValidationError::VariableTypeMismatch {
+                    name: usage.name.to_string(),
+                    variable_type: variable.ty().to_string(),
+                    expected_type: usage.expected_type.to_string(),
+                    span: usage.span,
+                });
+            }
+        }
+
+        for variable in operation.variable_definitions() {
+            if !used_variables.contains(variable.name()) {
+                self.errors.push(ValidationError::UnusedVariable {
+                    name: variable.name().to_string(),
+                    span: variable.name_span(),
+                });
+            }
+        }
+    }
+
+    fn validate_variable_definitions(
+        &mut self,
+        operation: OperationDefinition<'a>,
+    ) -> HashMap<&'a str, VariableDefinition<'a>> {
+        let mut variables = HashMap::new();
+
+        for variable in operation.variable_definitions() {
+            if variables.insert(variable.name(), variable).is_some() {
+                self.errors.push(ValidationError::DuplicateVariable {
+                    name: variable.name().to_string(),
+                    span: variable.name_span(),
+                });
+            }
+
+            // Variables can't be used inside variable definitions, so this
+            // scope is just thrown away
+            let mut scope = Scope::default();
+            self.validate_directives(
+                variable.directives(),
+                DirectiveLocation::VariableDefinition,
+                &mut scope,
+            );
+
+            let ty = TypeRef::from(variable.ty());
+            match self.schema.kind(ty.name) {
+                None => self.errors.push(ValidationError::UnknownType {
+                    name: ty.name.to_string(),
+                    span: variable.ty().name_span(),
+                }),
+                Some(kind) if !kind.is_input() => {
+                    self.errors.push(ValidationError::NonInputVariableType {
+                        name: variable.name().to_string(),
+                        type_name: ty.name.to_string(),
+                        span: variable.ty().span(),
+                    })
+                }
+                Some(_) => {
+                    if let Some(default) = variable.default_value() {
+                        self.validate_value(Value::from(default), &ty, false, &mut scope);
+                    }
+                }
+            }
+        }
+
+        variables
+    }
+
+    fn validate_selection_set(
+        &mut self,
+        parent_type: &'a str,
+        selections: Iter<'a, Selection<'a>>,
+        scope: &mut Scope<'a>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    self.validate_directives(field.directives(), DirectiveLocation::Field, scope);
+
+                    let Some(definition) = self.schema.field(parent_type, field.name()) else {
+                        self.errors.push(ValidationError::UnknownField {
+                            type_name: parent_type.to_string(),
+                            field_name: field.name().to_string(),
+                            span: field.name_span(),
+                        });
+                        continue;
+                    };
+
+                    self.validate_arguments(
+                        &format!("{parent_type}.{}", field.name()),
+                        field.arguments(),
+                        definition.arguments().collect(),
+                        field.name_span(),
+                        scope,
+                    );
+
+                    let field_type = definition.ty().name();
+                    match (self.schema.kind(field_type), field.selection_set_span()) {
+                        (Some(kind), None) if kind.is_composite() => {
+                            self.errors.push(ValidationError::MissingSelectionSet {
+                                field_name: field.name().to_string(),
+                                type_name: definition.ty().to_string(),
+                                span: field.name_span(),
+                            });
+                        }
+                        (Some(kind), Some(_)) if kind.is_composite() => {
+                            self.validate_selection_set(field_type, field.selection_set(), scope);
+                        }
+                        (Some(_), Some(span)) => {
+                            self.errors.push(ValidationError::SelectionOnLeafField {
+                                field_name: field.name().to_string(),
+                                type_name: definition.ty().to_string(),
+                                span,
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                Selection::InlineFragment(fragment) => {
+                    self.validate_directives(
+                        fragment.directives(),
+                        DirectiveLocation::InlineFragment,
+                        scope,
+                    );
+
+                    let type_condition =
+                        match (fragment.type_condition(), fragment.type_condition_span()) {
+                            (Some(type_condition), Some(span)) => {
+                                if !self.check_type_condition(type_condition, span) {
+                                    continue;
+                                }
+                                self.check_spread_possible(type_condition, parent_type, span);
+                                type_condition
+                            }
+                            _ => parent_type,
+                        };
+
+                    self.validate_selection_set(type_condition, fragment.selection_set(), scope);
+                }
+                Selection::FragmentSpread(spread) => {
+                    self.validate_directives(
+                        spread.directives(),
+                        DirectiveLocation::FragmentSpread,
+                        scope,
+                    );
+
+                    scope.fragment_spreads.push(spread.fragment_name());
+
+                    let Some(fragment) = self.fragments.get(spread.fragment_name()).copied() else {
+                        self.errors.push(ValidationError::UnknownFragment {
+                            name: spread.fragment_name().to_string(),
+                            span: spread.fragment_name_span(),
+                        });
+                        continue;
+                    };
+
+                    if self
+                        .schema
+                        .kind(fragment.type_condition())
+                        .is_some_and(TypeKind::is_composite)
+                    {
+                        self.check_spread_possible(
+                            fragment.type_condition(),
+                            parent_type,
+                            spread.fragment_name_span(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks that a type condition refers to a composite type, returning
+    /// true if so
+    fn check_type_condition(&mut self, type_name: &'a str, span: Span) -> bool {
+        match self.schema.kind(type_name) {
+            Some(kind) if kind.is_composite() => true,
+            Some(_) => {
+                self.errors
+                    .push(ValidationError::NonCompositeTypeCondition {
+                        type_name: type_name.to_string(),
+                        span,
+                    });
+                false
+            }
+            None => {
+                self.errors.push(ValidationError::UnknownType {
+                    name: type_name.to_string(),
+                    span,
+                });
+                false
+            }
+        }
+    }
+
+    fn check_spread_possible(&mut self, type_name: &'a str, parent_type: &'a str, span: Span) {
+        let possible_types = self.schema.possible_types(type_name);
+        let parent_possible_types = self.schema.possible_types(parent_type);
+
+        if possible_types.is_disjoint(&parent_possible_types) {
+            self.errors.push(ValidationError::ImpossibleFragmentSpread {
+                type_name: type_name.to_string(),
+                parent_type: parent_type.to_string(),
+                span,
+            });
+        }
+    }
+
+    fn check_fragment_cycles(&mut self, fragment_scopes: &HashMap<&'a str, Scope<'a>>) {
+        let mut fragments = self.fragments.values().copied().collect::<Vec<_>>();
+        fragments.sort_by_key(|fragment| fragment.name_span().start);
+
+        for fragment in fragments {
+            let mut to_visit = fragment_scopes
+                .get(fragment.name())
+                .map(|scope| scope.fragment_spreads.clone())
+                .unwrap_or_default();
+            let mut visited = HashSet::new();
+
+            while let Some(name) = to_visit.pop() {
+                if name == fragment.name() {
+                    self.errors.push(ValidationError::FragmentCycle {
+                        name: fragment.name().to_string(),
+                        span: fragment.name_span(),
+                    });
+                    break;
+                }
+                if !visited.insert(name) {
+                    continue;
+                }
+                if let Some(scope) = fragment_scopes.get(name) {
+                    to_visit.extend(scope.fragment_spreads.iter().copied());
+                }
+            }
+        }
+    }
+
+    fn check_subscription_root(&mut self, operation: OperationDefinition<'a>) {
+        let mut response_keys = Vec::new();
+        let mut visited = HashSet::new();
+        self.collect_root_fields(operation.selection_set(), &mut response_keys, &mut visited);
+
+        let mut seen = HashSet::new();
+        response_keys.retain(|(key, _, _)| seen.insert(*key));
+
+        if response_keys.len() > 1 {
+            self.errors
+                .push(ValidationError::MultipleSubscriptionRootFields {
+                    span: response_keys[1].2,
+                });
+        }
+
+        for (_, name, span) in response_keys {
+            if name.starts_with("__") {
+                self.errors
+                    .push(ValidationError::IntrospectionSubscriptionRootField { span });
+            }
+        }
+    }
+
+    fn collect_root_fields(
+        &self,
+        selections: Iter<'a, Selection<'a>>,
+        response_keys: &mut Vec<(&'a str, &'a str, Span)>,
+        visited: &mut HashSet<&'a str>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => response_keys.push((
+                    field.alias().unwrap_or(field.name()),
+                    field.name(),
+                    field.name_span(),
+                )),
+                Selection::InlineFragment(fragment) => {
+                    self.collect_root_fields(fragment.selection_set(), response_keys, visited)
+                }
+                Selection::FragmentSpread(spread) => {
+                    if !visited.insert(spread.fragment_name()) {
+                        continue;
+                    }
+                    if let Some(fragment) = self.fragments.get(spread.fragment_name()) {
+                        self.collect_root_fields(fragment.selection_set(), response_keys, visited)
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_directives(
+        &mut self,
+        directives: Iter<'a, Directive<'a>>,
+        location: DirectiveLocation,
+        scope: &mut Scope<'a>,
+    ) {
+        let mut seen = HashSet::new();
+        for directive in directives {
+            let Some(definition) = self.schema.directive(directive.name()) else {
+                self.errors.push(ValidationError::UnknownDirective {
+                    name: directive.name().to_string(),
+                    span: directive.name_span(),
+                });
+                continue;
+            };
+
+            if !definition.locations().any(|allowed| allowed == location) {
+                self.errors.push(ValidationError::MisplacedDirective {
+                    name: directive.name().to_string(),
+                    location,
+                    span: directive.name_span(),
+                });
+            }
+
+            if !seen.insert(directive.name()) && !definition.is_repeatable() {
+                self.errors.push(ValidationError::RepeatedDirective {
+                    name: directive.name().to_string(),
+                    span: directive.name_span(),
+                });
+            }
+
+            self.validate_arguments(
+                &format!("@{}", directive.name()),
+                directive.arguments(),
+                definition.arguments().collect(),
+                directive.name_span(),
+                scope,
+            );
+        }
+    }
+
+    fn validate_arguments(
+        &mut self,
+        coordinate: &str,
+        arguments: Iter<'a, Argument<'a>>,
+        definitions: Vec<InputValueDefinition<'a>>,
+        span: Span,
+        scope: &mut Scope<'a>,
+    ) {
+        let mut provided = HashSet::new();
+        for argument in arguments {
+            if !provided.insert(argument.name()) {
+                self.errors.push(ValidationError::DuplicateArgument {
+                    name: argument.name().to_string(),
+                    span: argument.name_span(),
+                });
+                continue;
+            }
+
+            let Some(definition) = definitions
+                .iter()
+                .find(|definition| definition.name() == argument.name())
+            else {
+                self.errors.push(ValidationError::UnknownArgument {
+                    coordinate: coordinate.to_string(),
+                    name: argument.name().to_string(),
+                    span: argument.name_span(),
+                });
+                continue;
+            };
+
+            self.validate_value(
+                argument.value(),
+                &TypeRef::from(definition.ty()),
+                definition.default_value().is_some(),
+                scope,
+            );
+        }
+
+        for definition in definitions {
+            if definition.ty().is_non_null()
+                && definition.default_value().is_none()
+                && !provided.contains(definition.name())
+            {
+                self.errors.push(ValidationError::MissingArgument {
+                    coordinate: coordinate.to_string(),
+                    name: definition.name().to_string(),
+                    span,
+                });
+            }
+        }
+    }
+
+    fn validate_value(
+        &mut self,
+        value: Value<'a>,
+        expected_type: &TypeRef<'a>,
+        has_location_default: bool,
+        scope: &mut Scope<'a>,
+    ) {
+        let invalid_value = |expected_type: &TypeRef<'_>| ValidationError::InvalidValue {
+            expected_type: expected_type.to_string(),
+            span: value.span(),
+        };
+
+        match value {
+            Value::Variable(variable) => {
+                scope.variable_usages.push(VariableUsage {
+                    name: variable.name(),
+                    expected_type: expected_type.clone(),
+                    has_location_default,
+                    span: variable.span(),
+                });
+                return;
+            }
+            Value::Null(_) if expected_type.is_non_null() => {
+                self.errors.push(invalid_value(expected_type));
+                return;
+            }
+            Value::Null(_) => return,
+            _ => {}
+        }
+
+        if expected_type.is_non_null() {
+            self.validate_value(value, &expected_type.unwrap_one(), false, scope);
+            return;
+        }
+
+        if expected_type.is_list() {
+            let item_type = expected_type.unwrap_one();
+            match value {
+                Value::List(list) => {
+                    for item in list.items() {
+                        self.validate_value(item, &item_type, false, scope);
+                    }
+                }
+                value => self.validate_value(value, &item_type, false, scope),
+            }
+            return;
+        }
+
+        let valid = match self.schema.kind(expected_type.name) {
+            Some(TypeKind::Scalar) => match (expected_type.name, value) {
+                ("Int", Value::Int(int)) => i32::try_from(int.as_i64()).is_ok(),
+                ("Float", Value::Int(_) | Value::Float(_)) => true,
+                ("String", Value::String(_)) => true,
+                ("Boolean", Value::Boolean(_)) => true,
+                ("ID", Value::String(_) | Value::Int(_)) => true,
+                ("Int" | "Float" | "String" | "Boolean" | "ID", _) => false,
+                _ => true,
+            },
+            Some(TypeKind::Enum) => match value {
+                Value::Enum(enum_value) => self
+                    .schema
+                    .enum_values(expected_type.name)
+                    .any(|definition| definition.value() == enum_value.name()),
+                _ => false,
+            },
+            Some(TypeKind::InputObject) => match value {
+                Value::Object(object) => {
+                    self.validate_input_object(expected_type.name, object, scope);
+                    true
+                }
+                _ => false,
+            },
+            _ => true,
+        };
+
+        if !valid {
+            self.errors.push(invalid_value(expected_type));
+        }
+    }
+
+    fn validate_input_object(
+        &mut self,
+        type_name: &'a str,
+        object: crate::values::Object<'a>,
+        scope: &mut Scope<'a>,
+    ) {
+        let definitions = self.schema.input_fields(type_name).collect::<Vec<_>>();
+
+        let mut provided = HashSet::new();
+        for field in object.fields() {
+            if !provided.insert(field.name()) {
+                self.errors.push(ValidationError::DuplicateInputField {
+                    name: field.name().to_string(),
+                    span: field.name_span(),
+                });
+                continue;
+            }
+
+            let Some(definition) = definitions
+                .iter()
+                .find(|definition| definition.name() == field.name())
+            else {
+                self.errors.push(ValidationError::UnknownInputField {
+                    type_name: type_name.to_string(),
+                    name: field.name().to_string(),
+                    span: field.name_span(),
+                });
+                continue;
+            };
+
+            self.validate_value(
+                field.value(),
+                &TypeRef::from(definition.ty()),
+                definition.default_value().is_some(),
+                scope,
+            );
+        }
+
+        for definition in definitions {
+            if definition.ty().is_non_null()
+                && definition.default_value().is_none()
+                && !provided.contains(definition.name())
+            {
+                self.errors.push(ValidationError::MissingInputField {
+                    type_name: type_name.to_string(),
+                    name: definition.name().to_string(),
+                    span: object.span(),
+                });
+            }
+        }
+    }
+}
+
+/// The span we use for errors that apply to a whole operation
+fn operation_span(operation: OperationDefinition<'_>) -> Span {
+    operation
+        .name_span()
+        .or(operation.operation_type_span())
+        .unwrap_or(operation.selection_set_span())
+}
+
+/// Implements the `IsVariableUsageAllowed` algorithm from the spec
+fn variable_usage_allowed(variable: VariableDefinition<'_>, usage: &VariableUsage<'_>) -> bool {
+    let variable_type = TypeRef::from(variable.ty());
+    let location_type = &usage.expected_type;
+
+    if location_type.is_non_null() && !variable_type.is_non_null() {
+        let has_non_null_default = variable
+            .default_value()
+            .is_some_and(|default| !default.is_null());
+        if !has_non_null_default && !usage.has_location_default {
+            return false;
+        }
+        return types_compatible(&variable_type, &location_type.unwrap_one());
+    }
+
+    types_compatible(&variable_type, location_type)
+}
+
+/// Implements the `AreTypesCompatible` algorithm from the spec
+fn types_compatible(variable_type: &TypeRef<'_>, location_type: &TypeRef<'_>) -> bool {
+    if location_type.is_non_null() {
+        if !variable_type.is_non_null() {
+            return false;
+        }
+        return types_compatible(&variable_type.unwrap_one(), &location_type.unwrap_one());
+    }
+    if variable_type.is_non_null() {
+        return types_compatible(&variable_type.unwrap_one(), location_type);
+    }
+    if location_type.is_list() {
+        if !variable_type.is_list() {
+            return false;
+        }
+        return types_compatible(&variable_type.unwrap_one(), &location_type.unwrap_one());
+    }
+    if variable_type.is_list() {
+        return false;
+    }
+    variable_type.name == location_type.name
+}
@@ -0,0 +1,317 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::LazyLock,
+};
+
+use crate::{
+    TypeSystemDocument,
+    common::{OperationType, WrappingType},
+    type_system::{
+        Definition, DirectiveDefinition, EnumValueDefinition, FieldDefinition,
+        InputValueDefinition, TypeDefinition,
+    },
+};
+
+/// Definitions that every schema has, whether or not they're in the SDL
+static BUILT_INS: LazyLock<TypeSystemDocument> = LazyLock::new(|| {
+    crate::parse_type_system_document(include_str!("built_ins.graphql"))
+        .expect("the built in definitions to be valid")
+});
+
+/// The type we look up meta fields (`__schema`, `__type` & `__typename`) on
+const META_FIELDS_TYPE: &str = "__MetaFields";
+
+/// A lookup table for the definitions in a `TypeSystemDocument`
+pub(crate) struct SchemaIndex<'a> {
+    types: HashMap<&'a str, Vec<TypeDefinition<'a>>>,
+    directives: HashMap<&'a str, DirectiveDefinition<'a>>,
+    /// The object types that implement each interface
+    implementors: HashMap<&'a str, Vec<&'a str>>,
+    root_types: HashMap<OperationType, &'a str>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TypeKind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+}
+
+impl<'a> SchemaIndex<'a> {
+    pub fn new(schema: &'a TypeSystemDocument) -> Self {
+        let mut index = SchemaIndex {
+            types: HashMap::new(),
+            directives: HashMap::new(),
+            implementors: HashMap::new(),
+            root_types: HashMap::new(),
+        };
+
+        let mut schema_definition_found = false;
+        for definition in schema.definitions() {
+            match definition {
+                Definition::Schema(schema) | Definition::SchemaExtension(schema) => {
+                    schema_definition_found = true;
+                    for root in schema.root_operations() {
+                        index
+                            .root_types
+                            .insert(root.operation_type(), root.named_type());
+                    }
+                }
+                Definition::Type(ty) | Definition::TypeExtension(ty) => {
+                    index.types.entry(ty.name()).or_default().push(ty);
+                }
+                Definition::Directive(directive) => {
+                    index.directives.insert(directive.name(), directive);
+                }
+            }
+        }
+
+        for definition in BUILT_INS.definitions() {
+            match definition {
+                Definition::Type(ty) => {
+                    index.types.entry(ty.name()).or_insert_with(|| vec![ty]);
+                }
+                Definition::Directive(directive) => {
+                    index
+                        .directives
+                        .entry(directive.name())
+                        .or_insert(directive);
+                }
+                _ => {}
+            }
+        }
+
+        if !schema_definition_found {
+            for (operation_type, name) in [
+                (OperationType::Query, "Query"),
+                (OperationType::Mutation, "Mutation"),
+                (OperationType::Subscription, "Subscription"),
+            ] {
+                if index.types.contains_key(name) {
+                    index.root_types.insert(operation_type, name);
+                }
+            }
+        }
+
+        let mut implementors = HashMap::<&'a str, Vec<&'a str>>::new();
+        for (name, definitions) in &index.types {
+            for definition in definitions {
+                if let TypeDefinition::Object(object) = definition {
+                    for interface in object.implements_interfaces() {
+                        implementors.entry(interface).or_default().push(name);
+                    }
+                }
+            }
+        }
+        index.implementors = implementors;
+
+        index
+    }
+
+    /// Whether a type is one of the built in types that every schema has
+    pub fn is_built_in(name: &str) -> bool {
+        BUILT_INS.definitions().any(|definition| match definition {
+            Definition::Type(ty) => ty.name() == name,
+            _ => false,
+        })
+    }
+
+    pub fn root_type(&self, operation_type: OperationType) -> Option<&'a str> {
+        self.root_types.get(&operation_type).copied()
+    }
+
+    pub fn kind(&self, name: &str) -> Option<TypeKind> {
+        Some(match self.types.get(name)?.first()? {
+            TypeDefinition::Scalar(_) => TypeKind::Scalar,
+            TypeDefinition::Object(_) => TypeKind::Object,
+            TypeDefinition::Interface(_) => TypeKind::Interface,
+            TypeDefinition::Union(_) => TypeKind::Union,
+            TypeDefinition::Enum(_) => TypeKind::Enum,
+            TypeDefinition::InputObject(_) => TypeKind::InputObject,
+        })
+    }
+
+    pub fn directive(&self, name: &str) -> Option<DirectiveDefinition<'a>> {
+        self.directives.get(name).copied()
+    }
+
+    /// Looks up a field on an object or interface type.
+    ///
+    /// This also handles the meta fields, which are available on every
+    /// composite type (`__typename`) or the query root (`__schema` & `__type`)
+    pub fn field(&self, type_name: &str, field_name: &str) -> Option<FieldDefinition<'a>> {
+        if let Some(field) = self
+            .fields(type_name)
+            .find(|field| field.name() == field_name)
+        {
+            return Some(field);
+        }
+
+        let meta_field_allowed = match field_name {
+            "__typename" => true,
+            "__schema" | "__type" => self.root_type(OperationType::Query) == Some(type_name),
+            _ => false,
+        };
+        if !meta_field_allowed {
+            return None;
+        }
+
+        self.definitions(META_FIELDS_TYPE)
+            .flat_map(|definition| match definition {
+                TypeDefinition::Object(object) => Some(object.fields()),
+                _ => None,
+            })
+            .flatten()
+            .find(|field| field.name() == field_name)
+    }
+
+    /// The fields of an object or interface type, not including any meta fields
+    pub fn fields(&self, type_name: &str) -> impl Iterator<Item = FieldDefinition<'a>> {
+        self.definitions(type_name)
+            .flat_map(|definition| match definition {
+                TypeDefinition::Object(object) => Some(object.fields()),
+                TypeDefinition::Interface(interface) => Some(interface.fields()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// The interfaces an object or interface type declares that it implements
+    pub fn implements_interfaces(&self, type_name: &str) -> impl Iterator<Item = &'a str> {
+        self.definitions(type_name)
+            .flat_map(|definition| match definition {
+                TypeDefinition::Object(object) => {
+                    object.implements_interfaces().collect::<Vec<_>>()
+                }
+                TypeDefinition::Interface(interface) => {
+                    interface.implements_interfaces().collect::<Vec<_>>()
+                }
+                _ => vec![],
+            })
+    }
+
+    pub fn input_fields(&self, type_name: &str) -> impl Iterator<Item = InputValueDefinition<'a>> {
+        self.definitions(type_name)
+            .flat_map(|definition| match definition {
+                TypeDefinition::InputObject(object) => Some(object.fields()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    pub fn enum_values(&self, type_name: &str) -> impl Iterator<Item = EnumValueDefinition<'a>> {
+        self.definitions(type_name)
+            .flat_map(|definition| match definition {
+                TypeDefinition::Enum(definition) => Some(definition.values()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// The object types that a value of the given type could be
+    pub fn possible_types(&self, type_name: &'a str) -> HashSet<&'a str> {
+        match self.kind(type_name) {
+            Some(TypeKind::Object) => HashSet::from([type_name]),
+            Some(TypeKind::Interface) => self
+                .implementors
+                .get(type_name)
+                .map(|implementors| implementors.iter().copied().collect())
+                .unwrap_or_default(),
+            Some(TypeKind::Union) => self
+                .definitions(type_name)
+                .flat_map(|definition| match definition {
+                    TypeDefinition::Union(union) => Some(union.members()),
+                    _ => None,
+                })
+                .flatten()
+                .map(|member| member.name())
+                .collect(),
+            _ => HashSet::new(),
+        }
+    }
+
+    pub fn definitions(&self, type_name: &str) -> impl Iterator<Item = TypeDefinition<'a>> + '_ {
+        self.types.get(type_name).into_iter().flatten().copied()
+    }
+}
+
+impl TypeKind {
+    pub fn is_composite(self) -> bool {
+        matches!(
+            self,
+            TypeKind::Object | TypeKind::Interface | TypeKind::Union
+        )
+    }
+
+    pub fn is_input(self) -> bool {
+        matches!(
+            self,
+            TypeKind::Scalar | TypeKind::Enum | TypeKind::InputObject
+        )
+    }
+}
+
+/// A type reference that can come from either a schema or an executable document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TypeRef<'a> {
+    pub name: &'a str,
+    /// The wrappers of the type, from outermost to innermost
+    pub wrappers: Vec<WrappingType>,
+}
+
+impl<'a> TypeRef<'a> {
+    pub fn is_non_null(&self) -> bool {
+        self.wrappers.first() == Some(&WrappingType::NonNull)
+    }
+
+    pub fn is_list(&self) -> bool {
+        self.wrappers.first() == Some(&WrappingType::List)
+    }
+
+    /// The type with its outermost wrapper removed
+    pub fn unwrap_one(&self) -> TypeRef<'a> {
+        TypeRef {
+            name: self.name,
+            wrappers: self.wrappers.iter().skip(1).copied().collect(),
+        }
+    }
+}
+
+impl<'a> From<crate::type_system::Type<'a>> for TypeRef<'a> {
+    fn from(ty: crate::type_system::Type<'a>) -> Self {
+        TypeRef {
+            name: ty.name(),
+            wrappers: ty.wrappers().collect(),
+        }
+    }
+}
+
+impl<'a> From<crate::executable::Type<'a>> for TypeRef<'a> {
+    fn from(ty: crate::executable::Type<'a>) -> Self {
+        TypeRef {
+            name: ty.name(),
+            wrappers: ty.wrappers().collect(),
+        }
+    }
+}
+
+impl std::fmt::Display for TypeRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for wrapper in &self.wrappers {
+            if let WrappingType::List = wrapper {
+                write!(f, "[")?;
+            }
+        }
+        write!(f, "{}", self.name)?;
+        for wrapper in self.wrappers.iter().rev() {
+            match wrapper {
+                WrappingType::NonNull => write!(f, "!")?,
+                WrappingType::List => write!(f, "]")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,80 @@
+//! Validation of GraphQL documents, following the rules in the [GraphQL
+//! specification][spec].
+//!
+//! [spec]: https://spec.graphql.org/October2021/#sec-Validation
+
+mod error;
+mod executable;
+mod index;
+mod type_system;
+
+#[cfg(feature = "report")]
+mod report;
+
+pub use error::ValidationError;
+
+use crate::{ExecutableDocument, TypeSystemDocument};
+
+/// Validates an `ExecutableDocument` against the schema in a `TypeSystemDocument`
+///
+/// This checks that every field, argument, fragment, variable & directive in
+/// the document is valid for the schema, returning every problem found.
+///
+/// ```rust
+/// use cynic_parser::{parse_executable_document, parse_type_system_document};
+///
+/// let schema = parse_type_system_document("type Query { hello: String }").unwrap();
+/// let document = parse_executable_document("query { goodbye }").unwrap();
+///
+/// let errors = cynic_parser::validation::validate_executable_document(&document, &schema)
+///     .unwrap_err();
+///
+/// assert_eq!(errors[0].to_string(), "no field named goodbye on the type Query");
+/// ```
+pub fn validate_executable_document(
+    document: &ExecutableDocument,
+    schema: &TypeSystemDocument,
+) -> Result<(), Vec<ValidationError>> {
+    let errors =
+        executable::ExecutableValidator::new(index::SchemaIndex::new(schema), document).validate();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(())
+}
+
+/// Validates that the schema in a `TypeSystemDocument` is well formed
+///
+/// This checks the rules the GraphQL spec places on type definitions - e.g.
+/// that objects correctly implement their interfaces, that union members are
+/// objects and that input objects only contain input types.  Directives
+/// applied in the document are not checked, as schemas often use directives
+/// that they don't define.
+///
+/// This is also available as [`TypeSystemDocument::validate`].
+///
+/// ```rust
+/// use cynic_parser::parse_type_system_document;
+///
+/// let schema = parse_type_system_document("type Query { pet: Pet } union Pet = String").unwrap();
+///
+/// let errors = cynic_parser::validation::validate_type_system_document(&schema).unwrap_err();
+///
+/// assert_eq!(
+///     errors[0].to_string(),
+///     "the union Pet can only contain object types, but String is not an object"
+/// );
+/// ```
+pub fn validate_type_system_document(
+    document: &TypeSystemDocument,
+) -> Result<(), Vec<ValidationError>> {
+    let errors = type_system::TypeSystemValidator::new(document).validate();
+
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+use ariadne::{Config, Label, ReportKind};
+
+use crate::Report;
+
+use super::ValidationError;
+
+impl ValidationError {
+    pub fn to_report<'a>(&self, document: &'a str) -> Report<'a> {
+        let span = self.span();
+
+        let inner = ariadne::Report::build(ReportKind::Error, (), span.start)
+            .with_message(self.to_string())
+            .with_config(Config::default().with_color(false))
+            .with_label(Label::new(span.start..span.end).with_message(self.label()))
+            .finish();
+
+        Report::new(inner, document)
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            ValidationError::UnknownType { .. } => "this type is not in the schema",
+            ValidationError::UnknownField { .. } => "this field is not in the schema",
+            ValidationError::UnknownArgument { .. } => "this argument is not in the schema",
+            ValidationError::DuplicateArgument { .. }
+            | ValidationError::DuplicateInputField { .. } => "this was already provided",
+            ValidationError::MissingArgument { .. } => "an argument is missing here",
+            ValidationError::InvalidValue { .. } => "this value has the wrong type",
+            ValidationError::UnknownInputField { .. } => "this field is not in the schema",
+            ValidationError::MissingInputField { .. } => "a field is missing here",
+            ValidationError::UnknownDirective { .. } => "this directive is not in the schema",
+            ValidationError::MisplacedDirective { .. } => "this directive is not allowed here",
+            ValidationError::RepeatedDirective { .. } => "this directive was already used",
+            ValidationError::DuplicateOperationName { .. }
+            | ValidationError::DuplicateFragmentName { .. }
+            | ValidationError::DuplicateVariable { .. } => "this name was already used",
+            ValidationError::AnonymousOperationNotAlone { .. } => "this operation has no name",
+            ValidationError::UnsupportedOperation { .. } => "this operation is not supported",
+            ValidationError::MultipleSubscriptionRootFields { .. } => "this is an extra root field",
+            ValidationError::IntrospectionSubscriptionRootField { .. } => {
+                "this field is not allowed here"
+            }
+            ValidationError::UnknownFragment { .. } => "this fragment is not defined",
+            ValidationError::UnusedFragment { .. } => "this fragment is not used",
+            ValidationError::FragmentCycle { .. } => "this fragment spreads itself",
+            ValidationError::NonCompositeTypeCondition { .. } => "this type can't have fragments",
+            ValidationError::ImpossibleFragmentSpread { .. } => "this fragment can never match",
+            ValidationError::SelectionOnLeafField { .. } => "this selection set is not allowed",
+            ValidationError::MissingSelectionSet { .. } => "this field needs a selection set",
+            ValidationError::NonInputVariableType { .. } => "this is not an input type",
+            ValidationError::UndefinedVariable { .. } => "this variable is not defined",
+            ValidationError::UnusedVariable { .. } => "this variable is not used",
+            ValidationError::VariableTypeMismatch { .. } => "this variable has the wrong type",
+            ValidationError::DuplicateTypeDefinition { .. }
+            | ValidationError::DuplicateDirectiveDefinition { .. }
+            | ValidationError::DuplicateField { .. }
+            | ValidationError::DuplicateArgumentDefinition { .. }
+            | ValidationError::DuplicateEnumValue { .. } => "this name was already used",
+            ValidationError::DuplicateSchemaDefinition { .. }
+            | ValidationError::DuplicateRootOperation { .. }
+            | ValidationError::DuplicateUnionMember { .. }
+            | ValidationError::DuplicateImplementedInterface { .. } => "this was already defined",
+            ValidationError::MissingQueryRoot { .. } => "this needs a query root type",
+            ValidationError::NonObjectRootType { .. } => "this is not an object",
+            ValidationError::ExtensionOfUnknownType { .. } => "this type is not defined",
+            ValidationError::ExtensionKindMismatch { .. } => "this extension has the wrong kind",
+            ValidationError::ReservedName { .. } => "this name is reserved",
+            ValidationError::EmptyFields { .. } => "this type has no fields",
+            ValidationError::EmptyEnum { .. } => "this enum has no values",
+            ValidationError::EmptyUnion { .. } => "this union has no members",
+            ValidationError::NonOutputType { .. } => "this is not an output type",
+            ValidationError::NonInputType { .. } => "this is not an input type",
+            ValidationError::NonObjectUnionMember { .. } => "this is not an object",
+            ValidationError::ImplementsNonInterface { .. } => "this is not an interface",
+            ValidationError::InterfaceImplementsItself { .. } => "this interface implements itself",
+            ValidationError::MissingTransitiveInterface { .. } => "an interface is missing here",
+            ValidationError::MissingInterfaceField { .. } => "a field is missing here",
+            ValidationError::InterfaceFieldTypeMismatch { .. }
+            | ValidationError::InterfaceArgumentTypeMismatch { .. } => {
+                "this type doesn't match the interface"
+            }
+            ValidationError::MissingInterfaceArgument { .. } => "an argument is missing here",
+            ValidationError::RequiredExtraArgument { .. } => "this argument must be optional",
+            ValidationError::InvalidOneOfField { .. } => "this field must be optional",
+            ValidationError::InputObjectCycle { .. } => "this input object references itself",
+        }
+    }
+}
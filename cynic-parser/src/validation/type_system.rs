@@ -0,0 +1,616 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    Span, TypeSystemDocument,
+    common::{OperationType, WrappingType},
+    type_system::{
+        Definition, DirectiveDefinition, FieldDefinition, InputValueDefinition, SchemaDefinition,
+        TypeDefinition,
+    },
+};
+
+use super::{
+    ValidationError,
+    index::{SchemaIndex, TypeKind, TypeRef},
+};
+
+pub(super) struct TypeSystemValidator<'a> {
+    schema: SchemaIndex<'a>,
+    document: &'a TypeSystemDocument,
+    errors: Vec<ValidationError>,
+}
+
+impl<'a> TypeSystemValidator<'a> {
+    pub fn new(document: &'a TypeSystemDocument) -> Self {
+        TypeSystemValidator {
+            schema: SchemaIndex::new(document),
+            document,
+            errors: Vec::new(),
+        }
+    }
+
+    pub fn validate(mut self) -> Vec<ValidationError> {
+        let mut schema_definitions = Vec::new();
+        let mut type_definitions = HashMap::new();
+        let mut directive_names = HashSet::new();
+
+        // The definitions of each type in document order, with extensions
+        // of the wrong kind filtered out
+        let mut types = Vec::<(&'a str, Vec<TypeDefinition<'a>>)>::new();
+        let mut extensions = Vec::new();
+
+        for definition in self.document.definitions() {
+            match definition {
+                Definition::Schema(schema) => {
+                    if !schema_definitions.is_empty() {
+                        self.errors
+                            .push(ValidationError::DuplicateSchemaDefinition {
+                                span: schema.span(),
+                            });
+                        continue;
+                    }
+                    schema_definitions.push(schema);
+                }
+                Definition::SchemaExtension(schema) => schema_definitions.push(schema),
+                Definition::Type(ty) => {
+                    self.check_name(ty.name(), ty.name_span());
+                    if type_definitions.insert(ty.name(), ty).is_some() {
+                        self.errors.push(ValidationError::DuplicateTypeDefinition {
+                            name: ty.name().to_string(),
+                            span: ty.name_span(),
+                        });
+                        continue;
+                    }
+                    types.push((ty.name(), vec![ty]));
+                }
+                Definition::TypeExtension(ty) => extensions.push(ty),
+                Definition::Directive(directive) => {
+                    self.check_name(directive.name(), directive.name_span());
+                    if !directive_names.insert(directive.name()) {
+                        self.errors
+                            .push(ValidationError::DuplicateDirectiveDefinition {
+                                name: directive.name().to_string(),
+                                span: directive.name_span(),
+                            });
+                    }
+                    self.validate_directive_definition(directive);
+                }
+            }
+        }
+
+        for extension in extensions {
+            let Some(definition) = type_definitions.get(extension.name()) else {
+                // Extensions of built in types are fine, but anything else must be defined
+                if !SchemaIndex::is_built_in(extension.name()) {
+                    self.errors.push(ValidationError::ExtensionOfUnknownType {
+                        name: extension.name().to_string(),
+                        span: extension.name_span(),
+                    });
+                }
+                continue;
+            };
+            if std::mem::discriminant(definition) != std::mem::discriminant(&extension) {
+                self.errors.push(ValidationError::ExtensionKindMismatch {
+                    name: extension.name().to_string(),
+                    span: extension.name_span(),
+                });
+                continue;
+            }
+            if let Some((_, definitions)) =
+                types.iter_mut().find(|(name, _)| *name == extension.name())
+            {
+                definitions.push(extension);
+            }
+        }
+
+        for (name, definitions) in &types {
+            self.validate_type(name, definitions);
+        }
+
+        self.check_input_object_cycles(&types);
+        self.validate_schema_definitions(&schema_definitions);
+
+        let mut errors = self.errors;
+        errors.sort_by_key(|error| error.span().start);
+        errors
+    }
+
+    fn validate_schema_definitions(&mut self, definitions: &[SchemaDefinition<'a>]) {
+        let mut root_types = HashSet::new();
+        for root in definitions
+            .iter()
+            .flat_map(|schema| schema.root_operations())
+        {
+            if !root_types.insert(root.operation_type()) {
+                self.errors.push(ValidationError::DuplicateRootOperation {
+                    operation_type: root.operation_type(),
+                    span: root.operation_type_span(),
+                });
+                continue;
+            }
+
+            match self.schema.kind(root.named_type()) {
+                None => self.errors.push(ValidationError::UnknownType {
+                    name: root.named_type().to_string(),
+                    span: root.named_type_span(),
+                }),
+                Some(TypeKind::Object) => {}
+                Some(_) => self.errors.push(ValidationError::NonObjectRootType {
+                    operation_type: root.operation_type(),
+                    name: root.named_type().to_string(),
+                    span: root.named_type_span(),
+                }),
+            }
+        }
+
+        if let Some(schema) = definitions.first() {
+            if !root_types.contains(&OperationType::Query) {
+                self.errors.push(ValidationError::MissingQueryRoot {
+                    span: schema.span(),
+                });
+            }
+        }
+    }
+
+    fn validate_directive_definition(&mut self, directive: DirectiveDefinition<'a>) {
+        let coordinate = format!("@{}", directive.name());
+        self.validate_argument_definitions(&coordinate, directive.arguments().collect());
+    }
+
+    fn validate_type(&mut self, name: &'a str, definitions: &[TypeDefinition<'a>]) {
+        let span = definitions[0].name_span();
+        match definitions[0] {
+            TypeDefinition::Scalar(_) => {}
+            TypeDefinition::Object(_) | TypeDefinition::Interface(_) => {
+                self.validate_fields(name, definitions, span);
+                self.validate_implementations(name, definitions);
+            }
+            TypeDefinition::Union(_) => self.validate_union(name, definitions, span),
+            TypeDefinition::Enum(_) => {
+                let mut values = HashSet::new();
+                let enum_values = definitions.iter().flat_map(|definition| match definition {
+                    TypeDefinition::Enum(definition) => definition.values().collect(),
+                    _ => Vec::new(),
+                });
+                for value in enum_values {
+                    self.check_name(value.value(), value.value_span());
+                    if !values.insert(value.value()) {
+                        self.errors.push(ValidationError::DuplicateEnumValue {
+                            enum_name: name.to_string(),
+                            name: value.value().to_string(),
+                            span: value.value_span(),
+                        });
+                    }
+                }
+                if values.is_empty() {
+                    self.errors.push(ValidationError::EmptyEnum {
+                        name: name.to_string(),
+                        span,
+                    });
+                }
+            }
+            TypeDefinition::InputObject(_) => self.validate_input_object(name, definitions, span),
+        }
+    }
+
+    fn validate_fields(
+        &mut self,
+        type_name: &'a str,
+        definitions: &[TypeDefinition<'a>],
+        span: Span,
+    ) {
+        let fields = definitions.iter().flat_map(|definition| match definition {
+            TypeDefinition::Object(object) => object.fields().collect(),
+            TypeDefinition::Interface(interface) => interface.fields().collect(),
+            _ => Vec::new(),
+        });
+
+        let mut names = HashSet::new();
+        for field in fields {
+            self.check_name(field.name(), field.name_span());
+            if !names.insert(field.name()) {
+                self.errors.push(ValidationError::DuplicateField {
+                    type_name: type_name.to_string(),
+                    name: field.name().to_string(),
+                    span: field.name_span(),
+                });
+                continue;
+            }
+
+            let coordinate = format!("{type_name}.{}", field.name());
+            match self.schema.kind(field.ty().name()) {
+                None => self.errors.push(ValidationError::UnknownType {
+                    name: field.ty().name().to_string(),
+                    span: field.ty().name_span(),
+                }),
+                Some(TypeKind::InputObject) => self.errors.push(ValidationError::NonOutputType {
+                    coordinate: coordinate.clone(),
+                    type_name: field.ty().name().to_string(),
+                    span: field.ty().span(),
+                }),
+                Some(_) => {}
+            }
+
+            self.validate_argument_definitions(&coordinate, field.arguments().collect());
+        }
+
+        if names.is_empty() {
+            self.errors.push(ValidationError::EmptyFields {
+                type_name: type_name.to_string(),
+                span,
+            });
+        }
+    }
+
+    fn validate_argument_definitions(
+        &mut self,
+        coordinate: &str,
+        arguments: Vec<InputValueDefinition<'a>>,
+    ) {
+        let mut names = HashSet::new();
+        for argument in arguments {
+            self.check_name(argument.name(), argument.name_span());
+            if !names.insert(argument.name()) {
+                self.errors
+                    .push(ValidationError::DuplicateArgumentDefinition {
+                        coordinate: coordinate.to_string(),
+                        name: argument.name().to_string(),
+                        span: argument.name_span(),
+                    });
+                continue;
+            }
+            self.check_input_type(&format!("{coordinate}({}:)", argument.name()), argument);
+        }
+    }
+
+    fn validate_implementations(&mut self, type_name: &'a str, definitions: &[TypeDefinition<'a>]) {
+        let implemented = self
+            .schema
+            .implements_interfaces(type_name)
+            .collect::<HashSet<_>>();
+
+        let mut seen = HashSet::new();
+        for definition in definitions {
+            let span = definition.name_span();
+            let interfaces = match definition {
+                TypeDefinition::Object(object) => object.implements_interfaces().collect(),
+                TypeDefinition::Interface(interface) => interface.implements_interfaces().collect(),
+                _ => Vec::new(),
+            };
+
+            for interface in interfaces {
+                if !seen.insert(interface) {
+                    self.errors
+                        .push(ValidationError::DuplicateImplementedInterface {
+                            type_name: type_name.to_string(),
+                            interface: interface.to_string(),
+                            span,
+                        });
+                    continue;
+                }
+                if interface == type_name {
+                    self.errors
+                        .push(ValidationError::InterfaceImplementsItself {
+                            name: type_name.to_string(),
+                            span,
+                        });
+                    continue;
+                }
+                match self.schema.kind(interface) {
+                    None => {
+                        self.errors.push(ValidationError::UnknownType {
+                            name: interface.to_string(),
+                            span,
+                        });
+                        continue;
+                    }
+                    Some(TypeKind::Interface) => {}
+                    Some(_) => {
+                        self.errors.push(ValidationError::ImplementsNonInterface {
+                            type_name: type_name.to_string(),
+                            interface: interface.to_string(),
+                            span,
+                        });
+                        continue;
+                    }
+                }
+
+                for transitive in self.schema.implements_interfaces(interface) {
+                    if transitive != type_name && !implemented.contains(transitive) {
+                        self.errors
+                            .push(ValidationError::MissingTransitiveInterface {
+                                type_name: type_name.to_string(),
+                                interface: transitive.to_string(),
+                                via: interface.to_string(),
+                                span,
+                            });
+                    }
+                }
+
+                self.validate_interface_fields(type_name, interface, span);
+            }
+        }
+    }
+
+    fn validate_interface_fields(&mut self, type_name: &'a str, interface: &'a str, span: Span) {
+        let interface_fields = self.schema.fields(interface).collect::<Vec<_>>();
+        for interface_field in interface_fields {
+            let Some(field) = self
+                .schema
+                .fields(type_name)
+                .find(|field| field.name() == interface_field.name())
+            else {
+                self.errors.push(ValidationError::MissingInterfaceField {
+                    type_name: type_name.to_string(),
+                    interface: interface.to_string(),
+                    field_name: interface_field.name().to_string(),
+                    span,
+                });
+                continue;
+            };
+
+            let coordinate = format!("{type_name}.{}", field.name());
+            let interface_coordinate = format!("{interface}.{}", field.name());
+
+            if !self.is_valid_implementation_type(
+                &TypeRef::from(field.ty()),
+                &TypeRef::from(interface_field.ty()),
+            ) {
+                self.errors
+                    .push(ValidationError::InterfaceFieldTypeMismatch {
+                        coordinate: coordinate.clone(),
+                        interface_coordinate: interface_coordinate.clone(),
+                        field_type: field.ty().to_string(),
+                        interface_field_type: interface_field.ty().to_string(),
+                        span: field.ty().span(),
+                    });
+            }
+
+            self.validate_interface_arguments(
+                field,
+                interface_field,
+                &coordinate,
+                &interface_coordinate,
+            );
+        }
+    }
+
+    fn validate_interface_arguments(
+        &mut self,
+        field: FieldDefinition<'a>,
+        interface_field: FieldDefinition<'a>,
+        coordinate: &str,
+        interface_coordinate: &str,
+    ) {
+        for interface_argument in interface_field.arguments() {
+            let Some(argument) = field
+                .arguments()
+                .find(|argument| argument.name() == interface_argument.name())
+            else {
+                self.errors.push(ValidationError::MissingInterfaceArgument {
+                    coordinate: coordinate.to_string(),
+                    interface_coordinate: interface_coordinate.to_string(),
+                    name: interface_argument.name().to_string(),
+                    span: field.name_span(),
+                });
+                continue;
+            };
+
+            if argument.ty() != interface_argument.ty() {
+                self.errors
+                    .push(ValidationError::InterfaceArgumentTypeMismatch {
+                        coordinate: format!("{coordinate}({}:)", argument.name()),
+                        argument_type: argument.ty().to_string(),
+                        interface_argument_type: interface_argument.ty().to_string(),
+                        span: argument.ty().span(),
+                    });
+            }
+        }
+
+        for argument in field.arguments() {
+            let in_interface = interface_field
+                .arguments()
+                .any(|interface_argument| interface_argument.name() == argument.name());
+
+            if !in_interface && argument.ty().is_non_null() && argument.default_value().is_none() {
+                self.errors.push(ValidationError::RequiredExtraArgument {
+                    coordinate: format!("{coordinate}({}:)", argument.name()),
+                    interface_coordinate: interface_coordinate.to_string(),
+                    span: argument.name_span(),
+                });
+            }
+        }
+    }
+
+    /// Implements `IsValidImplementationFieldType` from the spec
+    fn is_valid_implementation_type(
+        &self,
+        field_type: &TypeRef<'a>,
+        interface_type: &TypeRef<'a>,
+    ) -> bool {
+        if field_type.is_non_null() {
+            let interface_type = match interface_type.is_non_null() {
+                true => interface_type.unwrap_one(),
+                false => interface_type.clone(),
+            };
+            return self.is_valid_implementation_type(&field_type.unwrap_one(), &interface_type);
+        }
+
+        if interface_type.is_non_null() {
+            return false;
+        }
+
+        match (field_type.is_list(), interface_type.is_list()) {
+            (true, true) => {
+                return self.is_valid_implementation_type(
+                    &field_type.unwrap_one(),
+                    &interface_type.unwrap_one(),
+                );
+            }
+            (false, false) => {}
+            _ => return false,
+        }
+
+        if field_type.name == interface_type.name {
+            return true;
+        }
+
+        match self.schema.kind(interface_type.name) {
+            Some(TypeKind::Union) => self
+                .schema
+                .possible_types(interface_type.name)
+                .contains(field_type.name),
+            Some(TypeKind::Interface) => self
+                .schema
+                .implements_interfaces(field_type.name)
+                .any(|name| name == interface_type.name),
+            _ => false,
+        }
+    }
+
+    fn validate_union(&mut self, name: &'a str, definitions: &[TypeDefinition<'a>], span: Span) {
+        let mut members = HashSet::new();
+        for definition in definitions {
+            let TypeDefinition::Union(union) = definition else {
+                continue;
+            };
+            for member in union.members() {
+                if !members.insert(member.name()) {
+                    self.errors.push(ValidationError::DuplicateUnionMember {
+                        union_name: name.to_string(),
+                        name: member.name().to_string(),
+                        span: member.span(),
+                    });
+                    continue;
+                }
+                match self.schema.kind(member.name()) {
+                    None => self.errors.push(ValidationError::UnknownType {
+                        name: member.name().to_string(),
+                        span: member.span(),
+                    }),
+                    Some(TypeKind::Object) => {}
+                    Some(_) => self.errors.push(ValidationError::NonObjectUnionMember {
+                        union_name: name.to_string(),
+                        name: member.name().to_string(),
+                        span: member.span(),
+                    }),
+                }
+            }
+        }
+
+        if members.is_empty() {
+            self.errors.push(ValidationError::EmptyUnion {
+                name: name.to_string(),
+                span,
+            });
+        }
+    }
+
+    fn validate_input_object(
+        &mut self,
+        name: &'a str,
+        definitions: &[TypeDefinition<'a>],
+        span: Span,
+    ) {
+        let is_one_of = definitions.iter().any(|definition| match definition {
+            TypeDefinition::InputObject(object) => object.is_one_of(),
+            _ => false,
+        });
+
+        let fields = definitions.iter().flat_map(|definition| match definition {
+            TypeDefinition::InputObject(object) => object.fields().collect(),
+            _ => Vec::new(),
+        });
+
+        let mut names = HashSet::new();
+        for field in fields {
+            self.check_name(field.name(), field.name_span());
+            if !names.insert(field.name()) {
+                self.errors.push(ValidationError::DuplicateField {
+                    type_name: name.to_string(),
+                    name: field.name().to_string(),
+                    span: field.name_span(),
+                });
+                continue;
+            }
+
+            let coordinate = format!("{name}.{}", field.name());
+            if is_one_of && (field.ty().is_non_null() || field.default_value().is_some()) {
+                self.errors.push(ValidationError::InvalidOneOfField {
+                    coordinate: coordinate.clone(),
+                    span: field.name_span(),
+                });
+            }
+            self.check_input_type(&coordinate, field);
+        }
+
+        if names.is_empty() {
+            self.errors.push(ValidationError::EmptyFields {
+                type_name: name.to_string(),
+                span,
+            });
+        }
+    }
+
+    fn check_input_type(&mut self, coordinate: &str, definition: InputValueDefinition<'a>) {
+        let ty = definition.ty();
+        match self.schema.kind(ty.name()) {
+            None => self.errors.push(ValidationError::UnknownType {
+                name: ty.name().to_string(),
+                span: ty.name_span(),
+            }),
+            Some(kind) if !kind.is_input() => self.errors.push(ValidationError::NonInputType {
+                coordinate: coordinate.to_string(),
+                type_name: ty.name().to_string(),
+                span: ty.span(),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    /// Checks for input objects that can't be provided because they contain
+    /// themselves via a chain of non-null, non-list fields
+    fn check_input_object_cycles(&mut self, types: &[(&'a str, Vec<TypeDefinition<'a>>)]) {
+        for (name, definitions) in types {
+            if !matches!(definitions[0], TypeDefinition::InputObject(_)) {
+                continue;
+            }
+
+            let mut to_visit = self.required_input_fields(name);
+            let mut visited = HashSet::new();
+            while let Some(current) = to_visit.pop() {
+                if current == *name {
+                    self.errors.push(ValidationError::InputObjectCycle {
+                        name: name.to_string(),
+                        span: definitions[0].name_span(),
+                    });
+                    break;
+                }
+                if visited.insert(current) {
+                    to_visit.extend(self.required_input_fields(current));
+                }
+            }
+        }
+    }
+
+    /// The input object types of the non-null, non-list fields of an input object
+    fn required_input_fields(&self, name: &str) -> Vec<&'a str> {
+        self.schema
+            .input_fields(name)
+            .filter(|field| {
+                field.ty().wrappers().eq([WrappingType::NonNull])
+                    && self.schema.kind(field.ty().name()) == Some(TypeKind::InputObject)
+            })
+            .map(|field| field.ty().name())
+            .collect()
+    }
+
+    fn check_name(&mut self, name: &str, span: Span) {
+        if name.starts_with("__") {
+            self.errors.push(ValidationError::ReservedName {
+                name: name.to_string(),
+                span,
+            });
+        }
+    }
+}
@@ -47,6 +47,16 @@ pub struct ValueStore {
 }
 
 impl ValueStore {
+    /// Moves the spans of any values at or after `position` by `delta`
+    pub(crate) fn shift_spans(&mut self, position: usize, delta: isize) {
+        for value in &mut self.values {
+            value.span.shift_from(position, delta);
+        }
+        for field in &mut self.fields {
+            field.name_span.shift_from(position, delta);
+        }
+    }
+
     pub fn read<T>(&self, id: T) -> T::Reader<'_>
     where
         T: ValueStoreId,
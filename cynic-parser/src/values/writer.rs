@@ -87,6 +87,18 @@ impl ValueWriter {
         IdRange::new(start, end)
     }
 
+    pub(crate) fn checkpoint(&self) -> ValueCheckpoint {
+        ValueCheckpoint {
+            values: self.values.len(),
+            fields: self.fields.len(),
+        }
+    }
+
+    pub(crate) fn rollback(&mut self, checkpoint: ValueCheckpoint) {
+        self.values.truncate(checkpoint.values);
+        self.fields.truncate(checkpoint.fields);
+    }
+
     pub(crate) fn finish(self, strings: Arc<IndexSet<Box<str>>>) -> super::ValueStore {
         let ValueWriter { values, fields } = self;
 
@@ -97,3 +109,10 @@ impl ValueWriter {
         }
     }
 }
+
+/// The state of a `ValueWriter` at some point in time, which it can be rolled back to
+#[derive(Clone, Copy)]
+pub(crate) struct ValueCheckpoint {
+    values: usize,
+    fields: usize,
+}
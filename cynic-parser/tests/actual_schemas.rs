@@ -27,6 +27,35 @@ macro_rules! schema_tests {
 
                 assert_eq!(sdl, reparsed.to_sdl_pretty());
             }
+
+            #[test]
+            fn recovering() {
+                let parsed = cynic_parser::parse_type_system_document(SCHEMA).unwrap();
+                let (recovered, errors) =
+                    cynic_parser::parse_type_system_document_recovering(SCHEMA);
+
+                assert_eq!(errors, vec![]);
+                assert_eq!(parsed.to_sdl_pretty(), recovered.to_sdl_pretty());
+            }
+
+            #[test]
+            fn normalize() {
+                let parsed = cynic_parser::parse_type_system_document(SCHEMA).unwrap();
+                let normalized = parsed.normalize().unwrap();
+
+                assert_eq!(parsed.to_sdl_pretty(), normalized.to_sdl_pretty());
+            }
+
+            #[test]
+            fn builder_roundtrip() {
+                use cynic_parser::type_system::builder::TypeSystemDocumentBuilder;
+
+                let parsed = cynic_parser::parse_type_system_document(SCHEMA).unwrap();
+                let builder = TypeSystemDocumentBuilder::from(&parsed);
+                let built = builder.build();
+
+                assert_eq!(builder, TypeSystemDocumentBuilder::from(&built));
+            }
         }
     };
 }
@@ -0,0 +1,186 @@
+use cynic_parser::{
+    common::OperationType,
+    parse_type_system_document,
+    type_system::{DirectiveLocation, builder::*},
+};
+
+#[test]
+fn test_building_a_document() {
+    let document = TypeSystemDocumentBuilder::new()
+        .definition(
+            SchemaDefinitionBuilder::new()
+                .root_operation(OperationType::Query, "Query")
+                .directive(DirectiveBuilder::new("link").argument("url", "https://example.com")),
+        )
+        .definition(
+            ObjectDefinitionBuilder::new("Query")
+                .description("The root query")
+                .implements("Node")
+                .field(
+                    FieldDefinitionBuilder::new(
+                        "users",
+                        TypeBuilder::named("User").non_null().list().non_null(),
+                    )
+                    .argument(
+                        InputValueDefinitionBuilder::new("first", TypeBuilder::named("Int"))
+                            .default_value(10),
+                    )
+                    .argument(
+                        InputValueDefinitionBuilder::new("filter", TypeBuilder::named("Filter"))
+                            .default_value(ConstValueBuilder::Object(vec![
+                                (
+                                    "roles".into(),
+                                    vec![ConstValueBuilder::Enum("ADMIN".into())].into(),
+                                ),
+                                ("name".into(), ConstValueBuilder::Null),
+                            ])),
+                    ),
+                )
+                .field(
+                    FieldDefinitionBuilder::new("id", TypeBuilder::named("ID").non_null())
+                        .description("A multi-line\ndescription"),
+                ),
+        )
+        .definition(
+            InterfaceDefinitionBuilder::new("Node").field(FieldDefinitionBuilder::new(
+                "id",
+                TypeBuilder::named("ID").non_null(),
+            )),
+        )
+        .definition(
+            UnionDefinitionBuilder::new("Search")
+                .member("Query")
+                .member("User"),
+        )
+        .definition(EnumDefinitionBuilder::new("Role").value("ADMIN").value(
+            EnumValueDefinitionBuilder::new("GUEST").directive(
+                DirectiveBuilder::new("deprecated").argument("reason", "No more guests"),
+            ),
+        ))
+        .definition(
+            InputObjectDefinitionBuilder::new("Filter")
+                .directive(DirectiveBuilder::new("oneOf"))
+                .field(InputValueDefinitionBuilder::new(
+                    "roles",
+                    TypeBuilder::named("Role").non_null().list(),
+                ))
+                .field(InputValueDefinitionBuilder::new(
+                    "name",
+                    TypeBuilder::named("String"),
+                )),
+        )
+        .definition(ScalarDefinitionBuilder::new("Date").directive(
+            DirectiveBuilder::new("specifiedBy").argument("url", "https://example.com/date"),
+        ))
+        .definition(
+            DirectiveDefinitionBuilder::new("cached")
+                .argument(InputValueDefinitionBuilder::new(
+                    "ttl",
+                    TypeBuilder::named("Int").non_null(),
+                ))
+                .repeatable()
+                .location(DirectiveLocation::FieldDefinition)
+                .location(DirectiveLocation::Object),
+        )
+        .definition(DefinitionBuilder::TypeExtension(
+            ObjectDefinitionBuilder::new("User")
+                .field(FieldDefinitionBuilder::new(
+                    "name",
+                    TypeBuilder::named("String"),
+                ))
+                .into(),
+        ))
+        .build();
+
+    insta::assert_snapshot!(document.to_sdl_pretty(), @r#"
+    schema @link(url: "https://example.com") {
+      query: Query
+    }
+
+    "The root query"
+    type Query implements Node {
+      users(
+        first: Int = 10
+        filter: Filter = { roles: [ADMIN], name: null }
+      ): [User!]!
+
+      "A multi-line\ndescription"
+      id: ID!
+    }
+
+    interface Node {
+      id: ID!
+    }
+
+    union Search = Query | User
+
+    enum Role {
+      ADMIN
+      GUEST @deprecated(reason: "No more guests")
+    }
+
+    input Filter @oneOf {
+      roles: [Role!]
+      name: String
+    }
+
+    scalar Date @specifiedBy(url: "https://example.com/date")
+
+    directive @cached(ttl: Int!) repeatable on FIELD_DEFINITION | OBJECT
+
+    extend type User {
+      name: String
+    }
+    "#);
+}
+
+#[test]
+fn test_editing_a_document() {
+    let document = parse_type_system_document(
+        r#"
+        type User {
+          id: ID!
+          "Use id instead"
+          legacyId: Int
+          name(format: NameFormat = FULL): String
+        }
+
+        enum NameFormat { FULL SHORT }
+        "#,
+    )
+    .unwrap();
+
+    let mut builder = TypeSystemDocumentBuilder::from(&document);
+    for definition in &mut builder.definitions {
+        match definition {
+            DefinitionBuilder::Type(TypeDefinitionBuilder::Object(object)) => {
+                object.fields.retain(|field| field.name != "legacyId");
+                object
+                    .field_mut("name")
+                    .unwrap()
+                    .directives
+                    .push(DirectiveBuilder::new("deprecated"));
+                object
+                    .directives
+                    .push(DirectiveBuilder::new("key").argument("fields", "id"));
+            }
+            DefinitionBuilder::Type(TypeDefinitionBuilder::Enum(definition)) => {
+                definition.values.push("INITIALS".into());
+            }
+            _ => {}
+        }
+    }
+
+    insta::assert_snapshot!(builder.build().to_sdl_pretty(), @r#"
+    type User @key(fields: "id") {
+      id: ID!
+      name(format: NameFormat = FULL): String @deprecated
+    }
+
+    enum NameFormat {
+      FULL
+      SHORT
+      INITIALS
+    }
+    "#);
+}
@@ -0,0 +1,113 @@
+use cynic_parser::{
+    parse_type_system_document, parse_type_system_document_with_comments,
+    type_system::{Comment, Definition, TypeDefinition},
+};
+
+fn texts<'a>(comments: impl Iterator<Item = Comment<'a>>) -> Vec<&'a str> {
+    comments.map(|comment| comment.text()).collect()
+}
+
+const SCHEMA: &str = r#"
+# Leading
+# Comments
+type Query {
+  # A field
+  field(
+    arg: Int # An argument
+  ): String # Trailing
+  # Dangling
+} # After Query
+
+enum Colour {
+  RED #Warm
+}
+
+# End of document
+"#;
+
+#[test]
+fn test_comment_attachment() {
+    let document = parse_type_system_document_with_comments(SCHEMA).unwrap();
+    let mut definitions = document.definitions();
+
+    let Some(Definition::Type(TypeDefinition::Object(query))) = definitions.next() else {
+        panic!("expected an object");
+    };
+    assert_eq!(
+        texts(query.leading_comments()),
+        vec![" Leading", " Comments"]
+    );
+    assert_eq!(
+        query.trailing_comment().map(|comment| comment.text()),
+        Some(" After Query")
+    );
+    assert_eq!(texts(query.dangling_comments()), vec![" Dangling"]);
+
+    let field = query.fields().next().unwrap();
+    assert_eq!(texts(field.leading_comments()), vec![" A field"]);
+    assert_eq!(
+        field.trailing_comment().map(|comment| comment.text()),
+        Some(" Trailing")
+    );
+
+    let argument = field.arguments().next().unwrap();
+    assert_eq!(argument.leading_comments().count(), 0);
+    let comment = argument.trailing_comment().unwrap();
+    assert_eq!(comment.text(), " An argument");
+    assert_eq!(
+        &SCHEMA[comment.span().start..comment.span().end],
+        "# An argument"
+    );
+
+    let Some(Definition::Type(TypeDefinition::Enum(colour))) = definitions.next() else {
+        panic!("expected an enum");
+    };
+    let value = colour.values().next().unwrap();
+    assert_eq!(
+        value.trailing_comment().map(|comment| comment.text()),
+        Some("Warm")
+    );
+
+    assert_eq!(
+        texts(document.end_of_document_comments()),
+        vec![" End of document"]
+    );
+}
+
+#[test]
+fn test_comments_not_kept_by_default() {
+    let document = parse_type_system_document(SCHEMA).unwrap();
+
+    let definition = document.definitions().next().unwrap();
+    assert_eq!(definition.leading_comments().count(), 0);
+    assert!(definition.trailing_comment().is_none());
+    assert_eq!(document.end_of_document_comments().count(), 0);
+}
+
+#[test]
+fn test_trailing_comment_breaks_arguments() {
+    let document = parse_type_system_document_with_comments(
+        "type Query { field(arg: Int # Hello\n): String }",
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(document.to_sdl_pretty(), @r###"
+    type Query {
+      field(
+        arg: Int # Hello
+      ): String
+    }
+    "###);
+}
+
+#[test]
+fn test_comments_only_in_document_end() {
+    let document = parse_type_system_document_with_comments("scalar Date\n# One\n# Two\n").unwrap();
+
+    insta::assert_snapshot!(document.to_sdl_pretty(), @r###"
+    scalar Date
+
+    # One
+    # Two
+    "###);
+}
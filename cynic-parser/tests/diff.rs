@@ -0,0 +1,223 @@
+use cynic_parser::{
+    diff::{ChangeKind, Severity},
+    parse_type_system_document,
+};
+
+#[test]
+fn test_no_changes() {
+    let schema = parse_type_system_document(OLD).unwrap();
+
+    assert_eq!(schema.diff(&schema), vec![]);
+}
+
+#[test]
+fn test_changes() {
+    insta::assert_snapshot!(diff(OLD, NEW), @r"
+    breaking: the required argument Query.user(includeDeleted:) of type Boolean! was added
+    dangerous: the default value of the argument Query.users(first:) changed from 10 to 20
+    safe: the field Query.posts was added
+    safe: the description of User changed
+    breaking: User no longer implements the interface Node
+    safe: the type of the field User.name changed from String to String!
+    safe: User.email was deprecated: Use emails
+    breaking: the field User.legacyId was removed
+    safe: the field User.emails was added
+    dangerous: Comment was added to the union SearchResult
+    breaking: the enum value Role.GUEST was removed
+    dangerous: the enum value Role.MODERATOR was added
+    breaking: the required input field UserFilter.createdAfter of type Date! was added
+    breaking: the type Date changed kind from scalar to input object
+    safe: the object Comment was added
+    safe: the directive @auth is now repeatable
+    breaking: the directive @auth can no longer be used on FIELD_DEFINITION
+    breaking: the type of the argument @auth(role:) changed from Role to Role!
+    breaking: the directive @cached was removed
+");
+}
+
+#[test]
+fn test_type_changes() {
+    insta::assert_snapshot!(
+        diff(
+            "type Query { a: String, b: String!, c: [String!], d: [String], e: Int }",
+            "type Query { a: String!, b: String, c: [String!]!, d: [String!], e: String }"
+        ),
+        @r"
+        safe: the type of the field Query.a changed from String to String!
+        breaking: the type of the field Query.b changed from String! to String
+        safe: the type of the field Query.c changed from [String!] to [String!]!
+        safe: the type of the field Query.d changed from [String] to [String!]
+        breaking: the type of the field Query.e changed from Int to String
+    "
+    );
+
+    insta::assert_snapshot!(
+        diff(
+            "type Query { f(a: String, b: String!, c: [String!], d: [String]): String }",
+            "type Query { f(a: String!, b: String, c: [String], d: [String!]): String }"
+        ),
+        @r"
+        breaking: the type of the argument Query.f(a:) changed from String to String!
+        safe: the type of the argument Query.f(b:) changed from String! to String
+        safe: the type of the argument Query.f(c:) changed from [String!] to [String]
+        breaking: the type of the argument Query.f(d:) changed from [String] to [String!]
+    "
+    );
+}
+
+#[test]
+fn test_extensions_are_merged() {
+    insta::assert_snapshot!(
+        diff(
+            "type Query { a: String } extend type Query { b: String }",
+            "type Query { a: String, b: String }"
+        ),
+        @""
+    );
+}
+
+#[test]
+fn test_root_operations() {
+    insta::assert_snapshot!(
+        diff(
+            "type Query { a: String } type Mutation { a: String }",
+            "schema { query: Root, mutation: Mutation } type Root { a: String } type Mutation { a: String }"
+        ),
+        @r"
+        dangerous: the query root type changed from Query to Root
+        breaking: the object Query was removed
+        safe: the object Root was added
+    "
+    );
+}
+
+#[test]
+fn test_argument_added() {
+    let old = parse_type_system_document("type Query { users: [String] }").unwrap();
+    let new = parse_type_system_document(
+        "type Query { users(first: Int!, after: String, last: Int! = 10): [String] }",
+    )
+    .unwrap();
+
+    let changes = old.diff(&new);
+
+    assert_eq!(changes[0].coordinate, "Query.users(first:)");
+    assert_eq!(
+        changes[0].kind,
+        ChangeKind::ArgumentAdded {
+            ty: "Int!".into(),
+            required: true
+        }
+    );
+    assert_eq!(
+        changes
+            .iter()
+            .map(|change| change.severity())
+            .collect::<Vec<_>>(),
+        vec![Severity::Breaking, Severity::Dangerous, Severity::Dangerous]
+    );
+}
+
+fn diff(old: &str, new: &str) -> String {
+    let old = parse_type_system_document(old).unwrap();
+    let new = parse_type_system_document(new).unwrap();
+
+    old.diff(&new)
+        .iter()
+        .map(|change| format!("{}: {change}", change.severity()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+const OLD: &str = r#"
+type Query {
+  user(id: ID!): User
+  users(first: Int = 10): [User!]!
+  search(term: String): [SearchResult!]!
+}
+
+"Someone who uses the app"
+type User implements Node {
+  id: ID!
+  name: String
+  email: String
+  role: Role
+  legacyId: Int
+}
+
+interface Node {
+  id: ID!
+}
+
+union SearchResult = User | Post
+
+type Post {
+  title: String
+}
+
+enum Role {
+  ADMIN
+  USER
+  GUEST
+}
+
+input UserFilter {
+  name: String
+  role: Role
+}
+
+scalar Date
+
+directive @auth(role: Role) on FIELD_DEFINITION | OBJECT
+directive @cached on FIELD_DEFINITION
+"#;
+
+const NEW: &str = r#"
+type Query {
+  user(id: ID!, includeDeleted: Boolean!): User
+  users(first: Int = 20): [User!]!
+  search(term: String): [SearchResult!]!
+  posts: [Post!]!
+}
+
+"A person who uses the app"
+type User {
+  id: ID!
+  name: String!
+  email: String @deprecated(reason: "Use emails")
+  emails: [String!]!
+  role: Role
+}
+
+interface Node {
+  id: ID!
+}
+
+union SearchResult = User | Post | Comment
+
+type Post {
+  title: String
+}
+
+type Comment {
+  body: String
+}
+
+enum Role {
+  ADMIN
+  USER
+  MODERATOR
+}
+
+input UserFilter {
+  name: String
+  role: Role
+  createdAfter: Date!
+}
+
+input Date {
+  value: String
+}
+
+directive @auth(role: Role!) repeatable on OBJECT
+"#;
@@ -0,0 +1,170 @@
+use cynic_parser::{executable::builder::*, parse_executable_document};
+
+#[test]
+fn test_normalize() {
+    let document = parse_executable_document(
+        r#"
+        query UserQuery($unused: Int, $id: ID!, $includeEmail: Boolean = false) {
+          user(options: { b: 1, a: 2 }, id: $id) {
+            ...UserFields
+            name: name
+            id
+            ... on Admin {
+              permissions
+            }
+            ... on Admin {
+              role
+            }
+          }
+          user(id: $id, options: { a: 2, b: 1 }) {
+            friends {
+              name
+            }
+          }
+        }
+
+        fragment UserFields on User {
+          name
+          email @include(if: $includeEmail)
+          ...Nested
+        }
+
+        fragment Nested on User {
+          avatar
+        }
+
+        fragment Unused on User {
+          id
+        }
+        "#,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(document.normalize().to_string_pretty(), @r"
+    query UserQuery($id: ID!, $includeEmail: Boolean = false) {
+      user(id: $id, options: { a: 2, b: 1 }) {
+        friends {
+          name
+        }
+        id
+        name
+        ... on Admin {
+          permissions
+          role
+        }
+        ... on User {
+          email @include(if: $includeEmail)
+          name
+          ... on User {
+            avatar
+          }
+        }
+      }
+    }
+    ");
+}
+
+#[test]
+fn test_inline_fragments() {
+    let document = parse_executable_document(
+        r#"
+        query {
+          node {
+            ...Node @skip(if: true)
+            ...Missing
+          }
+        }
+
+        fragment Node on Node {
+          id
+          ...Node
+        }
+        "#,
+    )
+    .unwrap();
+
+    let mut builder = ExecutableDocumentBuilder::from(&document);
+    builder.inline_fragments();
+
+    insta::assert_snapshot!(builder.build().to_string_pretty(), @r"
+    {
+      node {
+        ... on Node @skip(if: true) {
+          id
+          ...Node
+        }
+        ...Missing
+      }
+    }
+
+    fragment Node on Node {
+      id
+      ...Node
+    }
+    ");
+}
+
+#[test]
+fn test_remove_unused_variables() {
+    let document = parse_executable_document(
+        r#"
+        query($a: Int, $b: Int, $c: Int, $d: Int) @dir(arg: $a) {
+          field(arg: [{ nested: $b }])
+          ...Spread
+        }
+
+        fragment Spread on Query {
+          other(arg: $c)
+        }
+
+        fragment Unreachable on Query {
+          other(arg: $d)
+        }
+        "#,
+    )
+    .unwrap();
+
+    let mut builder = ExecutableDocumentBuilder::from(&document);
+    builder.remove_unused_variables();
+
+    insta::assert_snapshot!(builder.build().to_string_pretty(), @r"
+    query($a: Int, $b: Int, $c: Int) @dir(arg: $a) {
+      field(arg: [{ nested: $b }])
+      ...Spread
+    }
+
+    fragment Spread on Query {
+      other(arg: $c)
+    }
+
+    fragment Unreachable on Query {
+      other(arg: $d)
+    }
+    ");
+}
+
+#[test]
+fn test_normalized_documents_are_equal() {
+    let lhs = parse_executable_document(
+        "query($id: ID!) { user(id: $id) { id ... on User { name ... on Admin { role } } } }",
+    )
+    .unwrap();
+    let rhs = parse_executable_document(
+        r#"
+        query($id: ID!) {
+          user(id: $id) {
+            ...Fields
+            id: id
+          }
+        }
+
+        fragment Fields on User {
+          ... on Admin { role }
+          name
+        }
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(lhs.normalize().to_string(), rhs.normalize().to_string());
+}
@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use cynic_parser::executable::{Selection, builder::ExecutableDocumentBuilder};
+
 use similar_asserts::assert_eq;
 
 #[test]
@@ -192,6 +194,23 @@ fn variable_directive() {
     display_test("tests/executables/variable_directive.graphql");
 }
 
+#[test]
+fn field_spans() {
+    let input = "{ dog { nickname: name } }";
+    let document = cynic_parser::parse_executable_document(input).unwrap();
+
+    let operation = document.operations().next().unwrap();
+    let Some(Selection::Field(dog)) = operation.selection_set().next() else {
+        unreachable!()
+    };
+    let Some(Selection::Field(name)) = dog.selection_set().next() else {
+        unreachable!()
+    };
+
+    assert_eq!(&input[dog.name_span().start..dog.name_span().end], "dog");
+    assert_eq!(&input[name.name_span().start..name.name_span().end], "name");
+}
+
 fn roundtrip_test(filename: &str) {
     let data = std::fs::read_to_string(filename).unwrap();
     let ast = cynic_parser::parse_executable_document(&data)
@@ -201,6 +220,9 @@ fn roundtrip_test(filename: &str) {
     let output = ast.to_string_pretty();
 
     assert_eq!(data, output);
+
+    let builder = ExecutableDocumentBuilder::from(&ast);
+    assert_eq!(builder, ExecutableDocumentBuilder::from(&builder.build()));
 }
 
 fn display_test(filename: &str) {
@@ -0,0 +1,188 @@
+use cynic_parser::{
+    ExecutableDocument, Span, TextEdit, TypeSystemDocument, parse_executable_document,
+    parse_type_system_document, parse_type_system_document_with_comments,
+    reparse_executable_document, reparse_type_system_document,
+    type_system::{Definition, TypeDefinition},
+};
+
+const SCHEMA: &str = r#"type Query {
+  user(id: ID!): User
+}
+
+"A user"
+type User {
+  name: String
+}
+
+scalar Date @specifiedBy(url: "https://example.com")
+"#;
+
+/// Applies an edit that replaces the first `old` in `input` with `new`
+fn edit_schema(
+    document: TypeSystemDocument,
+    input: &str,
+    old: &str,
+    new: &str,
+) -> (TypeSystemDocument, String) {
+    let start = input.find(old).unwrap();
+    let edit = TextEdit::new(Span::new(start, start + old.len()), new);
+    let input = edit.apply(input);
+
+    let document = reparse_type_system_document(document, &input, &edit).unwrap();
+
+    (document, input)
+}
+
+fn edit_executable(
+    document: ExecutableDocument,
+    input: &str,
+    old: &str,
+    new: &str,
+) -> (ExecutableDocument, String) {
+    let start = input.find(old).unwrap();
+    let edit = TextEdit::new(Span::new(start, start + old.len()), new);
+    let input = edit.apply(input);
+
+    let document = reparse_executable_document(document, &input, &edit).unwrap();
+
+    (document, input)
+}
+
+fn object(definition: Option<Definition<'_>>) -> cynic_parser::type_system::ObjectDefinition<'_> {
+    match definition {
+        Some(Definition::Type(TypeDefinition::Object(object))) => object,
+        _ => panic!("expected an object"),
+    }
+}
+
+#[test]
+fn test_editing_a_definition() {
+    let document = parse_type_system_document(SCHEMA).unwrap();
+    let user_id = object(document.definitions().nth(1)).id();
+
+    let (document, input) = edit_schema(document, SCHEMA, "ID!", "String!");
+
+    assert_eq!(
+        document.to_sdl_pretty(),
+        parse_type_system_document(&input).unwrap().to_sdl_pretty()
+    );
+
+    // Definitions after the edit are reused, but their spans have moved
+    let user = object(document.definitions().nth(1));
+    assert_eq!(user.id(), user_id);
+    assert_eq!(&input[user.name_span().start..user.name_span().end], "User");
+    assert_eq!(
+        &input[user.description().unwrap().span().start..user.span().end],
+        "\"A user\"\ntype User {\n  name: String\n}"
+    );
+}
+
+#[test]
+fn test_adding_and_removing_definitions() {
+    let document = parse_type_system_document(SCHEMA).unwrap();
+
+    let (document, input) =
+        edit_schema(document, SCHEMA, "\"A user\"", "scalar JSON\n\n\"A user\"");
+    assert_eq!(document.definitions().count(), 4);
+    assert_eq!(
+        document.to_sdl_pretty(),
+        parse_type_system_document(&input).unwrap().to_sdl_pretty()
+    );
+
+    let start = input.find("scalar Date").unwrap();
+    let (document, input) = edit_schema(document, &input, &input[start..], "");
+    assert_eq!(document.definitions().count(), 3);
+    assert_eq!(
+        document.to_sdl_pretty(),
+        parse_type_system_document(&input).unwrap().to_sdl_pretty()
+    );
+}
+
+#[test]
+fn test_editing_a_directive_definition() {
+    let input = "directive @one on FIELD\n\ndirective @two on FIELD\n";
+    let document = parse_type_system_document(input).unwrap();
+
+    let (document, _) = edit_schema(document, input, "@one", "@uno");
+
+    let names = document
+        .directive_definitions()
+        .map(|directive| directive.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["uno", "two"]);
+}
+
+#[test]
+fn test_edits_that_join_definitions() {
+    let input = "type Query { hello: String }\n\nscalar Date\n";
+    let document = parse_type_system_document(input).unwrap();
+
+    // Edits at the very end of one definition can change how the next one
+    // parses, so that needs reparsing too
+    let (document, input) =
+        edit_schema(document, input, "String }", "String, type: Int }\n\nextend");
+
+    assert_eq!(document.definitions().count(), 2);
+    assert_eq!(
+        document.to_sdl_pretty(),
+        parse_type_system_document(&input).unwrap().to_sdl_pretty()
+    );
+}
+
+#[test]
+fn test_edit_with_error() {
+    let document = parse_type_system_document(SCHEMA).unwrap();
+    let edit = TextEdit::new(Span::new(0, 4), "");
+    let input = edit.apply(SCHEMA);
+
+    assert!(reparse_type_system_document(document, &input, &edit).is_err());
+}
+
+#[test]
+fn test_comments_are_kept() {
+    let input = "# The root\ntype Query {\n  hello: String\n}\n\nscalar Date # A date\n";
+    let document = parse_type_system_document_with_comments(input).unwrap();
+
+    let (document, input) = edit_schema(document, input, "hello", "goodbye");
+
+    insta::assert_snapshot!(document.to_sdl_pretty(), @r###"
+    # The root
+    type Query {
+      goodbye: String
+    }
+
+    scalar Date # A date
+    "###);
+    assert_eq!(
+        document.to_sdl_pretty(),
+        parse_type_system_document_with_comments(&input)
+            .unwrap()
+            .to_sdl_pretty()
+    );
+}
+
+#[test]
+fn test_executable_documents() {
+    let input = "query Foo { user { name } }\n\nfragment Bar on User { id }\n\n{ hello }\n";
+    let document = parse_executable_document(input).unwrap();
+
+    let (document, input) = edit_executable(document, input, "name", "name email");
+    let (document, input) = edit_executable(
+        document,
+        &input,
+        "{ hello }",
+        "{ hello }\n\nquery Baz { a }",
+    );
+
+    assert_eq!(document.definitions().count(), 4);
+    assert_eq!(
+        document.to_string(),
+        parse_executable_document(&input).unwrap().to_string()
+    );
+
+    let fragment = document.fragments().next().unwrap();
+    assert_eq!(
+        &input[fragment.name_span().start..fragment.name_span().end],
+        "Bar"
+    );
+}
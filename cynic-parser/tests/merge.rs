@@ -0,0 +1,166 @@
+use cynic_parser::{merge::merge_type_system_documents, parse_type_system_document};
+
+#[test]
+fn test_normalize() {
+    let document = parse_type_system_document(
+        r#"
+        "The root query"
+        type Query @one {
+          hello(name: String = "world" @deprecated): String
+        }
+
+        extend type Query implements Node @two(values: [1, 2, { three: THREE }]) {
+          id: ID!
+        }
+
+        extend schema @schemaDirective {
+          mutation: Mutation
+        }
+
+        schema {
+          query: Query
+        }
+
+        enum Role { ADMIN }
+        extend enum Role { """Can read things""" USER }
+
+        union Search = Query
+        extend union Search = Other
+
+        input Filter { name: String }
+        extend input Filter @oneOf { id: ID }
+
+        scalar Date
+        extend scalar Date @specifiedBy(url: "https://example.com")
+
+        directive @one on OBJECT
+        "#,
+    )
+    .unwrap();
+
+    insta::assert_snapshot!(document.normalize().unwrap().to_sdl_pretty(), @r#"
+    "The root query"
+    type Query implements Node @one @two(values: [1, 2, { three: THREE }]) {
+      hello(name: String = "world" @deprecated): String
+      id: ID!
+    }
+
+    schema @schemaDirective {
+      query: Query
+      mutation: Mutation
+    }
+
+    enum Role {
+      ADMIN
+
+      """Can read things"""
+      USER
+    }
+
+    union Search = Query | Other
+
+    input Filter @oneOf {
+      name: String
+      id: ID
+    }
+
+    scalar Date @specifiedBy(url: "https://example.com")
+
+    directive @one on OBJECT
+
+    "#);
+}
+
+#[test]
+fn test_extension_without_definition() {
+    let document = parse_type_system_document("extend type Query { hello: String }").unwrap();
+
+    insta::assert_snapshot!(document.normalize().unwrap().to_sdl_pretty(), @r"
+    type Query {
+      hello: String
+    }
+    ");
+}
+
+#[test]
+fn test_merging_documents() {
+    let query = parse_type_system_document("type Query implements Node { id: ID! }").unwrap();
+    let users = parse_type_system_document(
+        "extend type Query implements Node { users: [User!]! } type User { name: String! }",
+    )
+    .unwrap();
+
+    let merged = merge_type_system_documents([&query, &users]).unwrap();
+
+    insta::assert_snapshot!(merged.to_sdl_pretty(), @r"
+    type Query implements Node {
+      id: ID!
+      users: [User!]!
+    }
+
+    type User {
+      name: String!
+    }
+    ");
+}
+
+#[test]
+fn test_conflicts() {
+    let first = parse_type_system_document(
+        r#"
+        schema { query: Query }
+        type Query { hello: String }
+        enum Role { ADMIN }
+        union Search = Query
+        directive @auth on FIELD_DEFINITION
+        "#,
+    )
+    .unwrap();
+    let second = parse_type_system_document(
+        r#"
+        schema { query: Query }
+        extend schema { query: Query }
+        type Query { goodbye: String }
+        extend type Query { hello: String }
+        extend input Role { id: ID }
+        extend enum Role { ADMIN }
+        extend union Search = Query
+        directive @auth on OBJECT
+        "#,
+    )
+    .unwrap();
+
+    let Err(errors) = merge_type_system_documents([&first, &second]) else {
+        panic!("expected the documents to conflict")
+    };
+    let errors = errors
+        .into_iter()
+        .map(|error| format!("{}: {error}", error.document))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    insta::assert_snapshot!(errors, @r"
+    1: there is more than one schema definition
+    1: there is more than one type named Query
+    1: there is more than one directive named @auth
+    1: the query root type is defined more than once
+    1: the field hello is defined more than once on Query
+    1: this extension of Role is a different kind of type from its definition
+    1: the value ADMIN is defined more than once on Role
+    1: Query is a member of the union Search more than once
+    ");
+}
+
+#[test]
+fn test_duplicate_root_operation_span() {
+    let first = parse_type_system_document("schema { query: Query }").unwrap();
+    let source = "extend schema { query: Other }";
+    let second = parse_type_system_document(source).unwrap();
+
+    let Err(errors) = merge_type_system_documents([&first, &second]) else {
+        panic!("expected the documents to conflict")
+    };
+    let span = errors[0].span();
+
+    assert_eq!(&source[span.start..span.end], "query");
+}
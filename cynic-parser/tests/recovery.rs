@@ -0,0 +1,174 @@
+use cynic_parser::{
+    Error, parse_executable_document_recovering, parse_type_system_document_recovering,
+};
+
+/// The source text that each error points at
+fn error_locations<'a>(document: &'a str, errors: &[Error]) -> Vec<&'a str> {
+    errors
+        .iter()
+        .map(|error| {
+            let span = error.span().unwrap();
+            &document[span.start..span.end]
+        })
+        .collect()
+}
+
+#[test]
+fn test_valid_type_system_document() {
+    let schema = r#"
+type Query {
+type: String
+input(query: Int): String
+}
+
+"A description"
+input Input {
+schema: Int
+}
+extend type Query {
+  extend: Int
+}
+"#;
+    let (document, errors) = parse_type_system_document_recovering(schema);
+
+    assert_eq!(errors, vec![]);
+    insta::assert_snapshot!(document.to_sdl_pretty(), @r#"
+    type Query {
+      type: String
+      input(query: Int): String
+    }
+
+    "A description"
+    input Input {
+      schema: Int
+    }
+
+    extend type Query {
+      extend: Int
+    }
+    "#);
+}
+
+#[test]
+fn test_skips_broken_definitions() {
+    let schema = r#"
+        type Query { hello: String }
+        type Broken { oops }
+        "A scalar"
+        scalar Date
+        union Bad = | | Two
+        enum Good { ONE TWO }
+    "#;
+    let (document, errors) = parse_type_system_document_recovering(schema);
+
+    assert_eq!(error_locations(schema, &errors), vec!["}", "|"]);
+    insta::assert_snapshot!(document.to_sdl_pretty(), @r#"
+    type Query {
+      hello: String
+    }
+
+    "A scalar"
+    scalar Date
+
+    enum Good {
+      ONE
+      TWO
+    }
+    "#);
+}
+
+#[test]
+fn test_missing_closing_brace() {
+    let schema = r#"
+type Query {
+  hello: String
+
+type Other {
+  goodbye: String
+}
+
+scalar Date @specifiedBy(url: "https://example.com"
+scalar DateTime
+"#;
+    let (document, errors) = parse_type_system_document_recovering(schema);
+
+    assert_eq!(errors.len(), 2);
+    insta::assert_snapshot!(document.to_sdl_pretty(), @r#"
+    type Other {
+      goodbye: String
+    }
+
+    scalar DateTime
+    "#);
+}
+
+#[test]
+fn test_error_at_end_of_document() {
+    let schema = "type Query { hello: String } type Other {";
+    let (document, errors) = parse_type_system_document_recovering(schema);
+
+    assert!(matches!(errors.as_slice(), [Error::UnrecognizedEof { .. }]));
+    assert_eq!(document.definitions().count(), 1);
+}
+
+#[test]
+fn test_lexical_errors() {
+    let schema = "type Query { hello: String } type Other { ^ } scalar Date";
+    let (document, errors) = parse_type_system_document_recovering(schema);
+
+    assert_eq!(error_locations(schema, &errors), vec!["^"]);
+    assert_eq!(document.definitions().count(), 2);
+}
+
+#[test]
+fn test_empty_documents() {
+    let (document, errors) = parse_type_system_document_recovering("  ");
+    assert_eq!(errors, vec![Error::EmptyTypeSystemDocument]);
+    assert_eq!(document.definitions().count(), 0);
+
+    let (document, errors) = parse_executable_document_recovering("");
+    assert_eq!(errors, vec![Error::EmptyExecutableDocument]);
+    assert_eq!(document.definitions().count(), 0);
+}
+
+#[test]
+fn test_executable_document() {
+    let query = r#"
+        query One { dog { name } }
+        query Broken { dog { name }
+        fragment DogFields on Dog {
+          name
+        }
+        { pets { name } }
+        mutation Bad($x: ) { thing }
+        {
+          query
+          fragment
+          mutation { id }
+        }
+    "#;
+    let (document, errors) = parse_executable_document_recovering(query);
+
+    assert_eq!(errors.len(), 2);
+    insta::assert_snapshot!(document.to_string(), @r#"
+    query One {
+      dog {
+        name
+      }
+    }
+
+    query {
+      pets {
+        name
+      }
+    }
+
+    query {
+      query
+      fragment
+      mutation {
+        id
+      }
+    }
+    "#);
+}
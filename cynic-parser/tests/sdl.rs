@@ -165,6 +165,16 @@ fn schema_definition_directives_only() {
     roundtrip_test("tests/sdl/schema_definition_directives_only.graphql");
 }
 
+#[test]
+fn comments() {
+    let data = std::fs::read_to_string("tests/sdl/comments.graphql").unwrap();
+    let ast = cynic_parser::parse_type_system_document_with_comments(&data)
+        .map_err(|error| error.to_report(&data))
+        .unwrap();
+
+    assert_eq!(data, ast.to_sdl_pretty());
+}
+
 fn roundtrip_test(filename: &str) {
     let data = std::fs::read_to_string(filename).unwrap();
     let ast = cynic_parser::parse_type_system_document(&data)
@@ -0,0 +1,483 @@
+use cynic_parser::{
+    TypeSystemDocument, parse_executable_document, parse_type_system_document,
+    validation::validate_executable_document,
+};
+
+const SCHEMA: &str = r#"
+    type Query {
+      dog: Dog
+      pets(first: Int, kind: PetKind = DOG): [Pet!]!
+      findDog(searchBy: FindDogInput!): Dog
+      catOrDog: CatOrDog
+      booleanList(booleanListArg: [Boolean!]): Boolean
+      nonNullBoolean(arg: Boolean!): Boolean
+      nonNullBooleanWithDefault(arg: Boolean! = false): Boolean
+    }
+
+    type Subscription {
+      newMessage: Message!
+      disallowedSecondRootField: Boolean
+    }
+
+    type Message {
+      body: String
+      sender: String
+    }
+
+    enum PetKind {
+      DOG
+      CAT
+    }
+
+    enum DogCommand {
+      SIT
+      DOWN
+      HEEL
+    }
+
+    input FindDogInput {
+      name: String
+      owner: String!
+      commands: [DogCommand!]
+    }
+
+    interface Pet {
+      name: String!
+    }
+
+    type Dog implements Pet {
+      name: String!
+      nickname: String
+      barkVolume: Int
+      doesKnowCommand(dogCommand: DogCommand!): Boolean!
+      isHouseTrained(atOtherHomes: Boolean): Boolean!
+      owner: Human
+    }
+
+    type Cat implements Pet {
+      name: String!
+      meowVolume: Int
+    }
+
+    type Human {
+      name: String!
+      pets: [Pet!]
+    }
+
+    union CatOrDog = Cat | Dog
+
+    directive @cached(ttl: Int!) on FIELD
+"#;
+
+fn schema() -> TypeSystemDocument {
+    parse_type_system_document(SCHEMA).unwrap()
+}
+
+fn validate(query: &str) -> Vec<String> {
+    let document = parse_executable_document(query).unwrap();
+    match validate_executable_document(&document, &schema()) {
+        Ok(()) => vec![],
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    }
+}
+
+#[test]
+fn test_valid_document() {
+    let errors = validate(
+        r#"
+        query GetDog($command: DogCommand!, $atOtherHomes: Boolean) {
+          dog {
+            ...DogFields
+            doesKnowCommand(dogCommand: $command)
+            isHouseTrained(atOtherHomes: $atOtherHomes) @include(if: true)
+            owner { name }
+          }
+          pets(first: 10) {
+            __typename
+            ... on Dog { barkVolume }
+            ... on Cat { meowVolume }
+          }
+          findDog(searchBy: {owner: "Alice", commands: [SIT, HEEL]}) { name }
+          catOrDog { ... on Pet { name } }
+          booleanList(booleanListArg: true)
+          __schema { queryType { name } }
+        }
+
+        fragment DogFields on Dog {
+          name @cached(ttl: 60)
+          nickname
+        }
+
+        subscription NewMessage {
+          newMessage { body }
+        }
+        "#,
+    );
+
+    assert_eq!(errors, Vec::<String>::new());
+}
+
+#[test]
+fn test_field_existence() {
+    assert_eq!(
+        validate("{ dog { meowVolume } catOrDog { name } }"),
+        vec![
+            "no field named meowVolume on the type Dog",
+            "no field named name on the type CatOrDog"
+        ]
+    );
+}
+
+#[test]
+fn test_leaf_selections() {
+    assert_eq!(
+        validate("{ dog { name { length } owner } }"),
+        vec![
+            "the field name has type String! which can't have a selection set",
+            "the field owner has type Human which requires a selection set"
+        ]
+    );
+}
+
+#[test]
+fn test_arguments() {
+    assert_eq!(
+        validate(
+            r#"{
+              dog {
+                doesKnowCommand(command: SIT)
+                isHouseTrained(atOtherHomes: true, atOtherHomes: false)
+              }
+              pets(first: "ten", kind: FISH) { name }
+              findDog(searchBy: {name: "Rex", age: 3}) { name }
+              nonNullBoolean(arg: null)
+            }"#
+        ),
+        vec![
+            "Dog.doesKnowCommand is missing the required argument dogCommand",
+            "Dog.doesKnowCommand has no argument named command",
+            "the argument atOtherHomes was provided more than once",
+            "expected a value of type Int",
+            "expected a value of type PetKind",
+            "the input type FindDogInput requires the field owner",
+            "no field named age on the input type FindDogInput",
+            "expected a value of type Boolean!",
+        ]
+    );
+}
+
+#[test]
+fn test_unique_names() {
+    assert_eq!(
+        validate(
+            r#"
+            query Dog { dog { name } }
+            query Dog { dog { nickname } }
+            fragment Name on Dog { name }
+            fragment Name on Dog { nickname }
+            query Other { dog { ...Name } }
+            "#
+        ),
+        vec![
+            "there is more than one operation named Dog",
+            "there is more than one fragment named Name",
+        ]
+    );
+
+    assert_eq!(
+        validate("{ dog { name } } query Named { dog { name } }"),
+        vec!["anonymous operations must be the only operation in a document"]
+    );
+}
+
+#[test]
+fn test_fragments() {
+    assert_eq!(
+        validate(
+            r#"
+            {
+              dog { ...Missing ...CatFields ...NameFragment }
+              pets { ... on Human { name } }
+              catOrDog { ... on PetKind { name } }
+            }
+            fragment CatFields on Cat { meowVolume }
+            fragment NameFragment on Dog { name ...OwnerFragment }
+            fragment OwnerFragment on Dog { owner { name } ...NameFragment }
+            fragment Unused on Dog { name }
+            "#
+        ),
+        vec![
+            "unknown fragment: Missing",
+            "a fragment on Cat can never match inside Dog",
+            "a fragment on Human can never match inside Pet",
+            "fragments can't be used on PetKind as it is not an object, interface or union",
+            "the fragment NameFragment spreads itself",
+            "the fragment OwnerFragment spreads itself",
+            "the fragment Unused is never used",
+        ]
+    );
+}
+
+#[test]
+fn test_variables() {
+    assert_eq!(
+        validate(
+            r#"
+            query Variables(
+              $unused: Int,
+              $dog: Dog,
+              $first: Int,
+              $first: Int,
+              $boolean: Boolean,
+              $booleanWithDefault: Boolean = true,
+              $booleans: [Boolean]
+            ) {
+              pets(first: $first) { name }
+              dog { doesKnowCommand(dogCommand: $command) }
+              nonNullBoolean(arg: $boolean)
+              other: nonNullBoolean(arg: $booleanWithDefault)
+              nonNullBooleanWithDefault(arg: $boolean)
+              booleanList(booleanListArg: $booleans)
+            }
+            "#
+        ),
+        vec![
+            "the variable $unused is never used",
+            "the variable $dog is never used",
+            "the variable $dog has type Dog which is not an input type",
+            "the variable $first is defined more than once",
+            "the variable $command is not defined by the operation Variables",
+            "the variable $boolean has type Boolean but is used where Boolean! is expected",
+            "the variable $booleans has type [Boolean] but is used where [Boolean!] is expected",
+        ]
+    );
+}
+
+#[test]
+fn test_variables_used_in_fragments() {
+    assert_eq!(
+        validate(
+            r#"
+            query One($command: DogCommand!) { dog { ...Commands } }
+            query Two { dog { ...Commands } }
+            fragment Commands on Dog { doesKnowCommand(dogCommand: $command) }
+            "#
+        ),
+        vec!["the variable $command is not defined by the operation Two"]
+    );
+}
+
+#[test]
+fn test_directives() {
+    assert_eq!(
+        validate(
+            r#"
+            query Directives @include(if: true) {
+              dog @unknown { name @skip(if: true) @skip(if: false) }
+              pets @cached { name }
+            }
+            "#
+        ),
+        vec![
+            "the directive @include can't be used on QUERY",
+            "unknown directive: @unknown",
+            "the directive @skip can only be used once here",
+            "@cached is missing the required argument ttl",
+        ]
+    );
+}
+
+#[test]
+fn test_operation_types() {
+    assert_eq!(
+        validate("mutation { dog { name } }"),
+        vec!["the schema does not support mutation operations"]
+    );
+
+    assert_eq!(
+        validate(
+            "subscription { newMessage { body } disallowedSecondRootField } subscription Other { __typename }"
+        ),
+        vec![
+            "anonymous operations must be the only operation in a document",
+            "subscriptions must select exactly one root field",
+            "subscriptions can't select introspection fields at their root",
+        ]
+    );
+}
+
+#[test]
+fn test_report() {
+    let document = "{ dog { nam } }";
+    let errors =
+        validate_executable_document(&parse_executable_document(document).unwrap(), &schema())
+            .unwrap_err();
+
+    insta::assert_display_snapshot!(errors[0].to_report(document), @r#"
+    Error: no field named nam on the type Dog
+       ╭─[<unknown>:1:9]
+       │
+     1 │ { dog { nam } }
+       │         ─┬─  
+       │          ╰─── this field is not in the schema
+    ───╯
+    "#);
+}
+
+fn validate_schema(schema: &str) -> Vec<String> {
+    match parse_type_system_document(schema).unwrap().validate() {
+        Ok(()) => vec![],
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    }
+}
+
+#[test]
+fn test_valid_schema() {
+    assert_eq!(validate_schema(SCHEMA), Vec::<String>::new());
+    assert_eq!(
+        validate_schema(
+            r#"
+            schema { query: Root }
+            type Root { node(id: ID!): Node, search: [SearchResult!] }
+            interface Node { id: ID! }
+            interface Named implements Node { id: ID! name(full: Boolean): String }
+            type User implements Named & Node {
+              id: ID!
+              name(full: Boolean, locale: String): String!
+              friends: [User!]!
+            }
+            extend type User { email: String }
+            union SearchResult = User
+            input Filter @oneOf { id: ID, name: String }
+            input Tree { children: [Tree!]!, parent: Tree }
+            extend scalar String @specifiedBy(url: "https://example.com")
+            "#
+        ),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn test_schema_definitions() {
+    assert_eq!(
+        validate_schema(
+            r#"
+            schema { query: Query query: Query mutation: Input }
+            schema { query: Query }
+            type Query { a: Int }
+            type Query { b: Int }
+            directive @thing on FIELD
+            directive @thing on FIELD
+            input Input { a: Int }
+            extend type Missing { a: Int }
+            extend enum Input { A }
+            type __Reserved { __field: Int }
+            "#
+        ),
+        vec![
+            "the query root type is defined more than once",
+            "the mutation root type must be an object, but Input is not",
+            "there is more than one schema definition",
+            "there is more than one type named Query",
+            "there is more than one directive named @thing",
+            "can't extend Missing as it isn't defined",
+            "this extension of Input is a different kind of type from its definition",
+            "the name __Reserved is reserved, as it starts with two underscores",
+            "the name __field is reserved, as it starts with two underscores",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_types() {
+    assert_eq!(
+        validate_schema(
+            r#"
+            type Query {
+              a: Int
+              a: Int
+              input: Input
+              missing: Missing
+              withArgs(arg: Query, arg: Int): Int
+            }
+            input Input { query: Query, query: Int }
+            enum Empty
+            enum Letters { A B A }
+            union Union = Query | Input | Query
+            union EmptyUnion
+            "#
+        ),
+        vec![
+            "the field a is defined more than once on Query",
+            "Query.input has type Input which is not an output type",
+            "unknown type: Missing",
+            "Query.withArgs(arg:) has type Query which is not an input type",
+            "the argument arg is defined more than once on Query.withArgs",
+            "Input.query has type Query which is not an input type",
+            "the field query is defined more than once on Input",
+            "the enum Empty must define at least one value",
+            "the value A is defined more than once on Letters",
+            "the union Union can only contain object types, but Input is not an object",
+            "Query is a member of the union Union more than once",
+            "the union EmptyUnion must have at least one member",
+        ]
+    );
+}
+
+#[test]
+fn test_interface_implementations() {
+    assert_eq!(
+        validate_schema(
+            r#"
+            type Query { node: Node }
+            interface Node { id: ID! }
+            interface Named implements Node { id: ID! name(full: Boolean): String }
+            interface Looped implements Looped { id: ID }
+            type NotAnInterface { id: ID }
+            type MissingTransitive implements Named {
+              id: ID!
+              name(full: Boolean): String
+            }
+            type BadFields implements Node & Named & NotAnInterface & Node {
+              id: ID
+              name(full: String, extra: Int!): String
+            }
+            type MissingField implements Node & Named {
+              id: ID!
+              name: String
+            }
+            "#
+        ),
+        vec![
+            "the interface Looped can't implement itself",
+            "MissingTransitive must implement Node because it implements Named",
+            "BadFields can't implement NotAnInterface as it is not an interface",
+            "BadFields implements Node more than once",
+            "BadFields.id has type ID which is not compatible with Node.id of type ID!",
+            "BadFields.id has type ID which is not compatible with Named.id of type ID!",
+            "BadFields.name(full:) has type String but the interface expects Boolean",
+            "BadFields.name(extra:) must be optional as it is not an argument of Named.name",
+            "MissingField.name is missing the argument full from Named.name",
+        ]
+    );
+}
+
+#[test]
+fn test_input_objects() {
+    assert_eq!(
+        validate_schema(
+            r#"
+            type Query { a(one: OneOf, cycle: Cycle): Int }
+            input OneOf @oneOf { a: Int!, b: String = "hello", c: Boolean }
+            input Cycle { other: Other! }
+            input Other { cycle: Cycle!, list: [Other!]! }
+            "#
+        ),
+        vec![
+            "OneOf.a must be nullable and have no default, as its type is a @oneOf input object",
+            "OneOf.b must be nullable and have no default, as its type is a @oneOf input object",
+            "the input object Cycle references itself through non-nullable fields",
+            "the input object Other references itself through non-nullable fields",
+        ]
+    );
+}
@@ -117,6 +117,10 @@ type OverrideMap = HashMap<SchemaCoordinate, String>;
 impl Generator {
     pub fn new(schema: impl AsRef<str>) -> Result<Self, SchemaParseError> {
         let schema = cynic_parser::parse_type_system_document(schema.as_ref())?;
+
+        // Fold any type extensions into their definitions.  We're not a schema validator,
+        // so if there are conflicting definitions we just use the schema as written.
+        let schema = schema.normalize().unwrap_or(schema);
         let (schema, typename_id) = add_builtins(schema);
 
         Ok(Generator {
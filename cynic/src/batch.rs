@@ -0,0 +1,279 @@
+//! Support for sending several operations in a single request.
+//!
+//! Some servers accept a JSON array of operations in place of a single
+//! operation, and respond with an array of results in the same order.  A
+//! [`Batch`] collects operations to send this way, and hands out a
+//! [`BatchHandle`] for each of them that can be used to get its typed result
+//! out of the [`BatchResponse`].
+
+use std::marker::PhantomData;
+
+use serde::{Deserialize, de::IgnoredAny};
+use serde_json::Value;
+
+use crate::{GraphQlResponse, Operation};
+
+/// A set of operations to be sent to the server in a single request.
+///
+/// ```rust,no_run
+/// # mod schema {
+/// #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(
+/// #    schema_path = "../schemas/starwars.schema.graphql",
+/// #    schema_module = "schema",
+/// # )]
+/// # struct Film {
+/// #    title: Option<String>,
+/// # }
+/// #
+/// # #[derive(cynic::QueryVariables)]
+/// # struct FilmArguments {
+/// #     id: Option<cynic::Id>,
+/// # }
+/// #
+/// # #[derive(cynic::QueryFragment)]
+/// # #[cynic(
+/// #     schema_path = "../schemas/starwars.schema.graphql",
+/// #     schema_module = "schema",
+/// #     graphql_type = "Root",
+/// #     variables = "FilmArguments"
+/// # )]
+/// # struct FilmQuery {
+/// #     #[arguments(id: $id)]
+/// #     film: Option<Film>,
+/// # }
+/// use cynic::{Batch, QueryBuilder, http::ReqwestExt};
+///
+/// # async move {
+/// let mut batch = Batch::new();
+/// let first = batch
+///     .add(FilmQuery::build(FilmArguments {
+///         id: Some("ZmlsbXM6MQ==".into()),
+///     }))
+///     .unwrap();
+/// let second = batch
+///     .add(FilmQuery::build(FilmArguments {
+///         id: Some("ZmlsbXM6Mg==".into()),
+///     }))
+///     .unwrap();
+///
+/// let response = reqwest::Client::new()
+///     .post("https://swapi-graphql.netlify.app/.netlify/functions/index")
+///     .run_graphql_batch(batch)
+///     .await
+///     .unwrap();
+///
+/// let first = response.get(first).unwrap();
+/// let second = response.get(second).unwrap();
+/// # };
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Batch {
+    operations: Vec<Value>,
+}
+
+impl Batch {
+    /// Creates an empty `Batch`
+    pub fn new() -> Self {
+        Batch::default()
+    }
+
+    /// Adds an operation to the batch, returning a handle that can be used
+    /// to get its result from the `BatchResponse`.
+    ///
+    /// This will fail if the operation can't be serialized as JSON - e.g. if
+    /// its variables contain an `Upload`, which can't be sent in a batch.
+    pub fn add<ResponseData, Vars>(
+        &mut self,
+        operation: impl AsRef<Operation<ResponseData, Vars>>,
+    ) -> Result<BatchHandle<ResponseData>, serde_json::Error>
+    where
+        Vars: serde::Serialize,
+    {
+        let operation = serde_json::to_value(operation.as_ref())?;
+
+        let index = self.operations.len();
+        self.operations.push(operation);
+
+        Ok(BatchHandle {
+            index,
+            phantom: PhantomData,
+        })
+    }
+
+    /// The number of operations in the batch
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Returns true if the batch contains no operations
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+}
+
+impl serde::Serialize for Batch {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.operations.serialize(serializer)
+    }
+}
+
+/// A handle to one of the operations in a `Batch`
+pub struct BatchHandle<ResponseData, ErrorExtensions = IgnoredAny> {
+    index: usize,
+    phantom: PhantomData<fn() -> (ResponseData, ErrorExtensions)>,
+}
+
+impl<ResponseData> BatchHandle<ResponseData, IgnoredAny> {
+    /// Sets the type that will be deserialized for the extensions fields of any errors in the response
+    pub fn retain_extensions<ErrorExtensions>(self) -> BatchHandle<ResponseData, ErrorExtensions>
+    where
+        ErrorExtensions: serde::de::DeserializeOwned,
+    {
+        BatchHandle {
+            index: self.index,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<ResponseData, ErrorExtensions> BatchHandle<ResponseData, ErrorExtensions> {
+    /// The position of this handles operation within the batch
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<ResponseData, ErrorExtensions> Clone for BatchHandle<ResponseData, ErrorExtensions> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<ResponseData, ErrorExtensions> Copy for BatchHandle<ResponseData, ErrorExtensions> {}
+
+impl<ResponseData, ErrorExtensions> std::fmt::Debug for BatchHandle<ResponseData, ErrorExtensions> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BatchHandle")
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// The response to a `Batch`
+#[derive(Clone, Debug)]
+pub struct BatchResponse {
+    results: Vec<Value>,
+}
+
+impl BatchResponse {
+    /// Creates a `BatchResponse` from the JSON body of a response to a batch
+    /// of `len` operations.
+    ///
+    /// Servers that don't support batching will usually respond with a single
+    /// GraphQL response containing errors.  In that case every operation in
+    /// the batch gets that response.
+    pub(crate) fn from_json(body: Value, len: usize) -> Result<Self, serde_json::Error> {
+        match body {
+            Value::Array(results) => Ok(BatchResponse { results }),
+            body => {
+                // Make sure this is actually a GraphQL response
+                serde_json::from_value::<GraphQlResponse<IgnoredAny>>(body.clone())?;
+                Ok(BatchResponse {
+                    results: vec![body; len],
+                })
+            }
+        }
+    }
+
+    /// Gets the result of one of the operations in the batch
+    pub fn get<ResponseData, ErrorExtensions>(
+        &self,
+        handle: BatchHandle<ResponseData, ErrorExtensions>,
+    ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, BatchResultError>
+    where
+        ResponseData: serde::de::DeserializeOwned,
+        ErrorExtensions: serde::de::DeserializeOwned,
+    {
+        let result = self
+            .results
+            .get(handle.index)
+            .ok_or(BatchResultError::MissingResult(handle.index))?;
+
+        Ok(GraphQlResponse::deserialize(result)?)
+    }
+
+    /// The number of results in the response
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns true if the response contains no results
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+/// An error getting the result of an operation out of a `BatchResponse`
+#[derive(thiserror::Error, Debug)]
+pub enum BatchResultError {
+    /// The server didn't return a result for the operation
+    #[error("The server didn't return a result for operation {0} of the batch")]
+    MissingResult(usize),
+
+    /// The result for the operation couldn't be decoded
+    #[error("Error decoding result: {0}")]
+    Decode(#[from] serde_json::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn handle<ResponseData>(index: usize) -> BatchHandle<ResponseData> {
+        BatchHandle {
+            index,
+            phantom: PhantomData,
+        }
+    }
+
+    #[test]
+    fn test_results_by_handle() {
+        let response =
+            BatchResponse::from_json(json!([{"data": 1}, {"errors": [{"message": "Oops"}]}]), 3)
+                .unwrap();
+
+        let first = response.get(handle::<i32>(0)).unwrap();
+        assert_eq!(first.data, Some(1));
+
+        let second = response.get(handle::<i32>(1)).unwrap();
+        assert_eq!(second.errors.unwrap()[0].message, "Oops");
+
+        assert!(matches!(
+            response.get(handle::<i32>(2)),
+            Err(BatchResultError::MissingResult(2))
+        ));
+    }
+
+    #[test]
+    fn test_non_batched_response() {
+        let response =
+            BatchResponse::from_json(json!({"errors": [{"message": "Batching disabled"}]}), 2)
+                .unwrap();
+
+        assert_eq!(response.len(), 2);
+        assert_eq!(
+            response.get(handle::<i32>(1)).unwrap().errors.unwrap()[0].message,
+            "Batching disabled"
+        );
+        assert!(BatchResponse::from_json(json!("nope"), 2).is_err());
+    }
+}
@@ -0,0 +1,259 @@
+use std::{sync::Arc, time::Duration};
+
+use futures_util::future::{Either, select};
+
+use super::{BoxFuture, ClientError, Request, Response, Transport};
+
+/// Middleware that can inspect or modify the requests & responses of a
+/// [`Client`](super::Client).
+///
+/// Middleware should call [`Next::run`] to pass the request on to the rest of
+/// the stack, and eventually the transport.
+///
+/// ```rust
+/// use cynic::client::{BoxFuture, ClientError, Middleware, Next, Request, Response};
+///
+/// struct RequestId;
+///
+/// impl Middleware for RequestId {
+///     fn handle<'a>(
+///         &'a self,
+///         mut request: Request,
+///         next: Next<'a>,
+///     ) -> BoxFuture<'a, Result<Response, ClientError>> {
+///         request.set_header("X-Request-Id", "1234");
+///         next.run(request)
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Handles a request, usually by passing it on to `next`
+    fn handle<'a>(
+        &'a self,
+        request: Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<Response, ClientError>>;
+}
+
+/// The remainder of a middleware stack.
+#[derive(Clone, Copy)]
+pub struct Next<'a> {
+    middleware: &'a [Arc<dyn Middleware>],
+    transport: &'a dyn Transport,
+}
+
+impl<'a> Next<'a> {
+    pub(super) fn new(middleware: &'a [Arc<dyn Middleware>], transport: &'a dyn Transport) -> Self {
+        Next {
+            middleware,
+            transport,
+        }
+    }
+
+    /// Runs the rest of the middleware stack, and then sends the request with
+    /// the transport.
+    ///
+    /// `Next` is `Copy`, so this can be called more than once if a middleware
+    /// needs to send a request several times.
+    pub fn run(self, request: Request) -> BoxFuture<'a, Result<Response, ClientError>> {
+        match self.middleware.split_first() {
+            Some((current, rest)) => current.handle(request, Next::new(rest, self.transport)),
+            None => self.transport.send(request),
+        }
+    }
+}
+
+/// Middleware that sets a header on every request.
+///
+/// This is mostly useful for authentication:
+///
+/// ```rust
+/// # use cynic::client::SetHeader;
+/// let auth = SetHeader::bearer_auth("my-token");
+/// let api_key = SetHeader::new("X-Api-Key", "my-key");
+/// ```
+#[derive(Clone, Debug)]
+pub struct SetHeader {
+    name: String,
+    value: String,
+}
+
+impl SetHeader {
+    /// Creates a middleware that sets the header `name` to `value`
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        SetHeader {
+            name: name.into(),
+            value: value.into(),
+        }
+    }
+
+    /// Creates a middleware that sets an `Authorization` header with the given
+    /// bearer token
+    pub fn bearer_auth(token: impl std::fmt::Display) -> Self {
+        SetHeader::new("Authorization", format!("Bearer {token}"))
+    }
+}
+
+impl Middleware for SetHeader {
+    fn handle<'a>(
+        &'a self,
+        mut request: Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<Response, ClientError>> {
+        request.set_header(self.name.clone(), self.value.clone());
+        next.run(request)
+    }
+}
+
+/// Middleware that retries requests that fail with a transport error, a
+/// timeout, or a 5xx or 429 status.
+///
+/// Each retry waits for twice as long as the one before it, starting from the
+/// configured backoff.
+///
+/// Note that this will retry mutations as well as queries, so you should make
+/// sure your mutations are safe to retry before using this.
+#[derive(Clone, Debug)]
+pub struct Retry {
+    max_retries: u32,
+    backoff: Duration,
+}
+
+impl Retry {
+    /// Creates a middleware that retries failing requests up to `max_retries`
+    /// times
+    pub fn new(max_retries: u32) -> Self {
+        Retry {
+            max_retries,
+            backoff: Duration::from_millis(100),
+        }
+    }
+
+    /// Sets how long to wait before the first retry.  Defaults to 100ms.
+    pub fn backoff(mut self, backoff: Duration) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    fn should_retry(result: &Result<Response, ClientError>) -> bool {
+        match result {
+            Ok(response) => response.status >= 500 || response.status == 429,
+            Err(ClientError::Transport(_) | ClientError::Timeout) => true,
+            Err(_) => false,
+        }
+    }
+}
+
+impl Middleware for Retry {
+    fn handle<'a>(
+        &'a self,
+        request: Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<Response, ClientError>> {
+        Box::pin(async move {
+            let mut backoff = self.backoff;
+            let mut retries = 0;
+            loop {
+                let result = next.run(request.clone()).await;
+                if retries >= self.max_retries || !Retry::should_retry(&result) {
+                    return result;
+                }
+
+                futures_timer::Delay::new(backoff).await;
+                backoff *= 2;
+                retries += 1;
+            }
+        })
+    }
+}
+
+/// Middleware that fails requests with [`ClientError::Timeout`] if they
+/// take longer than a given duration.
+///
+/// If this is used alongside [`Retry`] then its position in the stack matters:
+/// adding it before `Retry` limits the time taken by all attempts, adding it
+/// after limits the time taken by each attempt.
+#[derive(Clone, Debug)]
+pub struct Timeout {
+    duration: Duration,
+}
+
+impl Timeout {
+    /// Creates a middleware that times out requests after `duration`
+    pub fn new(duration: Duration) -> Self {
+        Timeout { duration }
+    }
+}
+
+impl Middleware for Timeout {
+    fn handle<'a>(
+        &'a self,
+        request: Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<Response, ClientError>> {
+        Box::pin(async move {
+            match select(next.run(request), futures_timer::Delay::new(self.duration)).await {
+                Either::Left((result, _)) => result,
+                Either::Right(_) => Err(ClientError::Timeout),
+            }
+        })
+    }
+}
+
+/// Middleware that logs requests & their outcome with the [`log`] crate.
+#[derive(Clone, Debug)]
+pub struct Logging {
+    level: log::Level,
+}
+
+impl Logging {
+    /// Creates a middleware that logs at the `Debug` level
+    pub fn new() -> Self {
+        Logging {
+            level: log::Level::Debug,
+        }
+    }
+
+    /// Sets the level to log successful requests at.
+    ///
+    /// Failures are always logged at the `Warn` level.
+    pub fn level(mut self, level: log::Level) -> Self {
+        self.level = level;
+        self
+    }
+}
+
+impl Default for Logging {
+    fn default() -> Self {
+        Logging::new()
+    }
+}
+
+impl Middleware for Logging {
+    fn handle<'a>(
+        &'a self,
+        request: Request,
+        next: Next<'a>,
+    ) -> BoxFuture<'a, Result<Response, ClientError>> {
+        Box::pin(async move {
+            let name = request.operation_name.clone().unwrap_or("<unnamed>".into());
+            log::log!(self.level, "Sending GraphQL operation {name}");
+
+            let result = next.run(request).await;
+            match &result {
+                Ok(response) if response.is_success() => log::log!(
+                    self.level,
+                    "GraphQL operation {name} responded with status {}",
+                    response.status
+                ),
+                Ok(response) => log::warn!(
+                    "GraphQL operation {name} responded with status {}",
+                    response.status
+                ),
+                Err(error) => log::warn!("GraphQL operation {name} failed: {error}"),
+            }
+
+            result
+        })
+    }
+}
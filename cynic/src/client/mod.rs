@@ -0,0 +1,736 @@
+//! A transport agnostic GraphQL client.
+//!
+//! The [`Client`] in this module takes care of serializing operations &
+//! decoding responses, and runs each request through a stack of
+//! [`Middleware`] before handing it off to a [`Transport`] to actually send.
+//!
+//! Transports are only responsible for moving bytes around, so adding support
+//! for a new HTTP client means implementing a single method.  Cynic provides
+//! [`ReqwestTransport`] & [`SurfTransport`] when the `http-reqwest` &
+//! `http-surf` features are enabled.
+//!
+//! Persisted queries (with the `persisted-queries` feature), file uploads,
+//! batches & incremental delivery are all supported, as they are by the
+//! extension traits in [`crate::http`].
+//!
+//! This is hidden behind the `client` feature flag.
+//!
+//! ```rust,no_run
+//! # mod schema {
+//! #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(
+//! #    schema_path = "../schemas/starwars.schema.graphql",
+//! #    schema_module = "schema",
+//! # )]
+//! # struct Film {
+//! #    title: Option<String>,
+//! #    director: Option<String>
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(
+//! #     schema_path = "../schemas/starwars.schema.graphql",
+//! #     schema_module = "schema",
+//! #     graphql_type = "Root"
+//! # )]
+//! # struct FilmDirectorQuery {
+//! #     #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+//! #     film: Option<Film>,
+//! # }
+//! use std::time::Duration;
+//!
+//! use cynic::{
+//!     QueryBuilder,
+//!     client::{Client, Logging, ReqwestTransport, Retry, SetHeader, Timeout},
+//! };
+//!
+//! # async move {
+//! let client = Client::new(ReqwestTransport::new(
+//!     reqwest::Client::new(),
+//!     "https://swapi-graphql.netlify.app/.netlify/functions/index",
+//! ))
+//! .with(Logging::new())
+//! .with(Retry::new(3))
+//! .with(Timeout::new(Duration::from_secs(10)))
+//! .with(SetHeader::bearer_auth("my-token"));
+//!
+//! let response = client
+//!     .run_graphql(FilmDirectorQuery::build(()))
+//!     .await
+//!     .unwrap();
+//!
+//! println!(
+//!     "The director is {}",
+//!     response.data
+//!         .and_then(|d| d.film)
+//!         .and_then(|f| f.director)
+//!         .unwrap()
+//! );
+//! # };
+//! ```
+
+mod middleware;
+mod transports;
+
+use std::{borrow::Cow, future::Future, marker::PhantomData, pin::Pin, sync::Arc};
+
+pub use self::middleware::{Logging, Middleware, Next, Retry, SetHeader, Timeout};
+
+#[cfg(feature = "http-reqwest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http-reqwest")))]
+pub use self::transports::ReqwestTransport;
+
+#[cfg(feature = "http-surf")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http-surf")))]
+pub use self::transports::SurfTransport;
+
+use futures_util::Stream;
+use serde::de::IgnoredAny;
+
+#[cfg(feature = "persisted-queries")]
+use crate::http::persisted_queries::PersistedQueryMethod;
+use crate::{
+    Batch, BatchResponse, GraphQlResponse, Operation,
+    http::{
+        incremental::{INCREMENTAL_ACCEPT, IncrementalMerger},
+        streaming::StreamDecoder,
+    },
+    upload::OperationBody,
+};
+
+/// A boxed future, as returned by [`Transport`] & [`Middleware`]
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A boxed future, as returned by [`Transport`] & [`Middleware`]
+#[cfg(target_arch = "wasm32")]
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A boxed stream, as returned by [`Client::run_graphql_incremental`]
+#[cfg(not(target_arch = "wasm32"))]
+pub type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + Send + 'a>>;
+
+/// A boxed stream, as returned by [`Client::run_graphql_incremental`]
+#[cfg(target_arch = "wasm32")]
+pub type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + 'a>>;
+
+/// Something that can send a serialized GraphQL operation to a server.
+///
+/// Transports don't need to know anything about GraphQL - the [`Client`]
+/// serializes operations before they're sent and decodes the responses
+/// afterwards.
+pub trait Transport: Send + Sync {
+    /// Sends a request to the server, returning its response.
+    ///
+    /// Implementations should only return an error if they failed to get a
+    /// response: error statuses should be returned as a [`Response`] so the
+    /// client can try to decode any GraphQL errors in the body.
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, ClientError>>;
+}
+
+impl<T> Transport for Arc<T>
+where
+    T: Transport + ?Sized,
+{
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, ClientError>> {
+        (**self).send(request)
+    }
+}
+
+/// A request that is ready to be sent by a [`Transport`]
+#[derive(Clone, Debug)]
+pub struct Request {
+    /// The name of the operation this request will run, if it has one
+    pub operation_name: Option<Cow<'static, str>>,
+
+    /// The HTTP method to send this request with
+    pub method: Method,
+
+    /// Any parameters that should be added to the query string of the URL
+    pub query_params: Vec<(String, String)>,
+
+    /// Any headers that should be sent along with this request
+    pub headers: Vec<(String, String)>,
+
+    /// The serialized operation.  This is empty for `GET` requests.
+    pub body: Vec<u8>,
+}
+
+/// The HTTP method of a [`Request`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Method {
+    /// A `POST` request, with the operation in the body
+    #[default]
+    Post,
+
+    /// A `GET` request, with the operation in the query string
+    Get,
+}
+
+impl Request {
+    /// Creates a `POST` request with the given body & `Content-Type`
+    fn post(
+        operation_name: Option<Cow<'static, str>>,
+        content_type: impl Into<String>,
+        body: Vec<u8>,
+    ) -> Self {
+        Request {
+            operation_name,
+            method: Method::Post,
+            query_params: Vec::new(),
+            headers: vec![("Content-Type".into(), content_type.into())],
+            body,
+        }
+    }
+
+    /// Serializes `operation` into a request, using a multipart request if it
+    /// contains any [`Upload`](crate::Upload)s
+    fn for_operation<ResponseData, Vars>(
+        operation: &Operation<ResponseData, Vars>,
+    ) -> Result<Self, ClientError>
+    where
+        Vars: serde::Serialize,
+    {
+        let operation_name = operation.operation_name.clone();
+        match OperationBody::new(operation).map_err(ClientError::Serialization)? {
+            OperationBody::Json(body) => {
+                Ok(Request::post(operation_name, "application/json", body))
+            }
+            OperationBody::Multipart(multipart) => {
+                let (content_type, body) = multipart.into_form_data();
+                Ok(Request::post(operation_name, content_type, body))
+            }
+        }
+    }
+
+    /// Gets the value of a header on this request.
+    ///
+    /// Header names are compared case insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Sets a header on this request, replacing any existing header with the
+    /// same name.
+    pub fn set_header(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        self.headers
+            .retain(|(existing, _)| !existing.eq_ignore_ascii_case(&name));
+        self.headers.push((name, value.into()));
+    }
+}
+
+/// A response that was received by a [`Transport`]
+#[derive(Clone, Debug)]
+pub struct Response {
+    /// The HTTP status code of the response
+    pub status: u16,
+
+    /// The headers that were returned with the response
+    pub headers: Vec<(String, String)>,
+
+    /// The body of the response
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    /// Gets the value of a header on this response.
+    ///
+    /// Header names are compared case insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+
+    /// Whether the status of this response is in the 2xx range
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+fn find_header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|(header, _)| header.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.as_str())
+}
+
+/// The error type returned by the [`Client`]
+#[derive(thiserror::Error, Debug)]
+pub enum ClientError {
+    /// An error from the underlying transport
+    #[error("Error from transport: {0}")]
+    Transport(Box<dyn std::error::Error + Send + Sync>),
+
+    /// An error response from the server with the given status code and body.
+    #[error("Server returned {0}: {1}")]
+    ErrorResponse(u16, String),
+
+    /// An error serializing the operation
+    #[error("Couldn't serialize operation: {0}")]
+    Serialization(#[source] serde_json::Error),
+
+    /// An error decoding the response from the server
+    #[error("Couldn't decode response: {0}")]
+    Decode(#[source] serde_json::Error),
+
+    /// The request didn't complete before its [`Timeout`]
+    #[error("The request timed out")]
+    Timeout,
+
+    /// The server responded to an incremental operation with a content type
+    /// that we don't know how to decode.
+    #[error("Server responded with an unsupported content type: {0}")]
+    UnsupportedContentType(String),
+
+    /// An error decoding one of the payloads in the response to an
+    /// incremental operation.
+    #[error("Error decoding incremental response: {0}")]
+    IncrementalDecode(String),
+}
+
+impl ClientError {
+    /// Creates a `ClientError` from an error in a [`Transport`]
+    pub fn transport(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> Self {
+        ClientError::Transport(error.into())
+    }
+}
+
+/// A GraphQL client that runs operations over a [`Transport`].
+///
+/// Clients are cheap to clone, and clones share the same transport &
+/// middleware.
+#[derive(Clone)]
+pub struct Client {
+    transport: Arc<dyn Transport>,
+    middleware: Vec<Arc<dyn Middleware>>,
+}
+
+impl Client {
+    /// Creates a new `Client` that sends requests using `transport`
+    pub fn new(transport: impl Transport + 'static) -> Self {
+        Client {
+            transport: Arc::new(transport),
+            middleware: Vec::new(),
+        }
+    }
+
+    /// Adds a middleware to this client.
+    ///
+    /// Middleware run in the order they're added: the first middleware added
+    /// sees each request first and its response last.
+    pub fn with(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Runs a GraphQL operation, deserializes the response and returns the
+    /// result.
+    pub fn run_graphql<ResponseData, Vars>(
+        &self,
+        operation: impl AsRef<Operation<ResponseData, Vars>>,
+    ) -> ClientRequestBuilder<ResponseData>
+    where
+        Vars: serde::Serialize,
+        ResponseData: serde::de::DeserializeOwned + 'static,
+    {
+        ClientRequestBuilder {
+            client: self.clone(),
+            request: Request::for_operation(operation.as_ref()),
+            #[cfg(feature = "persisted-queries")]
+            persisted_query: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Runs a GraphQL operation that uses `@defer` or `@stream`, resolving to
+    /// a stream of progressively more complete responses.
+    ///
+    /// Each incremental payload from the server is merged into the response
+    /// so far, so every item in the stream is a full response.  Transports
+    /// buffer the whole response, so the stream is only available once the
+    /// server has finished responding.
+    pub fn run_graphql_incremental<ResponseData, Vars>(
+        &self,
+        operation: impl AsRef<Operation<ResponseData, Vars>>,
+    ) -> ClientIncrementalBuilder<ResponseData>
+    where
+        Vars: serde::Serialize,
+        ResponseData: serde::de::DeserializeOwned + 'static,
+    {
+        let request = Request::for_operation(operation.as_ref()).map(|mut request| {
+            request.set_header("Accept", INCREMENTAL_ACCEPT);
+            request
+        });
+
+        ClientIncrementalBuilder {
+            client: self.clone(),
+            request,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Runs a batch of GraphQL operations in a single request.
+    ///
+    /// The results of each operation can be fetched from the `BatchResponse`
+    /// with the handles returned by `Batch::add`.
+    pub fn run_graphql_batch(&self, batch: Batch) -> ClientBatchBuilder {
+        let request = serde_json::to_vec(&batch)
+            .map(|body| Request::post(None, "application/json", body))
+            .map_err(ClientError::Serialization);
+
+        ClientBatchBuilder {
+            client: self.clone(),
+            request,
+            len: batch.len(),
+        }
+    }
+
+    /// Sends a request through this clients middleware & transport.
+    pub async fn send(&self, request: Request) -> Result<Response, ClientError> {
+        Next::new(&self.middleware, self.transport.as_ref())
+            .run(request)
+            .await
+    }
+}
+
+/// A builder for requests made with a [`Client`]
+///
+/// Implements `IntoFuture`, users should `.await` the builder or call
+/// `into_future` directly when they're ready to send the request.
+pub struct ClientRequestBuilder<ResponseData, ErrorExtensions = serde::de::IgnoredAny> {
+    client: Client,
+    request: Result<Request, ClientError>,
+    #[cfg(feature = "persisted-queries")]
+    persisted_query: Option<PersistedQueryMethod>,
+    _marker: PhantomData<fn() -> (ResponseData, ErrorExtensions)>,
+}
+
+impl<ResponseData, Errors> ClientRequestBuilder<ResponseData, Errors> {
+    /// Sets a header on this request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Ok(request) = &mut self.request {
+            request.set_header(name, value);
+        }
+        self
+    }
+
+    /// Sends this operation as an [automatic persisted query][apq].
+    ///
+    /// The first request will contain only the hash of the query.  If the
+    /// server doesn't recognise that hash the request will be retried with
+    /// the full query.
+    ///
+    /// [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+    #[cfg(feature = "persisted-queries")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+    pub fn persisted_query(mut self) -> Self {
+        self.persisted_query = Some(PersistedQueryMethod::Post);
+        self
+    }
+
+    /// Sends this operation as an [automatic persisted query][apq], using
+    /// a GET request for the initial hash-only request.
+    ///
+    /// This allows CDNs to cache the response to persisted queries.  Any
+    /// retry with the full query will still be sent as a POST.
+    ///
+    /// [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+    #[cfg(feature = "persisted-queries")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+    pub fn persisted_query_get(mut self) -> Self {
+        self.persisted_query = Some(PersistedQueryMethod::Get);
+        self
+    }
+
+    /// Sends only the ID of this operations document, rather than the
+    /// full query.
+    ///
+    /// This is for use with servers that only accept a safelist of trusted
+    /// documents, which can be generated with a
+    /// [`Manifest`](crate::persisted::Manifest).  Unlike
+    /// [`persisted_query`](Self::persisted_query) the full query is never
+    /// sent.
+    #[cfg(feature = "persisted-queries")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+    pub fn persisted_document(mut self) -> Self {
+        self.persisted_query = Some(PersistedQueryMethod::DocumentId);
+        self
+    }
+}
+
+impl<ResponseData> ClientRequestBuilder<ResponseData, serde::de::IgnoredAny> {
+    /// Sets the type that will be deserialized for the extensions fields of any errors in the response
+    pub fn retain_extensions<ErrorExtensions>(
+        self,
+    ) -> ClientRequestBuilder<ResponseData, ErrorExtensions>
+    where
+        ErrorExtensions: serde::de::DeserializeOwned,
+    {
+        let ClientRequestBuilder {
+            client,
+            request,
+            #[cfg(feature = "persisted-queries")]
+            persisted_query,
+            _marker,
+        } = self;
+
+        ClientRequestBuilder {
+            client,
+            request,
+            #[cfg(feature = "persisted-queries")]
+            persisted_query,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<ResponseData, Errors> std::future::IntoFuture for ClientRequestBuilder<ResponseData, Errors>
+where
+    ResponseData: serde::de::DeserializeOwned + 'static,
+    Errors: serde::de::DeserializeOwned + 'static,
+{
+    type Output = Result<GraphQlResponse<ResponseData, Errors>, ClientError>;
+
+    type IntoFuture =
+        BoxFuture<'static, Result<GraphQlResponse<ResponseData, Errors>, ClientError>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            #[cfg(feature = "persisted-queries")]
+            if let Some(method) = self.persisted_query {
+                let response = self
+                    .client
+                    .send_persisted_query(self.request?, method)
+                    .await?;
+                return decode_response(response);
+            }
+
+            let response = self.client.send(self.request?).await?;
+            decode_response(response)
+        })
+    }
+}
+
+#[cfg(feature = "persisted-queries")]
+impl Client {
+    /// Sends a request as a persisted query, retrying with the full query if
+    /// the server doesn't recognise it & `method` allows.
+    async fn send_persisted_query(
+        &self,
+        request: Request,
+        method: PersistedQueryMethod,
+    ) -> Result<Response, ClientError> {
+        use crate::http::persisted_queries::{PersistedQuery, should_send_full_query};
+
+        // Multipart requests can't be sent as persisted queries, so we just
+        // send those as normal
+        let Some(persisted_query) = PersistedQuery::from_body(&request.body) else {
+            return self.send(request).await;
+        };
+
+        let mut hash_request = request.clone();
+        match method {
+            PersistedQueryMethod::Post => {
+                hash_request.body = persisted_query.hash_only_body().to_string().into_bytes();
+            }
+            PersistedQueryMethod::DocumentId => {
+                hash_request.body = persisted_query.document_id_body().to_string().into_bytes();
+            }
+            PersistedQueryMethod::Get => {
+                hash_request.method = Method::Get;
+                hash_request.body = Vec::new();
+                hash_request
+                    .headers
+                    .retain(|(name, _)| !name.eq_ignore_ascii_case("content-type"));
+                hash_request.query_params = persisted_query
+                    .query_params()
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value))
+                    .collect();
+            }
+        }
+
+        let response = self.send(hash_request).await?;
+        if !method.can_retry() || !should_send_full_query(&response.body) {
+            return Ok(response);
+        }
+
+        let mut full_request = request;
+        full_request.body = persisted_query.full_body().to_string().into_bytes();
+        self.send(full_request).await
+    }
+}
+
+/// A builder for batches of operations sent with a [`Client`]
+///
+/// Implements `IntoFuture`, users should `.await` the builder or call
+/// `into_future` directly when they're ready to send the request.
+pub struct ClientBatchBuilder {
+    client: Client,
+    request: Result<Request, ClientError>,
+    len: usize,
+}
+
+impl ClientBatchBuilder {
+    /// Sets a header on this request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Ok(request) = &mut self.request {
+            request.set_header(name, value);
+        }
+        self
+    }
+}
+
+impl std::future::IntoFuture for ClientBatchBuilder {
+    type Output = Result<BatchResponse, ClientError>;
+
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let response = self.client.send(self.request?).await?;
+
+            let decoded = serde_json::from_slice(&response.body)
+                .and_then(|body| BatchResponse::from_json(body, self.len));
+
+            match decoded {
+                Ok(response) => Ok(response),
+                Err(_) if !response.is_success() => Err(error_response(&response)),
+                Err(error) => Err(ClientError::Decode(error)),
+            }
+        })
+    }
+}
+
+/// A builder for incremental operations sent with a [`Client`]
+///
+/// Implements `IntoFuture`, users should `.await` the builder or call
+/// `into_future` directly when they're ready to send the request.  This
+/// will resolve to a `Stream` of responses once the server has responded.
+pub struct ClientIncrementalBuilder<ResponseData, ErrorExtensions = serde::de::IgnoredAny> {
+    client: Client,
+    request: Result<Request, ClientError>,
+    _marker: PhantomData<fn() -> (ResponseData, ErrorExtensions)>,
+}
+
+impl<ResponseData, Errors> ClientIncrementalBuilder<ResponseData, Errors> {
+    /// Sets a header on this request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        if let Ok(request) = &mut self.request {
+            request.set_header(name, value);
+        }
+        self
+    }
+}
+
+impl<ResponseData> ClientIncrementalBuilder<ResponseData, serde::de::IgnoredAny> {
+    /// Sets the type that will be deserialized for the extensions fields of any errors in the response
+    pub fn retain_extensions<ErrorExtensions>(
+        self,
+    ) -> ClientIncrementalBuilder<ResponseData, ErrorExtensions>
+    where
+        ErrorExtensions: serde::de::DeserializeOwned,
+    {
+        let ClientIncrementalBuilder {
+            client, request, ..
+        } = self;
+
+        ClientIncrementalBuilder {
+            client,
+            request,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A stream of responses to an incremental operation
+pub type ResponseStream<ResponseData, ErrorExtensions = serde::de::IgnoredAny> =
+    BoxStream<'static, Result<GraphQlResponse<ResponseData, ErrorExtensions>, ClientError>>;
+
+impl<ResponseData, Errors> std::future::IntoFuture
+    for ClientIncrementalBuilder<ResponseData, Errors>
+where
+    ResponseData: serde::de::DeserializeOwned + Send + 'static,
+    Errors: serde::de::DeserializeOwned + Send + 'static,
+{
+    type Output = Result<ResponseStream<ResponseData, Errors>, ClientError>;
+
+    type IntoFuture = BoxFuture<'static, Self::Output>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let response = self.client.send(self.request?).await?;
+
+            let content_type = if response.is_success() {
+                response.header("content-type").unwrap_or_default()
+            } else {
+                // If the body looks like a GraphQL response we pass it on,
+                // otherwise we treat it as an error.
+                type AnyResponse = GraphQlResponse<IgnoredAny, IgnoredAny>;
+                if serde_json::from_slice::<AnyResponse>(&response.body).is_err() {
+                    return Err(error_response(&response));
+                }
+                "application/json"
+            };
+
+            let Some(mut decoder) = StreamDecoder::for_content_type(content_type) else {
+                return Err(ClientError::UnsupportedContentType(
+                    content_type.to_string(),
+                ));
+            };
+            decoder.push(&response.body);
+            decoder.finish();
+
+            let mut merger = IncrementalMerger::default();
+            let mut responses = Vec::new();
+            while let Some(payload) = decoder.next_payload() {
+                let payload = match payload.and_then(|payload| merger.apply(payload)) {
+                    Ok(Some(payload)) => Ok(payload),
+                    // This payload didn't change the response
+                    Ok(None) => continue,
+                    Err(error) => Err(error),
+                };
+
+                responses.push(
+                    payload
+                        .and_then(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+                        .map_err(ClientError::IncrementalDecode),
+                );
+            }
+
+            let stream: ResponseStream<ResponseData, Errors> =
+                Box::pin(futures_util::stream::iter(responses));
+
+            Ok(stream)
+        })
+    }
+}
+
+/// Decodes a `GraphQlResponse` from the response to a request.
+///
+/// Error responses are decoded as a `GraphQlResponse` if possible, as many
+/// servers return GraphQL errors with a non 2xx status.
+fn decode_response<ResponseData, ErrorExtensions>(
+    response: Response,
+) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, ClientError>
+where
+    ResponseData: serde::de::DeserializeOwned,
+    ErrorExtensions: serde::de::DeserializeOwned,
+{
+    if !response.is_success() {
+        return serde_json::from_slice(&response.body).map_err(|_| error_response(&response));
+    }
+
+    serde_json::from_slice(&response.body).map_err(ClientError::Decode)
+}
+
+/// Creates a [`ClientError::ErrorResponse`] from a response
+fn error_response(response: &Response) -> ClientError {
+    ClientError::ErrorResponse(
+        response.status,
+        String::from_utf8_lossy(&response.body).into_owned(),
+    )
+}
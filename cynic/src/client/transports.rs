@@ -0,0 +1,131 @@
+#[cfg(feature = "http-reqwest")]
+pub use self::reqwest_transport::ReqwestTransport;
+
+#[cfg(feature = "http-surf")]
+pub use self::surf_transport::SurfTransport;
+
+#[cfg(feature = "http-reqwest")]
+mod reqwest_transport {
+    use crate::client::{BoxFuture, ClientError, Method, Request, Response, Transport};
+
+    /// A [`Transport`] that sends requests to a URL with reqwest
+    #[derive(Clone, Debug)]
+    pub struct ReqwestTransport {
+        client: reqwest::Client,
+        url: String,
+    }
+
+    impl ReqwestTransport {
+        /// Creates a transport that sends requests to `url` with `client`
+        pub fn new(client: reqwest::Client, url: impl Into<String>) -> Self {
+            ReqwestTransport {
+                client,
+                url: url.into(),
+            }
+        }
+    }
+
+    impl Transport for ReqwestTransport {
+        fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, ClientError>> {
+            Box::pin(async move {
+                let mut url = reqwest::Url::parse(&self.url).map_err(ClientError::transport)?;
+                if !request.query_params.is_empty() {
+                    url.query_pairs_mut().extend_pairs(&request.query_params);
+                }
+
+                let mut builder = match request.method {
+                    Method::Post => self.client.post(url).body(request.body),
+                    Method::Get => self.client.get(url),
+                };
+                for (name, value) in &request.headers {
+                    builder = builder.header(name, value);
+                }
+
+                let response = builder.send().await.map_err(ClientError::transport)?;
+
+                let status = response.status().as_u16();
+                let headers = response
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        Some((name.to_string(), value.to_str().ok()?.to_string()))
+                    })
+                    .collect();
+                let body = response.bytes().await.map_err(ClientError::transport)?;
+
+                Ok(Response {
+                    status,
+                    headers,
+                    body: body.to_vec(),
+                })
+            })
+        }
+    }
+}
+
+#[cfg(feature = "http-surf")]
+mod surf_transport {
+    use crate::client::{BoxFuture, ClientError, Method, Request, Response, Transport};
+
+    /// A [`Transport`] that sends requests to a URL with surf
+    #[derive(Clone, Debug)]
+    pub struct SurfTransport {
+        client: surf::Client,
+        url: String,
+    }
+
+    impl SurfTransport {
+        /// Creates a transport that sends requests to `url` with `client`
+        pub fn new(client: surf::Client, url: impl Into<String>) -> Self {
+            SurfTransport {
+                client,
+                url: url.into(),
+            }
+        }
+    }
+
+    impl Transport for SurfTransport {
+        fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, ClientError>> {
+            Box::pin(async move {
+                let mut url = surf::Url::parse(&self.url).map_err(ClientError::transport)?;
+                if !request.query_params.is_empty() {
+                    url.query_pairs_mut().extend_pairs(&request.query_params);
+                }
+
+                let mut builder = match request.method {
+                    Method::Post => self
+                        .client
+                        .post(url)
+                        .body(surf::Body::from_bytes(request.body)),
+                    Method::Get => self.client.get(url),
+                };
+                for (name, value) in &request.headers {
+                    builder = builder.header(name.as_str(), value.as_str());
+                }
+
+                let mut response = builder.await.map_err(ClientError::transport)?;
+
+                let status = response.status() as u16;
+                let headers = response
+                    .iter()
+                    .flat_map(|(name, values)| {
+                        values
+                            .iter()
+                            .map(|value| (name.to_string(), value.to_string()))
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                let body = response
+                    .body_bytes()
+                    .await
+                    .map_err(ClientError::transport)?;
+
+                Ok(Response {
+                    status,
+                    headers,
+                    body,
+                })
+            })
+        }
+    }
+}
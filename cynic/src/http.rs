@@ -15,12 +15,26 @@ pub use reqwest_ext::ReqwestExt;
 #[cfg_attr(docsrs, doc(cfg(feature = "reqwest-blocking")))]
 pub use reqwest_blocking_ext::ReqwestBlockingExt;
 
+#[cfg(any(feature = "http-reqwest", feature = "client"))]
+pub(crate) mod streaming;
+
+#[cfg(any(feature = "http-reqwest", feature = "client"))]
+pub(crate) mod incremental;
+
+#[cfg(all(
+    any(feature = "http-reqwest", feature = "http-surf", feature = "client"),
+    feature = "persisted-queries"
+))]
+pub(crate) mod persisted_queries;
+
 #[cfg(feature = "http-surf")]
 mod surf_ext {
-    use serde_json::json;
     use std::{future::Future, pin::Pin};
 
-    use crate::{GraphQlResponse, Operation};
+    use crate::{Batch, BatchResponse, GraphQlResponse, Operation, upload::OperationBody};
+
+    #[cfg(feature = "persisted-queries")]
+    use super::persisted_queries::PersistedQueryMethod;
 
     type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
@@ -81,6 +95,66 @@ mod surf_ext {
         where
             Vars: serde::Serialize,
             ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a batch of GraphQL operations in a single request.
+        ///
+        /// The results of each operation can be fetched from the
+        /// `BatchResponse` with the handles returned by `Batch::add`.
+        fn run_graphql_batch(
+            self,
+            batch: Batch,
+        ) -> BoxFuture<'static, Result<BatchResponse, surf::Error>>;
+
+        /// Runs a GraphQL query as an [automatic persisted query][apq].
+        ///
+        /// The first request will contain only the hash of the query.  If the
+        /// server doesn't recognise that hash the request will be retried with
+        /// the full query.
+        ///
+        /// [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        fn run_persisted_query<ResponseData, Vars>(
+            self,
+            operation: Operation<ResponseData, Vars>,
+        ) -> BoxFuture<'static, Result<GraphQlResponse<ResponseData>, surf::Error>>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a GraphQL query as an [automatic persisted query][apq], using
+        /// a GET request for the initial hash-only request.
+        ///
+        /// Any retry with the full query will still be sent as a POST.
+        ///
+        /// [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        fn run_persisted_query_get<ResponseData, Vars>(
+            self,
+            operation: Operation<ResponseData, Vars>,
+        ) -> BoxFuture<'static, Result<GraphQlResponse<ResponseData>, surf::Error>>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a GraphQL query by sending only the ID of its document, rather
+        /// than the full query.
+        ///
+        /// This is for use with servers that only accept a safelist of trusted
+        /// documents, which can be generated with a
+        /// [`Manifest`](crate::persisted::Manifest).  Unlike
+        /// [`run_persisted_query`](Self::run_persisted_query) the full query is
+        /// never sent.
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        fn run_persisted_document<ResponseData, Vars>(
+            self,
+            operation: Operation<ResponseData, Vars>,
+        ) -> BoxFuture<'static, Result<GraphQlResponse<ResponseData>, surf::Error>>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static;
     }
 
     impl SurfExt for surf::RequestBuilder {
@@ -92,9 +166,19 @@ mod surf_ext {
             Vars: serde::Serialize,
             ResponseData: serde::de::DeserializeOwned + 'static,
         {
-            let operation = json!(&operation);
+            let body = OperationBody::new(&operation);
             Box::pin(async move {
-                let mut response = self.body(operation).await?;
+                let (content_type, body) = match body? {
+                    OperationBody::Json(body) => (surf::http::mime::JSON, body),
+                    OperationBody::Multipart(multipart) => {
+                        let (content_type, body) = multipart.into_form_data();
+                        (content_type.parse()?, body)
+                    }
+                };
+                let mut response = self
+                    .body(surf::Body::from_bytes(body))
+                    .content_type(content_type)
+                    .await?;
 
                 if !response.status().is_success() {
                     let body_string = response.body_string().await?;
@@ -112,6 +196,144 @@ mod surf_ext {
                 response.body_json::<GraphQlResponse<ResponseData>>().await
             })
         }
+
+        fn run_graphql_batch(
+            self,
+            batch: Batch,
+        ) -> BoxFuture<'static, Result<BatchResponse, surf::Error>> {
+            let len = batch.len();
+            let batch = serde_json::to_vec(&batch);
+            Box::pin(async move {
+                let batch = surf::Body::from_bytes(batch?);
+                let mut response = self
+                    .body(batch)
+                    .content_type(surf::http::mime::JSON)
+                    .await?;
+
+                let status = response.status();
+                let body_string = response.body_string().await?;
+
+                let body = serde_json::from_str(&body_string)
+                    .and_then(|body| BatchResponse::from_json(body, len));
+
+                match body {
+                    Ok(response) => Ok(response),
+                    Err(_) if !status.is_success() => Err(surf::Error::from_str(
+                        status,
+                        format!("Server returned {}: {}", status, body_string),
+                    )),
+                    Err(error) => Err(surf::Error::new(
+                        surf::StatusCode::InternalServerError,
+                        error,
+                    )),
+                }
+            })
+        }
+
+        #[cfg(feature = "persisted-queries")]
+        fn run_persisted_query<ResponseData, Vars>(
+            self,
+            operation: Operation<ResponseData, Vars>,
+        ) -> BoxFuture<'static, Result<GraphQlResponse<ResponseData>, surf::Error>>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static,
+        {
+            self.middleware(PersistedQueryMiddleware(PersistedQueryMethod::Post))
+                .run_graphql(operation)
+        }
+
+        #[cfg(feature = "persisted-queries")]
+        fn run_persisted_query_get<ResponseData, Vars>(
+            self,
+            operation: Operation<ResponseData, Vars>,
+        ) -> BoxFuture<'static, Result<GraphQlResponse<ResponseData>, surf::Error>>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static,
+        {
+            self.middleware(PersistedQueryMiddleware(PersistedQueryMethod::Get))
+                .run_graphql(operation)
+        }
+
+        #[cfg(feature = "persisted-queries")]
+        fn run_persisted_document<ResponseData, Vars>(
+            self,
+            operation: Operation<ResponseData, Vars>,
+        ) -> BoxFuture<'static, Result<GraphQlResponse<ResponseData>, surf::Error>>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static,
+        {
+            self.middleware(PersistedQueryMiddleware(PersistedQueryMethod::DocumentId))
+                .run_graphql(operation)
+        }
+    }
+
+    /// Per-request middleware that rewrites a GraphQL request into a
+    /// persisted query.
+    ///
+    /// We need a middleware for this as it's the only place surf gives us
+    /// access to the client, which we need to retry with the full query.
+    #[cfg(feature = "persisted-queries")]
+    struct PersistedQueryMiddleware(PersistedQueryMethod);
+
+    #[cfg(feature = "persisted-queries")]
+    #[surf::utils::async_trait]
+    impl surf::middleware::Middleware for PersistedQueryMiddleware {
+        async fn handle(
+            &self,
+            mut request: surf::Request,
+            client: surf::Client,
+            next: surf::middleware::Next<'_>,
+        ) -> surf::Result<surf::Response> {
+            use super::persisted_queries::{PersistedQuery, should_send_full_query};
+            use surf::http::{Method, headers::CONTENT_TYPE};
+
+            let method = self.0;
+
+            let body = request.take_body().into_bytes().await?;
+            let Some(persisted_query) = PersistedQuery::from_body(&body) else {
+                request.set_body(body);
+                return next.run(request, client).await;
+            };
+
+            // Cloning a request leaves its body empty, so we set this one up
+            // with the full query in case we need to retry
+            let mut full_query_request = request.clone();
+            full_query_request.set_body(persisted_query.full_body().to_string());
+
+            match method {
+                PersistedQueryMethod::Post => {
+                    request.set_body(persisted_query.hash_only_body().to_string());
+                }
+                PersistedQueryMethod::DocumentId => {
+                    request.set_body(persisted_query.document_id_body().to_string());
+                }
+                PersistedQueryMethod::Get => {
+                    let request: &mut surf::http::Request = request.as_mut();
+                    request.set_method(Method::Get);
+                    request.remove_header(CONTENT_TYPE);
+                    request
+                        .url_mut()
+                        .query_pairs_mut()
+                        .extend_pairs(persisted_query.query_params());
+                }
+            }
+
+            let mut response = next.run(request, client.clone()).await?;
+            if !method.can_retry() {
+                return Ok(response);
+            }
+
+            let body = response.body_bytes().await?;
+            if should_send_full_query(&body) {
+                return next.run(full_query_request, client).await;
+            }
+
+            response.set_body(body);
+            Ok(response)
+        }
     }
 }
 
@@ -126,14 +348,110 @@ pub enum CynicReqwestError {
     /// An error response from the server with the given status code and body.
     #[error("Server returned {0}: {1}")]
     ErrorResponse(reqwest::StatusCode, String),
+
+    /// The server responded to a streaming operation with a content type
+    /// that we don't know how to decode.
+    #[error("Server responded with an unsupported content type: {0}")]
+    UnsupportedContentType(String),
+
+    /// An error decoding one of the responses to a streaming operation.
+    #[error("Error decoding streamed response: {0}")]
+    StreamDecodeError(String),
+
+    /// An error decoding the response to a persisted query.
+    #[error("Error decoding response: {0}")]
+    DecodeError(serde_json::Error),
+}
+
+/// Decodes the body of a response to a batch of `len` operations
+#[cfg(feature = "http-reqwest")]
+fn decode_batch_response(
+    status: reqwest::StatusCode,
+    body: String,
+    len: usize,
+) -> Result<crate::BatchResponse, CynicReqwestError> {
+    let decoded =
+        serde_json::from_str(&body).and_then(|body| crate::BatchResponse::from_json(body, len));
+
+    match decoded {
+        Ok(response) => Ok(response),
+        Err(_) if !status.is_success() => Err(CynicReqwestError::ErrorResponse(status, body)),
+        Err(error) => Err(CynicReqwestError::DecodeError(error)),
+    }
+}
+
+/// The multipart form types from reqwest's async & blocking APIs
+#[cfg(feature = "http-reqwest")]
+trait MultipartForm: Sized {
+    fn new() -> Self;
+    fn text(self, name: &'static str, value: String) -> Self;
+    fn file(self, name: String, upload: crate::Upload) -> Self;
+}
+
+#[cfg(feature = "http-reqwest")]
+macro_rules! impl_multipart_form {
+    ($form:ty, $part:ty) => {
+        impl MultipartForm for $form {
+            fn new() -> Self {
+                <$form>::new()
+            }
+
+            fn text(self, name: &'static str, value: String) -> Self {
+                <$form>::text(self, name, value)
+            }
+
+            fn file(self, name: String, upload: crate::Upload) -> Self {
+                let mut part = <$part>::bytes(upload.bytes().to_vec())
+                    .file_name(upload.filename().to_string());
+                if let Some(mime_type) = upload.mime_type() {
+                    part = part
+                        .mime_str(mime_type)
+                        .expect("Upload::with_mime_type to only accept valid mime types");
+                }
+                self.part(name, part)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "http-reqwest")]
+impl_multipart_form!(reqwest::multipart::Form, reqwest::multipart::Part);
+
+#[cfg(feature = "http-reqwest-blocking")]
+impl_multipart_form!(
+    reqwest::blocking::multipart::Form,
+    reqwest::blocking::multipart::Part
+);
+
+/// Builds a form for the GraphQL multipart request spec
+#[cfg(feature = "http-reqwest")]
+fn multipart_form<Form: MultipartForm>(multipart: crate::upload::MultipartOperation) -> Form {
+    let mut form = Form::new()
+        .text("operations", multipart.operations)
+        .text("map", multipart.map);
+
+    for (index, upload) in multipart.files.into_iter().enumerate() {
+        form = form.file(index.to_string(), upload);
+    }
+
+    form
 }
 
 #[cfg(feature = "http-reqwest")]
 mod reqwest_ext {
-    use super::CynicReqwestError;
+    use super::{
+        CynicReqwestError, decode_batch_response,
+        incremental::{INCREMENTAL_ACCEPT, IncrementalMerger},
+        multipart_form,
+        streaming::{STREAMING_ACCEPT, StreamDecoder},
+    };
+    use futures_util::Stream;
+    use serde::de::IgnoredAny;
     use std::{future::Future, marker::PhantomData, pin::Pin};
 
-    use crate::{GraphQlResponse, Operation};
+    use crate::{
+        Batch, BatchResponse, GraphQlResponse, Operation, StreamingOperation, upload::OperationBody,
+    };
 
     #[cfg(not(target_arch = "wasm32"))]
     type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
@@ -141,6 +459,12 @@ mod reqwest_ext {
     #[cfg(target_arch = "wasm32")]
     type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
 
+    #[cfg(not(target_arch = "wasm32"))]
+    type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + Send + 'a>>;
+
+    #[cfg(target_arch = "wasm32")]
+    type BoxStream<'a, T> = Pin<Box<dyn Stream<Item = T> + 'a>>;
+
     /// An extension trait for reqwest::RequestBuilder.
     ///
     /// ```rust,no_run
@@ -199,6 +523,42 @@ mod reqwest_ext {
         where
             Vars: serde::Serialize,
             ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a GraphQL subscription with the parameters in RequestBuilder,
+        /// returning a stream of the responses.
+        ///
+        /// The server can stream responses using either `text/event-stream`
+        /// (as in the graphql-sse protocol) or `multipart/mixed`.  A server
+        /// that responds with plain JSON will result in a stream of one
+        /// response.
+        fn run_graphql_stream<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<StreamingOperation<ResponseData, Vars>>,
+        ) -> CynicReqwestStreamBuilder<ResponseData>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a GraphQL operation that uses `@defer` or `@stream`, returning
+        /// a stream of progressively more complete responses.
+        ///
+        /// Each incremental payload from the server is merged into the
+        /// response so far, so every item in the stream is a full response.
+        /// Deferred fields will be `None` until their data arrives, and the
+        /// last item in the stream is the complete response.
+        fn run_graphql_incremental<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<Operation<ResponseData, Vars>>,
+        ) -> CynicReqwestStreamBuilder<ResponseData>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a batch of GraphQL operations in a single request.
+        ///
+        /// The results of each operation can be fetched from the
+        /// `BatchResponse` with the handles returned by `Batch::add`.
+        fn run_graphql_batch(self, batch: Batch) -> CynicReqwestBatchBuilder;
     }
 
     /// A builder for cynics reqwest integration
@@ -207,16 +567,66 @@ mod reqwest_ext {
     /// `into_future` directly when they're ready to send the request.
     pub struct CynicReqwestBuilder<ResponseData, ErrorExtensions = serde::de::IgnoredAny> {
         builder: reqwest::RequestBuilder,
+        #[cfg(feature = "persisted-queries")]
+        persisted_query: Option<PersistedQueryMethod>,
         _marker: std::marker::PhantomData<fn() -> (ResponseData, ErrorExtensions)>,
     }
 
+    #[cfg(feature = "persisted-queries")]
+    use super::persisted_queries::PersistedQueryMethod;
+
     impl<ResponseData, Errors> CynicReqwestBuilder<ResponseData, Errors> {
         pub fn new(builder: reqwest::RequestBuilder) -> Self {
             Self {
                 builder,
+                #[cfg(feature = "persisted-queries")]
+                persisted_query: None,
                 _marker: std::marker::PhantomData,
             }
         }
+
+        /// Sends this operation as an [automatic persisted query][apq].
+        ///
+        /// The first request will contain only the hash of the query.  If the
+        /// server doesn't recognise that hash the request will be retried with
+        /// the full query.
+        ///
+        /// [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        pub fn persisted_query(mut self) -> Self {
+            self.persisted_query = Some(PersistedQueryMethod::Post);
+            self
+        }
+
+        /// Sends this operation as an [automatic persisted query][apq], using
+        /// a GET request for the initial hash-only request.
+        ///
+        /// This allows CDNs to cache the response to persisted queries.  Any
+        /// retry with the full query will still be sent as a POST.
+        ///
+        /// [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        pub fn persisted_query_get(mut self) -> Self {
+            self.persisted_query = Some(PersistedQueryMethod::Get);
+            self
+        }
+
+        /// Sends only the ID of this operations document, rather than the
+        /// full query.
+        ///
+        /// This is for use with servers that only accept a safelist of trusted
+        /// documents, which can be generated with a
+        /// [`Manifest`](crate::persisted::Manifest).  Unlike
+        /// [`persisted_query`](Self::persisted_query) the full query is never
+        /// sent.
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        pub fn persisted_document(mut self) -> Self {
+            self.persisted_query = Some(PersistedQueryMethod::DocumentId);
+            self
+        }
     }
 
     impl<ResponseData: serde::de::DeserializeOwned, Errors: serde::de::DeserializeOwned>
@@ -229,12 +639,89 @@ mod reqwest_ext {
 
         fn into_future(self) -> Self::IntoFuture {
             Box::pin(async move {
+                #[cfg(feature = "persisted-queries")]
+                if let Some(method) = self.persisted_query {
+                    return send_persisted_query(self.builder, method).await;
+                }
+
                 let http_result = self.builder.send().await;
                 deser_gql(http_result).await
             })
         }
     }
 
+    #[cfg(feature = "persisted-queries")]
+    async fn send_persisted_query<ResponseData, ErrorExtensions>(
+        builder: reqwest::RequestBuilder,
+        method: PersistedQueryMethod,
+    ) -> Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError>
+    where
+        ResponseData: serde::de::DeserializeOwned,
+        ErrorExtensions: serde::de::DeserializeOwned,
+    {
+        use super::persisted_queries::{PersistedQuery, should_send_full_query};
+        use reqwest::header::{CONTENT_TYPE, HeaderValue};
+
+        // We need a copy of the builder in case we need to retry with the full query
+        let Some(full_query_builder) = builder.try_clone() else {
+            return deser_gql(builder.send().await).await;
+        };
+
+        let (client, request) = builder.build_split();
+        let mut request = request?;
+
+        let persisted_query = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(PersistedQuery::from_body);
+
+        let Some(persisted_query) = persisted_query else {
+            return deser_gql(client.execute(request).await).await;
+        };
+
+        match method {
+            PersistedQueryMethod::Post => {
+                let body = persisted_query.hash_only_body().to_string();
+                *request.body_mut() = Some(body.into());
+            }
+            PersistedQueryMethod::DocumentId => {
+                let body = persisted_query.document_id_body().to_string();
+                *request.body_mut() = Some(body.into());
+            }
+            PersistedQueryMethod::Get => {
+                *request.method_mut() = reqwest::Method::GET;
+                *request.body_mut() = None;
+                request.headers_mut().remove(CONTENT_TYPE);
+                request
+                    .url_mut()
+                    .query_pairs_mut()
+                    .extend_pairs(persisted_query.query_params());
+            }
+        }
+
+        let response = client.execute(request).await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+
+        if method.can_retry() && should_send_full_query(&body) {
+            let response = full_query_builder
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .body(persisted_query.full_body().to_string())
+                .send()
+                .await;
+
+            return deser_gql(response).await;
+        }
+
+        if !status.is_success() {
+            let text = String::from_utf8_lossy(&body).into_owned();
+            return serde_json::from_str(&text)
+                .map_err(|_| CynicReqwestError::ErrorResponse(status, text));
+        }
+
+        serde_json::from_slice(&body).map_err(CynicReqwestError::DecodeError)
+    }
+
     impl<ResponseData> CynicReqwestBuilder<ResponseData, serde::de::IgnoredAny> {
         /// Sets the type that will be deserialized for the extensions fields of any errors in the response
         pub fn retain_extensions<ErrorExtensions>(
@@ -243,10 +730,17 @@ mod reqwest_ext {
         where
             ErrorExtensions: serde::de::DeserializeOwned,
         {
-            let CynicReqwestBuilder { builder, _marker } = self;
+            let CynicReqwestBuilder {
+                builder,
+                #[cfg(feature = "persisted-queries")]
+                persisted_query,
+                _marker,
+            } = self;
 
             CynicReqwestBuilder {
                 builder,
+                #[cfg(feature = "persisted-queries")]
+                persisted_query,
                 _marker: PhantomData,
             }
         }
@@ -284,6 +778,217 @@ mod reqwest_ext {
         }
     }
 
+    /// A builder for batches of operations with cynics reqwest integration
+    ///
+    /// Implements `IntoFuture`, users should `.await` the builder or call
+    /// `into_future` directly when they're ready to send the request.
+    pub struct CynicReqwestBatchBuilder {
+        builder: reqwest::RequestBuilder,
+        len: usize,
+    }
+
+    impl CynicReqwestBatchBuilder {
+        pub fn new(builder: reqwest::RequestBuilder, batch: &Batch) -> Self {
+            Self {
+                builder: builder.json(batch),
+                len: batch.len(),
+            }
+        }
+    }
+
+    impl std::future::IntoFuture for CynicReqwestBatchBuilder {
+        type Output = Result<BatchResponse, CynicReqwestError>;
+
+        type IntoFuture = BoxFuture<'static, Self::Output>;
+
+        fn into_future(self) -> Self::IntoFuture {
+            Box::pin(async move {
+                let response = self.builder.send().await?;
+                let status = response.status();
+                let body = response.text().await?;
+
+                decode_batch_response(status, body, self.len)
+            })
+        }
+    }
+
+    /// A builder for streaming operations with cynics reqwest integration
+    ///
+    /// Implements `IntoFuture`, users should `.await` the builder or call
+    /// `into_future` directly when they're ready to send the request.  This
+    /// will resolve to a `Stream` of responses once the server has started
+    /// responding.
+    pub struct CynicReqwestStreamBuilder<ResponseData, ErrorExtensions = serde::de::IgnoredAny> {
+        builder: reqwest::RequestBuilder,
+        incremental: bool,
+        _marker: std::marker::PhantomData<fn() -> (ResponseData, ErrorExtensions)>,
+    }
+
+    impl<ResponseData, Errors> CynicReqwestStreamBuilder<ResponseData, Errors> {
+        pub fn new(builder: reqwest::RequestBuilder) -> Self {
+            Self {
+                builder: builder.header(reqwest::header::ACCEPT, STREAMING_ACCEPT),
+                incremental: false,
+                _marker: std::marker::PhantomData,
+            }
+        }
+
+        fn incremental(builder: reqwest::RequestBuilder) -> Self {
+            Self {
+                builder: builder.header(reqwest::header::ACCEPT, INCREMENTAL_ACCEPT),
+                incremental: true,
+                _marker: std::marker::PhantomData,
+            }
+        }
+    }
+
+    impl<ResponseData> CynicReqwestStreamBuilder<ResponseData, serde::de::IgnoredAny> {
+        /// Sets the type that will be deserialized for the extensions fields of any errors in the response
+        pub fn retain_extensions<ErrorExtensions>(
+            self,
+        ) -> CynicReqwestStreamBuilder<ResponseData, ErrorExtensions>
+        where
+            ErrorExtensions: serde::de::DeserializeOwned,
+        {
+            let CynicReqwestStreamBuilder {
+                builder,
+                incremental,
+                _marker,
+            } = self;
+
+            CynicReqwestStreamBuilder {
+                builder,
+                incremental,
+                _marker: PhantomData,
+            }
+        }
+    }
+
+    type ResponseStream<ResponseData, Errors> =
+        BoxStream<'static, Result<GraphQlResponse<ResponseData, Errors>, CynicReqwestError>>;
+
+    impl<ResponseData, Errors> std::future::IntoFuture
+        for CynicReqwestStreamBuilder<ResponseData, Errors>
+    where
+        ResponseData: serde::de::DeserializeOwned + 'static,
+        Errors: serde::de::DeserializeOwned + 'static,
+    {
+        type Output = Result<ResponseStream<ResponseData, Errors>, CynicReqwestError>;
+
+        type IntoFuture = BoxFuture<'static, Self::Output>;
+
+        fn into_future(self) -> Self::IntoFuture {
+            Box::pin(async move {
+                let merger = self.incremental.then(IncrementalMerger::default);
+                let response = self.builder.send().await?;
+
+                let status = response.status();
+                if !status.is_success() {
+                    let text = response.text().await?;
+
+                    // If the body looks like a GraphQL response we pass it on,
+                    // otherwise we treat it as an error.
+                    type AnyResponse = GraphQlResponse<IgnoredAny, IgnoredAny>;
+                    if serde_json::from_str::<AnyResponse>(&text).is_err() {
+                        return Err(CynicReqwestError::ErrorResponse(status, text));
+                    }
+
+                    let mut decoder = StreamDecoder::for_content_type("application/json")
+                        .expect("application/json to be supported");
+                    decoder.push(text.as_bytes());
+                    decoder.finish();
+
+                    let state = StreamState {
+                        response: None,
+                        decoder,
+                        merger,
+                    };
+                    let stream: ResponseStream<ResponseData, Errors> =
+                        Box::pin(futures_util::stream::unfold(state, next_streamed_response));
+
+                    return Ok(stream);
+                }
+
+                let content_type = response
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .unwrap_or_default();
+
+                let Some(decoder) = StreamDecoder::for_content_type(content_type) else {
+                    return Err(CynicReqwestError::UnsupportedContentType(
+                        content_type.to_string(),
+                    ));
+                };
+
+                let state = StreamState {
+                    response: Some(response),
+                    decoder,
+                    merger,
+                };
+                let stream: ResponseStream<ResponseData, Errors> =
+                    Box::pin(futures_util::stream::unfold(state, next_streamed_response));
+
+                Ok(stream)
+            })
+        }
+    }
+
+    struct StreamState {
+        /// The response we're reading from, or None if we've finished reading it
+        response: Option<reqwest::Response>,
+        decoder: StreamDecoder,
+        /// Merges incremental payloads, if this is an incremental operation
+        merger: Option<IncrementalMerger>,
+    }
+
+    async fn next_streamed_response<ResponseData, ErrorExtensions>(
+        mut state: StreamState,
+    ) -> Option<(
+        Result<GraphQlResponse<ResponseData, ErrorExtensions>, CynicReqwestError>,
+        StreamState,
+    )>
+    where
+        ResponseData: serde::de::DeserializeOwned,
+        ErrorExtensions: serde::de::DeserializeOwned,
+    {
+        loop {
+            if let Some(payload) = state.decoder.next_payload() {
+                let payload = match (payload, &mut state.merger) {
+                    (Ok(payload), Some(merger)) => match merger.apply(payload).transpose() {
+                        Some(payload) => payload,
+                        // This payload didn't change the response
+                        None => continue,
+                    },
+                    (payload, _) => payload,
+                };
+
+                let response = payload
+                    .and_then(|value| serde_json::from_value(value).map_err(|e| e.to_string()))
+                    .map_err(CynicReqwestError::StreamDecodeError);
+
+                return Some((response, state));
+            }
+
+            if state.decoder.is_complete() {
+                return None;
+            }
+            let response = state.response.as_mut()?;
+
+            match response.chunk().await {
+                Ok(Some(bytes)) => state.decoder.push(&bytes),
+                Ok(None) => {
+                    state.decoder.finish();
+                    state.response = None;
+                }
+                Err(error) => {
+                    state.response = None;
+                    return Some((Err(CynicReqwestError::ReqwestError(error)), state));
+                }
+            }
+        }
+    }
+
     impl ReqwestExt for reqwest::RequestBuilder {
         fn run_graphql<ResponseData, Vars>(
             self,
@@ -293,16 +998,56 @@ mod reqwest_ext {
             Vars: serde::Serialize,
             ResponseData: serde::de::DeserializeOwned + 'static,
         {
-            CynicReqwestBuilder::new(self.json(operation.as_ref()))
+            let operation = operation.as_ref();
+            match OperationBody::new(operation) {
+                Ok(OperationBody::Json(body)) => CynicReqwestBuilder::new(
+                    self.header(reqwest::header::CONTENT_TYPE, "application/json")
+                        .body(body),
+                ),
+                Ok(OperationBody::Multipart(multipart)) => {
+                    CynicReqwestBuilder::new(self.multipart(multipart_form(multipart)))
+                }
+                // reqwest will report the serialization error when the request is sent
+                Err(_) => CynicReqwestBuilder::new(self.json(operation)),
+            }
+        }
+
+        fn run_graphql_stream<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<StreamingOperation<ResponseData, Vars>>,
+        ) -> CynicReqwestStreamBuilder<ResponseData>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static,
+        {
+            CynicReqwestStreamBuilder::new(self.json(operation.as_ref()))
+        }
+
+        fn run_graphql_incremental<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<Operation<ResponseData, Vars>>,
+        ) -> CynicReqwestStreamBuilder<ResponseData>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static,
+        {
+            CynicReqwestStreamBuilder::incremental(self.json(operation.as_ref()))
+        }
+
+        fn run_graphql_batch(self, batch: Batch) -> CynicReqwestBatchBuilder {
+            CynicReqwestBatchBuilder::new(self, &batch)
         }
     }
 }
 
 #[cfg(feature = "http-reqwest-blocking")]
 mod reqwest_blocking_ext {
-    use super::CynicReqwestError;
+    use super::{CynicReqwestError, decode_batch_response, multipart_form};
 
-    use crate::{GraphQlResponse, Operation};
+    use crate::{Batch, BatchResponse, GraphQlResponse, Operation, upload::OperationBody};
+
+    #[cfg(feature = "persisted-queries")]
+    use super::persisted_queries::PersistedQueryMethod;
 
     /// An extension trait for reqwest::blocking::RequestBuilder.
     ///
@@ -359,6 +1104,63 @@ mod reqwest_blocking_ext {
         where
             Vars: serde::Serialize,
             ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a batch of GraphQL operations in a single request.
+        ///
+        /// The results of each operation can be fetched from the
+        /// `BatchResponse` with the handles returned by `Batch::add`.
+        fn run_graphql_batch(self, batch: Batch) -> Result<BatchResponse, CynicReqwestError>;
+
+        /// Runs a GraphQL query as an [automatic persisted query][apq].
+        ///
+        /// The first request will contain only the hash of the query.  If the
+        /// server doesn't recognise that hash the request will be retried with
+        /// the full query.
+        ///
+        /// [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        fn run_persisted_query<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<Operation<ResponseData, Vars>>,
+        ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a GraphQL query as an [automatic persisted query][apq], using
+        /// a GET request for the initial hash-only request.
+        ///
+        /// Any retry with the full query will still be sent as a POST.
+        ///
+        /// [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        fn run_persisted_query_get<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<Operation<ResponseData, Vars>>,
+        ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static;
+
+        /// Runs a GraphQL query by sending only the ID of its document, rather
+        /// than the full query.
+        ///
+        /// This is for use with servers that only accept a safelist of trusted
+        /// documents, which can be generated with a
+        /// [`Manifest`](crate::persisted::Manifest).  Unlike
+        /// [`run_persisted_query`](Self::run_persisted_query) the full query is
+        /// never sent.
+        #[cfg(feature = "persisted-queries")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+        fn run_persisted_document<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<Operation<ResponseData, Vars>>,
+        ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static;
     }
 
     impl ReqwestBlockingExt for reqwest::blocking::RequestBuilder {
@@ -370,21 +1172,165 @@ mod reqwest_blocking_ext {
             Vars: serde::Serialize,
             ResponseData: serde::de::DeserializeOwned + 'static,
         {
-            let response = self.json(operation.as_ref()).send()?;
+            deser_gql(with_operation(self, operation.as_ref()).send())
+        }
 
+        fn run_graphql_batch(self, batch: Batch) -> Result<BatchResponse, CynicReqwestError> {
+            let response = self.json(&batch).send()?;
             let status = response.status();
-            if !status.is_success() {
-                let body_string = response.text().map_err(CynicReqwestError::ReqwestError)?;
+            let body = response.text()?;
 
-                match serde_json::from_str::<GraphQlResponse<ResponseData>>(&body_string) {
-                    Ok(response) => return Ok(response),
-                    Err(_) => {
-                        return Err(CynicReqwestError::ErrorResponse(status, body_string));
-                    }
-                };
+            decode_batch_response(status, body, batch.len())
+        }
+
+        #[cfg(feature = "persisted-queries")]
+        fn run_persisted_query<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<Operation<ResponseData, Vars>>,
+        ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static,
+        {
+            let builder = with_operation(self, operation.as_ref());
+            send_persisted_query(builder, PersistedQueryMethod::Post)
+        }
+
+        #[cfg(feature = "persisted-queries")]
+        fn run_persisted_query_get<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<Operation<ResponseData, Vars>>,
+        ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static,
+        {
+            let builder = with_operation(self, operation.as_ref());
+            send_persisted_query(builder, PersistedQueryMethod::Get)
+        }
+
+        #[cfg(feature = "persisted-queries")]
+        fn run_persisted_document<ResponseData, Vars>(
+            self,
+            operation: impl AsRef<Operation<ResponseData, Vars>>,
+        ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+        where
+            Vars: serde::Serialize,
+            ResponseData: serde::de::DeserializeOwned + 'static,
+        {
+            let builder = with_operation(self, operation.as_ref());
+            send_persisted_query(builder, PersistedQueryMethod::DocumentId)
+        }
+    }
+
+    /// Sets the body of a request to the given operation
+    fn with_operation<ResponseData, Vars>(
+        builder: reqwest::blocking::RequestBuilder,
+        operation: &Operation<ResponseData, Vars>,
+    ) -> reqwest::blocking::RequestBuilder
+    where
+        Vars: serde::Serialize,
+    {
+        match OperationBody::new(operation) {
+            Ok(OperationBody::Json(body)) => builder
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body),
+            Ok(OperationBody::Multipart(multipart)) => builder.multipart(multipart_form(multipart)),
+            // reqwest will report the serialization error when the request is sent
+            Err(_) => builder.json(operation),
+        }
+    }
+
+    #[cfg(feature = "persisted-queries")]
+    fn send_persisted_query<ResponseData>(
+        builder: reqwest::blocking::RequestBuilder,
+        method: PersistedQueryMethod,
+    ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+    where
+        ResponseData: serde::de::DeserializeOwned,
+    {
+        use super::persisted_queries::{PersistedQuery, should_send_full_query};
+        use reqwest::header::{CONTENT_TYPE, HeaderValue};
+
+        // We need a copy of the builder in case we need to retry with the full query
+        let Some(full_query_builder) = builder.try_clone() else {
+            return deser_gql(builder.send());
+        };
+
+        let (client, request) = builder.build_split();
+        let mut request = request?;
+
+        let persisted_query = request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .and_then(PersistedQuery::from_body);
+
+        let Some(persisted_query) = persisted_query else {
+            return deser_gql(client.execute(request));
+        };
+
+        match method {
+            PersistedQueryMethod::Post => {
+                let body = persisted_query.hash_only_body().to_string();
+                *request.body_mut() = Some(body.into());
             }
+            PersistedQueryMethod::DocumentId => {
+                let body = persisted_query.document_id_body().to_string();
+                *request.body_mut() = Some(body.into());
+            }
+            PersistedQueryMethod::Get => {
+                *request.method_mut() = reqwest::Method::GET;
+                *request.body_mut() = None;
+                request.headers_mut().remove(CONTENT_TYPE);
+                request
+                    .url_mut()
+                    .query_pairs_mut()
+                    .extend_pairs(persisted_query.query_params());
+            }
+        }
+
+        let response = client.execute(request)?;
+        let status = response.status();
+        let body = response.bytes()?;
+
+        if method.can_retry() && should_send_full_query(&body) {
+            let response = full_query_builder
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+                .body(persisted_query.full_body().to_string())
+                .send();
 
-            Ok(response.json::<GraphQlResponse<ResponseData>>()?)
+            return deser_gql(response);
         }
+
+        if !status.is_success() {
+            let text = String::from_utf8_lossy(&body).into_owned();
+            return serde_json::from_str(&text)
+                .map_err(|_| CynicReqwestError::ErrorResponse(status, text));
+        }
+
+        serde_json::from_slice(&body).map_err(CynicReqwestError::DecodeError)
+    }
+
+    fn deser_gql<ResponseData>(
+        response: Result<reqwest::blocking::Response, reqwest::Error>,
+    ) -> Result<GraphQlResponse<ResponseData>, CynicReqwestError>
+    where
+        ResponseData: serde::de::DeserializeOwned,
+    {
+        let response = response?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body_string = response.text().map_err(CynicReqwestError::ReqwestError)?;
+
+            match serde_json::from_str::<GraphQlResponse<ResponseData>>(&body_string) {
+                Ok(response) => return Ok(response),
+                Err(_) => {
+                    return Err(CynicReqwestError::ErrorResponse(status, body_string));
+                }
+            };
+        }
+
+        Ok(response.json::<GraphQlResponse<ResponseData>>()?)
     }
 }
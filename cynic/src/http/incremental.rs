@@ -0,0 +1,316 @@
+//! Support for the incremental delivery of responses to operations that use
+//! `@defer` or `@stream`.
+//!
+//! Servers deliver these responses as a `multipart/mixed` body: an initial
+//! payload containing everything that wasn't deferred, followed by
+//! `incremental` patches that each contain some data and the `path` it should
+//! be merged in at.  The `IncrementalMerger` here merges these patches into a
+//! single response, which grows as each payload arrives.
+//!
+//! Both the format from the [original RFC][rfc] (where each patch has a
+//! `path`) and the [current proposal][proposal] (where each patch refers to
+//! a previously announced `pending` entry by `id`) are supported.
+//!
+//! [rfc]: https://github.com/graphql/graphql-spec/blob/2022-08-24-defer-stream/rfcs/DeferStream.md
+//! [proposal]: https://github.com/graphql/defer-stream-wg/discussions/69
+
+use std::collections::HashMap;
+
+use serde_json::{Map, Value};
+
+/// The `Accept` header to send when we're expecting an incremental response
+pub(crate) const INCREMENTAL_ACCEPT: &str =
+    r#"multipart/mixed;deferSpec=20220824, application/json;q=0.9"#;
+
+#[derive(Default)]
+pub(crate) struct IncrementalMerger {
+    data: Value,
+    errors: Vec<Value>,
+    extensions: Option<Value>,
+    /// The paths of any pending results, keyed by their ID
+    pending: HashMap<String, Vec<Value>>,
+}
+
+impl IncrementalMerger {
+    /// Merges a payload into the response.
+    ///
+    /// Returns the merged response, or `None` if the payload didn't change it
+    pub fn apply(&mut self, payload: Value) -> Result<Option<Value>, String> {
+        let Value::Object(mut payload) = payload else {
+            return Err("expected an incremental payload to be an object".into());
+        };
+
+        let mut changed = false;
+
+        if let Some(data) = payload.remove("data") {
+            self.data = data;
+            changed = true;
+        }
+
+        if let Some(Value::Array(errors)) = payload.remove("errors") {
+            changed |= !errors.is_empty();
+            self.errors.extend(errors);
+        }
+
+        if let Some(extensions) = payload.remove("extensions") {
+            merge(self.extensions.get_or_insert(Value::Null), extensions);
+            changed = true;
+        }
+
+        if let Some(Value::Array(pending)) = payload.remove("pending") {
+            for pending in pending {
+                let (Some(Value::String(id)), Some(Value::Array(path))) =
+                    (pending.get("id"), pending.get("path"))
+                else {
+                    continue;
+                };
+                self.pending.insert(id.clone(), path.clone());
+            }
+        }
+
+        if let Some(Value::Array(incremental)) = payload.remove("incremental") {
+            for patch in incremental {
+                self.apply_patch(patch)?;
+                changed = true;
+            }
+        }
+
+        if let Some(Value::Array(completed)) = payload.remove("completed") {
+            for mut completed in completed {
+                if let Some(Value::String(id)) = completed.get("id") {
+                    self.pending.remove(id);
+                }
+                if let Some(Value::Array(errors)) = completed.get_mut("errors").map(Value::take) {
+                    changed |= !errors.is_empty();
+                    self.errors.extend(errors);
+                }
+            }
+        }
+
+        if !changed {
+            return Ok(None);
+        }
+
+        Ok(Some(self.response()))
+    }
+
+    fn apply_patch(&mut self, patch: Value) -> Result<(), String> {
+        let Value::Object(mut patch) = patch else {
+            return Err("expected an incremental patch to be an object".into());
+        };
+
+        if let Some(Value::Array(errors)) = patch.remove("errors") {
+            self.errors.extend(errors);
+        }
+
+        let mut path = match (patch.remove("path"), patch.remove("id")) {
+            (Some(Value::Array(path)), _) => path,
+            (_, Some(Value::String(id))) => self
+                .pending
+                .get(&id)
+                .cloned()
+                .ok_or_else(|| format!("incremental patch refers to unknown id {id}"))?,
+            _ => return Err("incremental patch has no path".into()),
+        };
+        if let Some(Value::Array(sub_path)) = patch.remove("subPath") {
+            path.extend(sub_path);
+        }
+
+        if let Some(data) = patch.remove("data") {
+            let target = lookup(&mut self.data, &path)?;
+            merge(target, data);
+        } else if let Some(Value::Array(items)) = patch.remove("items") {
+            append_items(&mut self.data, &path, items)?;
+        }
+
+        Ok(())
+    }
+
+    fn response(&self) -> Value {
+        let mut response = Map::new();
+        response.insert("data".into(), self.data.clone());
+        if !self.errors.is_empty() {
+            response.insert("errors".into(), Value::Array(self.errors.clone()));
+        }
+        if let Some(extensions) = &self.extensions {
+            response.insert("extensions".into(), extensions.clone());
+        }
+        Value::Object(response)
+    }
+}
+
+/// Finds the value at `path` inside `data`
+fn lookup<'a>(data: &'a mut Value, path: &[Value]) -> Result<&'a mut Value, String> {
+    let mut current = data;
+    for segment in path {
+        let next = match (current, segment) {
+            (Value::Object(object), Value::String(key)) => object.get_mut(key),
+            (Value::Array(list), Value::Number(index)) => index
+                .as_u64()
+                .and_then(|index| list.get_mut(usize::try_from(index).ok()?)),
+            _ => None,
+        };
+        current = next.ok_or_else(|| format!("incremental patch has an invalid path: {path:?}"))?;
+    }
+    Ok(current)
+}
+
+/// Adds streamed items to the list at `path`.
+///
+/// The path can either point at the list itself, or at the index the items
+/// should be inserted at.
+fn append_items(data: &mut Value, path: &[Value], items: Vec<Value>) -> Result<(), String> {
+    if let Some((Value::Number(index), parent_path)) = path.split_last() {
+        let index = index
+            .as_u64()
+            .and_then(|index| usize::try_from(index).ok())
+            .ok_or_else(|| format!("incremental patch has an invalid path: {path:?}"))?;
+
+        let Value::Array(list) = lookup(data, parent_path)? else {
+            return Err(format!("incremental patch has an invalid path: {path:?}"));
+        };
+        list.truncate(index);
+        list.extend(items);
+        return Ok(());
+    }
+
+    let Value::Array(list) = lookup(data, path)? else {
+        return Err(format!("incremental patch has an invalid path: {path:?}"));
+    };
+    list.extend(items);
+    Ok(())
+}
+
+/// Deep merges `patch` into `target`
+fn merge(target: &mut Value, patch: Value) {
+    match (target, patch) {
+        (Value::Object(target), Value::Object(patch)) => {
+            for (key, value) in patch {
+                match target.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn merge_all(payloads: Vec<Value>) -> Vec<Value> {
+        let mut merger = IncrementalMerger::default();
+        payloads
+            .into_iter()
+            .filter_map(|payload| merger.apply(payload).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_deferred_patches_by_path() {
+        let responses = merge_all(vec![
+            json!({"data": {"film": {"title": "A New Hope"}}, "hasNext": true}),
+            json!({
+                "incremental": [{"data": {"director": "George Lucas"}, "path": ["film"]}],
+                "hasNext": true
+            }),
+            json!({
+                "incremental": [{
+                    "data": {"producers": ["Gary Kurtz"]},
+                    "path": ["film"],
+                    "errors": [{"message": "Oops"}]
+                }],
+                "hasNext": false
+            }),
+        ]);
+
+        assert_eq!(
+            responses,
+            vec![
+                json!({"data": {"film": {"title": "A New Hope"}}}),
+                json!({"data": {"film": {"title": "A New Hope", "director": "George Lucas"}}}),
+                json!({
+                    "data": {"film": {
+                        "title": "A New Hope",
+                        "director": "George Lucas",
+                        "producers": ["Gary Kurtz"]
+                    }},
+                    "errors": [{"message": "Oops"}]
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_streamed_items_by_path() {
+        let responses = merge_all(vec![
+            json!({"data": {"films": [{"title": "A New Hope"}]}, "hasNext": true}),
+            json!({
+                "incremental": [{"items": [{"title": "The Empire Strikes Back"}], "path": ["films", 1]}],
+                "hasNext": true
+            }),
+            json!({"hasNext": false}),
+        ]);
+
+        assert_eq!(
+            responses,
+            vec![
+                json!({"data": {"films": [{"title": "A New Hope"}]}}),
+                json!({"data": {"films": [
+                    {"title": "A New Hope"},
+                    {"title": "The Empire Strikes Back"}
+                ]}}),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_patches_by_pending_id() {
+        let responses = merge_all(vec![
+            json!({
+                "data": {"film": {"title": "A New Hope", "characters": []}},
+                "pending": [
+                    {"id": "0", "path": ["film"]},
+                    {"id": "1", "path": ["film", "characters"]}
+                ],
+                "hasNext": true
+            }),
+            json!({
+                "incremental": [
+                    {"id": "0", "data": {"director": "George Lucas"}},
+                    {"id": "0", "subPath": ["director"], "data": "George Lucas"},
+                    {"id": "1", "items": [{"name": "Luke"}]}
+                ],
+                "completed": [{"id": "0"}, {"id": "1"}],
+                "hasNext": false
+            }),
+        ]);
+
+        assert_eq!(
+            responses.last().unwrap(),
+            &json!({"data": {"film": {
+                "title": "A New Hope",
+                "director": "George Lucas",
+                "characters": [{"name": "Luke"}]
+            }}})
+        );
+    }
+
+    #[test]
+    fn test_invalid_path() {
+        let mut merger = IncrementalMerger::default();
+        merger.apply(json!({"data": {"film": null}})).unwrap();
+
+        assert!(
+            merger
+                .apply(json!({"incremental": [{"data": {"title": "A"}, "path": ["film", "x"]}]}))
+                .is_err()
+        );
+    }
+}
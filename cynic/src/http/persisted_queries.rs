@@ -0,0 +1,226 @@
+//! Support for Apollo-style [automatic persisted queries][apq].
+//!
+//! With APQ a client first sends just the hash of its query.  If the server
+//! doesn't recognise the hash it responds with a `PersistedQueryNotFound`
+//! error, and the client retries with the full query so that the server can
+//! store it for next time.
+//!
+//! [apq]: https://www.apollographql.com/docs/apollo-server/performance/apq/
+
+use serde_json::{Map, Value, json};
+
+use crate::operation::sha256_hex;
+
+/// How a persisted query should be sent to the server
+#[derive(Clone, Copy)]
+pub(crate) enum PersistedQueryMethod {
+    /// POST just the hash of the query, retrying with the full query if needed
+    Post,
+    /// GET with the hash of the query, retrying with a POST of the full query
+    /// if needed
+    Get,
+    /// POST the ID of the document, without ever sending the full query
+    DocumentId,
+}
+
+impl PersistedQueryMethod {
+    /// Whether we should retry with the full query when the server doesn't
+    /// recognise our hash
+    pub fn can_retry(self) -> bool {
+        !matches!(self, PersistedQueryMethod::DocumentId)
+    }
+}
+
+/// A serialized operation that we're sending as a persisted query
+pub(crate) struct PersistedQuery {
+    body: Map<String, Value>,
+    query: Value,
+    extensions: Value,
+}
+
+impl PersistedQuery {
+    /// Creates a `PersistedQuery` from the JSON body of a request.
+    ///
+    /// Returns `None` if the body doesn't look like a GraphQL operation
+    pub fn from_body(body: &[u8]) -> Option<Self> {
+        let Value::Object(mut body) = serde_json::from_slice(body).ok()? else {
+            return None;
+        };
+
+        let query = body.remove("query")?;
+        let extensions = json!({
+            "persistedQuery": {
+                "version": 1,
+                "sha256Hash": sha256_hex(query.as_str()?)
+            }
+        });
+
+        Some(PersistedQuery {
+            body,
+            query,
+            extensions,
+        })
+    }
+
+    /// The body to send when we're just sending the hash of the query
+    pub fn hash_only_body(&self) -> Value {
+        let mut body = self.body.clone();
+        body.insert("extensions".into(), self.extensions.clone());
+        Value::Object(body)
+    }
+
+    /// The body to send when the server didn't recognise our hash
+    pub fn full_body(&self) -> Value {
+        let mut body = self.body.clone();
+        body.insert("query".into(), self.query.clone());
+        body.insert("extensions".into(), self.extensions.clone());
+        Value::Object(body)
+    }
+
+    /// The body to send when we're identifying the query by its document ID
+    pub fn document_id_body(&self) -> Value {
+        let hash = &self.extensions["persistedQuery"]["sha256Hash"];
+
+        let mut body = self.body.clone();
+        body.insert("documentId".into(), hash.clone());
+        Value::Object(body)
+    }
+
+    /// The query parameters to send when we're sending the hash of the
+    /// query with a GET request
+    pub fn query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(Value::String(operation_name)) = self.body.get("operationName") {
+            params.push(("operationName", operation_name.clone()));
+        }
+        if let Some(variables) = self.body.get("variables") {
+            params.push(("variables", variables.to_string()));
+        }
+        params.push(("extensions", self.extensions.to_string()));
+        params
+    }
+}
+
+/// Checks whether a response tells us that the server doesn't have our query,
+/// in which case we should retry with the full query.
+pub(crate) fn should_send_full_query(body: &[u8]) -> bool {
+    #[derive(serde::Deserialize)]
+    struct Response {
+        #[serde(default)]
+        errors: Vec<Error>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Error {
+        #[serde(default)]
+        message: String,
+        #[serde(default)]
+        extensions: Option<Extensions>,
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Extensions {
+        #[serde(default)]
+        code: Option<String>,
+    }
+
+    let Ok(response) = serde_json::from_slice::<Response>(body) else {
+        return false;
+    };
+
+    response.errors.iter().any(|error| {
+        let code = error
+            .extensions
+            .as_ref()
+            .and_then(|extensions| extensions.code.as_deref());
+
+        matches!(
+            error.message.as_str(),
+            "PersistedQueryNotFound" | "PersistedQueryNotSupported"
+        ) || matches!(
+            code,
+            Some("PERSISTED_QUERY_NOT_FOUND" | "PERSISTED_QUERY_NOT_SUPPORTED")
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HASH: &str = "ecf4edb46db40b5132295c0291d62fb65d6759a9eedfa4d5d612dd5ec54a6b38";
+
+    fn persisted_query() -> PersistedQuery {
+        PersistedQuery::from_body(
+            br#"{"query": "{__typename}", "variables": {"id": 1}, "operationName": "Foo"}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hash_only_body() {
+        assert_eq!(
+            persisted_query().hash_only_body(),
+            json!({
+                "variables": {"id": 1},
+                "operationName": "Foo",
+                "extensions": {"persistedQuery": {"version": 1, "sha256Hash": HASH}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_full_body() {
+        assert_eq!(
+            persisted_query().full_body(),
+            json!({
+                "query": "{__typename}",
+                "variables": {"id": 1},
+                "operationName": "Foo",
+                "extensions": {"persistedQuery": {"version": 1, "sha256Hash": HASH}}
+            })
+        );
+    }
+
+    #[test]
+    fn test_document_id_body() {
+        assert_eq!(
+            persisted_query().document_id_body(),
+            json!({
+                "documentId": HASH,
+                "variables": {"id": 1},
+                "operationName": "Foo",
+            })
+        );
+    }
+
+    #[test]
+    fn test_query_params() {
+        let params = persisted_query().query_params();
+
+        assert_eq!(params[0], ("operationName", "Foo".to_string()));
+        assert_eq!(params[1], ("variables", r#"{"id":1}"#.to_string()));
+        assert_eq!(params[2].0, "extensions");
+        assert_eq!(
+            serde_json::from_str::<Value>(&params[2].1).unwrap(),
+            json!({"persistedQuery": {"version": 1, "sha256Hash": HASH}})
+        );
+    }
+
+    #[test]
+    fn test_should_send_full_query() {
+        assert!(should_send_full_query(
+            br#"{"errors": [{"message": "PersistedQueryNotFound"}]}"#
+        ));
+        assert!(should_send_full_query(
+            br#"{"errors": [{"message": "Nope", "extensions": {"code": "PERSISTED_QUERY_NOT_FOUND"}}]}"#
+        ));
+        assert!(!should_send_full_query(
+            br#"{"errors": [{"message": "Something else"}]}"#
+        ));
+        assert!(!should_send_full_query(
+            br#"{"data": {"__typename": "Query"}}"#
+        ));
+        assert!(!should_send_full_query(b"Internal Server Error"));
+    }
+}
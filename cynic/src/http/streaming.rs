@@ -0,0 +1,382 @@
+//! Decoders for GraphQL responses that are streamed over HTTP.
+//!
+//! Servers that deliver subscriptions over plain HTTP do so either with a
+//! `text/event-stream` body (as in the [graphql-sse][sse] protocol) or with a
+//! `multipart/mixed` body (as in Apollo's [multipart subscriptions][multipart]).
+//!
+//! These decoders don't do any IO themselves: chunks of the body are pushed
+//! in as they arrive, and complete payloads are pulled out once available.
+//!
+//! [sse]: https://github.com/enisdenjo/graphql-sse/blob/master/PROTOCOL.md
+//! [multipart]: https://www.apollographql.com/docs/router/executing-operations/subscription-multipart-protocol/
+
+use serde_json::Value;
+
+/// The `Accept` header to send when we're expecting a streamed response
+#[cfg(feature = "http-reqwest")]
+pub(crate) const STREAMING_ACCEPT: &str =
+    r#"text/event-stream, multipart/mixed;subscriptionSpec="1.0", application/json;q=0.9"#;
+
+pub(crate) enum StreamDecoder {
+    EventStream(EventStreamDecoder),
+    Multipart(MultipartDecoder),
+    Json(JsonDecoder),
+}
+
+impl StreamDecoder {
+    /// Creates the correct decoder for the given `Content-Type`, if we support it
+    pub fn for_content_type(content_type: &str) -> Option<Self> {
+        let mut params = content_type.split(';').map(str::trim);
+        let mime = params.next()?.to_ascii_lowercase();
+
+        match mime.as_str() {
+            "text/event-stream" => Some(StreamDecoder::EventStream(EventStreamDecoder::default())),
+            "multipart/mixed" => {
+                let boundary = params
+                    .filter_map(|param| param.split_once('='))
+                    .find(|(name, _)| name.trim().eq_ignore_ascii_case("boundary"))
+                    .map(|(_, value)| value.trim().trim_matches('"'))
+                    .unwrap_or("-");
+
+                Some(StreamDecoder::Multipart(MultipartDecoder::new(boundary)))
+            }
+            "application/json" | "application/graphql-response+json" => {
+                Some(StreamDecoder::Json(JsonDecoder::default()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Adds a chunk of the body to the decoders buffer
+    pub fn push(&mut self, bytes: &[u8]) {
+        match self {
+            StreamDecoder::EventStream(decoder) => decoder.buffer.extend_from_slice(bytes),
+            StreamDecoder::Multipart(decoder) => decoder.buffer.extend_from_slice(bytes),
+            StreamDecoder::Json(decoder) => decoder.buffer.extend_from_slice(bytes),
+        }
+    }
+
+    /// Tells the decoder that the body has ended
+    pub fn finish(&mut self) {
+        if let StreamDecoder::Json(decoder) = self {
+            decoder.finished = true;
+        }
+    }
+
+    /// Returns true if the decoder has seen the end of the stream.
+    ///
+    /// There may still be buffered payloads available from `next_payload`
+    #[cfg(feature = "http-reqwest")]
+    pub fn is_complete(&self) -> bool {
+        match self {
+            StreamDecoder::EventStream(decoder) => decoder.complete,
+            StreamDecoder::Multipart(decoder) => decoder.complete,
+            StreamDecoder::Json(decoder) => decoder.finished,
+        }
+    }
+
+    /// Pulls the next complete payload out of the buffer, if there is one
+    pub fn next_payload(&mut self) -> Option<Result<Value, String>> {
+        match self {
+            StreamDecoder::EventStream(decoder) => decoder.next_payload(),
+            StreamDecoder::Multipart(decoder) => decoder.next_payload(),
+            StreamDecoder::Json(decoder) => decoder.next_payload(),
+        }
+    }
+}
+
+/// Decodes a `text/event-stream` body.
+#[derive(Default)]
+pub(crate) struct EventStreamDecoder {
+    buffer: Vec<u8>,
+    complete: bool,
+}
+
+impl EventStreamDecoder {
+    fn next_payload(&mut self) -> Option<Result<Value, String>> {
+        while !self.complete {
+            let (end, delimiter_len) = find_event_end(&self.buffer)?;
+            let event = self.buffer.drain(..end + delimiter_len).collect::<Vec<_>>();
+            let event = String::from_utf8_lossy(&event[..end]);
+
+            let mut event_name = None;
+            let mut data = None::<String>;
+            for line in event.lines() {
+                if line.starts_with(':') {
+                    // Comments are often used as keep alives
+                    continue;
+                }
+                let (field, value) = match line.split_once(':') {
+                    Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+                    None => (line, ""),
+                };
+                match field {
+                    "event" => event_name = Some(value.to_string()),
+                    "data" => match &mut data {
+                        Some(data) => {
+                            data.push('\n');
+                            data.push_str(value);
+                        }
+                        None => data = Some(value.to_string()),
+                    },
+                    _ => {}
+                }
+            }
+
+            match (event_name.as_deref(), data) {
+                (Some("complete"), _) => self.complete = true,
+                (Some("next") | None, Some(data)) => {
+                    return Some(serde_json::from_str(&data).map_err(|error| error.to_string()));
+                }
+                _ => {}
+            }
+        }
+
+        None
+    }
+}
+
+/// Finds the end of the first event in the buffer, returning its position
+/// and the length of the delimiter.
+fn find_event_end(buffer: &[u8]) -> Option<(usize, usize)> {
+    let lf = find(buffer, b"\n\n").map(|position| (position, 2));
+    let crlf = find(buffer, b"\r\n\r\n").map(|position| (position, 4));
+
+    match (lf, crlf) {
+        (Some(lf), Some(crlf)) => Some(std::cmp::min(lf, crlf)),
+        (lf, crlf) => lf.or(crlf),
+    }
+}
+
+/// Decodes a `multipart/mixed` body.
+pub(crate) struct MultipartDecoder {
+    delimiter: Vec<u8>,
+    buffer: Vec<u8>,
+    started: bool,
+    complete: bool,
+}
+
+impl MultipartDecoder {
+    fn new(boundary: &str) -> Self {
+        MultipartDecoder {
+            delimiter: format!("--{boundary}").into_bytes(),
+            buffer: Vec::new(),
+            started: false,
+            complete: false,
+        }
+    }
+
+    fn next_payload(&mut self) -> Option<Result<Value, String>> {
+        while !self.complete {
+            if !self.started {
+                // Skip any preamble up until the first delimiter
+                let position = find(&self.buffer, &self.delimiter)?;
+                let after_delimiter = position + self.delimiter.len();
+                self.complete = self.buffer.get(after_delimiter..after_delimiter + 2)? == b"--";
+                self.buffer.drain(..after_delimiter);
+                self.started = true;
+                continue;
+            }
+
+            let mut part_end = vec![b'\n'];
+            part_end.extend_from_slice(&self.delimiter);
+
+            let position = find(&self.buffer, &part_end)?;
+            let after_delimiter = position + part_end.len();
+            let closing = self.buffer.get(after_delimiter..after_delimiter + 2)? == b"--";
+
+            let part = self.buffer.drain(..after_delimiter).collect::<Vec<_>>();
+            self.complete = closing;
+
+            if let Some(payload) = decode_part(&part[..position]) {
+                return Some(payload);
+            }
+        }
+
+        None
+    }
+}
+
+fn decode_part(part: &[u8]) -> Option<Result<Value, String>> {
+    let body = match (find(part, b"\r\n\r\n"), find(part, b"\n\n")) {
+        (Some(crlf), _) => &part[crlf + 4..],
+        (None, Some(lf)) => &part[lf + 2..],
+        // A part with no headers
+        (None, None) => part,
+    };
+
+    let body = String::from_utf8_lossy(body);
+    let body = body.trim();
+    if body.is_empty() {
+        return None;
+    }
+
+    match serde_json::from_str::<Value>(body) {
+        Ok(value) => unwrap_multipart_payload(value).map(Ok),
+        Err(error) => Some(Err(error.to_string())),
+    }
+}
+
+/// Multipart subscriptions wrap each response in a `payload` field, and use
+/// empty objects as heartbeats.  This unwraps the response, if there is one.
+fn unwrap_multipart_payload(value: Value) -> Option<Value> {
+    let Value::Object(mut object) = value else {
+        return Some(value);
+    };
+
+    if object.is_empty() {
+        return None;
+    }
+
+    match object.remove("payload") {
+        // A null payload means there were transport level errors
+        Some(Value::Null) => object
+            .remove("errors")
+            .map(|errors| serde_json::json!({ "errors": errors })),
+        Some(payload) => Some(payload),
+        None => Some(Value::Object(object)),
+    }
+}
+
+/// Decodes a plain JSON body, which some servers will respond with if they
+/// can't (or won't) stream their response.
+#[derive(Default)]
+pub(crate) struct JsonDecoder {
+    buffer: Vec<u8>,
+    finished: bool,
+    returned: bool,
+}
+
+impl JsonDecoder {
+    fn next_payload(&mut self) -> Option<Result<Value, String>> {
+        if !self.finished || self.returned {
+            return None;
+        }
+        self.returned = true;
+
+        Some(serde_json::from_slice(&self.buffer).map_err(|error| error.to_string()))
+    }
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn decode_in_chunks(content_type: &str, body: &str, chunk_size: usize) -> Vec<Value> {
+        let mut decoder = StreamDecoder::for_content_type(content_type).unwrap();
+        let mut output = Vec::new();
+
+        for chunk in body.as_bytes().chunks(chunk_size) {
+            decoder.push(chunk);
+            while let Some(payload) = decoder.next_payload() {
+                output.push(payload.unwrap());
+            }
+        }
+        decoder.finish();
+        while let Some(payload) = decoder.next_payload() {
+            output.push(payload.unwrap());
+        }
+
+        assert!(decoder.is_complete());
+
+        output
+    }
+
+    #[test]
+    fn test_event_stream() {
+        let body = concat!(
+            ": keep-alive\n\n",
+            "event: next\n",
+            "data: {\"data\": {\"interval\": 1}}\n\n",
+            "event: next\r\n",
+            "data: {\"data\":\r\n",
+            "data: {\"interval\": 2}}\r\n\r\n",
+            "event: complete\n",
+            "data:\n\n",
+            "event: next\n",
+            "data: {\"data\": {\"interval\": 3}}\n\n",
+        );
+
+        for chunk_size in [1, 7, body.len()] {
+            assert_eq!(
+                decode_in_chunks("text/event-stream; charset=utf-8", body, chunk_size),
+                vec![
+                    json!({"data": {"interval": 1}}),
+                    json!({"data": {"interval": 2}})
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_multipart() {
+        let body = concat!(
+            "\r\n--graphql\r\n",
+            "content-type: application/json\r\n\r\n",
+            "{}\r\n",
+            "--graphql\r\n",
+            "content-type: application/json\r\n\r\n",
+            "{\"payload\": {\"data\": {\"interval\": 1}}}\r\n",
+            "--graphql\r\n",
+            "content-type: application/json\r\n\r\n",
+            "{\"payload\": null, \"errors\": [{\"message\": \"Oops\"}]}\r\n",
+            "--graphql--\r\n",
+        );
+
+        for chunk_size in [1, 5, body.len()] {
+            assert_eq!(
+                decode_in_chunks(
+                    r#"multipart/mixed;boundary="graphql";subscriptionSpec="1.0""#,
+                    body,
+                    chunk_size
+                ),
+                vec![
+                    json!({"data": {"interval": 1}}),
+                    json!({"errors": [{"message": "Oops"}]})
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_multipart_with_unwrapped_payloads() {
+        let body = concat!(
+            "--abc\r\n",
+            "Content-Type: application/json; charset=utf-8\r\n\r\n",
+            "{\"data\": {\"interval\": 1}}\r\n",
+            "--abc\r\n",
+            "Content-Type: application/json; charset=utf-8\r\n\r\n",
+            "{\"data\": {\"interval\": 2}}\r\n",
+            "--abc--",
+        );
+
+        assert_eq!(
+            decode_in_chunks("multipart/mixed; boundary=abc", body, 3),
+            vec![
+                json!({"data": {"interval": 1}}),
+                json!({"data": {"interval": 2}})
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        assert_eq!(
+            decode_in_chunks("application/json", r#"{"data": {"interval": 1}}"#, 4),
+            vec![json!({"data": {"interval": 1}})]
+        );
+    }
+
+    #[test]
+    fn test_unsupported_content_type() {
+        assert!(StreamDecoder::for_content_type("text/html").is_none());
+    }
+}
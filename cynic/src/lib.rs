@@ -158,6 +158,12 @@
 //!   http client.
 //! - `http-reqwest-blocking` adds blocking integration with the [`reqwest`](https://github.com/seanmonstar/reqwest)
 //!   http client.
+//! - `client` adds a transport agnostic [`client::Client`] with support for
+//!   middleware such as retries & timeouts.
+//! - `persisted-queries` adds support for persisted queries to the `reqwest`
+//!   integration, and the ability to generate manifests of persisted documents.
+//! - `ws` adds a client for running subscriptions over websockets, built on
+//!   [`tungstenite`](https://github.com/snapview/tungstenite-rs).
 //! - `rkyv` can be used to speed up compiles when working with large schemas.
 //!
 //! It's worth noting that each of these features pulls in extra
@@ -169,11 +175,14 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![warn(missing_docs)]
 
+#[cfg(any(feature = "http-surf", feature = "http-reqwest", feature = "client"))]
+mod batch;
 mod builders;
 mod core;
 mod id;
 mod operation;
 mod result;
+mod upload;
 
 pub mod coercions;
 pub mod queries;
@@ -182,15 +191,35 @@ pub mod variables;
 pub mod http;
 pub mod schema;
 
+#[cfg(feature = "client")]
+#[cfg_attr(docsrs, doc(cfg(feature = "client")))]
+pub mod client;
+
+#[cfg(feature = "persisted-queries")]
+#[cfg_attr(docsrs, doc(cfg(feature = "persisted-queries")))]
+pub mod persisted;
+
+#[cfg(feature = "ws")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ws")))]
+pub mod ws;
+
 #[path = "private/mod.rs"]
 pub mod __private;
 
+#[cfg(any(feature = "http-surf", feature = "http-reqwest", feature = "client"))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "http-surf", feature = "http-reqwest", feature = "client")))
+)]
+pub use batch::{Batch, BatchHandle, BatchResponse, BatchResultError};
+
 pub use {
     self::core::{Enum, InlineFragments, InputObject, QueryFragment},
     builders::{MutationBuilder, QueryBuilder, SubscriptionBuilder},
     id::Id,
     operation::{Operation, OperationBuildError, OperationBuilder, StreamingOperation},
     result::*,
+    upload::{InvalidMimeType, Upload},
     variables::{QueryVariableLiterals, QueryVariables, QueryVariablesFields},
 };
 
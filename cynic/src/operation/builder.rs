@@ -2,8 +2,8 @@ use std::{borrow::Cow, collections::HashSet, marker::PhantomData};
 
 use crate::{
     QueryFragment, QueryVariableLiterals, QueryVariables,
-    queries::{OperationType, build_executable_document},
-    schema::{MutationRoot, QueryRoot, SubscriptionRoot},
+    queries::{OperationType, RecurseLimits, build_executable_document_with_recurse_limits},
+    schema::{self, MutationRoot, QueryRoot, SubscriptionRoot},
 };
 
 use super::Operation;
@@ -18,6 +18,7 @@ pub struct OperationBuilder<QueryFragment, Variables = ()> {
     operation_kind: OperationType,
     operation_name: Option<Cow<'static, str>>,
     features: HashSet<String>,
+    recurse_limits: RecurseLimits,
     phantom: PhantomData<fn() -> QueryFragment>,
 }
 
@@ -32,6 +33,7 @@ where
             operation_kind,
             operation_name: Fragment::name(),
             features: HashSet::new(),
+            recurse_limits: RecurseLimits::default(),
             phantom: PhantomData,
         }
     }
@@ -84,6 +86,29 @@ where
         self.features.insert(feature.to_string());
     }
 
+    /// Sets the maximum depth of the recursive field `Field`, overriding the
+    /// depth it was given with `#[cynic(recurse = N)]`
+    ///
+    /// This can be used when the depth that's needed isn't known until
+    /// runtime.  `Field` is the fields marker type from the schema module,
+    /// e.g. `schema::__fields::User::friends`.
+    pub fn with_recurse_limit<Field>(mut self, limit: u8) -> Self
+    where
+        Field: schema::Field,
+    {
+        self.set_recurse_limit::<Field>(limit);
+        self
+    }
+
+    /// Sets the maximum depth of the recursive field `Field`, overriding the
+    /// depth it was given with `#[cynic(recurse = N)]`
+    pub fn set_recurse_limit<Field>(&mut self, limit: u8)
+    where
+        Field: schema::Field,
+    {
+        self.recurse_limits.set::<Field>(limit);
+    }
+
     /// Adds a name to the operation
     pub fn with_operation_name(self, name: &str) -> Self {
         OperationBuilder {
@@ -100,11 +125,12 @@ where
     /// Tries to build an [Operation]
     pub fn build(self) -> Result<super::Operation<Fragment, Variables>, OperationBuildError> {
         Ok(Operation {
-            query: build_executable_document::<Fragment, Variables>(
+            query: build_executable_document_with_recurse_limits::<Fragment, Variables>(
                 self.operation_kind,
                 self.operation_name.as_deref(),
                 self.features.clone(),
                 None,
+                &self.recurse_limits,
             ),
             variables: self.variables.ok_or(OperationBuildError::VariablesNotSet)?,
             operation_name: self.operation_name,
@@ -125,11 +151,12 @@ where
     {
         let variables = self.variables.ok_or(OperationBuildError::VariablesNotSet)?;
         Ok(Operation {
-            query: build_executable_document::<Fragment, Variables>(
+            query: build_executable_document_with_recurse_limits::<Fragment, Variables>(
                 self.operation_kind,
                 self.operation_name.as_deref(),
                 self.features.clone(),
                 Some(&variables),
+                &self.recurse_limits,
             ),
             variables: (),
             operation_name: self.operation_name,
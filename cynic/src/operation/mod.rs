@@ -116,6 +116,31 @@ where
     }
 }
 
+#[cfg(feature = "persisted-queries")]
+impl<Fragment, Variables> Operation<Fragment, Variables> {
+    /// Returns the hex encoded SHA-256 hash of the query in this operation.
+    ///
+    /// This is the hash that servers use to identify a query when using
+    /// automatic persisted queries.
+    pub fn query_hash(&self) -> String {
+        sha256_hex(&self.query)
+    }
+}
+
+#[cfg(feature = "persisted-queries")]
+pub(crate) fn sha256_hex(input: &str) -> String {
+    use sha2::Digest;
+    use std::fmt::Write;
+
+    sha2::Sha256::digest(input.as_bytes()).iter().fold(
+        String::with_capacity(64),
+        |mut output, byte| {
+            write!(output, "{byte:02x}").unwrap();
+            output
+        },
+    )
+}
+
 impl<F, V> AsRef<Operation<F, V>> for Operation<F, V> {
     fn as_ref(&self) -> &Operation<F, V> {
         self
@@ -134,6 +159,16 @@ where
     Fragment: QueryFragment,
     Variables: QueryVariables,
 {
+    /// Constructs a new StreamingOperation from a String & some variables.
+    ///
+    /// This is useful for certain testing cirumstances, but offers no typesafety.
+    /// [crate::SubscriptionBuilder] should be preferred.
+    pub fn new(query: String, variables: Variables) -> Self {
+        StreamingOperation {
+            inner: Operation::new(query, variables),
+        }
+    }
+
     /// Constructs a new Operation for a subscription
     pub fn subscription(variables: Variables) -> Self
     where
@@ -148,6 +183,12 @@ where
     }
 }
 
+impl<F, V> AsRef<StreamingOperation<F, V>> for StreamingOperation<F, V> {
+    fn as_ref(&self) -> &StreamingOperation<F, V> {
+        self
+    }
+}
+
 impl<ResponseData, Variables> serde::Serialize for StreamingOperation<ResponseData, Variables>
 where
     Variables: serde::Serialize,
@@ -0,0 +1,264 @@
+//! Support for generating manifests of persisted documents.
+//!
+//! Servers that only run a safelist of "trusted documents" need to be told
+//! about every operation a client might send ahead of time.  A [`Manifest`]
+//! renders each of your operations the same way cynic would at runtime, and
+//! can be serialized into a JSON object that maps each document's ID (the hex
+//! encoded SHA-256 hash of the document) to the document itself.
+//!
+//! Operations can then be sent with just their document ID, e.g. using
+//! `persisted_document` on the reqwest integration.
+//!
+//! Manifests are usually generated from a test or a small binary in your
+//! project, and the output uploaded to your server as part of your release
+//! process:
+//!
+//! ```rust
+//! # mod schema {
+//! #   cynic::use_schema!("../schemas/starwars.schema.graphql");
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(
+//! #    schema_path = "../schemas/starwars.schema.graphql",
+//! #    schema_module = "schema",
+//! # )]
+//! # struct Film {
+//! #    title: Option<String>,
+//! #    director: Option<String>
+//! # }
+//! #
+//! # #[derive(cynic::QueryVariables)]
+//! # struct FilmArguments {
+//! #     id: Option<cynic::Id>,
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(
+//! #     schema_path = "../schemas/starwars.schema.graphql",
+//! #     schema_module = "schema",
+//! #     graphql_type = "Root",
+//! #     variables = "FilmArguments"
+//! # )]
+//! # struct FilmQuery {
+//! #     #[arguments(id: $id)]
+//! #     film: Option<Film>,
+//! # }
+//! use cynic::persisted::Manifest;
+//!
+//! let manifest = Manifest::new()
+//!     .with_query::<FilmQuery, FilmArguments>();
+//! ```
+//!
+//! Rather than listing every operation by hand, you can also add every
+//! operation that was derived in your program with
+//! [`Manifest::with_registered_operations`]:
+//!
+//! ```rust
+//! # use cynic::persisted::Manifest;
+//! let manifest = Manifest::new().with_registered_operations();
+//!
+//! # let path = std::env::temp_dir().join("persisted-documents.json");
+//! std::fs::write(path, manifest.to_json()).unwrap();
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::OnceLock,
+};
+
+use crate::{
+    QueryFragment, QueryVariables,
+    operation::sha256_hex,
+    queries::{OperationType, build_executable_document},
+    schema::{MutationRoot, QueryRoot, SubscriptionRoot},
+};
+
+/// A set of persisted documents, keyed by their document ID.
+#[derive(Clone, Debug, Default)]
+pub struct Manifest {
+    features: Vec<String>,
+    operations: Vec<RegisteredOperation>,
+    documents: OnceLock<BTreeMap<String, String>>,
+}
+
+impl Manifest {
+    /// Creates an empty `Manifest`
+    pub fn new() -> Self {
+        Manifest::default()
+    }
+
+    /// Sets the feature flags that operations might be built with.
+    ///
+    /// Every operation in the manifest will be rendered once for every
+    /// combination of these features, so that the manifest contains every
+    /// document that could be sent.  Note that the number of combinations
+    /// grows exponentially with the number of features.
+    pub fn with_features<I>(mut self, features: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.features = features.into_iter().map(Into::into).collect();
+        self.documents = OnceLock::new();
+        self
+    }
+
+    /// Adds a query to the manifest
+    pub fn with_query<Fragment, Variables>(self) -> Self
+    where
+        Fragment: QueryFragment<VariablesFields = Variables::Fields>,
+        Fragment::SchemaType: QueryRoot,
+        Variables: QueryVariables,
+    {
+        self.with_operation(RegisteredOperation::new::<Fragment, Variables>(
+            OperationType::Query,
+        ))
+    }
+
+    /// Adds a mutation to the manifest
+    pub fn with_mutation<Fragment, Variables>(self) -> Self
+    where
+        Fragment: QueryFragment<VariablesFields = Variables::Fields>,
+        Fragment::SchemaType: MutationRoot,
+        Variables: QueryVariables,
+    {
+        self.with_operation(RegisteredOperation::new::<Fragment, Variables>(
+            OperationType::Mutation,
+        ))
+    }
+
+    /// Adds a subscription to the manifest
+    pub fn with_subscription<Fragment, Variables>(self) -> Self
+    where
+        Fragment: QueryFragment<VariablesFields = Variables::Fields>,
+        Fragment::SchemaType: SubscriptionRoot,
+        Variables: QueryVariables,
+    {
+        self.with_operation(RegisteredOperation::new::<Fragment, Variables>(
+            OperationType::Subscription,
+        ))
+    }
+
+    /// Adds every operation that was derived in this program to the manifest.
+    ///
+    /// The `QueryFragment` derive registers every fragment on a query,
+    /// mutation or subscription root, so this picks up operations from any
+    /// crate in the program - not just the one calling it.  Fragments that are
+    /// generic, or whose variables are generic over any types, can't be
+    /// registered - these should be added with `with_query` & friends.
+    pub fn with_registered_operations(mut self) -> Self {
+        self.operations
+            .extend(inventory::iter::<RegisteredOperation>.into_iter().copied());
+        self.documents = OnceLock::new();
+        self
+    }
+
+    fn with_operation(mut self, operation: RegisteredOperation) -> Self {
+        self.operations.push(operation);
+        self.documents = OnceLock::new();
+        self
+    }
+
+    /// Renders every operation with every combination of features
+    fn render(&self) -> BTreeMap<String, String> {
+        let combinations = feature_combinations(&self.features);
+
+        let mut documents = BTreeMap::new();
+        for operation in &self.operations {
+            for features in &combinations {
+                if let Some(document) =
+                    (operation.build)(operation.operation_type, features.clone())
+                {
+                    documents.insert(sha256_hex(&document), document);
+                }
+            }
+        }
+        documents
+    }
+
+    /// Iterates over the IDs & documents in this manifest
+    pub fn documents(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.documents
+            .get_or_init(|| self.render())
+            .iter()
+            .map(|(id, document)| (id.as_str(), document.as_str()))
+    }
+
+    /// Serializes this manifest as a JSON object mapping document IDs to documents
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self.documents.get_or_init(|| self.render()))
+            .expect("manifests to serialize")
+    }
+}
+
+/// An operation that can be rendered into a [`Manifest`].
+///
+/// These are registered by the `QueryFragment` derive, and can be added to a
+/// manifest with [`Manifest::with_registered_operations`].
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug)]
+pub struct RegisteredOperation {
+    operation_type: OperationType,
+    build: fn(OperationType, HashSet<String>) -> Option<String>,
+}
+
+impl RegisteredOperation {
+    fn new<Fragment, Variables>(operation_type: OperationType) -> Self
+    where
+        Fragment: QueryFragment,
+        Variables: QueryVariables,
+    {
+        RegisteredOperation {
+            operation_type,
+            build: build_document::<Fragment, Variables>,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn from_fn(
+        operation_type: OperationType,
+        build: fn(OperationType, HashSet<String>) -> Option<String>,
+    ) -> Self {
+        RegisteredOperation {
+            operation_type,
+            build,
+        }
+    }
+}
+
+inventory::collect!(RegisteredOperation);
+
+pub(crate) fn build_document<Fragment, Variables>(
+    operation_type: OperationType,
+    features: HashSet<String>,
+) -> Option<String>
+where
+    Fragment: QueryFragment,
+    Variables: QueryVariables,
+{
+    Some(build_executable_document::<Fragment, Variables>(
+        operation_type,
+        Fragment::name().as_deref(),
+        features,
+        None,
+    ))
+}
+
+/// Returns every combination of the given features
+fn feature_combinations(features: &[String]) -> Vec<HashSet<String>> {
+    let mut combinations = vec![HashSet::new()];
+    for feature in features {
+        let with_feature = combinations
+            .iter()
+            .map(|combination| {
+                let mut combination = combination.clone();
+                combination.insert(feature.clone());
+                combination
+            })
+            .collect::<Vec<_>>();
+
+        combinations.extend(with_feature);
+    }
+    combinations
+}
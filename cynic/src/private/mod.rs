@@ -13,3 +13,64 @@ mod spread_de;
 pub use self::{
     flatten_de::Flattened, inline_fragment_de::InlineFragmentVisitor, spread_de::Spreadable,
 };
+
+#[cfg(feature = "persisted-queries")]
+mod registration;
+
+#[cfg(feature = "persisted-queries")]
+pub use {
+    inventory,
+    registration::{RegisterFallback, RegisterWithVariables, Registration},
+};
+
+/// Implemented by the `QueryVariables` derive on the `Fields` struct for
+/// variables that aren't generic over any types, so that the variables
+/// struct can be found from a fragment.
+pub trait KnownVariables {
+    type Variables: crate::QueryVariables;
+}
+
+impl KnownVariables for () {
+    type Variables = ();
+}
+
+/// Registers a root fragment so that it can be added to a persisted document
+/// `Manifest` automatically.
+///
+/// This does nothing unless the `persisted-queries` feature is enabled.
+#[cfg(feature = "persisted-queries")]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __register_operation {
+    ($operation_type:ident, $fragment:ty) => {
+        $crate::__private::inventory::submit! {
+            $crate::persisted::RegisteredOperation::from_fn(
+                $crate::queries::OperationType::$operation_type,
+                {
+                    fn build(
+                        operation_type: $crate::queries::OperationType,
+                        features: ::std::collections::HashSet<String>,
+                    ) -> Option<String> {
+                        #[allow(unused_imports)]
+                        use $crate::__private::{RegisterFallback, RegisterWithVariables};
+
+                        (&&$crate::__private::Registration::<$fragment>::new())
+                            .build(operation_type, features)
+                    }
+                    build
+                },
+            )
+        }
+    };
+}
+
+/// Registers a root fragment so that it can be added to a persisted document
+/// `Manifest` automatically.
+///
+/// This does nothing unless the `persisted-queries` feature is enabled.
+#[cfg(not(feature = "persisted-queries"))]
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __register_operation {
+    ($operation_type:ident, $fragment:ty) => {};
+}
@@ -0,0 +1,48 @@
+//! Support for registering operations in the persisted document registry.
+//!
+//! A fragment can only be registered if we can find its variables struct, and
+//! the derive can't tell whether that struct is generic.  So we use autoref
+//! specialization: `RegisterWithVariables` applies when the fragments
+//! `VariablesFields` implement `KnownVariables`, and `RegisterFallback` (which
+//! skips the fragment) applies otherwise.
+
+use std::{collections::HashSet, marker::PhantomData};
+
+use super::KnownVariables;
+use crate::{QueryFragment, queries::OperationType};
+
+pub struct Registration<Fragment>(PhantomData<fn() -> Fragment>);
+
+impl<Fragment> Registration<Fragment> {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Registration(PhantomData)
+    }
+}
+
+pub trait RegisterWithVariables {
+    fn build(&self, operation_type: OperationType, features: HashSet<String>) -> Option<String>;
+}
+
+impl<Fragment> RegisterWithVariables for &Registration<Fragment>
+where
+    Fragment: QueryFragment,
+    Fragment::VariablesFields: KnownVariables,
+{
+    fn build(&self, operation_type: OperationType, features: HashSet<String>) -> Option<String> {
+        crate::persisted::build_document::<
+            Fragment,
+            <Fragment::VariablesFields as KnownVariables>::Variables,
+        >(operation_type, features)
+    }
+}
+
+pub trait RegisterFallback {
+    fn build(&self, operation_type: OperationType, features: HashSet<String>) -> Option<String>;
+}
+
+impl<Fragment> RegisterFallback for Registration<Fragment> {
+    fn build(&self, _: OperationType, _: HashSet<String>) -> Option<String> {
+        None
+    }
+}
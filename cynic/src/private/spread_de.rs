@@ -45,6 +45,40 @@ where
         Err(E::missing_field(field))
     }
 
+    pub fn deserialize_field_or_default<T>(&self, field: &'static str) -> Result<T, E>
+    where
+        T: serde::de::Deserialize<'de> + Default,
+    {
+        match self.fields.get(field) {
+            Some(content) => T::deserialize(ContentRefDeserializer::new(content)),
+            None => Ok(T::default()),
+        }
+    }
+
+    /// Deserializes a deferred spread, which will be `None` until all of its
+    /// fields have been delivered.
+    ///
+    /// Only fields missing from the spread itself are treated as undelivered -
+    /// any other error is returned.
+    pub fn deserialize_deferred_spread<T>(&self) -> Result<Option<T>, E>
+    where
+        T: serde::de::Deserialize<'de>,
+    {
+        let deserializer = DeferredSpreadDeserializer {
+            inner: SpreadDeserializer::<E> {
+                iter: self.fields.iter(),
+                next_content: None,
+                error: PhantomData,
+            },
+        };
+
+        match T::deserialize(deserializer) {
+            Ok(value) => Ok(Some(value)),
+            Err(DeferredSpreadError::MissingField(_)) => Ok(None),
+            Err(DeferredSpreadError::Other(error)) => Err(error),
+        }
+    }
+
     pub fn spread_deserializer(&'_ self) -> impl Deserializer<'de, Error = E> + '_ {
         SpreadDeserializer::<E> {
             iter: self.fields.iter(),
@@ -112,3 +146,91 @@ where
         seed.deserialize(ContentRefDeserializer::new(content))
     }
 }
+
+/// Wraps a `SpreadDeserializer` so that fields missing from the spread itself
+/// can be told apart from any other error, including fields missing from
+/// objects nested inside the spread.
+struct DeferredSpreadDeserializer<'a, 'de, E> {
+    inner: SpreadDeserializer<'a, 'de, E>,
+}
+
+impl<'de, E> Deserializer<'de> for DeferredSpreadDeserializer<'_, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = DeferredSpreadError<E>;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::Visitor<'de>,
+    {
+        visitor.visit_map(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+impl<'de, E> MapAccess<'de> for DeferredSpreadDeserializer<'_, 'de, E>
+where
+    E: de::Error,
+{
+    type Error = DeferredSpreadError<E>;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        self.inner
+            .next_key_seed(seed)
+            .map_err(DeferredSpreadError::Other)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.inner
+            .next_value_seed(seed)
+            .map_err(DeferredSpreadError::Other)
+    }
+}
+
+#[derive(Debug)]
+enum DeferredSpreadError<E> {
+    MissingField(&'static str),
+    Other(E),
+}
+
+impl<E> std::fmt::Display for DeferredSpreadError<E>
+where
+    E: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeferredSpreadError::MissingField(field) => write!(f, "missing field `{field}`"),
+            DeferredSpreadError::Other(error) => error.fmt(f),
+        }
+    }
+}
+
+impl<E> std::error::Error for DeferredSpreadError<E> where E: de::Error {}
+
+impl<E> de::Error for DeferredSpreadError<E>
+where
+    E: de::Error,
+{
+    fn custom<T>(msg: T) -> Self
+    where
+        T: std::fmt::Display,
+    {
+        DeferredSpreadError::Other(E::custom(msg))
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        DeferredSpreadError::MissingField(field)
+    }
+}
@@ -87,6 +87,7 @@ pub struct Directive {
 /// An inline fragment that selects fields from one possible type
 pub struct InlineFragment {
     pub(super) on_clause: Option<&'static str>,
+    pub(super) directives: Vec<Directive>,
     pub(super) children: SelectionSet,
 }
 
@@ -139,20 +140,8 @@ impl std::fmt::Display for Selection {
                     write!(f, ")")?;
                 }
 
-                for Directive { name, arguments } in &field_selection.directives {
-                    write!(f, " @{name}")?;
-                    if !arguments.is_empty() {
-                        write!(f, "(")?;
-                        let mut first = true;
-                        for arg in arguments {
-                            if !first {
-                                write!(f, ", ")?;
-                            }
-                            first = false;
-                            write!(f, "{}", arg)?;
-                        }
-                        write!(f, ")")?;
-                    }
+                for directive in &field_selection.directives {
+                    write!(f, " {directive}")?;
                 }
                 write!(f, "{}", field_selection.children)
             }
@@ -163,6 +152,9 @@ impl std::fmt::Display for Selection {
                     if let Some(on_type) = inline_fragment.on_clause {
                         write!(f, " on {}", on_type)?;
                     }
+                    for directive in &inline_fragment.directives {
+                        write!(f, " {directive}")?;
+                    }
                     write!(f, "{}", inline_fragment.children)?;
                 }
                 Ok(())
@@ -171,6 +163,25 @@ impl std::fmt::Display for Selection {
     }
 }
 
+impl std::fmt::Display for Directive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@{}", self.name)?;
+        if !self.arguments.is_empty() {
+            write!(f, "(")?;
+            let mut first = true;
+            for arg in &self.arguments {
+                if !first {
+                    write!(f, ", ")?;
+                }
+                first = false;
+                write!(f, "{}", arg)?;
+            }
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
 impl std::fmt::Display for Argument {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}: {}", self.name, self.value)
@@ -1,4 +1,9 @@
-use std::{borrow::Cow, collections::HashSet, marker::PhantomData, sync::mpsc::Sender};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    sync::mpsc::Sender,
+};
 
 use crate::{QueryVariableLiterals, coercions::CoercesTo, schema, variables::VariableDefinition};
 
@@ -44,11 +49,13 @@ impl<'a, SchemaType, VariablesFields> SelectionBuilder<'a, SchemaType, Variables
         variables_used: &'a Sender<&'static str>,
         features_enabled: &'a HashSet<String>,
         inline_variables: Option<&'a dyn QueryVariableLiterals>,
+        recurse_limits: &'a RecurseLimits,
     ) -> Self {
         SelectionBuilder::private_new(
             selection_set,
             BuilderContext {
                 recurse_depth: None,
+                recurse_limits,
                 overall_depth: 0,
                 features_enabled,
                 variables_used,
@@ -110,6 +117,10 @@ impl<'a, SchemaType, VariablesFields> SelectionBuilder<'a, SchemaType, Variables
     /// Recursively selects a field into this selection, with the given
     /// `FieldMarker` and `FieldType`, up to the given `max_depth`.
     ///
+    /// If the operation was built with a recurse limit for this field (see
+    /// `OperationBuilder::with_recurse_limit`) that is used in place of
+    /// `max_depth`.
+    ///
     /// This will return a `None` if we have reached the max_depth.
     pub fn recurse<FieldMarker, FieldType>(
         &'_ mut self,
@@ -122,6 +133,10 @@ impl<'a, SchemaType, VariablesFields> SelectionBuilder<'a, SchemaType, Variables
     {
         let context = self.context.recurse();
         let new_depth = context.recurse_depth.unwrap();
+        let max_depth = context
+            .recurse_limits
+            .get::<FieldMarker>()
+            .unwrap_or(max_depth);
         if new_depth >= max_depth {
             return None;
         }
@@ -281,6 +296,17 @@ impl<'a, SchemaType, VariablesFields> InlineFragmentBuilder<'a, SchemaType, Vari
         }
     }
 
+    /// Adds an `@defer` directive to the inline fragment.
+    ///
+    /// Servers that support incremental delivery will send the fields of
+    /// this fragment in a later payload.
+    pub fn defer(&mut self) {
+        self.inline_fragment.directives.push(Directive {
+            name: Cow::Borrowed("defer"),
+            arguments: vec![],
+        });
+    }
+
     /// Returns a SelectionBuilder that can be used to select the fields
     /// of this fragment.
     pub fn select_children<InnerVariablesFields>(
@@ -508,11 +534,28 @@ pub trait VariableMatch<T> {}
 
 impl<T> VariableMatch<()> for T where T: crate::QueryVariablesFields {}
 
+/// Per field overrides for the `max_depth` of recursive fields
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RecurseLimits(HashMap<&'static str, u8>);
+
+impl RecurseLimits {
+    // Field markers are always concrete unit structs, so their type names are
+    // enough to tell them apart without requiring them to be 'static
+    pub(crate) fn set<FieldMarker>(&mut self, limit: u8) {
+        self.0.insert(std::any::type_name::<FieldMarker>(), limit);
+    }
+
+    fn get<FieldMarker>(&self) -> Option<u8> {
+        self.0.get(std::any::type_name::<FieldMarker>()).copied()
+    }
+}
+
 #[derive(Clone, Copy)]
 struct BuilderContext<'a> {
     features_enabled: &'a HashSet<String>,
     variables_used: &'a Sender<&'static str>,
     recurse_depth: Option<u8>,
+    recurse_limits: &'a RecurseLimits,
     overall_depth: u16,
     inline_variables: Option<&'a dyn QueryVariableLiterals>,
 }
@@ -13,6 +13,8 @@ use variables::VariableDefinitions;
 
 use crate::QueryVariableLiterals;
 
+pub(crate) use self::builders::RecurseLimits;
+
 pub use self::{
     ast::{Argument, InputLiteral, SelectionSet},
     builders::{SelectionBuilder, VariableMatch},
@@ -40,6 +42,28 @@ pub fn build_executable_document<Fragment, Variables>(
     features_enabled: HashSet<String>,
     inline_variables: Option<&dyn QueryVariableLiterals>,
 ) -> String
+where
+    Fragment: crate::QueryFragment,
+    Variables: crate::QueryVariables,
+{
+    build_executable_document_with_recurse_limits::<Fragment, Variables>(
+        r#type,
+        operation_name,
+        features_enabled,
+        inline_variables,
+        &RecurseLimits::default(),
+    )
+}
+
+/// Builds an executable document for the given Fragment, overriding the
+/// maximum depth of some recursive fields with `recurse_limits`
+pub(crate) fn build_executable_document_with_recurse_limits<Fragment, Variables>(
+    r#type: OperationType,
+    operation_name: Option<&str>,
+    features_enabled: HashSet<String>,
+    inline_variables: Option<&dyn QueryVariableLiterals>,
+    recurse_limits: &RecurseLimits,
+) -> String
 where
     Fragment: crate::QueryFragment,
     Variables: crate::QueryVariables,
@@ -52,6 +76,7 @@ where
         &variable_tx,
         &features_enabled,
         inline_variables,
+        recurse_limits,
     );
 
     Fragment::query(builder);
@@ -66,6 +91,7 @@ where
 }
 
 /// The kind of operation to build an executable document for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OperationType {
     /// A query operation
     Query,
@@ -0,0 +1,431 @@
+//! Support for file uploads via the [GraphQL multipart request spec][spec].
+//!
+//! An [`Upload`] can be used anywhere in the variables of an operation that
+//! the schema expects an `Upload` scalar.  When an operation containing
+//! uploads is sent, each upload is replaced with `null` in the JSON encoded
+//! operation, and the files are sent alongside it as parts of a
+//! `multipart/form-data` request.
+//!
+//! [spec]: https://github.com/jaydenseric/graphql-multipart-request-spec
+
+use std::{cell::RefCell, sync::Arc};
+
+/// A file to upload as part of an operation.
+///
+/// To use this, register it as the scalar for the `Upload` type in your schema:
+///
+/// ```rust
+/// # mod schema { cynic::use_schema!("../schemas/uploads.graphql"); }
+/// cynic::impl_scalar!(cynic::Upload, schema::Upload);
+/// ```
+///
+/// Only the reqwest & surf integrations & `cynic::client::Client` know how to
+/// send uploads - serializing an `Upload` anywhere else is an error.
+#[derive(Clone)]
+pub struct Upload {
+    filename: String,
+    mime_type: Option<String>,
+    content: Arc<[u8]>,
+}
+
+impl Upload {
+    /// Creates an `Upload` from the contents of a file
+    pub fn from_bytes(bytes: impl Into<Vec<u8>>, filename: impl Into<String>) -> Self {
+        Upload {
+            filename: filename.into(),
+            mime_type: None,
+            content: bytes.into().into(),
+        }
+    }
+
+    /// Creates an `Upload` by reading the contents of an `AsyncRead`.
+    ///
+    /// Note that the whole contents of the reader will be buffered in memory.
+    #[cfg(feature = "http-reqwest")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http-reqwest")))]
+    pub async fn from_reader<R>(
+        mut reader: R,
+        filename: impl Into<String>,
+    ) -> Result<Self, std::io::Error>
+    where
+        R: futures_util::AsyncRead + Unpin,
+    {
+        use futures_util::AsyncReadExt;
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        Ok(Upload::from_bytes(bytes, filename))
+    }
+
+    /// Sets the MIME type of this upload
+    ///
+    /// This returns an error if `mime_type` isn't a valid MIME type, e.g.
+    /// `text/plain` or `text/plain; charset=utf-8`
+    pub fn with_mime_type(mut self, mime_type: impl Into<String>) -> Result<Self, InvalidMimeType> {
+        let mime_type = mime_type.into();
+        if !is_valid_mime_type(&mime_type) {
+            return Err(InvalidMimeType(mime_type));
+        }
+        self.mime_type = Some(mime_type);
+        Ok(self)
+    }
+
+    /// The filename of this upload
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+
+    /// The MIME type of this upload, if it has one
+    pub fn mime_type(&self) -> Option<&str> {
+        self.mime_type.as_deref()
+    }
+
+    /// The contents of this upload
+    pub fn bytes(&self) -> &[u8] {
+        &self.content
+    }
+}
+
+impl std::fmt::Debug for Upload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Upload")
+            .field("filename", &self.filename)
+            .field("mime_type", &self.mime_type)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The error returned by [`Upload::with_mime_type`] for an invalid MIME type
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("{0:?} is not a valid MIME type")]
+pub struct InvalidMimeType(String);
+
+/// Checks `mime_type` is a `type/subtype` with optional `; name=value`
+/// parameters, as described in RFC 2045
+fn is_valid_mime_type(mime_type: &str) -> bool {
+    fn is_token(value: &str) -> bool {
+        !value.is_empty()
+            && value
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte))
+    }
+
+    fn is_quoted_string(value: &str) -> bool {
+        value.len() >= 2
+            && value.starts_with('"')
+            && value.ends_with('"')
+            && value[1..value.len() - 1]
+                .bytes()
+                .all(|byte| byte == b'\t' || (b' '..=b'~').contains(&byte))
+    }
+
+    let mut parts = mime_type.split(';');
+    let Some((ty, subtype)) = parts.next().and_then(|essence| essence.split_once('/')) else {
+        return false;
+    };
+
+    is_token(ty)
+        && is_token(subtype)
+        && parts.all(|parameter| match parameter.trim().split_once('=') {
+            Some((name, value)) => is_token(name) && (is_token(value) || is_quoted_string(value)),
+            None => false,
+        })
+}
+
+/// The prefix of the placeholders we serialize uploads as while extracting them
+const PLACEHOLDER_PREFIX: &str = "\u{0}cynic-upload:";
+
+thread_local! {
+    static COLLECTED_UPLOADS: RefCell<Option<Vec<Upload>>> = const { RefCell::new(None) };
+}
+
+impl serde::Serialize for Upload {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let placeholder = COLLECTED_UPLOADS.with(|uploads| {
+            let mut uploads = uploads.borrow_mut();
+            let uploads = uploads.as_mut()?;
+            uploads.push(self.clone());
+            Some(format!("{PLACEHOLDER_PREFIX}{}", uploads.len() - 1))
+        });
+
+        match placeholder {
+            Some(placeholder) => serializer.serialize_str(&placeholder),
+            None => Err(serde::ser::Error::custom(
+                "uploads can only be sent with an integration that supports multipart requests",
+            )),
+        }
+    }
+}
+
+/// The serialized body of a request for an operation
+#[cfg(any(feature = "http-reqwest", feature = "http-surf", feature = "client"))]
+pub(crate) enum OperationBody {
+    /// The operation doesn't contain any uploads, so can be sent as JSON
+    Json(Vec<u8>),
+    /// The operation contains uploads, so must be sent as a multipart request
+    Multipart(MultipartOperation),
+}
+
+#[cfg(any(feature = "http-reqwest", feature = "http-surf", feature = "client"))]
+impl OperationBody {
+    /// Serializes an operation, extracting any uploads it contains.
+    ///
+    /// Operations are serialized straight to JSON, so this costs no more than
+    /// a normal serialization unless the operation contains uploads.
+    pub fn new(operation: &impl serde::Serialize) -> Result<Self, serde_json::Error> {
+        struct ResetOnDrop;
+        impl Drop for ResetOnDrop {
+            fn drop(&mut self) {
+                COLLECTED_UPLOADS.with(|uploads| uploads.borrow_mut().take());
+            }
+        }
+
+        let (json, files) = {
+            let _reset = ResetOnDrop;
+            COLLECTED_UPLOADS.with(|uploads| *uploads.borrow_mut() = Some(Vec::new()));
+            let json = serde_json::to_vec(operation);
+            let files = COLLECTED_UPLOADS.with(|uploads| uploads.borrow_mut().take());
+            (json?, files.unwrap_or_default())
+        };
+
+        if files.is_empty() {
+            return Ok(OperationBody::Json(json));
+        }
+
+        let mut value = serde_json::from_slice(&json)?;
+        let mut map = std::collections::BTreeMap::<usize, Vec<String>>::new();
+        replace_placeholders(&mut value, &mut String::new(), &mut map);
+
+        Ok(OperationBody::Multipart(MultipartOperation {
+            operations: value.to_string(),
+            map: serde_json::to_string(&map)?,
+            files,
+        }))
+    }
+}
+
+/// An operation that contains uploads, ready to be sent as a multipart request
+#[cfg(any(feature = "http-reqwest", feature = "http-surf", feature = "client"))]
+pub(crate) struct MultipartOperation {
+    /// The JSON encoded operation, with `null` in place of each upload
+    pub operations: String,
+    /// The JSON encoded map from file part names to paths in `operations`
+    pub map: String,
+    /// The uploads, in the order of their part names
+    pub files: Vec<Upload>,
+}
+
+#[cfg(any(feature = "http-surf", feature = "client"))]
+impl MultipartOperation {
+    /// Encodes this operation as a `multipart/form-data` body, returning the
+    /// `Content-Type` to send it with alongside the body itself.
+    pub fn into_form_data(self) -> (String, Vec<u8>) {
+        use std::hash::{BuildHasher, Hasher};
+
+        let random = std::collections::hash_map::RandomState::new()
+            .build_hasher()
+            .finish();
+        let boundary = format!("cynic-{random:016x}");
+
+        let mut body = Vec::new();
+        let mut write_part = |disposition: String, mime_type: Option<&str>, content: &[u8]| {
+            body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+            body.extend_from_slice(
+                format!("Content-Disposition: form-data; {disposition}\r\n").as_bytes(),
+            );
+            if let Some(mime_type) = mime_type {
+                body.extend_from_slice(format!("Content-Type: {mime_type}\r\n").as_bytes());
+            }
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(content);
+            body.extend_from_slice(b"\r\n");
+        };
+
+        write_part(
+            r#"name="operations""#.into(),
+            None,
+            self.operations.as_bytes(),
+        );
+        write_part(r#"name="map""#.into(), None, self.map.as_bytes());
+        for (index, upload) in self.files.iter().enumerate() {
+            let filename = upload
+                .filename()
+                .replace('"', "%22")
+                .replace('\r', "%0D")
+                .replace('\n', "%0A");
+            write_part(
+                format!(r#"name="{index}"; filename="{filename}""#),
+                Some(upload.mime_type().unwrap_or("application/octet-stream")),
+                upload.bytes(),
+            );
+        }
+        body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+        (format!("multipart/form-data; boundary={boundary}"), body)
+    }
+}
+
+/// Replaces any upload placeholders in `value` with null, recording the path
+/// to each in `map`
+#[cfg(any(feature = "http-reqwest", feature = "http-surf", feature = "client"))]
+fn replace_placeholders(
+    value: &mut serde_json::Value,
+    path: &mut String,
+    map: &mut std::collections::BTreeMap<usize, Vec<String>>,
+) {
+    use serde_json::Value;
+
+    let push_segment = |path: &mut String, segment: &str| {
+        let original_len = path.len();
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(segment);
+        original_len
+    };
+
+    match value {
+        Value::String(string) => {
+            let Some(index) = string
+                .strip_prefix(PLACEHOLDER_PREFIX)
+                .and_then(|index| index.parse().ok())
+            else {
+                return;
+            };
+            map.entry(index).or_default().push(path.clone());
+            *value = Value::Null;
+        }
+        Value::Array(values) => {
+            for (index, value) in values.iter_mut().enumerate() {
+                let original_len = push_segment(path, &index.to_string());
+                replace_placeholders(value, path, map);
+                path.truncate(original_len);
+            }
+        }
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                let original_len = push_segment(path, key);
+                replace_placeholders(value, path, map);
+                path.truncate(original_len);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(all(test, feature = "http-reqwest"))]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(serde::Serialize)]
+    struct Operation {
+        query: &'static str,
+        variables: Variables,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Variables {
+        file: Upload,
+        files: Vec<Upload>,
+        name: &'static str,
+    }
+
+    #[test]
+    fn test_extracting_uploads() {
+        let operation = Operation {
+            query: "mutation($file: Upload!, $files: [Upload!]!) { upload(file: $file, files: $files) }",
+            variables: Variables {
+                file: Upload::from_bytes("one", "one.txt"),
+                files: vec![
+                    Upload::from_bytes("two", "two.txt"),
+                    Upload::from_bytes("three", "three.txt"),
+                ],
+                name: "hello",
+            },
+        };
+
+        let Ok(OperationBody::Multipart(multipart)) = OperationBody::new(&operation) else {
+            panic!("expected a multipart body");
+        };
+
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&multipart.operations).unwrap(),
+            json!({
+                "query": "mutation($file: Upload!, $files: [Upload!]!) { upload(file: $file, files: $files) }",
+                "variables": {
+                    "file": null,
+                    "files": [null, null],
+                    "name": "hello"
+                }
+            })
+        );
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&multipart.map).unwrap(),
+            json!({
+                "0": ["variables.file"],
+                "1": ["variables.files.0"],
+                "2": ["variables.files.1"]
+            })
+        );
+        assert_eq!(
+            multipart
+                .files
+                .iter()
+                .map(Upload::filename)
+                .collect::<Vec<_>>(),
+            vec!["one.txt", "two.txt", "three.txt"]
+        );
+    }
+
+    #[test]
+    fn test_operations_without_uploads() {
+        let Ok(OperationBody::Json(json)) = OperationBody::new(&json!({"query": "{ hello }"}))
+        else {
+            panic!("expected a JSON body");
+        };
+        assert_eq!(json, br#"{"query":"{ hello }"}"#);
+    }
+
+    #[test]
+    fn test_uploads_fail_to_serialize_outside_of_extraction() {
+        assert!(serde_json::to_value(Upload::from_bytes("one", "one.txt")).is_err());
+    }
+
+    #[test]
+    fn test_mime_type_validation() {
+        let upload = || Upload::from_bytes("one", "one.txt");
+
+        for valid in [
+            "text/plain",
+            "application/vnd.api+json",
+            "text/plain; charset=utf-8",
+            "multipart/mixed; boundary=\"a b\"",
+        ] {
+            assert_eq!(
+                upload().with_mime_type(valid).unwrap().mime_type(),
+                Some(valid)
+            );
+        }
+
+        for invalid in [
+            "",
+            "text",
+            "text/",
+            "/plain",
+            "text/plain/x",
+            "text plain",
+            "text/plain; charset",
+        ] {
+            assert_eq!(
+                upload().with_mime_type(invalid).unwrap_err(),
+                InvalidMimeType(invalid.into())
+            );
+        }
+    }
+}
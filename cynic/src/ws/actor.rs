@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    future::{Future, IntoFuture},
+    pin::Pin,
+};
+
+use futures_channel::mpsc;
+use futures_util::{SinkExt, StreamExt, future, stream};
+use serde_json::Value;
+use tungstenite::{
+    Message,
+    protocol::{CloseFrame, frame::coding::CloseCode},
+};
+
+use super::{
+    Connection,
+    protocol::{Output, ProtocolHandler},
+};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Commands sent from a [`super::Client`] or [`super::Subscription`] to the actor
+pub(super) enum Command {
+    Subscribe {
+        id: String,
+        payload: Value,
+        sender: mpsc::UnboundedSender<Value>,
+    },
+    Stop {
+        id: String,
+    },
+}
+
+/// Drives a websocket connection, routing messages to & from each subscription.
+///
+/// This must be spawned onto an async runtime for a [`super::Client`] to
+/// function.  It will run until the connection is closed, either by the server
+/// or because the client and all of its subscriptions have been dropped.
+///
+/// Implements `IntoFuture` so you can spawn the result of `into_future`
+pub struct ConnectionActor {
+    connection: Pin<Box<dyn Connection>>,
+    commands: mpsc::UnboundedReceiver<Command>,
+    handler: Box<dyn ProtocolHandler>,
+}
+
+enum Event {
+    Incoming(Result<Message, tungstenite::Error>),
+    ConnectionEnded,
+    Command(Command),
+    ClientDropped,
+}
+
+impl ConnectionActor {
+    pub(super) fn new(
+        connection: impl Connection,
+        commands: mpsc::UnboundedReceiver<Command>,
+        handler: Box<dyn ProtocolHandler>,
+    ) -> Self {
+        ConnectionActor {
+            connection: Box::pin(connection),
+            commands,
+            handler,
+        }
+    }
+
+    async fn run(self) {
+        let ConnectionActor {
+            connection,
+            commands,
+            mut handler,
+        } = self;
+
+        let (mut sink, connection) = connection.split();
+
+        let incoming = connection
+            .map(Event::Incoming)
+            .chain(stream::once(future::ready(Event::ConnectionEnded)));
+        let commands = commands
+            .map(Event::Command)
+            .chain(stream::once(future::ready(Event::ClientDropped)));
+
+        let mut events = stream::select(incoming, commands);
+
+        // Dropping a sender will end the stream of the corresponding subscription,
+        // so any that are still in here when we return are ended automatically.
+        let mut subscriptions = HashMap::<String, mpsc::UnboundedSender<Value>>::new();
+
+        let mut client_dropped = false;
+
+        // The handler may have queued up outputs during the handshake
+        // so we flush before handling any events.
+        loop {
+            while let Some(output) = handler.poll_output() {
+                match output {
+                    Output::Send(text) => {
+                        if sink.send(Message::Text(text)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Output::Response { id, payload } => {
+                        if let Some(sender) = subscriptions.get(&id) {
+                            // If this fails the subscription has been dropped, and
+                            // will shortly tell us to stop it.
+                            sender.unbounded_send(payload).ok();
+                        }
+                    }
+                    Output::Complete { id } => {
+                        subscriptions.remove(&id);
+                    }
+                    Output::Close { code, reason } => {
+                        sink.send(close_message(code.into(), reason)).await.ok();
+                        return;
+                    }
+                    Output::Acknowledged => {}
+                }
+            }
+
+            if client_dropped {
+                sink.send(close_message(CloseCode::Normal, "")).await.ok();
+                sink.close().await.ok();
+                return;
+            }
+
+            let Some(event) = events.next().await else {
+                return;
+            };
+
+            match event {
+                Event::Command(Command::Subscribe {
+                    id,
+                    payload,
+                    sender,
+                }) => {
+                    handler.subscribe(&id, payload);
+                    subscriptions.insert(id, sender);
+                }
+                Event::Command(Command::Stop { id }) => {
+                    subscriptions.remove(&id);
+                    handler.stop(&id);
+                }
+                Event::ClientDropped => {
+                    handler.terminate();
+                    client_dropped = true;
+                }
+                Event::Incoming(Ok(Message::Text(text))) => handler.receive(&text),
+                Event::Incoming(Ok(Message::Close(_)) | Err(_)) | Event::ConnectionEnded => {
+                    return;
+                }
+                Event::Incoming(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+fn close_message(code: CloseCode, reason: &'static str) -> Message {
+    Message::Close(Some(CloseFrame {
+        code,
+        reason: reason.into(),
+    }))
+}
+
+impl IntoFuture for ConnectionActor {
+    type Output = ();
+
+    type IntoFuture = BoxFuture<'static, ()>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.run())
+    }
+}
@@ -0,0 +1,224 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU64, Ordering},
+};
+
+use futures_channel::mpsc;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use tungstenite::{
+    Message,
+    client::IntoClientRequest,
+    handshake::client::Request,
+    http::{self, HeaderValue, header::SEC_WEBSOCKET_PROTOCOL},
+    protocol::CloseFrame,
+};
+
+use crate::StreamingOperation;
+
+use super::{
+    Connection, ConnectionActor, Protocol, Subscription, WebsocketError, actor::Command,
+    protocol::Output,
+};
+
+/// A client for running subscriptions over a websocket.
+///
+/// This is cheap to clone - all clones share the same underlying connection.
+/// The connection will be closed once the client and all of its
+/// subscriptions have been dropped.
+#[derive(Clone)]
+pub struct Client {
+    commands: mpsc::UnboundedSender<Command>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Client {
+    /// Creates a [`ClientBuilder`] for configuring a new client
+    pub fn builder() -> ClientBuilder {
+        ClientBuilder::default()
+    }
+
+    /// Starts a subscription on the server.
+    ///
+    /// The returned [`Subscription`] is a `Stream` of the responses to this
+    /// operation.  Dropping it will stop the subscription on the server.
+    pub fn subscribe<ResponseData, Vars>(
+        &self,
+        operation: StreamingOperation<ResponseData, Vars>,
+    ) -> Result<Subscription<ResponseData>, WebsocketError>
+    where
+        Vars: serde::Serialize,
+        ResponseData: serde::de::DeserializeOwned + 'static,
+    {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed).to_string();
+        let payload = serde_json::to_value(&operation)?;
+        let (sender, receiver) = mpsc::unbounded();
+
+        self.commands
+            .unbounded_send(Command::Subscribe {
+                id: id.clone(),
+                payload,
+                sender,
+            })
+            .map_err(|_| WebsocketError::ConnectionClosed)?;
+
+        Ok(Subscription::new(id, receiver, self.commands.clone()))
+    }
+}
+
+/// A builder for a websocket [`Client`].
+#[derive(Debug)]
+pub struct ClientBuilder {
+    payload: Option<Value>,
+    protocols: Vec<Protocol>,
+}
+
+impl Default for ClientBuilder {
+    fn default() -> Self {
+        ClientBuilder {
+            payload: None,
+            protocols: vec![Protocol::GraphQlTransportWs],
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Sets the payload to send with the `connection_init` message.
+    ///
+    /// Servers often use this for authentication.
+    pub fn payload(mut self, payload: Value) -> Self {
+        self.payload = Some(payload);
+        self
+    }
+
+    /// Sets the protocol the client should use.
+    ///
+    /// Defaults to [`Protocol::GraphQlTransportWs`]
+    pub fn protocol(mut self, protocol: Protocol) -> Self {
+        self.protocols = vec![protocol];
+        self
+    }
+
+    /// Sets a list of protocols the client supports, in order of preference.
+    ///
+    /// All of these will be offered to the server in the request built by
+    /// [`ClientBuilder::request`], and you should call
+    /// [`ClientBuilder::negotiated`] with the handshake response to pick the one
+    /// the server selected.
+    pub fn protocols(mut self, protocols: impl IntoIterator<Item = Protocol>) -> Self {
+        self.protocols = protocols.into_iter().collect();
+        self
+    }
+
+    /// Selects the protocol that the server chose from the handshake response.
+    ///
+    /// Returns an error if the server chose a protocol that we didn't offer.
+    /// If the server didn't choose a protocol we stick with our first choice.
+    pub fn negotiated<T>(mut self, response: &http::Response<T>) -> Result<Self, WebsocketError> {
+        let Some(header) = response.headers().get(SEC_WEBSOCKET_PROTOCOL) else {
+            return Ok(self);
+        };
+
+        let name = String::from_utf8_lossy(header.as_bytes());
+        match Protocol::from_name(&name) {
+            Some(protocol) if self.protocols.contains(&protocol) => {
+                self.protocols = vec![protocol];
+                Ok(self)
+            }
+            _ => Err(WebsocketError::UnsupportedProtocol(name.into_owned())),
+        }
+    }
+
+    fn selected_protocol(&self) -> Protocol {
+        self.protocols
+            .first()
+            .copied()
+            .unwrap_or(Protocol::GraphQlTransportWs)
+    }
+
+    /// Builds a websocket handshake request for the given URL, with the
+    /// `Sec-WebSocket-Protocol` header set for this client.
+    ///
+    /// This can be passed to the `connect_async` function of whichever
+    /// tungstenite integration you're using.
+    pub fn request(&self, request: impl IntoClientRequest) -> Result<Request, WebsocketError> {
+        let protocols = match self.protocols.as_slice() {
+            [] => self.selected_protocol().name().to_string(),
+            protocols => protocols
+                .iter()
+                .map(|protocol| protocol.name())
+                .collect::<Vec<_>>()
+                .join(","),
+        };
+
+        let mut request = request.into_client_request()?;
+        request.headers_mut().insert(
+            SEC_WEBSOCKET_PROTOCOL,
+            HeaderValue::from_str(&protocols).expect("protocol names to be valid header values"),
+        );
+
+        Ok(request)
+    }
+
+    /// Initialises the connection and builds a [`Client`].
+    ///
+    /// This returns the client along with a [`ConnectionActor`] that must be
+    /// spawned onto your async runtime - no messages will be sent or received
+    /// until it is running.
+    pub async fn build<C>(
+        self,
+        mut connection: C,
+    ) -> Result<(Client, ConnectionActor), WebsocketError>
+    where
+        C: Connection,
+    {
+        let mut handler = self.selected_protocol().handler();
+        handler.connection_init(self.payload);
+
+        let mut last_message = None;
+        'handshake: loop {
+            while let Some(output) = handler.poll_output() {
+                match output {
+                    Output::Send(text) => connection.send(Message::Text(text)).await?,
+                    Output::Acknowledged => break 'handshake,
+                    Output::Close { code, reason } => {
+                        let frame = CloseFrame {
+                            code: code.into(),
+                            reason: reason.into(),
+                        };
+                        connection.send(Message::Close(Some(frame))).await.ok();
+                        return Err(WebsocketError::UnexpectedMessage(
+                            last_message.unwrap_or_default(),
+                        ));
+                    }
+                    Output::Response { .. } | Output::Complete { .. } => {}
+                }
+            }
+
+            let Some(message) = connection.next().await else {
+                return Err(WebsocketError::ConnectionClosed);
+            };
+
+            match message? {
+                Message::Text(text) => {
+                    handler.receive(&text);
+                    last_message = Some(text);
+                }
+                Message::Close(_) => return Err(WebsocketError::ConnectionClosed),
+                _ => {}
+            }
+        }
+
+        let (commands, command_receiver) = mpsc::unbounded();
+
+        let client = Client {
+            commands,
+            next_id: Arc::new(AtomicU64::new(1)),
+        };
+
+        Ok((
+            client,
+            ConnectionActor::new(connection, command_receiver, handler),
+        ))
+    }
+}
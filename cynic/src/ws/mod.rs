@@ -0,0 +1,129 @@
+//! Websocket subscription support for cynic.
+//!
+//! This module provides a client for running [`StreamingOperation`]s over a
+//! websocket.  Many subscriptions can be multiplexed over a single connection.
+//!
+//! The client defaults to the [`graphql-transport-ws`][protocol] protocol, but
+//! also supports the legacy `subscriptions-transport-ws` protocol - see
+//! [`Protocol`] and [`ClientBuilder::protocols`] for how to choose between them.
+//!
+//! The client isn't tied to any particular async runtime or websocket library:
+//! it runs on top of any `Stream` + `Sink` of [`tungstenite`] messages, which
+//! covers both `tokio-tungstenite` and `async-tungstenite`.  The connection
+//! itself is driven by a [`ConnectionActor`] which you should spawn on
+//! whatever executor you're using.
+//!
+//! This is hidden behind the `ws` feature flag.
+//!
+//! ```rust,no_run
+//! # mod schema {
+//! #   cynic::use_schema!("../schemas/books.graphql");
+//! # }
+//! #
+//! # #[derive(cynic::QueryVariables)]
+//! # struct IntervalVariables {
+//! #     n: i32,
+//! # }
+//! #
+//! # #[derive(cynic::QueryFragment)]
+//! # #[cynic(
+//! #     schema_path = "../schemas/books.graphql",
+//! #     schema_module = "schema",
+//! #     graphql_type = "SubscriptionRoot",
+//! #     variables = "IntervalVariables"
+//! # )]
+//! # struct IntervalSubscription {
+//! #     #[arguments(n: $n)]
+//! #     interval: i32,
+//! # }
+//! use cynic::{SubscriptionBuilder, ws::Client};
+//! use futures_util::StreamExt;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! let builder = Client::builder();
+//!
+//! let (connection, _) =
+//!     tokio_tungstenite::connect_async(builder.request("ws://localhost:8080/graphql")?).await?;
+//!
+//! let (client, actor) = builder.build(connection).await?;
+//! tokio::spawn(actor.into_future());
+//!
+//! let mut subscription = client.subscribe(IntervalSubscription::build(IntervalVariables { n: 5 }))?;
+//!
+//! while let Some(response) = subscription.next().await {
+//!     println!("{:?}", response.data.map(|data| data.interval));
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [protocol]: https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md
+//! [`StreamingOperation`]: crate::StreamingOperation
+
+mod actor;
+mod client;
+mod protocol;
+mod subscription;
+
+pub use self::{
+    actor::ConnectionActor,
+    client::{Client, ClientBuilder},
+    protocol::Protocol,
+    subscription::Subscription,
+};
+
+use futures_util::{Sink, Stream};
+use tungstenite::Message;
+
+/// A websocket connection that the subscription [`Client`] can run on.
+///
+/// This is automatically implemented for any `Stream` + `Sink` of
+/// `tungstenite` messages, such as the `WebSocketStream` from either
+/// `tokio-tungstenite` or `async-tungstenite`.
+pub trait Connection:
+    Stream<Item = Result<Message, tungstenite::Error>>
+    + Sink<Message, Error = tungstenite::Error>
+    + Send
+    + Unpin
+    + 'static
+{
+}
+
+impl<T> Connection for T where
+    T: Stream<Item = Result<Message, tungstenite::Error>>
+        + Sink<Message, Error = tungstenite::Error>
+        + Send
+        + Unpin
+        + 'static
+{
+}
+
+/// The error type returned by the websocket [`Client`]
+#[derive(thiserror::Error, Debug)]
+pub enum WebsocketError {
+    /// An error from the underlying websocket connection
+    #[error("Error from websocket connection: {0}")]
+    Transport(#[from] Box<tungstenite::Error>),
+
+    /// An error serializing a message to send to the server
+    #[error("Couldn't serialize message: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    /// The server sent a message that we weren't expecting
+    #[error("Received an unexpected message from the server: {0}")]
+    UnexpectedMessage(String),
+
+    /// The server selected a protocol that we didn't offer
+    #[error("The server selected an unsupported protocol: {0}")]
+    UnsupportedProtocol(String),
+
+    /// The connection was closed before we expected
+    #[error("The websocket connection was closed")]
+    ConnectionClosed,
+}
+
+impl From<tungstenite::Error> for WebsocketError {
+    fn from(error: tungstenite::Error) -> Self {
+        WebsocketError::Transport(Box::new(error))
+    }
+}
@@ -0,0 +1,271 @@
+//! The `graphql-transport-ws` protocol.
+//!
+//! See <https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md>
+
+use std::collections::{HashSet, VecDeque};
+
+use serde_json::{Value, json};
+
+use super::{INVALID_MESSAGE_CODE, Output, ProtocolHandler};
+
+pub(super) struct GraphQlTransportWs {
+    acknowledged: bool,
+    active: HashSet<String>,
+    outputs: VecDeque<Output>,
+}
+
+impl GraphQlTransportWs {
+    pub fn new() -> Self {
+        GraphQlTransportWs {
+            acknowledged: false,
+            active: HashSet::new(),
+            outputs: VecDeque::new(),
+        }
+    }
+
+    fn send(&mut self, message: ClientMessage<'_>) {
+        // Serializing these messages can't fail - they're all either strings
+        // or `serde_json::Value`
+        let text = serde_json::to_string(&message).expect("messages to serialize");
+        self.outputs.push_back(Output::Send(text));
+    }
+
+    fn invalid_message(&mut self) {
+        self.outputs.push_back(Output::Close {
+            code: INVALID_MESSAGE_CODE,
+            reason: "Invalid message received",
+        });
+    }
+}
+
+impl ProtocolHandler for GraphQlTransportWs {
+    fn connection_init(&mut self, payload: Option<Value>) {
+        self.send(ClientMessage::ConnectionInit { payload });
+    }
+
+    fn subscribe(&mut self, id: &str, payload: Value) {
+        self.active.insert(id.to_string());
+        self.send(ClientMessage::Subscribe { id, payload });
+    }
+
+    fn stop(&mut self, id: &str) {
+        if self.active.remove(id) {
+            self.send(ClientMessage::Complete { id });
+        }
+    }
+
+    fn terminate(&mut self) {
+        // This protocol just expects the connection to be closed
+    }
+
+    fn receive(&mut self, text: &str) {
+        let Ok(message) = serde_json::from_str::<ServerMessage>(text) else {
+            self.invalid_message();
+            return;
+        };
+
+        match message {
+            ServerMessage::ConnectionAck {} if !self.acknowledged => {
+                self.acknowledged = true;
+                self.outputs.push_back(Output::Acknowledged);
+            }
+            ServerMessage::ConnectionAck {} => {}
+            ServerMessage::Ping {} => self.send(ClientMessage::Pong {}),
+            ServerMessage::Pong {} => {}
+            _ if !self.acknowledged => self.invalid_message(),
+            ServerMessage::Next { id, payload } => {
+                if self.active.contains(&id) {
+                    self.outputs.push_back(Output::Response { id, payload });
+                }
+            }
+            ServerMessage::Error { id, payload } => {
+                if self.active.remove(&id) {
+                    self.outputs.push_back(Output::Response {
+                        id: id.clone(),
+                        payload: json!({ "errors": payload }),
+                    });
+                    self.outputs.push_back(Output::Complete { id });
+                }
+            }
+            ServerMessage::Complete { id } => {
+                if self.active.remove(&id) {
+                    self.outputs.push_back(Output::Complete { id });
+                }
+            }
+        }
+    }
+
+    fn poll_output(&mut self) -> Option<Output> {
+        self.outputs.pop_front()
+    }
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage<'a> {
+    ConnectionInit {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<Value>,
+    },
+    Subscribe {
+        id: &'a str,
+        payload: Value,
+    },
+    Complete {
+        id: &'a str,
+    },
+    Pong {},
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    ConnectionAck {},
+    Next { id: String, payload: Value },
+    Error { id: String, payload: Value },
+    Complete { id: String },
+    Ping {},
+    Pong {},
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn outputs(handler: &mut GraphQlTransportWs) -> Vec<Output> {
+        std::iter::from_fn(|| handler.poll_output()).collect()
+    }
+
+    fn connected() -> GraphQlTransportWs {
+        let mut handler = GraphQlTransportWs::new();
+        handler.connection_init(None);
+        handler.receive(r#"{"type": "connection_ack"}"#);
+        outputs(&mut handler);
+        handler
+    }
+
+    #[test]
+    fn test_connection_init() {
+        let mut handler = GraphQlTransportWs::new();
+        handler.connection_init(Some(json!({"token": "abcd"})));
+        handler.receive(r#"{"type": "connection_ack", "payload": {}}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![
+                Output::Send(r#"{"type":"connection_init","payload":{"token":"abcd"}}"#.into()),
+                Output::Acknowledged
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subscription_lifecycle() {
+        let mut handler = connected();
+        handler.subscribe("1", json!({"query": "subscription { interval }"}));
+        handler.receive(r#"{"type": "next", "id": "1", "payload": {"data": {"interval": 1}}}"#);
+        handler.receive(r#"{"type": "complete", "id": "1"}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![
+                Output::Send(
+                    r#"{"type":"subscribe","id":"1","payload":{"query":"subscription { interval }"}}"#
+                        .into()
+                ),
+                Output::Response {
+                    id: "1".into(),
+                    payload: json!({"data": {"interval": 1}})
+                },
+                Output::Complete { id: "1".into() }
+            ]
+        );
+
+        // Stopping a subscription the server has completed should be a no-op
+        handler.stop("1");
+        assert_eq!(outputs(&mut handler), vec![]);
+    }
+
+    #[test]
+    fn test_stop() {
+        let mut handler = connected();
+        handler.subscribe("1", json!({}));
+        outputs(&mut handler);
+
+        handler.stop("1");
+        handler.receive(r#"{"type": "next", "id": "1", "payload": {"data": null}}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![Output::Send(r#"{"type":"complete","id":"1"}"#.into())]
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut handler = connected();
+        handler.subscribe("1", json!({}));
+        outputs(&mut handler);
+
+        handler.receive(r#"{"type": "error", "id": "1", "payload": [{"message": "Oops"}]}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![
+                Output::Response {
+                    id: "1".into(),
+                    payload: json!({"errors": [{"message": "Oops"}]})
+                },
+                Output::Complete { id: "1".into() }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_ping() {
+        let mut handler = connected();
+        handler.receive(r#"{"type": "ping"}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![Output::Send(r#"{"type":"pong"}"#.into())]
+        );
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        let mut handler = connected();
+        handler.receive(r#"{"type": "data", "id": "1"}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![Output::Close {
+                code: 4400,
+                reason: "Invalid message received"
+            }]
+        );
+    }
+
+    #[test]
+    fn test_messages_before_ack() {
+        let mut handler = GraphQlTransportWs::new();
+        handler.connection_init(None);
+        outputs(&mut handler);
+
+        handler.receive(r#"{"type": "ping"}"#);
+        handler.receive(r#"{"type": "next", "id": "1", "payload": {}}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![
+                Output::Send(r#"{"type":"pong"}"#.into()),
+                Output::Close {
+                    code: 4400,
+                    reason: "Invalid message received"
+                }
+            ]
+        );
+    }
+}
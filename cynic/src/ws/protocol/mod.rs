@@ -0,0 +1,103 @@
+//! The websocket subscription protocols cynic supports.
+//!
+//! Each protocol is implemented as a sans-IO state machine: the connection
+//! feeds it events (text messages from the server, or requests from the
+//! client) and then drains the resulting [`Output`]s, performing any IO they
+//! require.  This keeps the message framing independent of the actual socket.
+
+mod graphql_transport_ws;
+mod subscriptions_transport_ws;
+
+use serde_json::Value;
+
+use self::{
+    graphql_transport_ws::GraphQlTransportWs, subscriptions_transport_ws::SubscriptionsTransportWs,
+};
+
+/// The protocols that can be used to run subscriptions over a websocket
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Protocol {
+    /// The [`graphql-transport-ws`][spec] protocol.
+    ///
+    /// This is the default, and should be preferred where the server supports it.
+    ///
+    /// [spec]: https://github.com/enisdenjo/graphql-ws/blob/master/PROTOCOL.md
+    GraphQlTransportWs,
+
+    /// The legacy [`subscriptions-transport-ws`][spec] protocol from Apollo.
+    ///
+    /// Confusingly, this is negotiated with the `graphql-ws` sub-protocol name.
+    ///
+    /// [spec]: https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md
+    SubscriptionsTransportWs,
+}
+
+impl Protocol {
+    /// The name of the protocol, as sent in the `Sec-WebSocket-Protocol` header
+    pub fn name(self) -> &'static str {
+        match self {
+            Protocol::GraphQlTransportWs => "graphql-transport-ws",
+            Protocol::SubscriptionsTransportWs => "graphql-ws",
+        }
+    }
+
+    /// Looks up a protocol from the name used in the `Sec-WebSocket-Protocol` header
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "graphql-transport-ws" => Some(Protocol::GraphQlTransportWs),
+            "graphql-ws" => Some(Protocol::SubscriptionsTransportWs),
+            _ => None,
+        }
+    }
+
+    pub(super) fn handler(self) -> Box<dyn ProtocolHandler> {
+        match self {
+            Protocol::GraphQlTransportWs => Box::new(GraphQlTransportWs::new()),
+            Protocol::SubscriptionsTransportWs => Box::new(SubscriptionsTransportWs::new()),
+        }
+    }
+}
+
+/// The close code for messages we couldn't understand
+const INVALID_MESSAGE_CODE: u16 = 4400;
+
+/// A state machine implementing the framing of a subscription protocol.
+pub(super) trait ProtocolHandler: Send {
+    /// Starts the connection, optionally sending a payload to the server
+    fn connection_init(&mut self, payload: Option<Value>);
+
+    /// Starts a subscription with the given id
+    fn subscribe(&mut self, id: &str, payload: Value);
+
+    /// Stops a running subscription
+    fn stop(&mut self, id: &str);
+
+    /// Tells the server we're about to close the connection
+    fn terminate(&mut self);
+
+    /// Handles a text message received from the server
+    fn receive(&mut self, text: &str);
+
+    /// Returns the next thing the connection needs to do, if any
+    fn poll_output(&mut self) -> Option<Output>;
+}
+
+/// The things a [`ProtocolHandler`] can ask of its connection
+#[derive(Debug, PartialEq)]
+pub(super) enum Output {
+    /// Send a text message to the server
+    Send(String),
+
+    /// The server has acknowledged the connection
+    Acknowledged,
+
+    /// A response to pass on to the subscription with the given id
+    Response { id: String, payload: Value },
+
+    /// The subscription with the given id has finished
+    Complete { id: String },
+
+    /// The connection should be closed with the given code & reason
+    Close { code: u16, reason: &'static str },
+}
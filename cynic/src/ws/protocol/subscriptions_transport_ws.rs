@@ -0,0 +1,259 @@
+//! The legacy `subscriptions-transport-ws` protocol from Apollo.
+//!
+//! See <https://github.com/apollographql/subscriptions-transport-ws/blob/master/PROTOCOL.md>
+
+use std::collections::{HashSet, VecDeque};
+
+use serde_json::{Value, json};
+
+use super::{INVALID_MESSAGE_CODE, Output, ProtocolHandler};
+
+/// The close code we use if the server sends a `connection_error`
+const CONNECTION_ERROR_CODE: u16 = 4403;
+
+pub(super) struct SubscriptionsTransportWs {
+    acknowledged: bool,
+    active: HashSet<String>,
+    outputs: VecDeque<Output>,
+}
+
+impl SubscriptionsTransportWs {
+    pub fn new() -> Self {
+        SubscriptionsTransportWs {
+            acknowledged: false,
+            active: HashSet::new(),
+            outputs: VecDeque::new(),
+        }
+    }
+
+    fn send(&mut self, message: ClientMessage<'_>) {
+        // Serializing these messages can't fail - they're all either strings
+        // or `serde_json::Value`
+        let text = serde_json::to_string(&message).expect("messages to serialize");
+        self.outputs.push_back(Output::Send(text));
+    }
+}
+
+impl ProtocolHandler for SubscriptionsTransportWs {
+    fn connection_init(&mut self, payload: Option<Value>) {
+        self.send(ClientMessage::ConnectionInit { payload });
+    }
+
+    fn subscribe(&mut self, id: &str, payload: Value) {
+        self.active.insert(id.to_string());
+        self.send(ClientMessage::Start { id, payload });
+    }
+
+    fn stop(&mut self, id: &str) {
+        if self.active.remove(id) {
+            self.send(ClientMessage::Stop { id });
+        }
+    }
+
+    fn terminate(&mut self) {
+        self.send(ClientMessage::ConnectionTerminate {});
+    }
+
+    fn receive(&mut self, text: &str) {
+        let Ok(message) = serde_json::from_str::<ServerMessage>(text) else {
+            self.outputs.push_back(Output::Close {
+                code: INVALID_MESSAGE_CODE,
+                reason: "Invalid message received",
+            });
+            return;
+        };
+
+        match message {
+            ServerMessage::ConnectionAck {} if !self.acknowledged => {
+                self.acknowledged = true;
+                self.outputs.push_back(Output::Acknowledged);
+            }
+            ServerMessage::ConnectionAck {} | ServerMessage::KeepAlive {} => {}
+            ServerMessage::ConnectionError {} => {
+                self.outputs.push_back(Output::Close {
+                    code: CONNECTION_ERROR_CODE,
+                    reason: "Server rejected the connection",
+                });
+            }
+            ServerMessage::Data { id, payload } => {
+                if self.active.contains(&id) {
+                    self.outputs.push_back(Output::Response { id, payload });
+                }
+            }
+            ServerMessage::Error { id, payload } => {
+                if self.active.remove(&id) {
+                    // Servers vary on whether they send a single error or a list here
+                    let errors = match payload {
+                        Value::Array(errors) => errors,
+                        error => vec![error],
+                    };
+                    self.outputs.push_back(Output::Response {
+                        id: id.clone(),
+                        payload: json!({ "errors": errors }),
+                    });
+                    self.outputs.push_back(Output::Complete { id });
+                }
+            }
+            ServerMessage::Complete { id } => {
+                if self.active.remove(&id) {
+                    self.outputs.push_back(Output::Complete { id });
+                }
+            }
+        }
+    }
+
+    fn poll_output(&mut self) -> Option<Output> {
+        self.outputs.pop_front()
+    }
+}
+
+#[derive(serde::Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ClientMessage<'a> {
+    ConnectionInit {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        payload: Option<Value>,
+    },
+    Start {
+        id: &'a str,
+        payload: Value,
+    },
+    Stop {
+        id: &'a str,
+    },
+    ConnectionTerminate {},
+}
+
+#[derive(serde::Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    ConnectionAck {},
+    ConnectionError {},
+    #[serde(rename = "ka")]
+    KeepAlive {},
+    Data {
+        id: String,
+        payload: Value,
+    },
+    Error {
+        id: String,
+        payload: Value,
+    },
+    Complete {
+        id: String,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn outputs(handler: &mut SubscriptionsTransportWs) -> Vec<Output> {
+        std::iter::from_fn(|| handler.poll_output()).collect()
+    }
+
+    fn connected() -> SubscriptionsTransportWs {
+        let mut handler = SubscriptionsTransportWs::new();
+        handler.connection_init(None);
+        handler.receive(r#"{"type": "connection_ack"}"#);
+        outputs(&mut handler);
+        handler
+    }
+
+    #[test]
+    fn test_connection_init() {
+        let mut handler = SubscriptionsTransportWs::new();
+        handler.connection_init(Some(json!({"token": "abcd"})));
+        handler.receive(r#"{"type": "ka"}"#);
+        handler.receive(r#"{"type": "connection_ack"}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![
+                Output::Send(r#"{"type":"connection_init","payload":{"token":"abcd"}}"#.into()),
+                Output::Acknowledged
+            ]
+        );
+    }
+
+    #[test]
+    fn test_connection_error() {
+        let mut handler = SubscriptionsTransportWs::new();
+        handler.connection_init(None);
+        outputs(&mut handler);
+
+        handler.receive(r#"{"type": "connection_error", "payload": {"message": "Nope"}}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![Output::Close {
+                code: 4403,
+                reason: "Server rejected the connection"
+            }]
+        );
+    }
+
+    #[test]
+    fn test_subscription_lifecycle() {
+        let mut handler = connected();
+        handler.subscribe("1", json!({"query": "subscription { interval }"}));
+        handler.receive(r#"{"type": "data", "id": "1", "payload": {"data": {"interval": 1}}}"#);
+        handler.receive(r#"{"type": "complete", "id": "1"}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![
+                Output::Send(
+                    r#"{"type":"start","id":"1","payload":{"query":"subscription { interval }"}}"#
+                        .into()
+                ),
+                Output::Response {
+                    id: "1".into(),
+                    payload: json!({"data": {"interval": 1}})
+                },
+                Output::Complete { id: "1".into() }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stop_and_terminate() {
+        let mut handler = connected();
+        handler.subscribe("1", json!({}));
+        outputs(&mut handler);
+
+        handler.stop("1");
+        handler.stop("1");
+        handler.terminate();
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![
+                Output::Send(r#"{"type":"stop","id":"1"}"#.into()),
+                Output::Send(r#"{"type":"connection_terminate"}"#.into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_error() {
+        let mut handler = connected();
+        handler.subscribe("1", json!({}));
+        outputs(&mut handler);
+
+        handler.receive(r#"{"type": "error", "id": "1", "payload": {"message": "Oops"}}"#);
+
+        assert_eq!(
+            outputs(&mut handler),
+            vec![
+                Output::Response {
+                    id: "1".into(),
+                    payload: json!({"errors": [{"message": "Oops"}]})
+                },
+                Output::Complete { id: "1".into() }
+            ]
+        );
+    }
+}
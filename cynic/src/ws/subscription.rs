@@ -0,0 +1,78 @@
+use std::{
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_channel::mpsc;
+use futures_util::{Stream, StreamExt};
+use serde_json::Value;
+
+use crate::{GraphQlError, GraphQlResponse};
+
+use super::actor::Command;
+
+/// A subscription that is running on a websocket [`super::Client`].
+///
+/// This is a `Stream` of the responses the server sends for the subscription,
+/// which ends when the server completes the subscription or the connection
+/// closes.  Dropping a `Subscription` stops it on the server.
+pub struct Subscription<ResponseData> {
+    id: String,
+    responses: mpsc::UnboundedReceiver<Value>,
+    commands: mpsc::UnboundedSender<Command>,
+    phantom: PhantomData<fn() -> ResponseData>,
+}
+
+impl<ResponseData> Subscription<ResponseData> {
+    pub(super) fn new(
+        id: String,
+        responses: mpsc::UnboundedReceiver<Value>,
+        commands: mpsc::UnboundedSender<Command>,
+    ) -> Self {
+        Subscription {
+            id,
+            responses,
+            commands,
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<ResponseData> Stream for Subscription<ResponseData>
+where
+    ResponseData: serde::de::DeserializeOwned,
+{
+    type Item = GraphQlResponse<ResponseData>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.responses
+            .poll_next_unpin(cx)
+            .map(|response| response.map(decode_response))
+    }
+}
+
+fn decode_response<ResponseData>(response: Value) -> GraphQlResponse<ResponseData>
+where
+    ResponseData: serde::de::DeserializeOwned,
+{
+    serde_json::from_value(response).unwrap_or_else(|error| GraphQlResponse {
+        data: None,
+        errors: Some(vec![GraphQlError::new(
+            format!("Couldn't decode subscription response: {error}"),
+            None,
+            None,
+            None,
+        )]),
+    })
+}
+
+impl<ResponseData> Drop for Subscription<ResponseData> {
+    fn drop(&mut self) {
+        self.commands
+            .unbounded_send(Command::Stop {
+                id: std::mem::take(&mut self.id),
+            })
+            .ok();
+    }
+}
@@ -0,0 +1,212 @@
+#![cfg(feature = "client")]
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use cynic::{
+    QueryBuilder,
+    client::{
+        BoxFuture, Client, ClientError, Request, ReqwestTransport, Response, Retry, SetHeader,
+        Timeout, Transport,
+    },
+};
+
+mod schema {
+    cynic::use_schema!("tests/test-schema.graphql");
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct FieldWithStringVariables<'a> {
+    pub input: &'a str,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "FieldWithStringVariables")]
+#[cynic(schema_path = "tests/test-schema.graphql")]
+pub struct FieldWithString {
+    #[arguments(input: $input)]
+    pub field_with_string: i32,
+}
+
+fn operation() -> cynic::Operation<FieldWithString, FieldWithStringVariables<'static>> {
+    FieldWithString::build(FieldWithStringVariables { input: "hello" })
+}
+
+/// A transport that returns canned responses and records the requests it was sent
+#[derive(Default)]
+struct MockTransport {
+    responses: Mutex<Vec<(u16, &'static str)>>,
+    requests: Mutex<Vec<Request>>,
+    delay: Option<Duration>,
+}
+
+impl MockTransport {
+    fn new(responses: Vec<(u16, &'static str)>) -> Arc<Self> {
+        Arc::new(MockTransport {
+            responses: Mutex::new(responses),
+            ..MockTransport::default()
+        })
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: Request) -> BoxFuture<'_, Result<Response, ClientError>> {
+        Box::pin(async move {
+            self.requests.lock().unwrap().push(request);
+            if let Some(delay) = self.delay {
+                tokio::time::sleep(delay).await;
+            }
+
+            let (status, body) = self.responses.lock().unwrap().remove(0);
+            Ok(Response {
+                status,
+                headers: vec![],
+                body: body.as_bytes().to_vec(),
+            })
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_client_decodes_responses() {
+    let transport = MockTransport::new(vec![(200, r#"{"data": {"fieldWithString": 1}}"#)]);
+
+    let response = Client::new(transport.clone())
+        .run_graphql(operation())
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 1);
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("content-type"), Some("application/json"));
+
+    let body = serde_json::from_slice::<serde_json::Value>(&requests[0].body).unwrap();
+    assert_eq!(body["variables"]["input"], "hello");
+}
+
+#[tokio::test]
+async fn test_client_error_responses() {
+    let transport = MockTransport::new(vec![
+        (400, r#"{"errors": [{"message": "Bad"}]}"#),
+        (500, "Internal Server Error"),
+    ]);
+    let client = Client::new(transport);
+
+    let response = client.run_graphql(operation()).await.unwrap();
+    assert_eq!(response.errors.unwrap()[0].message, "Bad");
+
+    let error = client.run_graphql(operation()).await.unwrap_err();
+    assert!(matches!(
+        error,
+        ClientError::ErrorResponse(500, body) if body == "Internal Server Error"
+    ));
+}
+
+#[tokio::test]
+async fn test_set_header() {
+    let transport = MockTransport::new(vec![(200, r#"{"data": {"fieldWithString": 1}}"#)]);
+
+    Client::new(transport.clone())
+        .with(SetHeader::bearer_auth("abcd"))
+        .with(SetHeader::new("X-Api-Key", "1234"))
+        .run_graphql(operation())
+        .await
+        .unwrap();
+
+    let requests = transport.requests.lock().unwrap();
+    assert_eq!(requests[0].header("authorization"), Some("Bearer abcd"));
+    assert_eq!(requests[0].header("x-api-key"), Some("1234"));
+}
+
+#[tokio::test]
+async fn test_retry() {
+    let transport = MockTransport::new(vec![
+        (503, "Unavailable"),
+        (429, "Slow down"),
+        (200, r#"{"data": {"fieldWithString": 1}}"#),
+    ]);
+
+    let response = Client::new(transport.clone())
+        .with(Retry::new(2).backoff(Duration::from_millis(1)))
+        .run_graphql(operation())
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 1);
+    assert_eq!(transport.requests.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn test_retry_gives_up() {
+    let transport = MockTransport::new(vec![(500, "Error"), (500, "Error")]);
+
+    let error = Client::new(transport.clone())
+        .with(Retry::new(1).backoff(Duration::from_millis(1)))
+        .run_graphql(operation())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::ErrorResponse(500, _)));
+    assert_eq!(transport.requests.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_retry_ignores_client_errors() {
+    let transport = MockTransport::new(vec![(400, "Bad Request")]);
+
+    let error = Client::new(transport.clone())
+        .with(Retry::new(3).backoff(Duration::from_millis(1)))
+        .run_graphql(operation())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::ErrorResponse(400, _)));
+    assert_eq!(transport.requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_timeout() {
+    let transport = Arc::new(MockTransport {
+        responses: Mutex::new(vec![(200, r#"{"data": {"fieldWithString": 1}}"#)]),
+        delay: Some(Duration::from_secs(5)),
+        ..MockTransport::default()
+    });
+
+    let error = Client::new(transport)
+        .with(Timeout::new(Duration::from_millis(10)))
+        .run_graphql(operation())
+        .await
+        .unwrap_err();
+
+    assert!(matches!(error, ClientError::Timeout));
+}
+
+#[tokio::test]
+async fn test_reqwest_transport() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header("content-type", "application/json")
+        .match_header("authorization", "Bearer abcd")
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"data": {"fieldWithString": 1}}"#)
+        .create_async()
+        .await;
+
+    let client = Client::new(ReqwestTransport::new(
+        reqwest::Client::new(),
+        format!("http://{}/graphql", server.host_with_port()),
+    ))
+    .with(SetHeader::bearer_auth("abcd"));
+
+    let response = client.run_graphql(operation()).await.unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 1);
+
+    mock.assert_async().await;
+}
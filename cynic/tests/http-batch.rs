@@ -0,0 +1,223 @@
+use cynic::{Batch, QueryBuilder};
+use mockito::Matcher;
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("tests/test-schema.graphql");
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct FieldWithStringVariables<'a> {
+    pub input: &'a str,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "FieldWithStringVariables")]
+#[cynic(schema_path = "tests/test-schema.graphql")]
+pub struct FieldWithString {
+    #[arguments(input: $input)]
+    pub field_with_string: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", schema_path = "tests/test-schema.graphql")]
+pub struct AllAuthors {
+    pub all_authors: Vec<Author>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "tests/test-schema.graphql")]
+pub struct Author {
+    pub name: Option<String>,
+}
+
+#[derive(serde::Deserialize, Debug)]
+struct Extensions {
+    code: String,
+}
+
+fn batch() -> (
+    Batch,
+    cynic::BatchHandle<FieldWithString>,
+    cynic::BatchHandle<AllAuthors>,
+) {
+    let mut batch = Batch::new();
+    let field_with_string = batch
+        .add(FieldWithString::build(FieldWithStringVariables {
+            input: "hello",
+        }))
+        .unwrap();
+    let all_authors = batch.add(AllAuthors::build(())).unwrap();
+
+    (batch, field_with_string, all_authors)
+}
+
+fn expected_body() -> serde_json::Value {
+    json!([
+        {
+            "query": FieldWithString::build(FieldWithStringVariables { input: "hello" }).query,
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString"
+        },
+        {
+            "query": AllAuthors::build(()).query,
+            "variables": null,
+            "operationName": "AllAuthors"
+        }
+    ])
+}
+
+const RESPONSE_BODY: &str = r#"[
+    {"data": {"fieldWithString": 1}},
+    {"data": null, "errors": [{"message": "Nope", "extensions": {"code": "FORBIDDEN"}}]}
+]"#;
+
+#[tokio::test]
+async fn test_reqwest_batch() {
+    use cynic::http::ReqwestExt;
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Json(expected_body()))
+        .with_body(RESPONSE_BODY)
+        .create_async()
+        .await;
+
+    let (batch, field_with_string, all_authors) = batch();
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql_batch(batch)
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+
+    assert_eq!(
+        response
+            .get(field_with_string)
+            .unwrap()
+            .data
+            .unwrap()
+            .field_with_string,
+        1
+    );
+
+    let all_authors = response
+        .get(all_authors.retain_extensions::<Extensions>())
+        .unwrap();
+    assert!(all_authors.data.is_none());
+    assert_eq!(
+        all_authors.errors.unwrap()[0]
+            .extensions
+            .as_ref()
+            .unwrap()
+            .code,
+        "FORBIDDEN"
+    );
+}
+
+#[tokio::test]
+async fn test_reqwest_batch_error_response() {
+    use cynic::http::ReqwestExt;
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .with_status(400)
+        .with_body(r#"{"errors": [{"message": "Batching is not enabled"}]}"#)
+        .create_async()
+        .await;
+
+    let (batch, field_with_string, all_authors) = batch();
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql_batch(batch)
+        .await
+        .unwrap();
+
+    mock.assert_async().await;
+
+    for message in [
+        &response.get(field_with_string).unwrap().errors.unwrap()[0].message,
+        &response.get(all_authors).unwrap().errors.unwrap()[0].message,
+    ] {
+        assert_eq!(message, "Batching is not enabled");
+    }
+}
+
+#[cfg(feature = "http-reqwest-blocking")]
+#[test]
+fn test_reqwest_blocking_batch() {
+    use cynic::http::ReqwestBlockingExt;
+
+    let mut server = mockito::Server::new();
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Json(expected_body()))
+        .with_body(RESPONSE_BODY)
+        .create();
+
+    let (batch, field_with_string, _) = batch();
+
+    let response = reqwest::blocking::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql_batch(batch)
+        .unwrap();
+
+    mock.assert();
+
+    assert_eq!(
+        response
+            .get(field_with_string)
+            .unwrap()
+            .data
+            .unwrap()
+            .field_with_string,
+        1
+    );
+}
+
+#[cfg(feature = "client")]
+#[tokio::test]
+async fn test_client_batch() {
+    use cynic::client::{Client, ReqwestTransport};
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::Json(expected_body()))
+        .with_body(RESPONSE_BODY)
+        .create_async()
+        .await;
+
+    let (batch, field_with_string, all_authors) = batch();
+
+    let response = Client::new(ReqwestTransport::new(
+        reqwest::Client::new(),
+        format!("http://{}/graphql", server.host_with_port()),
+    ))
+    .run_graphql_batch(batch)
+    .await
+    .unwrap();
+
+    mock.assert_async().await;
+
+    assert_eq!(
+        response
+            .get(field_with_string)
+            .unwrap()
+            .data
+            .unwrap()
+            .field_with_string,
+        1
+    );
+    assert!(response.get(all_authors).unwrap().data.is_none());
+}
@@ -0,0 +1,214 @@
+use cynic::{QueryBuilder, http::ReqwestExt};
+use futures_util::StreamExt;
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    graphql_type = "Root"
+)]
+struct FilmQuery {
+    #[arguments(id: "ZmlsbXM6MQ==")]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+struct Film {
+    title: Option<String>,
+    #[cynic(spread, defer)]
+    details: Option<FilmDetails>,
+    #[cynic(defer)]
+    release_date: Option<String>,
+}
+
+#[derive(cynic::QueryFragment, Debug, PartialEq)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    graphql_type = "Film"
+)]
+struct FilmDetails {
+    director: Option<String>,
+    producers: Option<Vec<Option<String>>>,
+}
+
+#[test]
+fn test_deferred_query() {
+    let operation = FilmQuery::build(());
+
+    insta::assert_snapshot!(operation.query, @r###"
+    query FilmQuery {
+      film(id: "ZmlsbXM6MQ==") {
+        title
+        __typename
+        ... @defer {
+          director
+          producers
+        }
+        ... @defer {
+          releaseDate
+        }
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_undelivered_deferred_spread_is_none() {
+    let film = serde_json::from_value::<Film>(serde_json::json!({
+        "title": "A New Hope",
+        "__typename": "Film",
+    }))
+    .unwrap();
+
+    assert_eq!(film.details, None);
+}
+
+#[test]
+fn test_invalid_deferred_spread_is_an_error() {
+    let error = serde_json::from_value::<Film>(serde_json::json!({
+        "title": "A New Hope",
+        "__typename": "Film",
+        "director": 1,
+        "producers": null,
+    }))
+    .unwrap_err();
+
+    assert!(error.to_string().contains("invalid type"), "{error}");
+}
+
+#[tokio::test]
+async fn test_incremental_delivery() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header("accept", mockito::Matcher::Regex("deferSpec".into()))
+        .with_header(
+            "content-type",
+            r#"multipart/mixed; boundary="-"; deferSpec=20220824"#,
+        )
+        .with_body(concat!(
+            "\r\n---\r\n",
+            "content-type: application/json; charset=utf-8\r\n\r\n",
+            r#"{"data": {"film": {"title": "A New Hope", "__typename": "Film"}}, "hasNext": true}"#,
+            "\r\n---\r\n",
+            "content-type: application/json; charset=utf-8\r\n\r\n",
+            r#"{"incremental": [{"data": {"director": "George Lucas", "producers": ["Gary Kurtz"]}, "path": ["film"]}], "hasNext": true}"#,
+            "\r\n---\r\n",
+            "content-type: application/json; charset=utf-8\r\n\r\n",
+            r#"{"incremental": [{"data": {"releaseDate": "1977-05-25"}, "path": ["film"]}], "hasNext": false}"#,
+            "\r\n-----\r\n",
+        ))
+        .create_async()
+        .await;
+
+    let responses = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql_incremental(FilmQuery::build(()))
+        .await
+        .unwrap()
+        .map(|response| response.unwrap().data.unwrap().film.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    mock.assert_async().await;
+
+    assert_eq!(responses.len(), 3);
+
+    assert_eq!(responses[0].title.as_deref(), Some("A New Hope"));
+    assert_eq!(responses[0].details, None);
+    assert_eq!(responses[0].release_date, None);
+
+    let details = FilmDetails {
+        director: Some("George Lucas".into()),
+        producers: Some(vec![Some("Gary Kurtz".into())]),
+    };
+    assert_eq!(responses[1].details.as_ref(), Some(&details));
+    assert_eq!(responses[1].release_date, None);
+
+    assert_eq!(responses[2].details.as_ref(), Some(&details));
+    assert_eq!(responses[2].release_date.as_deref(), Some("1977-05-25"));
+}
+
+#[tokio::test]
+async fn test_incremental_operation_with_json_response() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .with_header("content-type", "application/json")
+        .with_body(
+            r#"{"data": {"film": {"title": "A New Hope", "director": "George Lucas", "producers": null, "releaseDate": null}}}"#,
+        )
+        .create_async()
+        .await;
+
+    let responses = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql_incremental(FilmQuery::build(()))
+        .await
+        .unwrap()
+        .map(|response| response.unwrap().data.unwrap().film.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    mock.assert_async().await;
+
+    assert_eq!(responses.len(), 1);
+    assert_eq!(
+        responses[0].details,
+        Some(FilmDetails {
+            director: Some("George Lucas".into()),
+            producers: None
+        })
+    );
+}
+
+#[cfg(feature = "client")]
+#[tokio::test]
+async fn test_client_incremental_delivery() {
+    use cynic::client::{Client, ReqwestTransport};
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header("accept", mockito::Matcher::Regex("deferSpec".into()))
+        .with_header(
+            "content-type",
+            r#"multipart/mixed; boundary="-"; deferSpec=20220824"#,
+        )
+        .with_body(concat!(
+            "\r\n---\r\n",
+            "content-type: application/json; charset=utf-8\r\n\r\n",
+            r#"{"data": {"film": {"title": "A New Hope", "__typename": "Film"}}, "hasNext": true}"#,
+            "\r\n---\r\n",
+            "content-type: application/json; charset=utf-8\r\n\r\n",
+            r#"{"incremental": [{"data": {"releaseDate": "1977-05-25"}, "path": ["film"]}], "hasNext": false}"#,
+            "\r\n-----\r\n",
+        ))
+        .create_async()
+        .await;
+
+    let responses = Client::new(ReqwestTransport::new(
+        reqwest::Client::new(),
+        format!("http://{}/graphql", server.host_with_port()),
+    ))
+    .run_graphql_incremental(FilmQuery::build(()))
+    .await
+    .unwrap()
+    .map(|response| response.unwrap().data.unwrap().film.unwrap())
+    .collect::<Vec<_>>()
+    .await;
+
+    mock.assert_async().await;
+
+    assert_eq!(responses.len(), 2);
+    assert_eq!(responses[0].release_date, None);
+    assert_eq!(responses[1].title.as_deref(), Some("A New Hope"));
+    assert_eq!(responses[1].release_date.as_deref(), Some("1977-05-25"));
+}
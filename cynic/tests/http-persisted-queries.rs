@@ -0,0 +1,364 @@
+#![cfg(feature = "persisted-queries")]
+
+use cynic::{QueryBuilder, http::ReqwestExt};
+use mockito::Matcher;
+use serde_json::json;
+
+mod schema {
+    cynic::use_schema!("tests/test-schema.graphql");
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+pub struct FieldWithStringVariables<'a> {
+    pub input: &'a str,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(graphql_type = "Query", variables = "FieldWithStringVariables")]
+#[cynic(schema_path = "tests/test-schema.graphql")]
+pub struct FieldWithString {
+    #[arguments(input: $input)]
+    pub field_with_string: i32,
+}
+
+fn operation() -> cynic::Operation<FieldWithString, FieldWithStringVariables<'static>> {
+    FieldWithString::build(FieldWithStringVariables { input: "hello" })
+}
+
+fn extensions() -> serde_json::Value {
+    json!({
+        "persistedQuery": {
+            "version": 1,
+            "sha256Hash": operation().query_hash()
+        }
+    })
+}
+
+#[test]
+fn test_query_hash() {
+    let hash = operation().query_hash();
+
+    assert_eq!(hash.len(), 64);
+    assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+}
+
+#[tokio::test]
+async fn test_persisted_query_hit() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Json(json!({
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+            "extensions": extensions()
+        })))
+        .with_body(r#"{"data": {"fieldWithString": 1}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql(operation())
+        .persisted_query()
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 1);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_persisted_query_miss() {
+    let mut server = mockito::Server::new_async().await;
+
+    let hash_only = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Json(json!({
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+            "extensions": extensions()
+        })))
+        .with_body(
+            r#"{"errors": [{"message": "PersistedQueryNotFound", "extensions": {"code": "PERSISTED_QUERY_NOT_FOUND"}}]}"#,
+        )
+        .expect(1)
+        .create_async()
+        .await;
+
+    let full_query = server
+        .mock("POST", "/graphql")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::Json(json!({
+            "query": operation().query,
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+            "extensions": extensions()
+        })))
+        .with_body(r#"{"data": {"fieldWithString": 2}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql(operation())
+        .persisted_query()
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 2);
+
+    hash_only.assert_async().await;
+    full_query.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_persisted_query_get() {
+    let mut server = mockito::Server::new_async().await;
+
+    let get = server
+        .mock("GET", "/graphql")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("operationName".into(), "FieldWithString".into()),
+            Matcher::UrlEncoded("variables".into(), r#"{"input":"hello"}"#.into()),
+            Matcher::UrlEncoded("extensions".into(), extensions().to_string()),
+        ]))
+        .with_body(r#"{"errors": [{"message": "PersistedQueryNotFound"}]}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let post = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::PartialJson(json!({ "query": operation().query })))
+        .with_body(r#"{"data": {"fieldWithString": 3}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql(operation())
+        .persisted_query_get()
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 3);
+
+    get.assert_async().await;
+    post.assert_async().await;
+}
+
+#[cfg(feature = "http-reqwest-blocking")]
+#[test]
+fn test_reqwest_blocking_persisted_query_miss() {
+    use cynic::http::ReqwestBlockingExt;
+
+    let mut server = mockito::Server::new();
+
+    let hash_only = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Json(json!({
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+            "extensions": extensions()
+        })))
+        .with_body(r#"{"errors": [{"message": "PersistedQueryNotFound"}]}"#)
+        .expect(1)
+        .create();
+
+    let full_query = server
+        .mock("POST", "/graphql")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::Json(json!({
+            "query": operation().query,
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+            "extensions": extensions()
+        })))
+        .with_body(r#"{"data": {"fieldWithString": 2}}"#)
+        .expect(1)
+        .create();
+
+    let response = reqwest::blocking::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_persisted_query(operation())
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 2);
+
+    hash_only.assert();
+    full_query.assert();
+}
+
+#[cfg(feature = "http-reqwest-blocking")]
+#[test]
+fn test_reqwest_blocking_persisted_query_get() {
+    use cynic::http::ReqwestBlockingExt;
+
+    let mut server = mockito::Server::new();
+
+    let get = server
+        .mock("GET", "/graphql")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("operationName".into(), "FieldWithString".into()),
+            Matcher::UrlEncoded("variables".into(), r#"{"input":"hello"}"#.into()),
+            Matcher::UrlEncoded("extensions".into(), extensions().to_string()),
+        ]))
+        .with_body(r#"{"data": {"fieldWithString": 3}}"#)
+        .expect(1)
+        .create();
+
+    let response = reqwest::blocking::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_persisted_query_get(operation())
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 3);
+
+    get.assert();
+}
+
+#[cfg(feature = "http-reqwest-blocking")]
+#[test]
+fn test_reqwest_blocking_persisted_document() {
+    use cynic::http::ReqwestBlockingExt;
+
+    let mut server = mockito::Server::new();
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Json(json!({
+            "documentId": operation().query_hash(),
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+        })))
+        .with_body(r#"{"errors": [{"message": "PersistedQueryNotFound"}]}"#)
+        .expect(1)
+        .create();
+
+    let response = reqwest::blocking::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_persisted_document(operation())
+        .unwrap();
+
+    assert_eq!(
+        response.errors.unwrap()[0].message,
+        "PersistedQueryNotFound"
+    );
+
+    mock.assert();
+}
+
+#[cfg(feature = "client")]
+fn client(server: &mockito::Server) -> cynic::client::Client {
+    cynic::client::Client::new(cynic::client::ReqwestTransport::new(
+        reqwest::Client::new(),
+        format!("http://{}/graphql", server.host_with_port()),
+    ))
+}
+
+#[cfg(feature = "client")]
+#[tokio::test]
+async fn test_client_persisted_query_miss() {
+    let mut server = mockito::Server::new_async().await;
+
+    let hash_only = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Json(json!({
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+            "extensions": extensions()
+        })))
+        .with_body(r#"{"errors": [{"message": "PersistedQueryNotFound"}]}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let full_query = server
+        .mock("POST", "/graphql")
+        .match_header("content-type", "application/json")
+        .match_body(Matcher::Json(json!({
+            "query": operation().query,
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+            "extensions": extensions()
+        })))
+        .with_body(r#"{"data": {"fieldWithString": 2}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let response = client(&server)
+        .run_graphql(operation())
+        .persisted_query()
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 2);
+
+    hash_only.assert_async().await;
+    full_query.assert_async().await;
+}
+
+#[cfg(feature = "client")]
+#[tokio::test]
+async fn test_client_persisted_query_get() {
+    let mut server = mockito::Server::new_async().await;
+
+    let get = server
+        .mock("GET", "/graphql")
+        .match_query(Matcher::AllOf(vec![
+            Matcher::UrlEncoded("operationName".into(), "FieldWithString".into()),
+            Matcher::UrlEncoded("variables".into(), r#"{"input":"hello"}"#.into()),
+            Matcher::UrlEncoded("extensions".into(), extensions().to_string()),
+        ]))
+        .with_body(r#"{"data": {"fieldWithString": 3}}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let response = client(&server)
+        .run_graphql(operation())
+        .persisted_query_get()
+        .await
+        .unwrap();
+
+    assert_eq!(response.data.unwrap().field_with_string, 3);
+
+    get.assert_async().await;
+}
+
+#[cfg(feature = "client")]
+#[tokio::test]
+async fn test_client_persisted_document() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_body(Matcher::Json(json!({
+            "documentId": operation().query_hash(),
+            "variables": {"input": "hello"},
+            "operationName": "FieldWithString",
+        })))
+        .with_body(r#"{"errors": [{"message": "PersistedQueryNotFound"}]}"#)
+        .expect(1)
+        .create_async()
+        .await;
+
+    let response = client(&server)
+        .run_graphql(operation())
+        .persisted_document()
+        .await
+        .unwrap();
+
+    assert_eq!(
+        response.errors.unwrap()[0].message,
+        "PersistedQueryNotFound"
+    );
+
+    mock.assert_async().await;
+}
@@ -0,0 +1,144 @@
+use cynic::{GraphQlResponse, SubscriptionBuilder, http::ReqwestExt};
+use futures_util::StreamExt;
+
+mod schema {
+    cynic::use_schema!("../schemas/books.graphql");
+}
+
+#[derive(cynic::QueryVariables)]
+struct IntervalVariables {
+    n: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/books.graphql",
+    graphql_type = "SubscriptionRoot",
+    variables = "IntervalVariables"
+)]
+struct IntervalSubscription {
+    #[arguments(n: $n)]
+    interval: i32,
+}
+
+async fn run_subscription(
+    server: &mockito::ServerGuard,
+) -> Vec<GraphQlResponse<IntervalSubscription>> {
+    reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql_stream(IntervalSubscription::build(IntervalVariables { n: 1 }))
+        .await
+        .unwrap()
+        .map(Result::unwrap)
+        .collect()
+        .await
+}
+
+fn intervals(responses: Vec<GraphQlResponse<IntervalSubscription>>) -> Vec<i32> {
+    responses
+        .into_iter()
+        .map(|response| response.data.unwrap().interval)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_event_stream_subscription() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header(
+            "accept",
+            mockito::Matcher::Regex("text/event-stream".into()),
+        )
+        .with_header("content-type", "text/event-stream")
+        .with_body(concat!(
+            "event: next\n",
+            "data: {\"data\": {\"interval\": 1}}\n\n",
+            ": keep-alive\n\n",
+            "event: next\n",
+            "data: {\"data\": {\"interval\": 2}}\n\n",
+            "event: complete\n\n",
+        ))
+        .create_async()
+        .await;
+
+    assert_eq!(intervals(run_subscription(&server).await), vec![1, 2]);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_multipart_subscription() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header("accept", mockito::Matcher::Regex("multipart/mixed".into()))
+        .with_header(
+            "content-type",
+            r#"multipart/mixed;boundary="graphql";subscriptionSpec="1.0""#,
+        )
+        .with_body(concat!(
+            "\r\n--graphql\r\n",
+            "content-type: application/json\r\n\r\n",
+            "{}\r\n",
+            "--graphql\r\n",
+            "content-type: application/json\r\n\r\n",
+            "{\"payload\": {\"data\": {\"interval\": 1}}}\r\n",
+            "--graphql\r\n",
+            "content-type: application/json\r\n\r\n",
+            "{\"payload\": {\"data\": {\"interval\": 2}}}\r\n",
+            "--graphql--\r\n",
+        ))
+        .create_async()
+        .await;
+
+    assert_eq!(intervals(run_subscription(&server).await), vec![1, 2]);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_json_response_to_subscription() {
+    let mut server = mockito::Server::new_async().await;
+
+    server
+        .mock("POST", "/graphql")
+        .with_status(400)
+        .with_header("content-type", "application/json")
+        .with_body(r#"{"errors": [{"message": "Subscriptions aren't supported"}]}"#)
+        .create_async()
+        .await;
+
+    let responses = run_subscription(&server).await;
+
+    assert_eq!(responses.len(), 1);
+    assert_eq!(
+        responses[0].errors.as_ref().unwrap()[0].message,
+        "Subscriptions aren't supported"
+    );
+}
+
+#[tokio::test]
+async fn test_unsupported_content_type() {
+    let mut server = mockito::Server::new_async().await;
+
+    server
+        .mock("POST", "/graphql")
+        .with_header("content-type", "text/html")
+        .with_body("<html></html>")
+        .create_async()
+        .await;
+
+    let result = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql_stream(IntervalSubscription::build(IntervalVariables { n: 1 }))
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(cynic::http::CynicReqwestError::UnsupportedContentType(content_type))
+            if content_type == "text/html"
+    ));
+}
@@ -0,0 +1,207 @@
+#![cfg(feature = "http-reqwest")]
+
+use cynic::{MutationBuilder, Upload, http::ReqwestExt};
+use mockito::Matcher;
+
+mod schema {
+    cynic::use_schema!("../schemas/uploads.graphql");
+}
+
+cynic::impl_scalar!(Upload, schema::Upload);
+
+#[derive(cynic::InputObject, Debug)]
+#[cynic(schema_path = "../schemas/uploads.graphql")]
+struct UploadFilesInput {
+    files: Vec<Upload>,
+    description: Option<String>,
+}
+
+#[derive(cynic::QueryVariables, Debug)]
+struct UploadFilesVariables {
+    input: UploadFilesInput,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/uploads.graphql",
+    graphql_type = "Mutation",
+    variables = "UploadFilesVariables"
+)]
+struct UploadFiles {
+    #[arguments(input: $input)]
+    upload_files: Vec<File>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../schemas/uploads.graphql")]
+struct File {
+    filename: String,
+}
+
+#[tokio::test]
+async fn test_uploads_are_sent_as_multipart() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header(
+            "content-type",
+            Matcher::Regex("^multipart/form-data; boundary=".into()),
+        )
+        .match_body(Matcher::AllOf(vec![
+            Matcher::Regex(r#"name="operations""#.into()),
+            Matcher::Regex(r#""files":\[null,null\]"#.into()),
+            Matcher::Regex(r#"name="map""#.into()),
+            Matcher::Regex(
+                r#"\{"0":\["variables.input.files.0"\],"1":\["variables.input.files.1"\]\}"#.into(),
+            ),
+            Matcher::Regex(r#"name="0"; filename="one.txt""#.into()),
+            Matcher::Regex("Content-Type: text/plain".into()),
+            Matcher::Regex("first file".into()),
+            Matcher::Regex(r#"name="1"; filename="two.txt""#.into()),
+            Matcher::Regex("second file".into()),
+        ]))
+        .with_body(
+            r#"{"data": {"uploadFiles": [{"filename": "one.txt"}, {"filename": "two.txt"}]}}"#,
+        )
+        .create_async()
+        .await;
+
+    let second = Upload::from_reader(&b"second file"[..], "two.txt")
+        .await
+        .unwrap();
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql(UploadFiles::build(UploadFilesVariables {
+            input: UploadFilesInput {
+                files: vec![
+                    Upload::from_bytes("first file", "one.txt")
+                        .with_mime_type("text/plain")
+                        .unwrap(),
+                    second,
+                ],
+                description: None,
+            },
+        }))
+        .await
+        .unwrap();
+
+    let filenames = response
+        .data
+        .unwrap()
+        .upload_files
+        .into_iter()
+        .map(|file| file.filename)
+        .collect::<Vec<_>>();
+    assert_eq!(filenames, vec!["one.txt", "two.txt"]);
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_operations_without_uploads_are_sent_as_json() {
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header("content-type", "application/json")
+        .with_body(r#"{"data": {"uploadFiles": []}}"#)
+        .create_async()
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql(UploadFiles::build(UploadFilesVariables {
+            input: UploadFilesInput {
+                files: vec![],
+                description: Some("nothing".into()),
+            },
+        }))
+        .await
+        .unwrap();
+
+    assert!(response.data.unwrap().upload_files.is_empty());
+
+    mock.assert_async().await;
+}
+
+#[cfg(feature = "client")]
+#[tokio::test]
+async fn test_client_sends_uploads_as_multipart() {
+    use cynic::client::{Client, ReqwestTransport};
+
+    let mut server = mockito::Server::new_async().await;
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_header(
+            "content-type",
+            Matcher::Regex("^multipart/form-data; boundary=".into()),
+        )
+        .match_body(Matcher::AllOf(vec![
+            Matcher::Regex(r#"name="operations""#.into()),
+            Matcher::Regex(r#""files":\[null\]"#.into()),
+            Matcher::Regex(r#"name="map""#.into()),
+            Matcher::Regex(r#"\{"0":\["variables.input.files.0"\]\}"#.into()),
+            Matcher::Regex(r#"name="0"; filename="one.txt""#.into()),
+            Matcher::Regex("Content-Type: text/plain".into()),
+            Matcher::Regex("first file".into()),
+        ]))
+        .with_body(r#"{"data": {"uploadFiles": [{"filename": "one.txt"}]}}"#)
+        .create_async()
+        .await;
+
+    let response = Client::new(ReqwestTransport::new(
+        reqwest::Client::new(),
+        format!("http://{}/graphql", server.host_with_port()),
+    ))
+    .run_graphql(UploadFiles::build(UploadFilesVariables {
+        input: UploadFilesInput {
+            files: vec![
+                Upload::from_bytes("first file", "one.txt")
+                    .with_mime_type("text/plain")
+                    .unwrap(),
+            ],
+            description: None,
+        },
+    }))
+    .await
+    .unwrap();
+
+    assert_eq!(response.data.unwrap().upload_files[0].filename, "one.txt");
+
+    mock.assert_async().await;
+}
+
+#[tokio::test]
+async fn test_uploads_are_an_error_where_multipart_is_unsupported() {
+    let server = mockito::Server::new_async().await;
+
+    let result = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql_incremental(UploadFiles::build(UploadFilesVariables {
+            input: UploadFilesInput {
+                files: vec![Upload::from_bytes("first file", "one.txt")],
+                description: None,
+            },
+        }))
+        .await;
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_uploads_cant_be_added_to_a_batch() {
+    let mut batch = cynic::Batch::new();
+
+    let result = batch.add(UploadFiles::build(UploadFilesVariables {
+        input: UploadFilesInput {
+            files: vec![Upload::from_bytes("first file", "one.txt")],
+            description: None,
+        },
+    }));
+
+    assert!(result.is_err());
+    assert!(batch.is_empty());
+}
@@ -0,0 +1,169 @@
+#![cfg(feature = "persisted-queries")]
+
+use cynic::{QueryBuilder, persisted::Manifest};
+
+mod schema {
+    cynic::use_schema!("../schemas/starwars.schema.graphql");
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    graphql_type = "Root"
+)]
+#[allow(dead_code)]
+struct QueryWithFeatures {
+    #[arguments(id = cynic::Id::new("ZmlsbXM6MQ=="))]
+    #[cynic(rename = "film", alias, feature = "a_new_hope")]
+    a_new_hope: Option<Film>,
+
+    #[arguments(id = cynic::Id::new("ZmlsbXM6Mg=="))]
+    #[cynic(rename = "film", alias)]
+    empire_strikes_back: Option<Film>,
+}
+
+#[derive(cynic::QueryVariables)]
+struct FilmArguments {
+    id: Option<cynic::Id>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/starwars.schema.graphql",
+    graphql_type = "Root",
+    variables = "FilmArguments"
+)]
+struct FilmQuery {
+    #[arguments(id: $id)]
+    film: Option<Film>,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../schemas/starwars.schema.graphql")]
+#[allow(dead_code)]
+struct Film {
+    title: Option<String>,
+}
+
+#[test]
+fn test_manifest_documents_match_operations() {
+    let manifest = Manifest::new().with_query::<FilmQuery, FilmArguments>();
+
+    let operation = FilmQuery::build(FilmArguments { id: None });
+
+    assert_eq!(
+        manifest.documents().collect::<Vec<_>>(),
+        vec![(operation.query_hash().as_str(), operation.query.as_str())]
+    );
+}
+
+#[test]
+fn test_manifest_includes_feature_permutations() {
+    let manifest = Manifest::new()
+        .with_features(["a_new_hope", "unused_feature"])
+        .with_query::<QueryWithFeatures, ()>();
+
+    let mut documents = manifest
+        .documents()
+        .map(|(_, document)| document)
+        .collect::<Vec<_>>();
+    documents.sort();
+
+    insta::assert_snapshot!(documents.join("\n"), @r###"
+    query QueryWithFeatures {
+      a_new_hope: film(id: "ZmlsbXM6MQ==") {
+        title
+      }
+      empire_strikes_back: film(id: "ZmlsbXM6Mg==") {
+        title
+      }
+    }
+
+    query QueryWithFeatures {
+      empire_strikes_back: film(id: "ZmlsbXM6Mg==") {
+        title
+      }
+    }
+    "###);
+}
+
+#[test]
+fn test_manifest_features_apply_to_earlier_operations() {
+    let features_first = Manifest::new()
+        .with_features(["a_new_hope"])
+        .with_query::<QueryWithFeatures, ()>();
+    let features_last = Manifest::new()
+        .with_query::<QueryWithFeatures, ()>()
+        .with_features(["a_new_hope"]);
+
+    assert_eq!(features_first.documents().count(), 2);
+    assert_eq!(features_first.to_json(), features_last.to_json());
+}
+
+#[test]
+fn test_manifest_registered_operations() {
+    let manifest = Manifest::new()
+        .with_features(["a_new_hope"])
+        .with_registered_operations();
+
+    let documents = manifest
+        .documents()
+        .map(|(_, document)| document)
+        .collect::<Vec<_>>();
+
+    let film_query = FilmQuery::build(FilmArguments { id: None });
+    assert!(documents.contains(&film_query.query.as_str()));
+    assert_eq!(
+        documents
+            .iter()
+            .filter(|document| document.starts_with("query QueryWithFeatures"))
+            .count(),
+        2
+    );
+    assert!(
+        !documents
+            .iter()
+            .any(|document| document.starts_with("query Film {"))
+    );
+}
+
+#[test]
+fn test_manifest_json() {
+    let manifest = Manifest::new().with_query::<FilmQuery, FilmArguments>();
+    let operation = FilmQuery::build(FilmArguments { id: None });
+
+    let json = serde_json::from_str::<serde_json::Value>(&manifest.to_json()).unwrap();
+
+    assert_eq!(json[operation.query_hash()], operation.query);
+    assert_eq!(json.as_object().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_persisted_document() {
+    use cynic::http::ReqwestExt;
+
+    let mut server = mockito::Server::new_async().await;
+    let operation = FilmQuery::build(FilmArguments { id: None });
+
+    let mock = server
+        .mock("POST", "/graphql")
+        .match_body(mockito::Matcher::Json(serde_json::json!({
+            "documentId": operation.query_hash(),
+            "variables": {"id": null},
+            "operationName": "FilmQuery"
+        })))
+        .with_body(r#"{"data": {"film": null}}"#)
+        .create_async()
+        .await;
+
+    let response = reqwest::Client::new()
+        .post(format!("http://{}/graphql", server.host_with_port()))
+        .run_graphql(operation)
+        .persisted_document()
+        .await
+        .unwrap();
+
+    assert!(response.data.unwrap().film.is_none());
+
+    mock.assert_async().await;
+}
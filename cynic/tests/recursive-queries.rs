@@ -102,6 +102,39 @@ mod optional_recursive_types {
         };
     }
 
+    #[test]
+    fn test_recurse_limit_only_applies_to_its_field() {
+        let operation = cynic::OperationBuilder::<FriendsQuery>::query()
+            .with_variables(())
+            .with_recurse_limit::<schema::__fields::Author::friends>(3)
+            .build()
+            .unwrap();
+
+        insta::assert_display_snapshot!(operation.query, @r###"
+        query FriendsQuery {
+          allAuthors {
+            friends {
+              friends {
+                friends
+              }
+              referrer {
+                friends
+              }
+            }
+            referrer {
+              friends {
+                friends
+              }
+              referrer {
+                friends
+              }
+            }
+          }
+        }
+
+        "###);
+    }
+
     #[test]
     fn test_friends_decoding_with_matching_depth() {
         let data = json!({
@@ -173,6 +206,28 @@ mod required_recursive_types {
         "###);
     }
 
+    #[test]
+    fn test_recurse_limit_overrides_depth() {
+        let operation = cynic::OperationBuilder::<FriendsQuery>::query()
+            .with_variables(())
+            .with_recurse_limit::<schema::__fields::Author::me>(3)
+            .build()
+            .unwrap();
+
+        insta::assert_display_snapshot!(operation.query, @r###"
+        query FriendsQuery {
+          allAuthors {
+            me {
+              me {
+                me
+              }
+            }
+          }
+        }
+
+        "###);
+    }
+
     #[test]
     fn test_friends_decoding_with_matching_depth() {
         let data = json!({ "allAuthors": [{"me": {"me": {}}}]});
@@ -0,0 +1,244 @@
+#![cfg(feature = "ws")]
+
+use cynic::{
+    SubscriptionBuilder,
+    ws::{Client, ClientBuilder, Protocol},
+};
+use futures_util::StreamExt;
+use graphql_mocks::mocks;
+
+mod schema {
+    cynic::use_schema!("../schemas/books.graphql");
+}
+
+#[derive(cynic::QueryVariables)]
+struct IntervalVariables {
+    n: i32,
+}
+
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/books.graphql",
+    graphql_type = "SubscriptionRoot",
+    variables = "IntervalVariables"
+)]
+struct IntervalSubscription {
+    #[arguments(n: $n)]
+    interval: i32,
+}
+
+#[derive(cynic::QueryVariables)]
+struct BooksVariables {
+    mutation_type: Option<MutationType>,
+}
+
+#[derive(cynic::Enum, Clone, Copy, Debug)]
+#[cynic(schema_path = "../schemas/books.graphql")]
+enum MutationType {
+    Created,
+    Deleted,
+}
+
+#[allow(dead_code)]
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(
+    schema_path = "../schemas/books.graphql",
+    graphql_type = "SubscriptionRoot",
+    variables = "BooksVariables"
+)]
+struct BooksSubscription {
+    #[arguments(mutationType: $mutation_type)]
+    books: BookChanged,
+}
+
+#[allow(dead_code)]
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../schemas/books.graphql")]
+struct BookChanged {
+    mutation_type: MutationType,
+    id: cynic::Id,
+    book: Option<Book>,
+}
+
+#[allow(dead_code)]
+#[derive(cynic::QueryFragment, Debug)]
+#[cynic(schema_path = "../schemas/books.graphql")]
+struct Book {
+    name: String,
+    author: String,
+}
+
+async fn connect(server: &graphql_mocks::MockGraphQlServer) -> Client {
+    connect_with(server, Client::builder()).await
+}
+
+async fn connect_with(server: &graphql_mocks::MockGraphQlServer, builder: ClientBuilder) -> Client {
+    let request = builder.request(server.websocket_url().as_str()).unwrap();
+    let (connection, response) = tokio_tungstenite::connect_async(request).await.unwrap();
+
+    let builder = builder.negotiated(&response).unwrap();
+    let (client, actor) = builder.build(connection).await.unwrap();
+    tokio::spawn(actor.into_future());
+
+    client
+}
+
+#[tokio::test]
+async fn test_subscription() {
+    let server = mocks::books::serve().await;
+    let client = connect(&server).await;
+
+    let responses = client
+        .subscribe(IntervalSubscription::build(IntervalVariables { n: 2 }))
+        .unwrap()
+        .map(|response| response.data.unwrap().interval)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(responses, vec![2, 4, 6]);
+}
+
+#[tokio::test]
+async fn test_legacy_protocol() {
+    let server = mocks::books::serve().await;
+    let client = connect_with(
+        &server,
+        Client::builder().protocol(Protocol::SubscriptionsTransportWs),
+    )
+    .await;
+
+    let responses = client
+        .subscribe(IntervalSubscription::build(IntervalVariables { n: 3 }))
+        .unwrap()
+        .map(|response| response.data.unwrap().interval)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(responses, vec![3, 6, 9]);
+}
+
+#[tokio::test]
+async fn test_protocol_negotiation() {
+    let server = mocks::books::serve().await;
+    let builder = Client::builder().protocols([
+        Protocol::GraphQlTransportWs,
+        Protocol::SubscriptionsTransportWs,
+    ]);
+
+    let request = builder.request(server.websocket_url().as_str()).unwrap();
+    assert_eq!(
+        request.headers()["Sec-WebSocket-Protocol"],
+        "graphql-transport-ws,graphql-ws"
+    );
+
+    let client = connect_with(&server, builder).await;
+
+    let responses = client
+        .subscribe(IntervalSubscription::build(IntervalVariables { n: 1 }))
+        .unwrap()
+        .map(|response| response.data.unwrap().interval)
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(responses, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_negotiating_unsupported_protocol() {
+    use tokio_tungstenite::tungstenite::http::Response;
+
+    let response = Response::builder()
+        .header("Sec-WebSocket-Protocol", "graphql-ws")
+        .body(())
+        .unwrap();
+
+    let error = Client::builder()
+        .protocol(Protocol::GraphQlTransportWs)
+        .negotiated(&response)
+        .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "The server selected an unsupported protocol: graphql-ws"
+    );
+}
+
+#[tokio::test]
+async fn test_multiplexed_subscriptions() {
+    let server = mocks::books::serve().await;
+    let client = connect(&server).await;
+
+    let intervals = client
+        .subscribe(IntervalSubscription::build(IntervalVariables { n: 10 }))
+        .unwrap();
+    let books = client
+        .subscribe(BooksSubscription::build(BooksVariables {
+            mutation_type: Some(MutationType::Created),
+        }))
+        .unwrap();
+
+    let (intervals, books) = futures_util::future::join(
+        intervals
+            .map(|response| response.data.unwrap().interval)
+            .collect::<Vec<_>>(),
+        books
+            .map(|response| response.data.unwrap())
+            .collect::<Vec<_>>(),
+    )
+    .await;
+
+    assert_eq!(intervals, vec![10, 20, 30]);
+    insta::assert_debug_snapshot!(books, @r#"
+    [
+        BooksSubscription {
+            books: BookChanged {
+                mutation_type: Created,
+                id: Id(
+                    "1",
+                ),
+                book: Some(
+                    Book {
+                        name: "Frankenstein",
+                        author: "Mary Shelley",
+                    },
+                ),
+            },
+        },
+        BooksSubscription {
+            books: BookChanged {
+                mutation_type: Created,
+                id: Id(
+                    "2",
+                ),
+                book: Some(
+                    Book {
+                        name: "Dune",
+                        author: "Frank Herbert",
+                    },
+                ),
+            },
+        },
+    ]
+    "#);
+}
+
+#[tokio::test]
+async fn test_subscription_errors() {
+    let server = mocks::books::serve().await;
+    let client = connect(&server).await;
+
+    let operation = cynic::StreamingOperation::<IntervalSubscription, ()>::new(
+        "subscription { doesNotExist }".into(),
+        (),
+    );
+
+    let responses = client
+        .subscribe(operation)
+        .unwrap()
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(responses.len(), 1);
+    assert!(responses[0].data.is_none());
+    assert_eq!(responses[0].errors.as_ref().unwrap().len(), 1);
+}
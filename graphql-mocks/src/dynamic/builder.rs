@@ -1,6 +1,6 @@
 #![allow(clippy::panic)]
 
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use async_graphql::dynamic::{FieldValue, ResolverContext};
 use cynic_parser::{common::WrappingType, type_system as parser};
@@ -8,20 +8,26 @@ use serde::Deserialize;
 
 use crate::{MockGraphQlServer, MockGraphQlServerBuilder};
 
-use super::{DynamicSchema, resolvers::Resolver};
+use super::{
+    DynamicSchema,
+    resolvers::{Resolver, SubscriptionResolver},
+};
 
 pub struct DynamicSchemaBuilder {
     sdl: String,
     field_resolvers: ResolverMap,
+    subscription_resolvers: SubscriptionResolverMap,
 }
 
 type ResolverMap = HashMap<(String, String), Box<dyn Resolver>>;
+type SubscriptionResolverMap = HashMap<String, Arc<dyn SubscriptionResolver>>;
 
 impl DynamicSchemaBuilder {
     pub fn new(sdl: &str) -> Self {
         DynamicSchemaBuilder {
             sdl: sdl.into(),
             field_resolvers: Default::default(),
+            subscription_resolvers: Default::default(),
         }
     }
 
@@ -36,20 +42,39 @@ impl DynamicSchemaBuilder {
         self
     }
 
+    /// Registers a resolver for a field of the subscription root.
+    pub fn with_subscription_resolver(
+        mut self,
+        field: &str,
+        resolver: impl SubscriptionResolver + 'static,
+    ) -> Self {
+        self.subscription_resolvers
+            .insert(field.into(), Arc::new(resolver));
+        self
+    }
+
     pub fn into_server_builder(self) -> MockGraphQlServerBuilder {
         let Self {
             sdl,
             mut field_resolvers,
+            mut subscription_resolvers,
         } = self;
 
         let schema = cynic_parser::parse_type_system_document(&sdl)
             .map_err(|e| e.to_report(&sdl))
             .expect("a valid document");
 
+        let (_, _, subscription_name) = root_types(&schema);
         let mut builder = schema_builder(&schema);
 
         for definition in schema.definitions() {
             match definition {
+                parser::Definition::Type(parser::TypeDefinition::Object(def))
+                    if Some(def.name()) == subscription_name =>
+                {
+                    builder =
+                        builder.register(convert_subscription(def, &mut subscription_resolvers));
+                }
                 parser::Definition::Type(def) => {
                     builder = builder.register(convert_type(def, &mut field_resolvers));
                 }
@@ -132,6 +157,50 @@ fn convert_object(
     object.into()
 }
 
+fn convert_subscription(
+    def: parser::ObjectDefinition<'_>,
+    resolvers: &mut SubscriptionResolverMap,
+) -> async_graphql::dynamic::Type {
+    use async_graphql::dynamic::*;
+
+    let mut subscription = Subscription::new(def.name());
+
+    if let Some(description) = def.description() {
+        subscription = subscription.description(description.to_cow());
+    }
+
+    for field_def in def.fields() {
+        let type_ref = convert_type_ref(field_def.ty());
+        let resolver = resolvers
+            .remove(field_def.name())
+            .unwrap_or_else(|| Arc::new(Vec::new()));
+
+        let mut field = SubscriptionField::new(field_def.name(), type_ref, move |context| {
+            let values = resolver.resolve(context);
+            SubscriptionFieldFuture::new(async move {
+                Ok(futures_lite::stream::iter(values.into_iter().map(
+                    |value| {
+                        let value = async_graphql::Value::deserialize(value).unwrap();
+                        Ok(transform_into_field_value(value))
+                    },
+                )))
+            })
+        });
+
+        if let Some(description) = field_def.description() {
+            field = field.description(description.to_cow());
+        }
+
+        for argument in field_def.arguments() {
+            field = field.argument(convert_input_value(argument));
+        }
+
+        subscription = subscription.field(field);
+    }
+
+    subscription.into()
+}
+
 fn transform_into_field_value(mut value: async_graphql::Value) -> FieldValue<'static> {
     match value {
         async_graphql::Value::Object(ref mut fields) => {
@@ -345,7 +414,7 @@ fn root_types(schema: &cynic_parser::TypeSystemDocument) -> (&str, Option<&str>,
             mutation_name = Some("Mutation");
         }
         if subscription_present {
-            mutation_name = Some("Subscription");
+            subscription_name = Some("Subscription");
         }
     }
 
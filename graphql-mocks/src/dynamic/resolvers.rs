@@ -31,3 +31,26 @@ impl Resolver for Option<serde_json::Value> {
         self.clone()
     }
 }
+
+/// A resolver for a field on the subscription root.
+///
+/// Returns the full list of values that the subscription should emit, after
+/// which the subscription will complete.
+pub trait SubscriptionResolver: Send + Sync {
+    fn resolve(&self, context: ResolverContext<'_>) -> Vec<serde_json::Value>;
+}
+
+impl<F> SubscriptionResolver for F
+where
+    for<'a> F: Fn(ResolverContext<'a>) -> Vec<serde_json::Value> + Send + Sync,
+{
+    fn resolve(&self, context: ResolverContext<'_>) -> Vec<serde_json::Value> {
+        self(context)
+    }
+}
+
+impl SubscriptionResolver for Vec<serde_json::Value> {
+    fn resolve(&self, _context: ResolverContext<'_>) -> Vec<serde_json::Value> {
+        self.clone()
+    }
+}
@@ -0,0 +1,53 @@
+use serde_json::{Value, json};
+
+use crate::{DynamicSchema, MockGraphQlServer, ResolverContext};
+
+pub async fn serve() -> MockGraphQlServer {
+    DynamicSchema::builder(include_str!("../../../schemas/books.graphql"))
+        .with_resolver("QueryRoot", "books", json!(books()))
+        .with_subscription_resolver("interval", interval_resolver)
+        .with_subscription_resolver("books", books_resolver)
+        .into_server_builder()
+        .await
+}
+
+fn books() -> Vec<Value> {
+    vec![
+        json!({"id": "1", "name": "Frankenstein", "author": "Mary Shelley"}),
+        json!({"id": "2", "name": "Dune", "author": "Frank Herbert"}),
+    ]
+}
+
+/// Emits three multiples of `n`
+fn interval_resolver(context: ResolverContext<'_>) -> Vec<Value> {
+    let n = context
+        .args
+        .get("n")
+        .and_then(|n| n.i64().ok())
+        .unwrap_or(1);
+
+    (1..=3).map(|i| json!(i * n)).collect()
+}
+
+fn books_resolver(context: ResolverContext<'_>) -> Vec<Value> {
+    let filter = context
+        .args
+        .get("mutationType")
+        .and_then(|ty| ty.enum_name().ok().map(str::to_owned));
+
+    books()
+        .into_iter()
+        .map(|book| {
+            json!({
+                "mutationType": "CREATED",
+                "id": book["id"],
+                "book": book
+            })
+        })
+        .chain([json!({"mutationType": "DELETED", "id": "1", "book": null})])
+        .filter(|change| match &filter {
+            Some(filter) => change["mutationType"] == *filter,
+            None => true,
+        })
+        .collect()
+}